@@ -0,0 +1,272 @@
+use std::{error::Error, fs, path::PathBuf, process::ExitCode};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use postcard_bindgen::{
+    compat::{self, Compatibility},
+    dynamic, javascript, python, ContainerCollection, PackageInfo,
+};
+
+type Result<T, E = Box<dyn Error>> = core::result::Result<T, E>;
+
+/// Generates postcard bindings from a schema written by `ContainerCollection::to_schema()` and
+/// decodes or encodes postcard bytes with it.
+#[derive(Parser)]
+#[command(name = "postcard-bindgen", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Generates a package with the bindings of the schema.
+    Generate(GenerateArgs),
+    /// Decodes postcard bytes and prints the value as JSON.
+    Decode(DecodeArgs),
+    /// Encodes a value given as JSON and prints the postcard bytes as hex.
+    Encode(EncodeArgs),
+    /// Compares two schemas and fails if the changes break peers using the old one.
+    Check(CheckArgs),
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Lang {
+    Js,
+    Python,
+}
+
+#[derive(Args)]
+struct GenerateArgs {
+    #[arg(long)]
+    lang: Lang,
+    #[arg(long)]
+    schema: PathBuf,
+    /// Directory of the package, its last component is the package name.
+    #[arg(long)]
+    out: PathBuf,
+    #[arg(long, default_value = "0.1.0")]
+    package_version: String,
+    #[command(flatten)]
+    settings: SettingsArgs,
+}
+
+/// Options of the `GenerationSettings` builders, unset options are taken from
+/// `GenerationSettings::enable_all()`.
+#[derive(Args)]
+struct SettingsArgs {
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    serialization: Option<bool>,
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    deserialization: Option<bool>,
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    runtime_type_checks: Option<bool>,
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    module_structure: Option<bool>,
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    cobs: Option<bool>,
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    crc32: Option<bool>,
+    /// JavaScript only.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    type_script_types: Option<bool>,
+    /// JavaScript only.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    esm_module: Option<bool>,
+    /// Python only.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    micropython: Option<bool>,
+}
+
+#[derive(Args)]
+struct DecodeArgs {
+    #[arg(long)]
+    schema: PathBuf,
+    /// Rust path of the type, with or without the crate name.
+    #[arg(long = "type", value_name = "TYPE")]
+    type_path: String,
+    /// The bytes as hex or a file containing them.
+    input: String,
+}
+
+#[derive(Args)]
+struct EncodeArgs {
+    #[arg(long)]
+    schema: PathBuf,
+    /// Rust path of the type, with or without the crate name.
+    #[arg(long = "type", value_name = "TYPE")]
+    type_path: String,
+    /// The value as JSON or a file containing it.
+    input: String,
+}
+
+#[derive(Args)]
+struct CheckArgs {
+    #[arg(long)]
+    old: PathBuf,
+    #[arg(long)]
+    new: PathBuf,
+    /// Also fail on forward-compatible changes, like an appended enum variant.
+    #[arg(long)]
+    deny_forward_compatible: bool,
+}
+
+fn main() -> ExitCode {
+    let result = match Cli::parse().command {
+        Command::Generate(args) => generate(args),
+        Command::Decode(args) => decode(args),
+        Command::Encode(args) => encode(args),
+        Command::Check(args) => check(args),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn generate(args: GenerateArgs) -> Result<()> {
+    let bindings = load_schema(&args.schema)?;
+    let name = args
+        .out
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or("`--out` must end with the name of the package")?;
+    let parent_dir = args.out.parent().unwrap_or(&args.out);
+    let package_info = PackageInfo {
+        name: name.to_owned(),
+        version: args.package_version.parse()?,
+    };
+
+    let settings = &args.settings;
+    match args.lang {
+        Lang::Js => {
+            use javascript::GenerationSettings as Settings;
+            if settings.micropython.is_some() {
+                return Err("`--micropython` is only supported for Python".into());
+            }
+            let gen_settings = apply(
+                Settings::enable_all(),
+                [
+                    (settings.serialization, Settings::serialization),
+                    (settings.deserialization, Settings::deserialization),
+                    (settings.runtime_type_checks, Settings::runtime_type_checks),
+                    (settings.module_structure, Settings::module_structure),
+                    (settings.cobs, Settings::cobs),
+                    (settings.crc32, Settings::crc32),
+                    (settings.type_script_types, Settings::type_script_types),
+                    (settings.esm_module, Settings::esm_module),
+                ],
+            );
+            javascript::build_package(parent_dir, package_info, gen_settings, bindings)?;
+        }
+        Lang::Python => {
+            use python::GenerationSettings as Settings;
+            if settings.type_script_types.is_some() || settings.esm_module.is_some() {
+                return Err(
+                    "`--type-script-types` and `--esm-module` are only supported for JavaScript"
+                        .into(),
+                );
+            }
+            let gen_settings = apply(
+                Settings::enable_all(),
+                [
+                    (settings.serialization, Settings::serialization),
+                    (settings.deserialization, Settings::deserialization),
+                    (settings.runtime_type_checks, Settings::runtime_type_checks),
+                    (settings.module_structure, Settings::module_structure),
+                    (settings.cobs, Settings::cobs),
+                    (settings.crc32, Settings::crc32),
+                    (settings.micropython, Settings::micropython),
+                ],
+            );
+            python::build_package(parent_dir, package_info, gen_settings, bindings)?;
+        }
+    }
+    Ok(())
+}
+
+/// A `GenerationSettings` builder.
+type Builder<S> = fn(S, bool) -> S;
+
+/// Calls the builders of the options which are set.
+fn apply<S, const N: usize>(settings: S, options: [(Option<bool>, Builder<S>); N]) -> S {
+    options
+        .into_iter()
+        .fold(settings, |settings, (enabled, builder)| match enabled {
+            Some(enabled) => builder(settings, enabled),
+            None => settings,
+        })
+}
+
+fn decode(args: DecodeArgs) -> Result<()> {
+    let bindings = load_schema(&args.schema)?;
+    let bytes = match fs::metadata(&args.input) {
+        Ok(metadata) if metadata.is_file() => fs::read(&args.input)?,
+        _ => parse_hex(&args.input)?,
+    };
+
+    let (value, rest) = dynamic::decode(&bindings, &args.type_path, &bytes)?;
+    println!("{}", serde_json::to_string_pretty(&value.to_json())?);
+    if !rest.is_empty() {
+        eprintln!("warning: {} bytes left after the value", rest.len());
+    }
+    Ok(())
+}
+
+fn encode(args: EncodeArgs) -> Result<()> {
+    let bindings = load_schema(&args.schema)?;
+    let json = match fs::metadata(&args.input) {
+        Ok(metadata) if metadata.is_file() => fs::read_to_string(&args.input)?,
+        _ => args.input,
+    };
+
+    let value = dynamic::from_json(&bindings, &args.type_path, &serde_json::from_str(&json)?)?;
+    let bytes = dynamic::encode(&bindings, &args.type_path, &value)?;
+    println!(
+        "{}",
+        bytes.iter().map(|b| format!("{b:02x}")).collect::<String>()
+    );
+    Ok(())
+}
+
+fn check(args: CheckArgs) -> Result<()> {
+    let report = compat::check(&load_schema(&args.old)?, &load_schema(&args.new)?);
+    print!("{report}");
+
+    let denied = if args.deny_forward_compatible {
+        Compatibility::ForwardCompatible
+    } else {
+        Compatibility::Breaking
+    };
+    if report.compatibility() >= denied {
+        return Err(format!("{} changes", report.compatibility()).into());
+    }
+    Ok(())
+}
+
+fn load_schema(path: &PathBuf) -> Result<ContainerCollection> {
+    let schema = fs::read_to_string(path)
+        .map_err(|err| format!("failed to read `{}`: {err}", path.display()))?;
+    Ok(ContainerCollection::from_schema(&schema)?)
+}
+
+/// Parses hex bytes, which may be prefixed with `0x` and separated by whitespace.
+fn parse_hex(hex: &str) -> Result<Vec<u8>> {
+    let hex = hex
+        .strip_prefix("0x")
+        .unwrap_or(hex)
+        .split_whitespace()
+        .collect::<String>();
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return Err(format!("`{hex}` is neither a file nor hex bytes").into());
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&hex[i..i + 2], 16)
+                .map_err(|_| format!("`{hex}` is neither a file nor hex bytes").into())
+        })
+        .collect()
+}
@@ -0,0 +1,273 @@
+use std::{path::Path, process::Command};
+
+use postcard_bindgen::{generate_bindings, PostcardBindings};
+use serde::Serialize;
+
+#[derive(Serialize, PostcardBindings)]
+enum Action {
+    Reset,
+    Move { x: i16, y: i16 },
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct Protocol {
+    id: u32,
+    name: String,
+    action: Action,
+    payload: Option<Vec<u8>>,
+}
+
+fn sample() -> Protocol {
+    Protocol {
+        id: 300,
+        name: "probe".into(),
+        action: Action::Move { x: -1, y: 2 },
+        payload: Some(vec![1, 2]),
+    }
+}
+
+fn write_schema(dir: &Path) -> String {
+    let path = dir.join("schema.json");
+    std::fs::write(&path, generate_bindings!(Protocol).to_schema()).unwrap();
+    path.to_str().unwrap().to_owned()
+}
+
+fn run(args: &[&str]) -> (bool, String, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_postcard-bindgen"))
+        .args(args)
+        .output()
+        .unwrap();
+    (
+        output.status.success(),
+        String::from_utf8(output.stdout).unwrap(),
+        String::from_utf8(output.stderr).unwrap(),
+    )
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[test]
+fn test_decode_and_encode() {
+    let dir = tempfile::tempdir().unwrap();
+    let schema = write_schema(dir.path());
+    let bytes = postcard::to_allocvec(&sample()).unwrap();
+
+    let (success, json, _) = run(&[
+        "decode",
+        "--schema",
+        &schema,
+        "--type",
+        "Protocol",
+        &hex(&bytes),
+    ]);
+    assert!(success);
+    assert_eq!(
+        json,
+        r#"{
+  "id": 300,
+  "name": "probe",
+  "action": {
+    "Move": {
+      "x": -1,
+      "y": 2
+    }
+  },
+  "payload": [
+    1,
+    2
+  ]
+}
+"#
+    );
+
+    let (success, encoded, _) = run(&["encode", "--schema", &schema, "--type", "Protocol", &json]);
+    assert!(success);
+    assert_eq!(encoded.trim_end(), hex(&bytes));
+
+    let (success, encoded, _) = run(&[
+        "encode",
+        "--schema",
+        &schema,
+        "--type",
+        "Protocol",
+        r#"{ "id": 1, "name": "", "action": "Reset" }"#,
+    ]);
+    assert!(success);
+    let reset = Protocol {
+        id: 1,
+        name: String::new(),
+        action: Action::Reset,
+        payload: None,
+    };
+    assert_eq!(
+        encoded.trim_end(),
+        hex(&postcard::to_allocvec(&reset).unwrap())
+    );
+}
+
+#[test]
+fn test_decode_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let schema = write_schema(dir.path());
+    let mut bytes = postcard::to_allocvec(&sample()).unwrap();
+    bytes.push(0xff);
+    let input = dir.path().join("frame.bin");
+    std::fs::write(&input, &bytes).unwrap();
+
+    let (success, json, warning) = run(&[
+        "decode",
+        "--schema",
+        &schema,
+        "--type",
+        "Protocol",
+        input.to_str().unwrap(),
+    ]);
+    assert!(success);
+    assert!(json.contains(r#""name": "probe""#));
+    assert_eq!(warning, "warning: 1 bytes left after the value\n");
+}
+
+#[test]
+fn test_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let schema = write_schema(dir.path());
+
+    let (success, _, error) = run(&["decode", "--schema", &schema, "--type", "Protocol", "0x2c"]);
+    assert!(!success);
+    assert_eq!(error, "error: unexpected end of the bytes\n");
+
+    let (success, _, error) = run(&["decode", "--schema", &schema, "--type", "Protocol", "zz"]);
+    assert!(!success);
+    assert_eq!(error, "error: `zz` is neither a file nor hex bytes\n");
+
+    let (success, _, error) = run(&[
+        "encode",
+        "--schema",
+        &schema,
+        "--type",
+        "Protocol",
+        r#"{ "id": -1, "name": "", "action": "Reset" }"#,
+    ]);
+    assert!(!success);
+    assert_eq!(error, "error: integer doesn't fit into `u32`\n");
+}
+
+#[test]
+fn test_generate() {
+    let dir = tempfile::tempdir().unwrap();
+    let schema = write_schema(dir.path());
+    let out = dir.path().join("generated");
+
+    let js_out = out.join("protocol-js");
+    let (success, _, _) = run(&[
+        "generate",
+        "--lang",
+        "js",
+        "--schema",
+        &schema,
+        "--out",
+        js_out.to_str().unwrap(),
+        "--cobs",
+        "--type-script-types=false",
+    ]);
+    assert!(success);
+    let index = std::fs::read_to_string(js_out.join("index.js")).unwrap();
+    assert!(index.contains("serialize_cobs"));
+    assert!(!js_out.join("index.d.ts").exists());
+
+    let python_out = out.join("protocol_py");
+    let (success, _, _) = run(&[
+        "generate",
+        "--lang",
+        "python",
+        "--schema",
+        &schema,
+        "--out",
+        python_out.to_str().unwrap(),
+        "--package-version",
+        "1.2.3",
+    ]);
+    assert!(success);
+    assert!(python_out.join("pyproject.toml").exists());
+
+    let (success, _, error) = run(&[
+        "generate",
+        "--lang",
+        "js",
+        "--schema",
+        &schema,
+        "--out",
+        js_out.to_str().unwrap(),
+        "--micropython",
+    ]);
+    assert!(!success);
+    assert_eq!(
+        error,
+        "error: `--micropython` is only supported for Python\n"
+    );
+}
+
+mod v1 {
+    use postcard_bindgen::PostcardBindings;
+    use serde::Serialize;
+
+    #[allow(unused)]
+    #[derive(Serialize, PostcardBindings)]
+    pub enum Status {
+        Idle,
+        Busy(u16),
+    }
+}
+
+mod v2 {
+    use postcard_bindgen::PostcardBindings;
+    use serde::Serialize;
+
+    #[allow(unused)]
+    #[derive(Serialize, PostcardBindings)]
+    pub enum Status {
+        Idle,
+        Busy(u32),
+        Failed,
+    }
+}
+
+#[test]
+fn test_check() {
+    let dir = tempfile::tempdir().unwrap();
+    let old = dir.path().join("old.json");
+    let new = dir.path().join("new.json");
+    std::fs::write(&old, generate_bindings!(v1::Status).to_schema()).unwrap();
+    // both versions are compared as if `v2` replaced `v1`
+    let schema = generate_bindings!(v2::Status).to_schema();
+    std::fs::write(&new, schema.replace("cli::v2", "cli::v1")).unwrap();
+    let (old, new) = (old.to_str().unwrap(), new.to_str().unwrap());
+
+    let (success, report, _) = run(&["check", "--old", old, "--new", new]);
+    assert!(success);
+    assert_eq!(
+        report,
+        "forward-compatible:
+  forward-compatible: cli::v1::Status::Busy.0: type widened from `u16` to `u32`
+  forward-compatible: cli::v1::Status::Failed: variant added with index 2
+"
+    );
+
+    let (success, _, error) = run(&[
+        "check",
+        "--old",
+        old,
+        "--new",
+        new,
+        "--deny-forward-compatible",
+    ]);
+    assert!(!success);
+    assert_eq!(error, "error: forward-compatible changes\n");
+
+    let (success, report, error) = run(&["check", "--old", new, "--new", old]);
+    assert!(!success);
+    assert!(report.starts_with("breaking:"));
+    assert_eq!(error, "error: breaking changes\n");
+}
@@ -0,0 +1,328 @@
+use genco::{quote, quote_in};
+
+use crate::registry::{BindingType, Container, EnumVariant, EnumVariantType};
+
+use super::{
+    container_namespace, identifier, std_item, variant_identifier, Tokens, ENUM_VALUE_MEMBER,
+};
+
+pub fn gen_deserializer_code(namespace: &str) -> Tokens {
+    let uint8_t = &std_item("cstdint", "std::uint8_t");
+    let size_t = &std_item("cstddef", "std::size_t");
+    let memcpy = &std_item("cstring", "std::memcpy");
+    let array = &std_item("array", "std::array");
+    let optional = &std_item("optional", "std::optional");
+    let tuple = &std_item("tuple", "std::tuple");
+    let apply = &std_item("tuple", "std::apply");
+    let move_ = &std_item("utility", "std::move");
+
+    quote! {
+        namespace $namespace::postcard {
+            class Deserializer {
+            public:
+                Deserializer(const $uint8_t* data, $size_t length) : data_(data), length_(length) {}
+
+                std::uint8_t read_byte() {
+                    if (position_ >= length_) { throw Error("unexpected end of input"); }
+                    return data_[position_++];
+                }
+
+                const std::uint8_t* read_bytes(std::size_t length) {
+                    if (length > length_ - position_) { throw Error("unexpected end of input"); }
+                    const std::uint8_t* bytes = data_ + position_;
+                    position_ += length;
+                    return bytes;
+                }
+
+                template <typename U>
+                U read_varint() {
+                    U value = 0;
+                    for (std::size_t i = 0; i < varint_max<U>(); i++) {
+                        std::uint8_t byte = read_byte();
+                        if (i == varint_max<U>() - 1 && byte > max_of_last_byte<U>()) { throw Error("bad varint"); }
+                        value |= static_cast<U>(static_cast<U>(byte & 0x7F) << (7 * i));
+                        if ((byte & 0x80) == 0) { return value; }
+                    }
+                    throw Error("bad varint");
+                }
+
+                std::size_t position() const { return position_; }
+
+            private:
+                const std::uint8_t* data_;
+                std::size_t length_;
+                std::size_t position_ = 0;
+            };
+
+            template <typename S, typename U>
+            S de_zig_zag(U value) {
+                return static_cast<S>(static_cast<U>((value >> 1) ^ static_cast<U>(U(0) - (value & 1))));
+            }
+
+            inline void deserialize(Deserializer& d, bool& v) {
+                std::uint8_t byte = d.read_byte();
+                if (byte > 1) { throw Error("bad bool"); }
+                v = byte == 1;
+            }
+            inline void deserialize(Deserializer& d, std::uint8_t& v) { v = d.read_byte(); }
+            inline void deserialize(Deserializer& d, std::int8_t& v) { v = static_cast<std::int8_t>(d.read_byte()); }
+            inline void deserialize(Deserializer& d, std::uint16_t& v) { v = d.read_varint<std::uint16_t>(); }
+            inline void deserialize(Deserializer& d, std::uint32_t& v) { v = d.read_varint<std::uint32_t>(); }
+            inline void deserialize(Deserializer& d, std::uint64_t& v) { v = d.read_varint<std::uint64_t>(); }
+            inline void deserialize(Deserializer& d, std::int16_t& v) { v = de_zig_zag<std::int16_t>(d.read_varint<std::uint16_t>()); }
+            inline void deserialize(Deserializer& d, std::int32_t& v) { v = de_zig_zag<std::int32_t>(d.read_varint<std::uint32_t>()); }
+            inline void deserialize(Deserializer& d, std::int64_t& v) { v = de_zig_zag<std::int64_t>(d.read_varint<std::uint64_t>()); }
+            $("#ifdef __SIZEOF_INT128__")
+            inline void deserialize(Deserializer& d, u128& v) { v = d.read_varint<u128>(); }
+            inline void deserialize(Deserializer& d, i128& v) { v = de_zig_zag<i128>(d.read_varint<u128>()); }
+            $("#endif")
+
+            inline void deserialize(Deserializer& d, float& v) {
+                const std::uint8_t* bytes = d.read_bytes(4);
+                std::uint32_t bits = 0;
+                for (std::size_t i = 0; i < sizeof(bits); i++) { bits |= static_cast<std::uint32_t>(bytes[i]) << (8 * i); }
+                $memcpy(&v, &bits, sizeof(bits));
+            }
+
+            inline void deserialize(Deserializer& d, double& v) {
+                const std::uint8_t* bytes = d.read_bytes(8);
+                std::uint64_t bits = 0;
+                for (std::size_t i = 0; i < sizeof(bits); i++) { bits |= static_cast<std::uint64_t>(bytes[i]) << (8 * i); }
+                std::memcpy(&v, &bits, sizeof(bits));
+            }
+
+            inline void deserialize(Deserializer& d, std::string& v) {
+                std::size_t length = d.read_varint<std::uint32_t>();
+                const std::uint8_t* bytes = d.read_bytes(length);
+                v.assign(reinterpret_cast<const char*>(bytes), length);
+            }
+
+            template <std::size_t N>
+            void deserialize(Deserializer& d, BoundedString<N>& v) {
+                deserialize(d, static_cast<std::string&>(v));
+                if (v.size() > N) { throw Error("string exceeds maximum length"); }
+            }
+
+            template <typename T, Endian E>
+            void deserialize(Deserializer& d, Fixint<T, E>& v) {
+                using U = typename Unsigned<T>::type;
+                const std::uint8_t* bytes = d.read_bytes(sizeof(U));
+                U bits = 0;
+                for (std::size_t i = 0; i < sizeof(U); i++) {
+                    std::size_t shift = E == Endian::Little ? i : sizeof(U) - 1 - i;
+                    bits |= static_cast<U>(static_cast<U>(bytes[i]) << (8 * shift));
+                }
+                v.value = static_cast<T>(bits);
+            }
+
+            template <typename T>
+            void deserialize(Deserializer& d, NonZero<T>& v) {
+                deserialize(d, v.value);
+                if (v.value == 0) { throw Error("non-zero value is zero"); }
+            }
+
+            template <typename T>
+            void deserialize(Deserializer& d, std::vector<T>& v) {
+                std::size_t length = d.read_varint<std::uint32_t>();
+                v.clear();
+                for (std::size_t i = 0; i < length; i++) {
+                    T item{};
+                    deserialize(d, item);
+                    v.push_back($move_(item));
+                }
+            }
+
+            template <typename T, std::size_t N>
+            void deserialize(Deserializer& d, BoundedVector<T, N>& v) {
+                deserialize(d, static_cast<std::vector<T>&>(v));
+                if (v.size() > N) { throw Error("array exceeds maximum length"); }
+            }
+
+            template <typename T, std::size_t N>
+            void deserialize(Deserializer& d, $array<T, N>& v) {
+                for (auto& item : v) { deserialize(d, item); }
+            }
+
+            template <typename T>
+            void deserialize(Deserializer& d, $optional<T>& v) {
+                switch (d.read_byte()) {
+                case 0:
+                    v.reset();
+                    break;
+                case 1: {
+                    T value{};
+                    deserialize(d, value);
+                    v = std::move(value);
+                    break;
+                }
+                default:
+                    throw Error("bad option tag");
+                }
+            }
+
+            template <typename K, typename V>
+            void deserialize(Deserializer& d, std::map<K, V>& v) {
+                std::size_t length = d.read_varint<std::uint32_t>();
+                v.clear();
+                for (std::size_t i = 0; i < length; i++) {
+                    K key{};
+                    V value{};
+                    deserialize(d, key);
+                    deserialize(d, value);
+                    v.emplace(std::move(key), std::move(value));
+                }
+            }
+
+            template <typename K, typename V, std::size_t N>
+            void deserialize(Deserializer& d, BoundedMap<K, V, N>& v) {
+                deserialize(d, static_cast<std::map<K, V>&>(v));
+                if (v.size() > N) { throw Error("map exceeds maximum length"); }
+            }
+
+            template <typename... T>
+            void deserialize(Deserializer& d, $tuple<T...>& v) {
+                $apply([&d](auto&... items) { (deserialize(d, items), ...); }, v);
+            }
+
+            template <typename T>
+            void deserialize(Deserializer& d, Range<T>& v) {
+                deserialize(d, v.start);
+                deserialize(d, v.end);
+            }
+        }
+    }
+}
+
+pub fn gen_des_functions(namespace: &str, containers: impl Iterator<Item = Container>) -> Tokens {
+    let containers = containers.collect::<Vec<_>>();
+    let deserializer = format!("::{namespace}::postcard::Deserializer");
+
+    let mut declarations = Tokens::new();
+    let mut definitions = Tokens::new();
+
+    for container in containers.iter() {
+        let container_namespace = container_namespace(namespace, &container.path);
+        let name = identifier(&container.name);
+
+        let mut functions = vec![(name.to_owned(), gen_container_body(namespace, container))];
+        if let BindingType::Enum(ty) = &container.r#type {
+            functions.extend(ty.variants.iter().map(|variant| {
+                (
+                    format!(
+                        "{name}::{}",
+                        variant_identifier(&container.name, &variant.name)
+                    ),
+                    gen_variant_body(variant),
+                )
+            }));
+        }
+
+        for (ty, body) in functions {
+            let args = if body.is_empty() {
+                quote!($(&deserializer)&, $(&ty)&)
+            } else {
+                quote!($(&deserializer)& d, $(&ty)& v)
+            };
+
+            quote_in! { declarations =>
+                namespace $(&container_namespace) { inline void deserialize($(args.clone())); }
+            };
+            declarations.push();
+
+            quote_in! { definitions =>
+                namespace $(&container_namespace) {
+                    inline void deserialize($args) {
+                        $body
+                    }
+                }
+            };
+            definitions.line();
+        }
+    }
+
+    quote! {
+        $declarations
+
+        $definitions
+    }
+}
+
+fn gen_container_body(namespace: &str, container: &Container) -> Tokens {
+    match &container.r#type {
+        BindingType::Struct(ty) => gen_fields_body(ty.fields.iter().map(|f| identifier(&f.name))),
+        BindingType::TupleStruct(ty) => {
+            gen_fields_body((0..ty.fields.len()).map(|i| format!("_{i}")))
+        }
+        BindingType::UnitStruct(_) => Tokens::new(),
+        BindingType::Enum(ty) => {
+            let uint32_t = &std_item("cstdint", "std::uint32_t");
+            let move_ = &std_item("utility", "std::move");
+            let name = identifier(&container.name);
+
+            let mut cases = Tokens::new();
+            for variant in ty.variants.iter() {
+                let variant_name = variant_identifier(&container.name, &variant.name);
+                quote_in! { cases =>
+                    case $(variant.index): {
+                        $(&name)::$variant_name variant{};
+                        deserialize(d, variant);
+                        v.$ENUM_VALUE_MEMBER = $move_(variant);
+                        break;
+                    }
+                };
+                cases.push();
+            }
+
+            quote! {
+                switch (d.read_varint<$uint32_t>()) {
+                $cases
+                default:
+                    throw ::$namespace::postcard::Error("unknown enum variant");
+                }
+            }
+        }
+    }
+}
+
+fn gen_variant_body(variant: &EnumVariant) -> Tokens {
+    match &variant.inner_type {
+        EnumVariantType::Empty => Tokens::new(),
+        EnumVariantType::Tuple(fields) => {
+            gen_fields_body((0..fields.len()).map(|i| format!("_{i}")))
+        }
+        EnumVariantType::NewType(fields) => {
+            gen_fields_body(fields.iter().map(|f| identifier(&f.name)))
+        }
+    }
+}
+
+fn gen_fields_body(fields: impl Iterator<Item = String>) -> Tokens {
+    let mut tokens = Tokens::new();
+    for field in fields {
+        quote_in!(tokens => deserialize(d, v.$field););
+        tokens.push();
+    }
+    tokens
+}
+
+pub fn gen_deserialize_func(namespace: &str) -> Tokens {
+    let vector = &std_item("vector", "std::vector");
+    let pair = &std_item("utility", "std::pair");
+
+    quote! {
+        namespace $namespace {
+            template <typename T>
+            $pair<T, $vector<std::uint8_t>> deserialize(const std::uint8_t* data, std::size_t length) {
+                postcard::Deserializer d(data, length);
+                T value{};
+                deserialize(d, value);
+                return {std::move(value), std::vector<std::uint8_t>(data + d.position(), data + length)};
+            }
+
+            template <typename T>
+            std::pair<T, std::vector<std::uint8_t>> deserialize(const std::vector<std::uint8_t>& bytes) {
+                return deserialize<T>(bytes.data(), bytes.size());
+            }
+        }
+    }
+}
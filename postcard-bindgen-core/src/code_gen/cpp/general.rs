@@ -0,0 +1,88 @@
+use genco::quote;
+
+use super::{std_item, Tokens};
+
+pub fn gen_runtime(namespace: &str) -> Tokens {
+    let uint8_t = &std_item("cstdint", "std::uint8_t");
+    let size_t = &std_item("cstddef", "std::size_t");
+    let runtime_error = &std_item("stdexcept", "std::runtime_error");
+    let string = &std_item("string", "std::string");
+    let vector = &std_item("vector", "std::vector");
+    let map = &std_item("map", "std::map");
+    let make_unsigned = &std_item("type_traits", "std::make_unsigned_t");
+
+    quote! {
+        namespace $namespace::postcard {
+            struct Error : $runtime_error {
+                using std::runtime_error::runtime_error;
+            };
+
+            $("#ifdef __SIZEOF_INT128__")
+            using u128 = unsigned __int128;
+            using i128 = __int128;
+            $("#endif")
+
+            template <typename T>
+            struct NonZero {
+                T value{};
+            };
+
+            enum class Endian { Little, Big };
+
+            template <typename T, Endian E>
+            struct Fixint {
+                T value{};
+
+                operator T() const { return value; }
+            };
+
+            template <typename T>
+            struct Unsigned {
+                using type = $make_unsigned<T>;
+            };
+
+            $("#ifdef __SIZEOF_INT128__")
+            template <>
+            struct Unsigned<i128> {
+                using type = u128;
+            };
+
+            template <>
+            struct Unsigned<u128> {
+                using type = u128;
+            };
+            $("#endif")
+
+            template <$size_t N>
+            struct BoundedString : $string {
+                using std::string::string;
+            };
+
+            template <typename T, std::size_t N>
+            struct BoundedVector : $vector<T> {
+                using std::vector<T>::vector;
+            };
+
+            template <typename K, typename V, std::size_t N>
+            struct BoundedMap : $map<K, V> {
+                using std::map<K, V>::map;
+            };
+
+            template <typename T>
+            struct Range {
+                T start{};
+                T end{};
+            };
+
+            template <typename U>
+            constexpr std::size_t varint_max() {
+                return (sizeof(U) * 8 + 6) / 7;
+            }
+
+            template <typename U>
+            constexpr $uint8_t max_of_last_byte() {
+                return static_cast<std::uint8_t>((1 << ((sizeof(U) * 8) % 7)) - 1);
+            }
+        }
+    }
+}
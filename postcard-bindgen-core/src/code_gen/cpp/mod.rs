@@ -0,0 +1,281 @@
+mod des;
+mod general;
+mod ser;
+mod types;
+
+use core::borrow::Borrow;
+
+use des::{gen_des_functions, gen_deserialize_func, gen_deserializer_code};
+use genco::lang::c::{self, C};
+use general::gen_runtime;
+use ser::{gen_ser_functions, gen_serialize_func, gen_serializer_code};
+use types::gen_type_definitions;
+
+use crate::{
+    path::PathBuf, registry::ContainerCollection, type_info::ObjectMeta,
+    validation::ValidationSettings, ExportFile, Exports,
+};
+
+use super::utils::snake_case;
+
+type Tokens = genco::Tokens<C>;
+
+/// Settings for C++ bindings generation.
+///
+/// The generated code is a single C++17 header. Types are plain structs, enums hold their
+/// variants in a `std::variant`.
+///
+/// By default, only deserialization is enabled. Serialization can be enabled by using [`GenerationSettings::serialization()`].
+/// Deserialization can be disabled with [`GenerationSettings::deserialization()`].
+/// To enable all at once use [`GenerationSettings::enable_all()`].
+#[derive(Debug)]
+pub struct GenerationSettings {
+    ser: bool,
+    des: bool,
+    module_structure: bool,
+}
+
+impl GenerationSettings {
+    /// Constructs [`GenerationSettings`] and enables all options at once.
+    pub fn enable_all() -> Self {
+        Self {
+            ser: true,
+            des: true,
+            module_structure: true,
+        }
+    }
+
+    /// Enabling or disabling of serialization code generation.
+    pub fn serialization(mut self, enabled: bool) -> Self {
+        self.ser = enabled;
+        self
+    }
+
+    /// Enabling or disabling of deserialization code generation.
+    pub fn deserialization(mut self, enabled: bool) -> Self {
+        self.des = enabled;
+        self
+    }
+
+    /// Enabling or disabling of module structure code generation.
+    ///
+    /// Enabling this will generate the types in the same module structure
+    /// as in rust. Root level types will be in the package namespace. Types
+    /// nested in modules will be in nested namespaces
+    /// (e.g. <package>::<mod_name>::<type_name>). This avoids name clashes.
+    ///
+    /// Disabling this will generate all types in the package namespace.
+    pub fn module_structure(mut self, enabled: bool) -> Self {
+        self.module_structure = enabled;
+        self
+    }
+}
+
+impl Default for GenerationSettings {
+    fn default() -> Self {
+        Self {
+            ser: false,
+            des: true,
+            module_structure: true,
+        }
+    }
+}
+
+impl From<&GenerationSettings> for ValidationSettings {
+    fn from(settings: &GenerationSettings) -> Self {
+        ValidationSettings::default().flattened(!settings.module_structure)
+    }
+}
+
+/// Generates the C++ code for the containers.
+///
+/// All code is placed in the namespace named after the package, the runtime in its
+/// `postcard` sub namespace.
+pub fn generate(
+    mut containers: ContainerCollection,
+    gen_settings: impl Borrow<GenerationSettings>,
+    package_name: String,
+) -> Exports<C> {
+    let gen_settings = gen_settings.borrow();
+    let namespace = snake_case(&package_name);
+
+    if !gen_settings.module_structure {
+        containers.flatten();
+    }
+
+    let mut files = vec![
+        ExportFile {
+            content_type: "runtime".to_owned(),
+            content: gen_runtime(&namespace),
+        },
+        ExportFile {
+            content_type: "types".to_owned(),
+            content: gen_type_definitions(&namespace, containers.all_containers()),
+        },
+    ];
+
+    if gen_settings.ser {
+        files.push(ExportFile {
+            content_type: "serializer".to_owned(),
+            content: gen_serializer_code(&namespace),
+        });
+
+        let mut ser = gen_ser_functions(&namespace, containers.all_containers());
+        ser.line();
+        ser.append(gen_serialize_func(&namespace));
+        files.push(ExportFile {
+            content_type: "ser".to_owned(),
+            content: ser,
+        });
+    }
+
+    if gen_settings.des {
+        files.push(ExportFile {
+            content_type: "deserializer".to_owned(),
+            content: gen_deserializer_code(&namespace),
+        });
+
+        let mut des = gen_des_functions(&namespace, containers.all_containers());
+        des.line();
+        des.append(gen_deserialize_func(&namespace));
+        files.push(ExportFile {
+            content_type: "des".to_owned(),
+            content: des,
+        });
+    }
+
+    Exports { files }
+}
+
+fn std_item(header: &'static str, item: &'static str) -> c::Import {
+    c::include_system(header, item)
+}
+
+/// Namespace the container is placed in.
+fn container_namespace(namespace: &str, path: &crate::path::Path<'_, '_>) -> String {
+    let mut path: PathBuf = path.clone().into_buf();
+    // We will skip the first part of the path, as it is the crate name.
+    path.pop_front();
+    path.parts()
+        .map(|part| identifier(part.as_ref()))
+        .fold(namespace.to_owned(), |ns, part| format!("{ns}::{part}"))
+}
+
+/// Fully qualified name of the referenced container, usable from any namespace.
+fn full_qualified_object_name(namespace: &str, meta: &ObjectMeta) -> String {
+    format!(
+        "::{}::{}",
+        container_namespace(namespace, &meta.path),
+        identifier(&meta.name)
+    )
+}
+
+/// Name of the struct holding an enum variant, nested in the enum struct.
+fn variant_identifier(enum_name: &str, variant_name: &str) -> String {
+    let variant = identifier(variant_name);
+    // members can't be named like the enclosing class
+    if variant == identifier(enum_name) || variant == ENUM_VALUE_MEMBER {
+        format!("{variant}_")
+    } else {
+        variant
+    }
+}
+
+/// Member of the enum struct holding the `std::variant`.
+const ENUM_VALUE_MEMBER: &str = "value";
+
+const KEYWORDS: &[&str] = &[
+    "alignas",
+    "alignof",
+    "and",
+    "and_eq",
+    "asm",
+    "auto",
+    "bitand",
+    "bitor",
+    "bool",
+    "break",
+    "case",
+    "catch",
+    "char",
+    "char16_t",
+    "char32_t",
+    "class",
+    "compl",
+    "const",
+    "const_cast",
+    "constexpr",
+    "continue",
+    "decltype",
+    "default",
+    "delete",
+    "do",
+    "double",
+    "dynamic_cast",
+    "else",
+    "enum",
+    "explicit",
+    "export",
+    "extern",
+    "false",
+    "float",
+    "for",
+    "friend",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "mutable",
+    "namespace",
+    "new",
+    "noexcept",
+    "not",
+    "not_eq",
+    "nullptr",
+    "operator",
+    "or",
+    "or_eq",
+    "private",
+    "protected",
+    "public",
+    "register",
+    "reinterpret_cast",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "static_assert",
+    "static_cast",
+    "struct",
+    "switch",
+    "template",
+    "this",
+    "thread_local",
+    "throw",
+    "true",
+    "try",
+    "typedef",
+    "typeid",
+    "typename",
+    "union",
+    "unsigned",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "wchar_t",
+    "while",
+    "xor",
+    "xor_eq",
+];
+
+/// Escapes names which are C++ keywords.
+fn identifier(name: &str) -> String {
+    if KEYWORDS.contains(&name) {
+        format!("{name}_")
+    } else {
+        name.to_owned()
+    }
+}
@@ -0,0 +1,261 @@
+use genco::{quote, quote_in};
+
+use crate::registry::{BindingType, Container, EnumVariant, EnumVariantType};
+
+use super::{
+    container_namespace, identifier, std_item, variant_identifier, Tokens, ENUM_VALUE_MEMBER,
+};
+
+pub fn gen_serializer_code(namespace: &str) -> Tokens {
+    let uint8_t = &std_item("cstdint", "std::uint8_t");
+    let memcpy = &std_item("cstring", "std::memcpy");
+    let array = &std_item("array", "std::array");
+    let optional = &std_item("optional", "std::optional");
+    let tuple = &std_item("tuple", "std::tuple");
+    let apply = &std_item("tuple", "std::apply");
+    let move_ = &std_item("utility", "std::move");
+
+    quote! {
+        namespace $namespace::postcard {
+            class Serializer {
+            public:
+                void write_byte($uint8_t byte) { bytes_.push_back(byte); }
+
+                void write_bytes(const std::uint8_t* data, std::size_t length) { bytes_.insert(bytes_.end(), data, data + length); }
+
+                template <typename U>
+                void write_varint(U value) {
+                    while (value >= 0x80) {
+                        write_byte(static_cast<std::uint8_t>((value & 0x7F) | 0x80));
+                        value >>= 7;
+                    }
+                    write_byte(static_cast<std::uint8_t>(value));
+                }
+
+                std::vector<std::uint8_t> finish() { return $move_(bytes_); }
+
+            private:
+                std::vector<std::uint8_t> bytes_;
+            };
+
+            template <typename S, typename U>
+            U zig_zag(S value) {
+                return static_cast<U>((static_cast<U>(value) << 1) ^ static_cast<U>(value >> (sizeof(S) * 8 - 1)));
+            }
+
+            inline void serialize(Serializer& s, bool v) { s.write_byte(v ? 1 : 0); }
+            inline void serialize(Serializer& s, std::uint8_t v) { s.write_byte(v); }
+            inline void serialize(Serializer& s, std::int8_t v) { s.write_byte(static_cast<std::uint8_t>(v)); }
+            inline void serialize(Serializer& s, std::uint16_t v) { s.write_varint(v); }
+            inline void serialize(Serializer& s, std::uint32_t v) { s.write_varint(v); }
+            inline void serialize(Serializer& s, std::uint64_t v) { s.write_varint(v); }
+            inline void serialize(Serializer& s, std::int16_t v) { s.write_varint(zig_zag<std::int16_t, std::uint16_t>(v)); }
+            inline void serialize(Serializer& s, std::int32_t v) { s.write_varint(zig_zag<std::int32_t, std::uint32_t>(v)); }
+            inline void serialize(Serializer& s, std::int64_t v) { s.write_varint(zig_zag<std::int64_t, std::uint64_t>(v)); }
+            $("#ifdef __SIZEOF_INT128__")
+            inline void serialize(Serializer& s, u128 v) { s.write_varint(v); }
+            inline void serialize(Serializer& s, i128 v) { s.write_varint(zig_zag<i128, u128>(v)); }
+            $("#endif")
+
+            inline void serialize(Serializer& s, float v) {
+                std::uint32_t bits;
+                $memcpy(&bits, &v, sizeof(bits));
+                for (std::size_t i = 0; i < sizeof(bits); i++) { s.write_byte(static_cast<std::uint8_t>(bits >> (8 * i))); }
+            }
+
+            inline void serialize(Serializer& s, double v) {
+                std::uint64_t bits;
+                std::memcpy(&bits, &v, sizeof(bits));
+                for (std::size_t i = 0; i < sizeof(bits); i++) { s.write_byte(static_cast<std::uint8_t>(bits >> (8 * i))); }
+            }
+
+            inline void serialize(Serializer& s, const std::string& v) {
+                s.write_varint(static_cast<std::uint32_t>(v.size()));
+                s.write_bytes(reinterpret_cast<const std::uint8_t*>(v.data()), v.size());
+            }
+
+            template <std::size_t N>
+            void serialize(Serializer& s, const BoundedString<N>& v) {
+                if (v.size() > N) { throw Error("string exceeds maximum length"); }
+                serialize(s, static_cast<const std::string&>(v));
+            }
+
+            template <typename T, Endian E>
+            void serialize(Serializer& s, const Fixint<T, E>& v) {
+                using U = typename Unsigned<T>::type;
+                U bits = static_cast<U>(v.value);
+                for (std::size_t i = 0; i < sizeof(U); i++) {
+                    std::size_t shift = E == Endian::Little ? i : sizeof(U) - 1 - i;
+                    s.write_byte(static_cast<std::uint8_t>(bits >> (8 * shift)));
+                }
+            }
+
+            template <typename T>
+            void serialize(Serializer& s, const NonZero<T>& v) {
+                if (v.value == 0) { throw Error("non-zero value is zero"); }
+                serialize(s, v.value);
+            }
+
+            template <typename T>
+            void serialize(Serializer& s, const std::vector<T>& v) {
+                s.write_varint(static_cast<std::uint32_t>(v.size()));
+                for (const auto& item : v) { serialize(s, item); }
+            }
+
+            template <typename T, std::size_t N>
+            void serialize(Serializer& s, const BoundedVector<T, N>& v) {
+                if (v.size() > N) { throw Error("array exceeds maximum length"); }
+                serialize(s, static_cast<const std::vector<T>&>(v));
+            }
+
+            template <typename T, std::size_t N>
+            void serialize(Serializer& s, const $array<T, N>& v) {
+                for (const auto& item : v) { serialize(s, item); }
+            }
+
+            template <typename T>
+            void serialize(Serializer& s, const $optional<T>& v) {
+                if (v) {
+                    s.write_byte(1);
+                    serialize(s, *v);
+                } else {
+                    s.write_byte(0);
+                }
+            }
+
+            template <typename K, typename V>
+            void serialize(Serializer& s, const std::map<K, V>& v) {
+                s.write_varint(static_cast<std::uint32_t>(v.size()));
+                for (const auto& [key, value] : v) {
+                    serialize(s, key);
+                    serialize(s, value);
+                }
+            }
+
+            template <typename K, typename V, std::size_t N>
+            void serialize(Serializer& s, const BoundedMap<K, V, N>& v) {
+                if (v.size() > N) { throw Error("map exceeds maximum length"); }
+                serialize(s, static_cast<const std::map<K, V>&>(v));
+            }
+
+            template <typename... T>
+            void serialize(Serializer& s, const $tuple<T...>& v) {
+                $apply([&s](const auto&... items) { (serialize(s, items), ...); }, v);
+            }
+
+            template <typename T>
+            void serialize(Serializer& s, const Range<T>& v) {
+                serialize(s, v.start);
+                serialize(s, v.end);
+            }
+        }
+    }
+}
+
+pub fn gen_ser_functions(namespace: &str, containers: impl Iterator<Item = Container>) -> Tokens {
+    let containers = containers.collect::<Vec<_>>();
+    let serializer = format!("::{namespace}::postcard::Serializer");
+
+    let mut declarations = Tokens::new();
+    let mut definitions = Tokens::new();
+
+    for container in containers.iter() {
+        let container_namespace = container_namespace(namespace, &container.path);
+        let name = identifier(&container.name);
+
+        let mut functions = vec![(name.to_owned(), gen_container_body(container))];
+        if let BindingType::Enum(ty) = &container.r#type {
+            functions.extend(ty.variants.iter().map(|variant| {
+                (
+                    format!(
+                        "{name}::{}",
+                        variant_identifier(&container.name, &variant.name)
+                    ),
+                    gen_variant_body(variant),
+                )
+            }));
+        }
+
+        for (ty, body) in functions {
+            let args = if body.is_empty() {
+                quote!($(&serializer)&, const $(&ty)&)
+            } else {
+                quote!($(&serializer)& s, const $(&ty)& v)
+            };
+
+            quote_in! { declarations =>
+                namespace $(&container_namespace) { inline void serialize($(args.clone())); }
+            };
+            declarations.push();
+
+            quote_in! { definitions =>
+                namespace $(&container_namespace) {
+                    inline void serialize($args) {
+                        $body
+                    }
+                }
+            };
+            definitions.line();
+        }
+    }
+
+    quote! {
+        $declarations
+
+        $definitions
+    }
+}
+
+fn gen_container_body(container: &Container) -> Tokens {
+    match &container.r#type {
+        BindingType::Struct(ty) => gen_fields_body(ty.fields.iter().map(|f| identifier(&f.name))),
+        BindingType::TupleStruct(ty) => {
+            gen_fields_body((0..ty.fields.len()).map(|i| format!("_{i}")))
+        }
+        BindingType::UnitStruct(_) => Tokens::new(),
+        BindingType::Enum(_) => {
+            let uint32_t = &std_item("cstdint", "std::uint32_t");
+            let visit = &std_item("variant", "std::visit");
+            quote! {
+                s.write_varint(static_cast<$uint32_t>(v.$ENUM_VALUE_MEMBER.index()));
+                $visit([&s](const auto& variant) { serialize(s, variant); }, v.$ENUM_VALUE_MEMBER);
+            }
+        }
+    }
+}
+
+fn gen_variant_body(variant: &EnumVariant) -> Tokens {
+    match &variant.inner_type {
+        EnumVariantType::Empty => Tokens::new(),
+        EnumVariantType::Tuple(fields) => {
+            gen_fields_body((0..fields.len()).map(|i| format!("_{i}")))
+        }
+        EnumVariantType::NewType(fields) => {
+            gen_fields_body(fields.iter().map(|f| identifier(&f.name)))
+        }
+    }
+}
+
+fn gen_fields_body(fields: impl Iterator<Item = String>) -> Tokens {
+    let mut tokens = Tokens::new();
+    for field in fields {
+        quote_in!(tokens => serialize(s, v.$field););
+        tokens.push();
+    }
+    tokens
+}
+
+pub fn gen_serialize_func(namespace: &str) -> Tokens {
+    let vector = &std_item("vector", "std::vector");
+
+    quote! {
+        namespace $namespace {
+            template <typename T>
+            $vector<std::uint8_t> serialize(const T& value) {
+                postcard::Serializer s;
+                serialize(s, value);
+                return s.finish();
+            }
+        }
+    }
+}
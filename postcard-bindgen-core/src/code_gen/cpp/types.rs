@@ -0,0 +1,240 @@
+use std::collections::BTreeSet;
+
+use genco::{quote, quote_in};
+
+use crate::{
+    registry::{BindingType, Container, EnumVariantType, StructField},
+    type_info::{IntegerEncoding, NumberMeta, ValueType},
+};
+
+use super::{
+    container_namespace, full_qualified_object_name, identifier, std_item, variant_identifier,
+    Tokens, ENUM_VALUE_MEMBER,
+};
+
+pub fn gen_type_definitions(
+    namespace: &str,
+    containers: impl Iterator<Item = Container>,
+) -> Tokens {
+    let containers = containers.collect::<Vec<_>>();
+
+    let mut forward_declarations = Tokens::new();
+    for container in containers.iter() {
+        let container_namespace = container_namespace(namespace, &container.path);
+        quote_in! { forward_declarations =>
+            namespace $container_namespace { struct $(identifier(&container.name)); }
+        };
+        forward_declarations.push();
+    }
+
+    let mut definitions = Tokens::new();
+    let mut blocks = Vec::<(String, Tokens)>::new();
+    for container in definition_order(&containers) {
+        let container_namespace = container_namespace(namespace, &container.path);
+        let definition = gen_container_definition(namespace, container);
+        match blocks.last_mut() {
+            Some((ns, tokens)) if *ns == container_namespace => {
+                tokens.line();
+                tokens.append(definition);
+            }
+            _ => blocks.push((container_namespace, definition)),
+        }
+    }
+    for (container_namespace, block) in blocks {
+        quote_in! { definitions =>
+            namespace $container_namespace {
+                $block
+            }
+        };
+        definitions.line();
+    }
+
+    quote! {
+        $forward_declarations
+
+        $definitions
+    }
+}
+
+/// Orders the containers so that every container is defined after the containers it
+/// references. C++ needs complete types for members.
+fn definition_order(containers: &[Container]) -> Vec<&Container> {
+    fn visit<'a>(
+        containers: &'a [Container],
+        index: usize,
+        visited: &mut BTreeSet<usize>,
+        ordered: &mut Vec<&'a Container>,
+    ) {
+        if !visited.insert(index) {
+            return;
+        }
+
+        for object in containers[index].r#type.referenced_objects() {
+            if let Some(dependency) = containers
+                .iter()
+                .position(|c| c.name == object.name && c.path == object.path)
+            {
+                visit(containers, dependency, visited, ordered);
+            }
+        }
+
+        ordered.push(&containers[index]);
+    }
+
+    let mut visited = BTreeSet::new();
+    let mut ordered = Vec::with_capacity(containers.len());
+    for index in 0..containers.len() {
+        visit(containers, index, &mut visited, &mut ordered);
+    }
+    ordered
+}
+
+fn gen_container_definition(namespace: &str, container: &Container) -> Tokens {
+    let name = identifier(&container.name);
+    match &container.r#type {
+        BindingType::Struct(ty) => gen_struct(&name, gen_struct_fields(namespace, &ty.fields)),
+        BindingType::TupleStruct(ty) => gen_struct(&name, gen_tuple_fields(namespace, &ty.fields)),
+        BindingType::UnitStruct(_) => gen_struct(&name, Tokens::new()),
+        BindingType::Enum(ty) => {
+            let variant = &std_item("variant", "std::variant");
+            let variant_names = ty
+                .variants
+                .iter()
+                .map(|v| variant_identifier(&container.name, &v.name))
+                .collect::<Vec<_>>();
+
+            let mut body = Tokens::new();
+            for (variant, variant_name) in ty.variants.iter().zip(variant_names.iter()) {
+                let fields = match &variant.inner_type {
+                    EnumVariantType::Empty => Tokens::new(),
+                    EnumVariantType::Tuple(fields) => gen_tuple_fields(namespace, fields),
+                    EnumVariantType::NewType(fields) => gen_struct_fields(namespace, fields),
+                };
+                body.append(gen_struct(variant_name, fields));
+                body.push();
+            }
+            quote_in! { body =>
+                $variant<$(for name in variant_names.iter() join (, ) => $name)> $ENUM_VALUE_MEMBER{};
+            };
+
+            gen_struct(&name, body)
+        }
+    }
+}
+
+fn gen_struct(name: &str, body: Tokens) -> Tokens {
+    if body.is_empty() {
+        quote!(struct $name {};)
+    } else {
+        quote! {
+            struct $name {
+                $body
+            };
+        }
+    }
+}
+
+fn gen_struct_fields(namespace: &str, fields: &[StructField]) -> Tokens {
+    let mut tokens = Tokens::new();
+    for field in fields {
+        quote_in!(tokens => $(cpp_type(namespace, &field.v_type)) $(identifier(&field.name)){};);
+        tokens.push();
+    }
+    tokens
+}
+
+fn gen_tuple_fields(namespace: &str, fields: &[ValueType]) -> Tokens {
+    let mut tokens = Tokens::new();
+    for (index, field) in fields.iter().enumerate() {
+        quote_in!(tokens => $(cpp_type(namespace, field)) $(format!("_{index}")){};);
+        tokens.push();
+    }
+    tokens
+}
+
+/// C++ type a [ValueType] is represented with.
+fn cpp_type(namespace: &str, value_type: &ValueType) -> Tokens {
+    let runtime = format!("::{namespace}::postcard");
+    match value_type {
+        ValueType::Bool(_) => quote!(bool),
+        ValueType::Number(NumberMeta::FloatingPoint { bytes }) => match bytes {
+            4 => quote!(float),
+            8 => quote!(double),
+            _ => unreachable!("unsupported floating point size {bytes}"),
+        },
+        ValueType::Number(NumberMeta::Integer {
+            bytes,
+            signed,
+            zero_able,
+            encoding,
+        }) => {
+            let int = match (bytes, signed) {
+                (16, false) => quote!($(&runtime)::u128),
+                (16, true) => quote!($(&runtime)::i128),
+                (bytes, false) => quote!($(std_item("cstdint", int_name(*bytes, false)))),
+                (bytes, true) => quote!($(std_item("cstdint", int_name(*bytes, true)))),
+            };
+            let int = match encoding {
+                IntegerEncoding::Varint => int,
+                IntegerEncoding::FixedLe => {
+                    quote!($(&runtime)::Fixint<$int, $(&runtime)::Endian::Little>)
+                }
+                IntegerEncoding::FixedBe => {
+                    quote!($(&runtime)::Fixint<$int, $(&runtime)::Endian::Big>)
+                }
+            };
+            if *zero_able {
+                int
+            } else {
+                quote!($(&runtime)::NonZero<$int>)
+            }
+        }
+        ValueType::String(meta) => match meta.max_length {
+            Some(max_length) => quote!($(&runtime)::BoundedString<$max_length>),
+            None => quote!($(std_item("string", "std::string"))),
+        },
+        ValueType::Array(meta) => {
+            let items = cpp_type(namespace, &meta.items_type);
+            match (meta.length, meta.max_length) {
+                (Some(length), _) => quote!($(std_item("array", "std::array"))<$items, $length>),
+                (None, Some(max_length)) => {
+                    quote!($(&runtime)::BoundedVector<$items, $max_length>)
+                }
+                (None, None) => quote!($(std_item("vector", "std::vector"))<$items>),
+            }
+        }
+        ValueType::Map(meta) => {
+            let key = cpp_type(namespace, &meta.key_type);
+            let value = cpp_type(namespace, &meta.value_type);
+            match meta.max_length {
+                Some(max_length) => quote!($(&runtime)::BoundedMap<$key, $value, $max_length>),
+                None => quote!($(std_item("map", "std::map"))<$key, $value>),
+            }
+        }
+        ValueType::Optional(meta) => {
+            quote!($(std_item("optional", "std::optional"))<$(cpp_type(namespace, &meta.inner))>)
+        }
+        ValueType::Tuple(meta) => {
+            let items = meta.items_types.iter().map(|t| cpp_type(namespace, t));
+            quote!($(std_item("tuple", "std::tuple"))<$(for item in items join (, ) => $item)>)
+        }
+        ValueType::Range(meta) => {
+            quote!($(&runtime)::Range<$(cpp_type(namespace, &meta.bounds_type))>)
+        }
+        ValueType::Object(meta) => quote!($(full_qualified_object_name(namespace, meta))),
+    }
+}
+
+fn int_name(bytes: usize, signed: bool) -> &'static str {
+    match (bytes, signed) {
+        (1, false) => "std::uint8_t",
+        (2, false) => "std::uint16_t",
+        (4, false) => "std::uint32_t",
+        (8, false) => "std::uint64_t",
+        (1, true) => "std::int8_t",
+        (2, true) => "std::int16_t",
+        (4, true) => "std::int32_t",
+        (8, true) => "std::int64_t",
+        _ => unreachable!("unsupported integer size {bytes}"),
+    }
+}
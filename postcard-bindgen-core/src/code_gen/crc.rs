@@ -0,0 +1,50 @@
+/// Parameters of a 32 bit CRC algorithm, named like in the catalog of the `crc` crate.
+pub(super) struct Crc32Algorithm {
+    pub(super) name: &'static str,
+    pub(super) poly: u32,
+    pub(super) init: u32,
+    pub(super) refin: bool,
+    pub(super) refout: bool,
+    pub(super) xorout: u32,
+}
+
+impl Crc32Algorithm {
+    /// All algorithms in the catalog reflect either both the input and the output or neither.
+    const fn new(name: &'static str, poly: u32, init: u32, reflected: bool, xorout: u32) -> Self {
+        Self {
+            name,
+            poly,
+            init,
+            refin: reflected,
+            refout: reflected,
+            xorout,
+        }
+    }
+}
+
+/// The 32 bit algorithms of the `crc` crate catalog, which can be used with
+/// `postcard::to_slice_crc32` and `postcard::from_bytes_crc32`.
+pub(super) const CRC_32_ALGORITHMS: &[Crc32Algorithm] = &[
+    Crc32Algorithm::new("CRC_32_AIXM", 0x814141ab, 0x00000000, false, 0x00000000),
+    Crc32Algorithm::new("CRC_32_AUTOSAR", 0xf4acfb13, 0xffffffff, true, 0xffffffff),
+    Crc32Algorithm::new("CRC_32_BASE91_D", 0xa833982b, 0xffffffff, true, 0xffffffff),
+    Crc32Algorithm::new("CRC_32_BZIP2", 0x04c11db7, 0xffffffff, false, 0xffffffff),
+    Crc32Algorithm::new(
+        "CRC_32_CD_ROM_EDC",
+        0x8001801b,
+        0x00000000,
+        true,
+        0x00000000,
+    ),
+    Crc32Algorithm::new("CRC_32_CKSUM", 0x04c11db7, 0x00000000, false, 0xffffffff),
+    Crc32Algorithm::new("CRC_32_ISCSI", 0x1edc6f41, 0xffffffff, true, 0xffffffff),
+    Crc32Algorithm::new("CRC_32_ISO_HDLC", 0x04c11db7, 0xffffffff, true, 0xffffffff),
+    Crc32Algorithm::new("CRC_32_JAMCRC", 0x04c11db7, 0xffffffff, true, 0x00000000),
+    Crc32Algorithm::new("CRC_32_MEF", 0x741b8cd7, 0xffffffff, true, 0x00000000),
+    Crc32Algorithm::new("CRC_32_MPEG_2", 0x04c11db7, 0xffffffff, false, 0x00000000),
+    Crc32Algorithm::new("CRC_32_XFER", 0x000000af, 0x00000000, false, 0x00000000),
+];
+
+pub(super) fn hex(value: u32) -> String {
+    format!("0x{value:08X}")
+}
@@ -0,0 +1,77 @@
+use genco::quote;
+
+use crate::type_info::ValueType;
+
+use super::{
+    full_qualified_object_name,
+    ser::{fixint, is_non_zero, number_suffix},
+    types::csharp_type,
+    Tokens,
+};
+
+/// Expression deserializing a value with the reader `reader`.
+pub(super) fn gen_des_value(namespace: &str, value_type: &ValueType) -> Tokens {
+    match value_type {
+        ValueType::Bool(_) => quote!(reader.ReadBool()),
+        ValueType::Number(meta) => {
+            let read = match fixint(meta) {
+                Some((16, little_endian)) => quote! {
+                    unchecked(($(csharp_type(namespace, value_type)))reader.ReadFixint128($(little_endian.to_string())))
+                },
+                Some((bytes, little_endian)) => quote! {
+                    unchecked(($(csharp_type(namespace, value_type)))reader.ReadFixint($bytes, $(little_endian.to_string())))
+                },
+                None => quote!(reader.$(format!("Read{}", number_suffix(meta)))()),
+            };
+            if is_non_zero(meta) {
+                quote!(PostcardChecks.NonZero($read))
+            } else {
+                read
+            }
+        }
+        ValueType::String(meta) => match meta.max_length {
+            Some(max_length) => quote!(reader.ReadString(maxLength: $max_length)),
+            None => quote!(reader.ReadString()),
+        },
+        ValueType::Array(meta) => {
+            let inner = gen_des_value(namespace, &meta.items_type);
+            match (meta.length, meta.max_length) {
+                (Some(length), _) => quote!(reader.ReadArray($length, () => $inner)),
+                (None, Some(max_length)) => {
+                    quote!(reader.ReadList(() => $inner, maxLength: $max_length))
+                }
+                (None, None) => quote!(reader.ReadList(() => $inner)),
+            }
+        }
+        ValueType::Map(meta) => {
+            let key = gen_des_value(namespace, &meta.key_type);
+            let value = gen_des_value(namespace, &meta.value_type);
+            match meta.max_length {
+                Some(max_length) => {
+                    quote!(reader.ReadMap(() => $key, () => $value, maxLength: $max_length))
+                }
+                None => quote!(reader.ReadMap(() => $key, () => $value)),
+            }
+        }
+        ValueType::Optional(meta) => quote! {
+            (reader.ReadOptionTag() ? ($(csharp_type(namespace, value_type)))$(gen_des_value(namespace, &meta.inner)) : null)
+        },
+        // a tuple with a single item is represented by the item itself
+        ValueType::Tuple(meta) if meta.items_types.len() == 1 => {
+            gen_des_value(namespace, &meta.items_types[0])
+        }
+        ValueType::Tuple(meta) => {
+            let items = meta
+                .items_types
+                .iter()
+                .map(|ty| gen_des_value(namespace, ty));
+            quote!(($(for item in items join (, ) => $item)))
+        }
+        ValueType::Range(meta) => {
+            quote!(reader.ReadRange(() => $(gen_des_value(namespace, &meta.bounds_type))))
+        }
+        ValueType::Object(meta) => {
+            quote!($(full_qualified_object_name(namespace, meta)).Deserialize(reader))
+        }
+    }
+}
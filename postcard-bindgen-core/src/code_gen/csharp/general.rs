@@ -0,0 +1,379 @@
+use genco::quote;
+
+use super::{GenerationSettings, Tokens};
+
+/// Generates the runtime shared by all generated records.
+pub(super) fn gen_runtime(namespace: &str, gen_settings: &GenerationSettings) -> Tokens {
+    let mut classes = Tokens::new();
+    if gen_settings.ser {
+        classes.line();
+        classes.append(gen_writer());
+    }
+    if gen_settings.des {
+        classes.line();
+        classes.append(gen_reader());
+    }
+
+    quote! {
+        $("#nullable enable")
+
+        namespace $namespace
+        {
+            public sealed class PostcardException : System.Exception
+            {
+                public PostcardException(string message) : base(message)
+                {
+                }
+            }
+
+            public sealed record PostcardRange<T>(T Start, T End);
+
+            public static class PostcardChecks
+            {
+                public static T NonZero<T>(T value) where T : System.Numerics.INumberBase<T>
+                {
+                    if (T.IsZero(value))
+                    {
+                        throw new PostcardException("zero value for a non-zero integer");
+                    }
+                    return value;
+                }
+            }
+            $classes
+        }
+    }
+}
+
+fn gen_writer() -> Tokens {
+    quote! {
+        public sealed class PostcardWriter
+        {
+            private readonly System.Collections.Generic.List<byte> buffer = new();
+
+            public byte[] ToArray() => buffer.ToArray();
+
+            public void WriteVarint(ulong value)
+            {
+                while (value >= 0x80)
+                {
+                    buffer.Add((byte)(value | 0x80));
+                    value >>= 7;
+                }
+                buffer.Add((byte)value);
+            }
+
+            public void WriteBool(bool value) => buffer.Add(value ? (byte)1 : (byte)0);
+
+            public void WriteU8(byte value) => buffer.Add(value);
+
+            public void WriteU16(ushort value) => WriteVarint(value);
+
+            public void WriteU32(uint value) => WriteVarint(value);
+
+            public void WriteU64(ulong value) => WriteVarint(value);
+
+            public void WriteU128(System.UInt128 value)
+            {
+                while (value >= 0x80u)
+                {
+                    buffer.Add((byte)((byte)value | 0x80));
+                    value >>= 7;
+                }
+                buffer.Add((byte)value);
+            }
+
+            public void WriteI8(sbyte value) => buffer.Add((byte)value);
+
+            public void WriteI16(short value) => WriteI64(value);
+
+            public void WriteI32(int value) => WriteI64(value);
+
+            public void WriteI64(long value) => WriteVarint((ulong)((value << 1) ^ (value >> 63)));
+
+            public void WriteI128(System.Int128 value) => WriteU128((System.UInt128)((value << 1) ^ (value >> 127)));
+
+            public void WriteFixint(ulong value, int bytes, bool littleEndian)
+            {
+                for (var i = 0; i < bytes; i++)
+                {
+                    var shift = littleEndian ? i : bytes - 1 - i;
+                    buffer.Add((byte)(value >> (8 * shift)));
+                }
+            }
+
+            public void WriteFixint128(System.UInt128 value, bool littleEndian)
+            {
+                for (var i = 0; i < 16; i++)
+                {
+                    var shift = littleEndian ? i : 15 - i;
+                    buffer.Add((byte)(value >> (8 * shift)));
+                }
+            }
+
+            public void WriteF32(float value)
+            {
+                var bits = System.BitConverter.SingleToUInt32Bits(value);
+                for (var i = 0; i < 4; i++)
+                {
+                    buffer.Add((byte)(bits >> (8 * i)));
+                }
+            }
+
+            public void WriteF64(double value)
+            {
+                var bits = System.BitConverter.DoubleToUInt64Bits(value);
+                for (var i = 0; i < 8; i++)
+                {
+                    buffer.Add((byte)(bits >> (8 * i)));
+                }
+            }
+
+            public void WriteString(string value, int? maxLength = null)
+            {
+                var bytes = System.Text.Encoding.UTF8.GetBytes(value);
+                WriteLength(bytes.Length, maxLength);
+                buffer.AddRange(bytes);
+            }
+
+            public void WriteLength(int length, int? maxLength = null)
+            {
+                if (length > maxLength)
+                {
+                    throw new PostcardException("length " + length + " exceeds the maximum of " + maxLength);
+                }
+                WriteVarint((ulong)length);
+            }
+
+            public void CheckLength(int length, int expected)
+            {
+                if (length != expected)
+                {
+                    throw new PostcardException("expected " + expected + " items, got " + length);
+                }
+            }
+
+            public void WriteOptionTag(bool some) => buffer.Add(some ? (byte)1 : (byte)0);
+
+            public void WriteVariantIndex(uint index) => WriteVarint(index);
+        }
+    }
+}
+
+fn gen_reader() -> Tokens {
+    quote! {
+        public sealed class PostcardReader
+        {
+            private static readonly System.Text.UTF8Encoding Utf8 = new(false, true);
+
+            private readonly byte[] bytes;
+            private int position;
+
+            public PostcardReader(byte[] bytes)
+            {
+                this.bytes = bytes;
+            }
+
+            public byte[] Remaining() => bytes[position..];
+
+            public ulong ReadVarint(int bits)
+            {
+                var maxBytes = (bits + 6) / 7;
+                ulong value = 0;
+                for (var i = 0; i < maxBytes; i++)
+                {
+                    var b = ReadU8();
+                    if (i == maxBytes - 1 && b >= 1 << (bits - 7 * i))
+                    {
+                        throw new PostcardException("invalid varint");
+                    }
+                    value |= (ulong)(b & 0x7f) << (7 * i);
+                    if ((b & 0x80) == 0)
+                    {
+                        return value;
+                    }
+                }
+                throw new PostcardException("invalid varint");
+            }
+
+            public bool ReadBool() => ReadU8() switch
+            {
+                0 => false,
+                1 => true,
+                var b => throw new PostcardException("invalid bool " + b),
+            };
+
+            public byte ReadU8()
+            {
+                if (position >= bytes.Length)
+                {
+                    throw new PostcardException("unexpected end of input");
+                }
+                return bytes[position++];
+            }
+
+            public ushort ReadU16() => (ushort)ReadVarint(16);
+
+            public uint ReadU32() => (uint)ReadVarint(32);
+
+            public ulong ReadU64() => ReadVarint(64);
+
+            public System.UInt128 ReadU128()
+            {
+                var value = System.UInt128.Zero;
+                for (var i = 0; i < 19; i++)
+                {
+                    var b = ReadU8();
+                    if (i == 18 && b >= 4)
+                    {
+                        throw new PostcardException("invalid varint");
+                    }
+                    value |= (System.UInt128)(b & 0x7f) << (7 * i);
+                    if ((b & 0x80) == 0)
+                    {
+                        return value;
+                    }
+                }
+                throw new PostcardException("invalid varint");
+            }
+
+            public sbyte ReadI8() => (sbyte)ReadU8();
+
+            public short ReadI16() => (short)ZigZag(ReadVarint(16));
+
+            public int ReadI32() => (int)ZigZag(ReadVarint(32));
+
+            public long ReadI64() => ZigZag(ReadVarint(64));
+
+            public System.Int128 ReadI128()
+            {
+                var value = ReadU128();
+                var sign = (value & 1u) == 1u ? System.Int128.NegativeOne : System.Int128.Zero;
+                return (System.Int128)(value >> 1) ^ sign;
+            }
+
+            public ulong ReadFixint(int bytes, bool littleEndian)
+            {
+                ulong value = 0;
+                for (var i = 0; i < bytes; i++)
+                {
+                    var shift = littleEndian ? i : bytes - 1 - i;
+                    value |= (ulong)ReadU8() << (8 * shift);
+                }
+                return value;
+            }
+
+            public System.UInt128 ReadFixint128(bool littleEndian)
+            {
+                var value = System.UInt128.Zero;
+                for (var i = 0; i < 16; i++)
+                {
+                    var shift = littleEndian ? i : 15 - i;
+                    value |= (System.UInt128)ReadU8() << (8 * shift);
+                }
+                return value;
+            }
+
+            public float ReadF32()
+            {
+                uint bits = 0;
+                for (var i = 0; i < 4; i++)
+                {
+                    bits |= (uint)ReadU8() << (8 * i);
+                }
+                return System.BitConverter.UInt32BitsToSingle(bits);
+            }
+
+            public double ReadF64()
+            {
+                ulong bits = 0;
+                for (var i = 0; i < 8; i++)
+                {
+                    bits |= (ulong)ReadU8() << (8 * i);
+                }
+                return System.BitConverter.UInt64BitsToDouble(bits);
+            }
+
+            public string ReadString(int? maxLength = null)
+            {
+                var length = ReadLength(maxLength);
+                if (bytes.Length - position < length)
+                {
+                    throw new PostcardException("unexpected end of input");
+                }
+                try
+                {
+                    return Utf8.GetString(bytes, position, length);
+                }
+                catch (System.ArgumentException)
+                {
+                    throw new PostcardException("invalid utf-8");
+                }
+                finally
+                {
+                    position += length;
+                }
+            }
+
+            public System.Collections.Generic.List<T> ReadList<T>(System.Func<T> read, int? maxLength = null)
+            {
+                var length = ReadLength(maxLength);
+                var list = new System.Collections.Generic.List<T>(System.Math.Min(length, bytes.Length - position));
+                for (var i = 0; i < length; i++)
+                {
+                    list.Add(read());
+                }
+                return list;
+            }
+
+            public System.Collections.Generic.List<T> ReadArray<T>(int length, System.Func<T> read)
+            {
+                var list = new System.Collections.Generic.List<T>(length);
+                for (var i = 0; i < length; i++)
+                {
+                    list.Add(read());
+                }
+                return list;
+            }
+
+            public System.Collections.Generic.Dictionary<K, V> ReadMap<K, V>(System.Func<K> readKey, System.Func<V> readValue, int? maxLength = null) where K : notnull
+            {
+                var length = ReadLength(maxLength);
+                var map = new System.Collections.Generic.Dictionary<K, V>();
+                for (var i = 0; i < length; i++)
+                {
+                    var key = readKey();
+                    map[key] = readValue();
+                }
+                return map;
+            }
+
+            public bool ReadOptionTag() => ReadU8() switch
+            {
+                0 => false,
+                1 => true,
+                var tag => throw new PostcardException("invalid option tag " + tag),
+            };
+
+            public PostcardRange<T> ReadRange<T>(System.Func<T> read)
+            {
+                var start = read();
+                var end = read();
+                return new PostcardRange<T>(start, end);
+            }
+
+            public uint ReadVariantIndex() => (uint)ReadVarint(32);
+
+            private int ReadLength(int? maxLength)
+            {
+                var length = ReadVarint(64);
+                if (length > int.MaxValue || (int)length > maxLength)
+                {
+                    throw new PostcardException("length " + length + " exceeds the maximum of " + (maxLength ?? int.MaxValue));
+                }
+                return (int)length;
+            }
+
+            private static long ZigZag(ulong value) => (long)(value >> 1) ^ -(long)(value & 1);
+        }
+    }
+}
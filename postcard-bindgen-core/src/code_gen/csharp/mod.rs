@@ -0,0 +1,228 @@
+mod des;
+mod general;
+mod ser;
+mod types;
+
+use core::borrow::Borrow;
+
+use genco::lang::csharp::Csharp;
+use general::gen_runtime;
+use types::gen_types;
+
+use crate::{
+    path::PathBuf, registry::ContainerCollection, type_info::ObjectMeta,
+    validation::ValidationSettings, ExportFile, Exports,
+};
+
+use super::utils::snake_case;
+
+type Tokens = genco::lang::csharp::Tokens;
+
+/// Settings for C# bindings generation.
+///
+/// Structs are generated as records, enums as abstract records with a nested record for
+/// each variant. Serialization and deserialization are members of the generated records.
+///
+/// By default, only deserialization is enabled. Serialization can be enabled by using [`GenerationSettings::serialization()`].
+/// Deserialization can be disabled with [`GenerationSettings::deserialization()`].
+/// To enable all at once use [`GenerationSettings::enable_all()`].
+#[derive(Debug)]
+pub struct GenerationSettings {
+    ser: bool,
+    des: bool,
+    module_structure: bool,
+}
+
+impl GenerationSettings {
+    /// Constructs [`GenerationSettings`] and enables all options at once.
+    pub fn enable_all() -> Self {
+        Self {
+            ser: true,
+            des: true,
+            module_structure: true,
+        }
+    }
+
+    /// Enabling or disabling of serialization code generation.
+    pub fn serialization(mut self, enabled: bool) -> Self {
+        self.ser = enabled;
+        self
+    }
+
+    /// Enabling or disabling of deserialization code generation.
+    pub fn deserialization(mut self, enabled: bool) -> Self {
+        self.des = enabled;
+        self
+    }
+
+    /// Enabling or disabling of module structure code generation.
+    ///
+    /// Enabling this will generate the types in the same module structure
+    /// as in rust. Root level types will be in the root namespace. Types
+    /// nested in modules will be in nested namespaces
+    /// (e.g. <package>.<mod_name>.<type_name>). This avoids name clashes.
+    ///
+    /// Disabling this will generate all types in the root namespace.
+    pub fn module_structure(mut self, enabled: bool) -> Self {
+        self.module_structure = enabled;
+        self
+    }
+}
+
+impl Default for GenerationSettings {
+    fn default() -> Self {
+        Self {
+            ser: false,
+            des: true,
+            module_structure: true,
+        }
+    }
+}
+
+impl From<&GenerationSettings> for ValidationSettings {
+    fn from(settings: &GenerationSettings) -> Self {
+        ValidationSettings::default().flattened(!settings.module_structure)
+    }
+}
+
+/// Generates the C# code for the containers.
+///
+/// The generated files are `Runtime`, holding the writer and reader, and `Types`. Both
+/// declare the root namespace named after the package.
+pub fn generate(
+    mut containers: ContainerCollection,
+    gen_settings: impl Borrow<GenerationSettings>,
+    package_name: String,
+) -> Exports<Csharp> {
+    let gen_settings = gen_settings.borrow();
+    let namespace = snake_case(&package_name);
+
+    if !gen_settings.module_structure {
+        containers.flatten();
+    }
+
+    Exports {
+        files: vec![
+            ExportFile {
+                content_type: "Runtime".to_owned(),
+                content: gen_runtime(&namespace, gen_settings),
+            },
+            ExportFile {
+                content_type: "Types".to_owned(),
+                content: gen_types(&namespace, &containers, gen_settings),
+            },
+        ],
+    }
+}
+
+/// Fully qualified name of the referenced record, usable from any namespace.
+fn full_qualified_object_name(namespace: &str, meta: &ObjectMeta) -> String {
+    let mut path: PathBuf = meta.path.clone().into_buf();
+    // We will skip the first part of the path, as it is the crate name.
+    path.pop_front();
+    path.parts()
+        .map(|part| identifier(part.as_ref()))
+        .chain([identifier(&meta.name)])
+        .fold(format!("global::{namespace}"), |name, part| {
+            format!("{name}.{part}")
+        })
+}
+
+/// Name of the record of an enum variant, nested in the abstract record.
+fn variant_identifier(enum_name: &str, variant_name: &str) -> String {
+    // nested types can't be named like the enclosing type or its members
+    if [enum_name, "Serialize", "Deserialize"].contains(&variant_name) {
+        identifier(&format!("{variant_name}_"))
+    } else {
+        identifier(variant_name)
+    }
+}
+
+const KEYWORDS: &[&str] = &[
+    "abstract",
+    "as",
+    "base",
+    "bool",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "checked",
+    "class",
+    "const",
+    "continue",
+    "decimal",
+    "default",
+    "delegate",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "event",
+    "explicit",
+    "extern",
+    "false",
+    "finally",
+    "fixed",
+    "float",
+    "for",
+    "foreach",
+    "goto",
+    "if",
+    "implicit",
+    "in",
+    "int",
+    "interface",
+    "internal",
+    "is",
+    "lock",
+    "long",
+    "namespace",
+    "new",
+    "null",
+    "object",
+    "operator",
+    "out",
+    "override",
+    "params",
+    "private",
+    "protected",
+    "public",
+    "readonly",
+    "ref",
+    "return",
+    "sbyte",
+    "sealed",
+    "short",
+    "sizeof",
+    "stackalloc",
+    "static",
+    "string",
+    "struct",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "uint",
+    "ulong",
+    "unchecked",
+    "unsafe",
+    "ushort",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "while",
+];
+
+/// Escapes names which are C# keywords.
+fn identifier(name: &str) -> String {
+    if KEYWORDS.contains(&name) {
+        format!("@{name}")
+    } else {
+        name.to_owned()
+    }
+}
@@ -0,0 +1,155 @@
+use genco::quote;
+
+use crate::type_info::{IntegerEncoding, NumberMeta, ValueType};
+
+use super::Tokens;
+
+/// Hands out the names of the local variables of a generated method.
+///
+/// Pattern variables of C# leak into the enclosing block, so every variable of a method
+/// gets a unique name.
+#[derive(Default)]
+pub(super) struct LocalNames(usize);
+
+impl LocalNames {
+    fn next(&mut self, prefix: &str) -> String {
+        let name = format!("{prefix}{}", self.0);
+        self.0 += 1;
+        name
+    }
+}
+
+/// Statements serializing the value of `expr` with the writer `writer`.
+pub(super) fn gen_ser_value(expr: &str, value_type: &ValueType, names: &mut LocalNames) -> Tokens {
+    match value_type {
+        ValueType::Bool(_) => quote!(writer.WriteBool($expr);),
+        ValueType::Number(meta) => {
+            let value = if is_non_zero(meta) {
+                quote!(PostcardChecks.NonZero($expr))
+            } else {
+                quote!($expr)
+            };
+            match fixint(meta) {
+                Some((16, little_endian)) => quote! {
+                    writer.WriteFixint128(unchecked((System.UInt128)$value), $(little_endian.to_string()));
+                },
+                Some((bytes, little_endian)) => quote! {
+                    writer.WriteFixint(unchecked((ulong)$value), $bytes, $(little_endian.to_string()));
+                },
+                None => quote!(writer.$(format!("Write{}", number_suffix(meta)))($value);),
+            }
+        }
+        ValueType::String(meta) => match meta.max_length {
+            Some(max_length) => quote!(writer.WriteString($expr, maxLength: $max_length);),
+            None => quote!(writer.WriteString($expr);),
+        },
+        ValueType::Array(meta) => {
+            let length = match (meta.length, meta.max_length) {
+                (Some(length), _) => quote!(writer.CheckLength($expr.Count, $length);),
+                (None, Some(max_length)) => {
+                    quote!(writer.WriteLength($expr.Count, maxLength: $max_length);)
+                }
+                (None, None) => quote!(writer.WriteLength($expr.Count);),
+            };
+            let item = names.next("x");
+            quote! {
+                $length
+                foreach (var $(&item) in $expr)
+                {
+                    $(gen_ser_value(&item, &meta.items_type, names))
+                }
+            }
+        }
+        ValueType::Map(meta) => {
+            let length = match meta.max_length {
+                Some(max_length) => {
+                    quote!(writer.WriteLength($expr.Count, maxLength: $max_length);)
+                }
+                None => quote!(writer.WriteLength($expr.Count);),
+            };
+            let key = names.next("k");
+            let value = names.next("v");
+            quote! {
+                $length
+                foreach (var ($(&key), $(&value)) in $expr)
+                {
+                    $(gen_ser_value(&key, &meta.key_type, names))
+                    $(gen_ser_value(&value, &meta.value_type, names))
+                }
+            }
+        }
+        ValueType::Optional(meta) => {
+            let item = names.next("x");
+            quote! {
+                if ($expr is { } $(&item))
+                {
+                    writer.WriteOptionTag(true);
+                    $(gen_ser_value(&item, &meta.inner, names))
+                }
+                else
+                {
+                    writer.WriteOptionTag(false);
+                }
+            }
+        }
+        // a tuple with a single item is represented by the item itself
+        ValueType::Tuple(meta) if meta.items_types.len() == 1 => {
+            gen_ser_value(expr, &meta.items_types[0], names)
+        }
+        ValueType::Tuple(meta) => {
+            let mut tokens = Tokens::new();
+            for (index, ty) in meta.items_types.iter().enumerate() {
+                tokens.append(gen_ser_value(
+                    &format!("{expr}.Item{}", index + 1),
+                    ty,
+                    names,
+                ));
+                tokens.push();
+            }
+            tokens
+        }
+        ValueType::Range(meta) => quote! {
+            $(gen_ser_value(&format!("{expr}.Start"), &meta.bounds_type, names))
+            $(gen_ser_value(&format!("{expr}.End"), &meta.bounds_type, names))
+        },
+        ValueType::Object(_) => quote!($expr.Serialize(writer);),
+    }
+}
+
+/// Suffix of the writer and reader methods of the number type.
+pub(super) fn number_suffix(meta: &NumberMeta) -> String {
+    match meta {
+        NumberMeta::Integer { bytes, signed, .. } => {
+            format!("{}{}", if *signed { "I" } else { "U" }, bytes * 8)
+        }
+        NumberMeta::FloatingPoint { bytes } => format!("F{}", bytes * 8),
+    }
+}
+
+/// Width and byte order (`true` for little endian) of an integer type which is encoded with
+/// a fixed width.
+pub(super) fn fixint(meta: &NumberMeta) -> Option<(usize, bool)> {
+    match meta {
+        NumberMeta::Integer {
+            bytes,
+            encoding: IntegerEncoding::FixedLe,
+            ..
+        } => Some((*bytes, true)),
+        NumberMeta::Integer {
+            bytes,
+            encoding: IntegerEncoding::FixedBe,
+            ..
+        } => Some((*bytes, false)),
+        _ => None,
+    }
+}
+
+pub(super) fn is_non_zero(meta: &NumberMeta) -> bool {
+    matches!(
+        meta,
+        NumberMeta::Integer {
+            zero_able: false,
+            ..
+        }
+    )
+}
@@ -0,0 +1,369 @@
+use genco::{quote, quote_in};
+
+use crate::{
+    registry::{BindingType, Container, ContainerCollection, EnumVariantType, Module, StructField},
+    type_info::{NumberMeta, ValueType},
+};
+
+use super::{
+    des::gen_des_value,
+    full_qualified_object_name, identifier,
+    ser::{gen_ser_value, LocalNames},
+    variant_identifier, GenerationSettings, Tokens,
+};
+
+/// Generates the records of all containers.
+///
+/// Rust modules become nested namespaces of the root namespace.
+pub(super) fn gen_types(
+    namespace: &str,
+    containers: &ContainerCollection,
+    gen_settings: &GenerationSettings,
+) -> Tokens {
+    let (containers, mods) = containers.containers_per_module();
+
+    quote! {
+        $("#nullable enable")
+
+        namespace $namespace
+        {
+            $(gen_namespace_body(namespace, containers, mods, gen_settings))
+        }
+    }
+}
+
+fn gen_namespace_body(
+    namespace: &str,
+    containers: Vec<Container>,
+    mods: Vec<Module<'_>>,
+    gen_settings: &GenerationSettings,
+) -> Tokens {
+    let mut tokens = Tokens::new();
+
+    for container in containers {
+        if !tokens.is_empty() {
+            tokens.line();
+        }
+        tokens.append(gen_record(namespace, &container, gen_settings));
+    }
+
+    for r#mod in mods {
+        let (containers, mods) = r#mod.entries();
+        if !tokens.is_empty() {
+            tokens.line();
+        }
+        quote_in! { tokens =>
+            namespace $(identifier(r#mod.name()))
+            {
+                $(gen_namespace_body(namespace, containers, mods, gen_settings))
+            }
+        };
+    }
+
+    tokens
+}
+
+fn gen_record(namespace: &str, container: &Container, gen_settings: &GenerationSettings) -> Tokens {
+    let name = identifier(&container.name);
+    match &container.r#type {
+        BindingType::Struct(ty) => {
+            gen_struct_record(namespace, &name, &named_fields(&ty.fields), gen_settings)
+        }
+        BindingType::TupleStruct(ty) => {
+            gen_struct_record(namespace, &name, &unnamed_fields(&ty.fields), gen_settings)
+        }
+        BindingType::UnitStruct(_) => gen_struct_record(namespace, &name, &[], gen_settings),
+        BindingType::Enum(ty) => {
+            let mut variants = Tokens::new();
+            let mut ser_cases = Tokens::new();
+            let mut des_arms = Tokens::new();
+            // the switch sections share the declaration space of the switch block
+            let mut names = LocalNames::default();
+
+            for variant in ty.variants.iter() {
+                let variant_name = variant_identifier(&container.name, &variant.name);
+                let fields = match &variant.inner_type {
+                    EnumVariantType::Empty => Vec::new(),
+                    EnumVariantType::Tuple(fields) => unnamed_fields(fields),
+                    EnumVariantType::NewType(fields) => named_fields(fields),
+                };
+                let index = variant.index;
+
+                quote_in! { variants =>
+                    public sealed record $(gen_record_header(namespace, &variant_name, &fields)) : $(&name);
+                };
+                variants.push();
+
+                if fields.is_empty() {
+                    quote_in! { ser_cases =>
+                        case $(&variant_name) _:
+                            writer.WriteVariantIndex($index);
+                            break;
+                    };
+                } else {
+                    quote_in! { ser_cases =>
+                        case $(&variant_name) value:
+                            writer.WriteVariantIndex($index);
+                            $(gen_ser_fields("value", &fields, &mut names))
+                            break;
+                    };
+                }
+                ser_cases.push();
+
+                quote_in! { des_arms =>
+                    $index => $(gen_constructor_call(namespace, &variant_name, &fields)),
+                };
+                des_arms.push();
+            }
+
+            let ser = gen_settings.ser.then(|| {
+                quote! {
+                    $(gen_serialize_to_bytes())
+
+                    public void Serialize(PostcardWriter writer)
+                    {
+                        switch (this)
+                        {
+                            $ser_cases
+                        }
+                    }
+                }
+            });
+
+            let des = gen_settings.des.then(|| {
+                quote! {
+                    $(gen_deserialize_from_bytes(&name))
+
+                    public static $(&name) Deserialize(PostcardReader reader)
+                    {
+                        var index = reader.ReadVariantIndex();
+                        return index switch
+                        {
+                            $des_arms
+                            _ => throw new PostcardException("unknown variant index " + index),
+                        };
+                    }
+                }
+            });
+
+            quote! {
+                public abstract record $(&name)
+                {
+                    private $(&name)()
+                    {
+                    }
+
+                    $(join_members([Some(variants), ser, des]))
+                }
+            }
+        }
+    }
+}
+
+/// Field of a generated record with the property name and the type.
+struct Field<'a> {
+    name: String,
+    value_type: &'a ValueType,
+}
+
+fn named_fields(fields: &[StructField]) -> Vec<Field<'_>> {
+    fields
+        .iter()
+        .map(|field| Field {
+            name: identifier(&field.name),
+            value_type: &field.v_type,
+        })
+        .collect()
+}
+
+fn unnamed_fields(fields: &[ValueType]) -> Vec<Field<'_>> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, value_type)| Field {
+            name: format!("_{index}"),
+            value_type,
+        })
+        .collect()
+}
+
+/// Name of the record with its primary constructor, if it has fields.
+fn gen_record_header(namespace: &str, name: &str, fields: &[Field]) -> Tokens {
+    if fields.is_empty() {
+        return quote!($name);
+    }
+
+    let parameters = fields
+        .iter()
+        .map(|field| quote!($(csharp_type(namespace, field.value_type)) $(&field.name)))
+        .collect();
+    quote!($name$(gen_argument_list(parameters)))
+}
+
+/// Argument list, with one argument per line if there is more than one.
+fn gen_argument_list(arguments: Vec<Tokens>) -> Tokens {
+    if arguments.len() > 1 {
+        quote! {
+            (
+                $(for argument in arguments join (,$['\r']) => $argument)
+            )
+        }
+    } else {
+        quote!(($(for argument in arguments => $argument)))
+    }
+}
+
+fn gen_ser_fields(target: &str, fields: &[Field], names: &mut LocalNames) -> Tokens {
+    let mut tokens = Tokens::new();
+    for field in fields {
+        tokens.append(gen_ser_value(
+            &format!("{target}.{}", field.name),
+            field.value_type,
+            names,
+        ));
+        tokens.push();
+    }
+    tokens
+}
+
+fn gen_constructor_call(namespace: &str, name: &str, fields: &[Field]) -> Tokens {
+    let arguments = fields
+        .iter()
+        .map(|field| gen_des_value(namespace, field.value_type))
+        .collect();
+    quote!(new $name$(gen_argument_list(arguments)))
+}
+
+fn gen_struct_record(
+    namespace: &str,
+    name: &str,
+    fields: &[Field],
+    gen_settings: &GenerationSettings,
+) -> Tokens {
+    let ser = gen_settings.ser.then(|| {
+        quote! {
+            $(gen_serialize_to_bytes())
+
+            public void Serialize(PostcardWriter writer)
+            $(gen_block(gen_ser_fields("this", fields, &mut LocalNames::default())))
+        }
+    });
+
+    let des = gen_settings.des.then(|| {
+        quote! {
+            $(gen_deserialize_from_bytes(name))
+
+            public static $name Deserialize(PostcardReader reader) => $(gen_constructor_call(namespace, name, fields));
+        }
+    });
+
+    let body = join_members([ser, des]);
+    let header = gen_record_header(namespace, name, fields);
+
+    if body.is_empty() {
+        quote!(public sealed record $header;)
+    } else {
+        quote! {
+            public sealed record $header
+            {
+                $body
+            }
+        }
+    }
+}
+
+/// Block on its own lines, also if it is empty.
+fn gen_block(statements: Tokens) -> Tokens {
+    let mut tokens = Tokens::new();
+    tokens.append("{");
+    if statements.is_empty() {
+        tokens.push();
+    } else {
+        tokens.indent();
+        tokens.append(statements);
+        tokens.unindent();
+    }
+    tokens.append("}");
+    tokens
+}
+
+fn join_members(members: impl IntoIterator<Item = Option<Tokens>>) -> Tokens {
+    let mut tokens = Tokens::new();
+    for member in members.into_iter().flatten() {
+        if !tokens.is_empty() {
+            tokens.line();
+        }
+        tokens.append(member);
+    }
+    tokens
+}
+
+fn gen_serialize_to_bytes() -> Tokens {
+    quote! {
+        public byte[] Serialize()
+        {
+            var writer = new PostcardWriter();
+            Serialize(writer);
+            return writer.ToArray();
+        }
+    }
+}
+
+fn gen_deserialize_from_bytes(name: &str) -> Tokens {
+    quote! {
+        public static ($name Value, byte[] Remaining) Deserialize(byte[] bytes)
+        {
+            var reader = new PostcardReader(bytes);
+            var value = Deserialize(reader);
+            return (value, reader.Remaining());
+        }
+    }
+}
+
+/// C# type a [ValueType] is represented with.
+pub(super) fn csharp_type(namespace: &str, value_type: &ValueType) -> Tokens {
+    match value_type {
+        ValueType::Bool(_) => quote!(bool),
+        ValueType::Number(NumberMeta::FloatingPoint { bytes }) => match bytes {
+            4 => quote!(float),
+            8 => quote!(double),
+            _ => unreachable!("unsupported floating point size {bytes}"),
+        },
+        ValueType::Number(NumberMeta::Integer { bytes, signed, .. }) => match (bytes, signed) {
+            (1, false) => quote!(byte),
+            (2, false) => quote!(ushort),
+            (4, false) => quote!(uint),
+            (8, false) => quote!(ulong),
+            (16, false) => quote!(System.UInt128),
+            (1, true) => quote!(sbyte),
+            (2, true) => quote!(short),
+            (4, true) => quote!(int),
+            (8, true) => quote!(long),
+            (16, true) => quote!(System.Int128),
+            _ => unreachable!("unsupported integer size {bytes}"),
+        },
+        ValueType::String(_) => quote!(string),
+        ValueType::Array(meta) => quote! {
+            System.Collections.Generic.List<$(csharp_type(namespace, &meta.items_type))>
+        },
+        ValueType::Map(meta) => quote! {
+            System.Collections.Generic.Dictionary<$(csharp_type(namespace, &meta.key_type)), $(csharp_type(namespace, &meta.value_type))>
+        },
+        // C# has no nested nullable types, `None` and `Some(None)` are both represented by null
+        ValueType::Optional(meta) if matches!(*meta.inner, ValueType::Optional(_)) => {
+            csharp_type(namespace, &meta.inner)
+        }
+        ValueType::Optional(meta) => quote!($(csharp_type(namespace, &meta.inner))?),
+        ValueType::Tuple(meta) if meta.items_types.len() == 1 => {
+            csharp_type(namespace, &meta.items_types[0])
+        }
+        ValueType::Tuple(meta) => {
+            let items = meta.items_types.iter().map(|ty| csharp_type(namespace, ty));
+            quote!(($(for item in items join (, ) => $item)))
+        }
+        ValueType::Range(meta) => {
+            quote!(PostcardRange<$(csharp_type(namespace, &meta.bounds_type))>)
+        }
+        ValueType::Object(meta) => quote!($(full_qualified_object_name(namespace, meta))),
+    }
+}
@@ -0,0 +1,73 @@
+use genco::quote;
+
+use crate::type_info::ValueType;
+
+use super::{
+    object_name,
+    ser::{fixint, is_non_zero, number_suffix},
+    Tokens,
+};
+
+/// Expression deserializing a value with the reader `reader`.
+pub(super) fn gen_des_value(value_type: &ValueType) -> Tokens {
+    match value_type {
+        ValueType::Bool(_) => quote!(reader.readBool()),
+        ValueType::Number(meta) => {
+            let read = match fixint(meta) {
+                Some((bytes, signed, little_endian)) => {
+                    let method = if bytes > 4 {
+                        "readBigFixint"
+                    } else {
+                        "readFixint"
+                    };
+                    quote!(reader.$method($bytes, $(signed.to_string()), $(little_endian.to_string())))
+                }
+                None => quote!(reader.$(format!("read{}", number_suffix(meta)))()),
+            };
+            if is_non_zero(meta) {
+                quote!(postcardNonZero($read))
+            } else {
+                read
+            }
+        }
+        ValueType::String(meta) => match meta.max_length {
+            Some(max_length) => quote!(reader.readString(maxLength: $max_length)),
+            None => quote!(reader.readString()),
+        },
+        ValueType::Array(meta) => {
+            let inner = gen_des_value(&meta.items_type);
+            match (meta.length, meta.max_length) {
+                (Some(length), _) => quote!(reader.readArray($length, () => $inner)),
+                (None, Some(max_length)) => {
+                    quote!(reader.readList(() => $inner, maxLength: $max_length))
+                }
+                (None, None) => quote!(reader.readList(() => $inner)),
+            }
+        }
+        ValueType::Map(meta) => {
+            let key = gen_des_value(&meta.key_type);
+            let value = gen_des_value(&meta.value_type);
+            match meta.max_length {
+                Some(max_length) => {
+                    quote!(reader.readMap(() => $key, () => $value, maxLength: $max_length))
+                }
+                None => quote!(reader.readMap(() => $key, () => $value)),
+            }
+        }
+        ValueType::Optional(meta) => {
+            quote!((reader.readOptionTag() ? $(gen_des_value(&meta.inner)) : null))
+        }
+        // a tuple with a single item is represented by the item itself
+        ValueType::Tuple(meta) if meta.items_types.len() == 1 => {
+            gen_des_value(&meta.items_types[0])
+        }
+        ValueType::Tuple(meta) => {
+            let items = meta.items_types.iter().map(gen_des_value);
+            quote!(($(for item in items join (, ) => $item)))
+        }
+        ValueType::Range(meta) => {
+            quote!(reader.readRange(() => $(gen_des_value(&meta.bounds_type))))
+        }
+        ValueType::Object(meta) => quote!($(object_name(meta)).deserializeFrom(reader)),
+    }
+}
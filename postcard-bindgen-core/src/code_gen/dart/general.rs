@@ -0,0 +1,423 @@
+use genco::{lang::dart, quote};
+
+use super::{typed_data, GenerationSettings, Tokens};
+
+/// Generates the runtime shared by all generated classes.
+pub(super) fn gen_runtime(gen_settings: &GenerationSettings) -> Tokens {
+    let mut classes = Tokens::new();
+    if gen_settings.ser {
+        classes.line();
+        classes.append(gen_writer());
+    }
+    if gen_settings.des {
+        classes.line();
+        classes.append(gen_reader());
+    }
+
+    quote! {
+        class PostcardException implements Exception {
+            PostcardException(this.message);
+
+            final String message;
+
+            @override
+            String toString() => $("\"PostcardException: $message\"");
+        }
+
+        final class PostcardRange<T> {
+            const PostcardRange(this.start, this.end);
+
+            final T start;
+            final T end;
+
+            @override
+            bool operator ==(Object other) =>
+                other is PostcardRange<T> && other.start == start && other.end == end;
+
+            @override
+            int get hashCode => Object.hash(start, end);
+
+            @override
+            String toString() => $("\"$start..$end\"");
+        }
+
+        T postcardNonZero<T>(T value) {
+            if (value == 0 || value == BigInt.zero) {
+                throw PostcardException("zero value for a non-zero integer");
+            }
+            return value;
+        }
+
+        bool postcardEquals(Object? a, Object? b) {
+            if (a is List && b is List) {
+                if (a.length != b.length) {
+                    return false;
+                }
+                for (var i = 0; i < a.length; i++) {
+                    if (!postcardEquals(a[i], b[i])) {
+                        return false;
+                    }
+                }
+                return true;
+            }
+            if (a is Map && b is Map) {
+                if (a.length != b.length) {
+                    return false;
+                }
+                for (final entry in a.entries) {
+                    if (!b.containsKey(entry.key) || !postcardEquals(entry.value, b[entry.key])) {
+                        return false;
+                    }
+                }
+                return true;
+            }
+            return a == b;
+        }
+
+        int postcardHash(Object? value) {
+            if (value is List) {
+                return Object.hashAll(value.map(postcardHash));
+            }
+            if (value is Map) {
+                return Object.hashAllUnordered(
+                    value.entries.map((entry) => Object.hash(postcardHash(entry.key), postcardHash(entry.value))),
+                );
+            }
+            return value.hashCode;
+        }
+        $classes
+    }
+}
+
+fn gen_writer() -> Tokens {
+    let bytes_builder = typed_data("BytesBuilder");
+    let byte_data = typed_data("ByteData");
+    let endian = typed_data("Endian");
+    let uint8_list = typed_data("Uint8List");
+    let utf8 = dart::import("dart:convert", "utf8");
+
+    quote! {
+        class PostcardWriter {
+            final $(&bytes_builder) _builder = $(&bytes_builder)();
+            final $(&byte_data) _scratch = $(&byte_data)(8);
+
+            $(&uint8_list) toBytes() => _builder.toBytes();
+
+            void writeVarint(int value) {
+                while (value >= 0x80) {
+                    _builder.addByte((value & 0x7f) | 0x80);
+                    value = value >> 7;
+                }
+                _builder.addByte(value);
+            }
+
+            void writeBigVarint(BigInt value) {
+                while (value >= _PostcardBigInts.byte) {
+                    _builder.addByte((value & _PostcardBigInts.mask).toInt() | 0x80);
+                    value = value >> 7;
+                }
+                _builder.addByte(value.toInt());
+            }
+
+            void writeBool(bool value) => _builder.addByte(value ? 1 : 0);
+
+            void writeU8(int value) => _builder.addByte(_checkInt(value, 0, 0xff));
+
+            void writeU16(int value) => writeVarint(_checkInt(value, 0, 0xffff));
+
+            void writeU32(int value) => writeVarint(_checkInt(value, 0, 0xffffffff));
+
+            void writeU64(BigInt value) => writeBigVarint(_checkBigInt(value, BigInt.zero, _PostcardBigInts.u64Max));
+
+            void writeU128(BigInt value) => writeBigVarint(_checkBigInt(value, BigInt.zero, _PostcardBigInts.u128Max));
+
+            void writeI8(int value) => _builder.addByte(_checkInt(value, -0x80, 0x7f) & 0xff);
+
+            void writeI16(int value) => writeVarint(_zigzag(_checkInt(value, -0x8000, 0x7fff)));
+
+            void writeI32(int value) => writeVarint(_zigzag(_checkInt(value, -0x80000000, 0x7fffffff)));
+
+            void writeI64(BigInt value) => writeBigVarint(_bigZigzag(_checkBigInt(value, _PostcardBigInts.i64Min, _PostcardBigInts.i64Max)));
+
+            void writeI128(BigInt value) => writeBigVarint(_bigZigzag(_checkBigInt(value, _PostcardBigInts.i128Min, _PostcardBigInts.i128Max)));
+
+            void writeFixint(int value, int bytes, bool signed, bool littleEndian) {
+                final bits = bytes * 8;
+                final min = signed ? -(1 << (bits - 1)) : 0;
+                final max = signed ? (1 << (bits - 1)) - 1 : (1 << bits) - 1;
+                var rest = _checkInt(value, min, max).toUnsigned(bits);
+                final out = $(&uint8_list)(bytes);
+                for (var i = 0; i < bytes; i++) {
+                    out[littleEndian ? i : bytes - 1 - i] = rest % 0x100;
+                    rest = rest ~/ 0x100;
+                }
+                _builder.add(out);
+            }
+
+            void writeBigFixint(BigInt value, int bytes, bool signed, bool littleEndian) {
+                final bits = bytes * 8;
+                final min = signed ? -(BigInt.one << (bits - 1)) : BigInt.zero;
+                final max = signed ? (BigInt.one << (bits - 1)) - BigInt.one : (BigInt.one << bits) - BigInt.one;
+                var rest = _checkBigInt(value, min, max).toUnsigned(bits);
+                final out = $(&uint8_list)(bytes);
+                for (var i = 0; i < bytes; i++) {
+                    out[littleEndian ? i : bytes - 1 - i] = (rest & _PostcardBigInts.byteMask).toInt();
+                    rest = rest >> 8;
+                }
+                _builder.add(out);
+            }
+
+            void writeF32(double value) {
+                _scratch.setFloat32(0, value, $(&endian).little);
+                _builder.add(_scratch.buffer.asUint8List(0, 4));
+            }
+
+            void writeF64(double value) {
+                _scratch.setFloat64(0, value, $(&endian).little);
+                _builder.add(_scratch.buffer.asUint8List(0, 8));
+            }
+
+            void writeString(String value, {int? maxLength}) {
+                final bytes = $utf8.encode(value);
+                writeLength(bytes.length, maxLength: maxLength);
+                _builder.add(bytes);
+            }
+
+            void writeLength(int length, {int? maxLength}) {
+                if (maxLength != null && length > maxLength) {
+                    throw PostcardException($("\"length $length exceeds the maximum length $maxLength\""));
+                }
+                writeVarint(length);
+            }
+
+            void checkLength(int length, int expected) {
+                if (length != expected) {
+                    throw PostcardException($("\"length $length doesn't match the expected length $expected\""));
+                }
+            }
+
+            void writeOptionTag(bool some) => writeBool(some);
+
+            void writeVariantIndex(int index) => writeU32(index);
+
+            static int _zigzag(int value) => value >= 0 ? value * 2 : -value * 2 - 1;
+
+            static BigInt _bigZigzag(BigInt value) =>
+                value >= BigInt.zero ? value << 1 : ((-value) << 1) - BigInt.one;
+
+            static int _checkInt(int value, int min, int max) {
+                if (value < min || value > max) {
+                    throw PostcardException($("\"value $value out of range $min..$max\""));
+                }
+                return value;
+            }
+
+            static BigInt _checkBigInt(BigInt value, BigInt min, BigInt max) {
+                if (value < min || value > max) {
+                    throw PostcardException($("\"value $value out of range $min..$max\""));
+                }
+                return value;
+            }
+        }
+
+        final class _PostcardBigInts {
+            static final byte = BigInt.from(0x80);
+            static final mask = BigInt.from(0x7f);
+            static final byteMask = BigInt.from(0xff);
+            static final u64Max = (BigInt.one << 64) - BigInt.one;
+            static final u128Max = (BigInt.one << 128) - BigInt.one;
+            static final i64Min = -(BigInt.one << 63);
+            static final i64Max = (BigInt.one << 63) - BigInt.one;
+            static final i128Min = -(BigInt.one << 127);
+            static final i128Max = (BigInt.one << 127) - BigInt.one;
+        }
+    }
+}
+
+fn gen_reader() -> Tokens {
+    let byte_data = typed_data("ByteData");
+    let endian = typed_data("Endian");
+    let uint8_list = typed_data("Uint8List");
+    let utf8 = dart::import("dart:convert", "utf8");
+
+    quote! {
+        class PostcardReader {
+            PostcardReader(this._bytes) : _data = $(&byte_data).sublistView(_bytes);
+
+            final $(&uint8_list) _bytes;
+            final $byte_data _data;
+            int _offset = 0;
+
+            $(&uint8_list) remaining() => $(&uint8_list).sublistView(_bytes, _offset);
+
+            int readByte() {
+                _ensure(1);
+                return _bytes[_offset++];
+            }
+
+            int readVarint(int bits) {
+                var value = 0;
+                var factor = 1;
+                for (var shift = 0; shift < bits; shift += 7) {
+                    final byte = readByte();
+                    final part = byte & 0x7f;
+                    if (shift + 7 > bits && part >> (bits - shift) != 0) {
+                        throw PostcardException("varint overflows the decoded type");
+                    }
+                    value += part * factor;
+                    if ((byte & 0x80) == 0) {
+                        return value;
+                    }
+                    factor *= 0x80;
+                }
+                throw PostcardException("varint overflows the decoded type");
+            }
+
+            BigInt readBigVarint(int bits) {
+                var value = BigInt.zero;
+                for (var shift = 0; shift < bits; shift += 7) {
+                    final byte = readByte();
+                    final part = byte & 0x7f;
+                    if (shift + 7 > bits && part >> (bits - shift) != 0) {
+                        throw PostcardException("varint overflows the decoded type");
+                    }
+                    value |= BigInt.from(part) << shift;
+                    if ((byte & 0x80) == 0) {
+                        return value;
+                    }
+                }
+                throw PostcardException("varint overflows the decoded type");
+            }
+
+            bool readBool() {
+                final byte = readByte();
+                return switch (byte) {
+                    0 => false,
+                    1 => true,
+                    _ => throw PostcardException($("\"invalid bool $byte\"")),
+                };
+            }
+
+            int readU8() => readByte();
+
+            int readU16() => readVarint(16);
+
+            int readU32() => readVarint(32);
+
+            BigInt readU64() => readBigVarint(64);
+
+            BigInt readU128() => readBigVarint(128);
+
+            int readI8() => readByte().toSigned(8);
+
+            int readI16() => _unzigzag(readVarint(16));
+
+            int readI32() => _unzigzag(readVarint(32));
+
+            BigInt readI64() => _bigUnzigzag(readBigVarint(64));
+
+            BigInt readI128() => _bigUnzigzag(readBigVarint(128));
+
+            int readFixint(int bytes, bool signed, bool littleEndian) {
+                _ensure(bytes);
+                var value = 0;
+                for (var i = 0; i < bytes; i++) {
+                    value = value * 0x100 + _bytes[_offset + (littleEndian ? bytes - 1 - i : i)];
+                }
+                _offset += bytes;
+                return signed ? value.toSigned(bytes * 8) : value;
+            }
+
+            BigInt readBigFixint(int bytes, bool signed, bool littleEndian) {
+                _ensure(bytes);
+                var value = BigInt.zero;
+                for (var i = 0; i < bytes; i++) {
+                    value = (value << 8) | BigInt.from(_bytes[_offset + (littleEndian ? bytes - 1 - i : i)]);
+                }
+                _offset += bytes;
+                return signed ? value.toSigned(bytes * 8) : value;
+            }
+
+            double readF32() {
+                _ensure(4);
+                final value = _data.getFloat32(_offset, $(&endian).little);
+                _offset += 4;
+                return value;
+            }
+
+            double readF64() {
+                _ensure(8);
+                final value = _data.getFloat64(_offset, $(&endian).little);
+                _offset += 8;
+                return value;
+            }
+
+            String readString({int? maxLength}) {
+                final length = readLength(maxLength: maxLength);
+                _ensure(length);
+                final bytes = $(&uint8_list).sublistView(_bytes, _offset, _offset + length);
+                _offset += length;
+                try {
+                    return $utf8.decode(bytes);
+                } on FormatException {
+                    throw PostcardException("invalid utf-8 string");
+                }
+            }
+
+            int readLength({int? maxLength}) {
+                final length = readBigVarint(64);
+                if (!length.isValidInt) {
+                    throw PostcardException($("\"length $length out of range\""));
+                }
+                final value = length.toInt();
+                if (maxLength != null && value > maxLength) {
+                    throw PostcardException($("\"length $value exceeds the maximum length $maxLength\""));
+                }
+                return value;
+            }
+
+            List<T> readList<T>(T Function() readItem, {int? maxLength}) =>
+                _readItems(readLength(maxLength: maxLength), readItem);
+
+            List<T> readArray<T>(int length, T Function() readItem) => _readItems(length, readItem);
+
+            Map<K, V> readMap<K, V>(K Function() readKey, V Function() readValue, {int? maxLength}) {
+                final length = readLength(maxLength: maxLength);
+                final map = <K, V>{};
+                for (var i = 0; i < length; i++) {
+                    final key = readKey();
+                    map[key] = readValue();
+                }
+                return Map<K, V>.unmodifiable(map);
+            }
+
+            PostcardRange<T> readRange<T>(T Function() readBound) {
+                final start = readBound();
+                return PostcardRange(start, readBound());
+            }
+
+            bool readOptionTag() => readBool();
+
+            int readVariantIndex() => readU32();
+
+            List<T> _readItems<T>(int length, T Function() readItem) {
+                final items = <T>[];
+                for (var i = 0; i < length; i++) {
+                    items.add(readItem());
+                }
+                return List<T>.unmodifiable(items);
+            }
+
+            void _ensure(int length) {
+                if (_bytes.length - _offset < length) {
+                    throw PostcardException("unexpected end of input");
+                }
+            }
+
+            static int _unzigzag(int value) => value.isEven ? value ~/ 2 : -(value ~/ 2) - 1;
+
+            static BigInt _bigUnzigzag(BigInt value) =>
+                value.isEven ? value >> 1 : -(value >> 1) - BigInt.one;
+        }
+    }
+}
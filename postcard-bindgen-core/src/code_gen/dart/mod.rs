@@ -0,0 +1,234 @@
+mod des;
+mod general;
+mod ser;
+mod types;
+
+use core::borrow::Borrow;
+
+use convert_case::{Case, Casing};
+use genco::lang::dart::{self, Dart};
+use general::gen_runtime;
+use types::gen_types;
+
+use crate::{
+    path::{Path, PathBuf},
+    registry::{Container, ContainerCollection},
+    type_info::ObjectMeta,
+    validation::ValidationSettings,
+    ExportFile, Exports,
+};
+
+type Tokens = genco::lang::dart::Tokens;
+
+/// Settings for Dart bindings generation.
+///
+/// Structs are generated as immutable classes, enums as sealed classes with a subclass for
+/// each variant. Serialization and deserialization are members of the generated classes.
+///
+/// By default, only deserialization is enabled. Serialization can be enabled by using [`GenerationSettings::serialization()`].
+/// Deserialization can be disabled with [`GenerationSettings::deserialization()`].
+/// To enable all at once use [`GenerationSettings::enable_all()`].
+#[derive(Debug)]
+pub struct GenerationSettings {
+    ser: bool,
+    des: bool,
+    module_structure: bool,
+}
+
+impl GenerationSettings {
+    /// Constructs [`GenerationSettings`] and enables all options at once.
+    pub fn enable_all() -> Self {
+        Self {
+            ser: true,
+            des: true,
+            module_structure: true,
+        }
+    }
+
+    /// Enabling or disabling of serialization code generation.
+    pub fn serialization(mut self, enabled: bool) -> Self {
+        self.ser = enabled;
+        self
+    }
+
+    /// Enabling or disabling of deserialization code generation.
+    pub fn deserialization(mut self, enabled: bool) -> Self {
+        self.des = enabled;
+        self
+    }
+
+    /// Enabling or disabling of module structure code generation.
+    ///
+    /// Dart has no namespaces within a library. Enabling this will prefix the names of
+    /// types nested in modules with the module path (e.g. `mod_name::TypeName` becomes
+    /// `ModNameTypeName`). Different paths can still join to the same name (e.g. `a_b::C`
+    /// and `a::BC`), such clashes are reported when the package is built.
+    ///
+    /// Disabling this will generate all types with their plain names.
+    pub fn module_structure(mut self, enabled: bool) -> Self {
+        self.module_structure = enabled;
+        self
+    }
+}
+
+impl Default for GenerationSettings {
+    fn default() -> Self {
+        Self {
+            ser: false,
+            des: true,
+            module_structure: true,
+        }
+    }
+}
+
+impl From<&GenerationSettings> for ValidationSettings {
+    fn from(settings: &GenerationSettings) -> Self {
+        // all classes, including the ones of the enum variants, share one library
+        ValidationSettings::default()
+            .flattened(!settings.module_structure)
+            .shared_namespace(class_name)
+            .variant_classes(variant_class_name)
+    }
+}
+
+/// Generates the Dart code for the containers.
+///
+/// The generated files are `runtime`, holding the writer and reader, and `types`. Both
+/// belong to the same library, so they have to be combined or joined with `part`
+/// directives.
+pub fn generate(
+    mut containers: ContainerCollection,
+    gen_settings: impl Borrow<GenerationSettings>,
+) -> Exports<Dart> {
+    let gen_settings = gen_settings.borrow();
+
+    if !gen_settings.module_structure {
+        containers.flatten();
+    }
+
+    Exports {
+        files: vec![
+            ExportFile {
+                content_type: "runtime".to_owned(),
+                content: gen_runtime(gen_settings),
+            },
+            ExportFile {
+                content_type: "types".to_owned(),
+                content: gen_types(containers.all_containers(), gen_settings),
+            },
+        ],
+    }
+}
+
+fn typed_data(name: &'static str) -> dart::Import {
+    dart::import("dart:typed_data", name)
+}
+
+/// Name of the Dart class of the container.
+fn container_name(container: &Container) -> String {
+    class_name(&container.path, &container.name)
+}
+
+/// Name of the Dart class of the referenced container.
+fn object_name(meta: &ObjectMeta) -> String {
+    class_name(&meta.path, &meta.name)
+}
+
+fn class_name(path: &Path<'_, '_>, name: &str) -> String {
+    let mut path: PathBuf = path.clone().into_buf();
+    // We will skip the first part of the path, as it is the crate name.
+    path.pop_front();
+    path.parts()
+        .map(|part| part.as_ref().to_case(Case::Pascal))
+        .chain([name.to_owned()])
+        .collect()
+}
+
+/// Name of the Dart class of an enum variant.
+fn variant_class_name(enum_name: &str, variant_name: &str) -> String {
+    format!("{enum_name}{variant_name}")
+}
+
+/// Name of a class field, escaped if it is a keyword or a member of the classes.
+fn field_name(name: &str) -> String {
+    let name = name.to_case(Case::Camel);
+    if RESERVED.contains(&name.as_str()) {
+        format!("{name}_")
+    } else {
+        name
+    }
+}
+
+const RESERVED: &[&str] = &[
+    // members of the generated classes
+    "serialize",
+    "serializeTo",
+    "deserialize",
+    "deserializeFrom",
+    "hashCode",
+    "runtimeType",
+    "toString",
+    "noSuchMethod",
+    // keywords
+    "abstract",
+    "as",
+    "assert",
+    "async",
+    "await",
+    "base",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "covariant",
+    "default",
+    "deferred",
+    "do",
+    "dynamic",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "extension",
+    "external",
+    "factory",
+    "false",
+    "final",
+    "finally",
+    "for",
+    "get",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "interface",
+    "is",
+    "late",
+    "library",
+    "mixin",
+    "new",
+    "null",
+    "operator",
+    "part",
+    "required",
+    "rethrow",
+    "return",
+    "sealed",
+    "set",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typedef",
+    "var",
+    "void",
+    "when",
+    "while",
+    "with",
+    "yield",
+];
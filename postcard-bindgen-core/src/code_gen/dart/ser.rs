@@ -0,0 +1,147 @@
+use genco::quote;
+
+use crate::type_info::{IntegerEncoding, NumberMeta, ValueType};
+
+use super::Tokens;
+
+/// Hands out the names of the local variables of a generated method.
+#[derive(Default)]
+pub(super) struct LocalNames(usize);
+
+impl LocalNames {
+    fn next(&mut self, prefix: &str) -> String {
+        let name = format!("{prefix}{}", self.0);
+        self.0 += 1;
+        name
+    }
+}
+
+/// Statements serializing the value of `expr` with the writer `writer`.
+pub(super) fn gen_ser_value(expr: &str, value_type: &ValueType, names: &mut LocalNames) -> Tokens {
+    match value_type {
+        ValueType::Bool(_) => quote!(writer.writeBool($expr);),
+        ValueType::Number(meta) => {
+            let value = if is_non_zero(meta) {
+                quote!(postcardNonZero($expr))
+            } else {
+                quote!($expr)
+            };
+            match fixint(meta) {
+                Some((bytes, signed, little_endian)) => {
+                    let method = if bytes > 4 {
+                        "writeBigFixint"
+                    } else {
+                        "writeFixint"
+                    };
+                    quote!(writer.$method($value, $bytes, $(signed.to_string()), $(little_endian.to_string()));)
+                }
+                None => quote!(writer.$(format!("write{}", number_suffix(meta)))($value);),
+            }
+        }
+        ValueType::String(meta) => match meta.max_length {
+            Some(max_length) => quote!(writer.writeString($expr, maxLength: $max_length);),
+            None => quote!(writer.writeString($expr);),
+        },
+        ValueType::Array(meta) => {
+            let length = match (meta.length, meta.max_length) {
+                (Some(length), _) => quote!(writer.checkLength($expr.length, $length);),
+                (None, Some(max_length)) => {
+                    quote!(writer.writeLength($expr.length, maxLength: $max_length);)
+                }
+                (None, None) => quote!(writer.writeLength($expr.length);),
+            };
+            let item = names.next("x");
+            quote! {
+                $length
+                for (final $(&item) in $expr) {
+                    $(gen_ser_value(&item, &meta.items_type, names))
+                }
+            }
+        }
+        ValueType::Map(meta) => {
+            let length = match meta.max_length {
+                Some(max_length) => {
+                    quote!(writer.writeLength($expr.length, maxLength: $max_length);)
+                }
+                None => quote!(writer.writeLength($expr.length);),
+            };
+            let key = names.next("k");
+            let value = names.next("v");
+            quote! {
+                $length
+                for (final MapEntry(key: $(&key), value: $(&value)) in $expr.entries) {
+                    $(gen_ser_value(&key, &meta.key_type, names))
+                    $(gen_ser_value(&value, &meta.value_type, names))
+                }
+            }
+        }
+        ValueType::Optional(meta) => {
+            let item = names.next("x");
+            quote! {
+                if ($expr case final $(&item)?) {
+                    writer.writeOptionTag(true);
+                    $(gen_ser_value(&item, &meta.inner, names))
+                } else {
+                    writer.writeOptionTag(false);
+                }
+            }
+        }
+        // a tuple with a single item is represented by the item itself
+        ValueType::Tuple(meta) if meta.items_types.len() == 1 => {
+            gen_ser_value(expr, &meta.items_types[0], names)
+        }
+        ValueType::Tuple(meta) => {
+            let mut tokens = Tokens::new();
+            for (index, ty) in meta.items_types.iter().enumerate() {
+                tokens.append(gen_ser_value(&format!("{expr}.${}", index + 1), ty, names));
+                tokens.push();
+            }
+            tokens
+        }
+        ValueType::Range(meta) => quote! {
+            $(gen_ser_value(&format!("{expr}.start"), &meta.bounds_type, names))
+            $(gen_ser_value(&format!("{expr}.end"), &meta.bounds_type, names))
+        },
+        ValueType::Object(_) => quote!($expr.serializeTo(writer);),
+    }
+}
+
+/// Suffix of the writer and reader methods of the number type.
+pub(super) fn number_suffix(meta: &NumberMeta) -> String {
+    match meta {
+        NumberMeta::Integer { bytes, signed, .. } => {
+            format!("{}{}", if *signed { "I" } else { "U" }, bytes * 8)
+        }
+        NumberMeta::FloatingPoint { bytes } => format!("F{}", bytes * 8),
+    }
+}
+
+/// Width, signedness and byte order (`true` for little endian) of an integer type which is
+/// encoded with a fixed width.
+pub(super) fn fixint(meta: &NumberMeta) -> Option<(usize, bool, bool)> {
+    match meta {
+        NumberMeta::Integer {
+            bytes,
+            signed,
+            encoding: IntegerEncoding::FixedLe,
+            ..
+        } => Some((*bytes, *signed, true)),
+        NumberMeta::Integer {
+            bytes,
+            signed,
+            encoding: IntegerEncoding::FixedBe,
+            ..
+        } => Some((*bytes, *signed, false)),
+        _ => None,
+    }
+}
+
+pub(super) fn is_non_zero(meta: &NumberMeta) -> bool {
+    matches!(
+        meta,
+        NumberMeta::Integer {
+            zero_able: false,
+            ..
+        }
+    )
+}
@@ -0,0 +1,375 @@
+use genco::{quote, quote_in};
+
+use crate::{
+    registry::{BindingType, Container, EnumVariantType, StructField},
+    type_info::{NumberMeta, ValueType},
+};
+
+use super::{
+    container_name,
+    des::gen_des_value,
+    field_name, object_name,
+    ser::{gen_ser_value, LocalNames},
+    typed_data, variant_class_name, GenerationSettings, Tokens,
+};
+
+/// Generates the classes of all containers.
+pub(super) fn gen_types(
+    containers: impl Iterator<Item = Container>,
+    gen_settings: &GenerationSettings,
+) -> Tokens {
+    let mut tokens = Tokens::new();
+    for container in containers {
+        if !tokens.is_empty() {
+            tokens.line();
+        }
+        tokens.append(gen_class(&container, gen_settings));
+    }
+    tokens
+}
+
+fn gen_class(container: &Container, gen_settings: &GenerationSettings) -> Tokens {
+    let name = container_name(container);
+    match &container.r#type {
+        BindingType::Struct(ty) => gen_struct_class(
+            &name,
+            None,
+            &Fields::Named(named_fields(&ty.fields)),
+            gen_settings,
+        ),
+        BindingType::TupleStruct(ty) => gen_struct_class(
+            &name,
+            None,
+            &Fields::Positional(positional_fields(&ty.fields)),
+            gen_settings,
+        ),
+        BindingType::UnitStruct(_) => {
+            gen_struct_class(&name, None, &Fields::Positional(Vec::new()), gen_settings)
+        }
+        BindingType::Enum(ty) => {
+            let mut variants = Tokens::new();
+            let mut des_arms = Tokens::new();
+
+            for variant in ty.variants.iter() {
+                let variant_name = variant_class_name(&name, &variant.name);
+                let fields = match &variant.inner_type {
+                    EnumVariantType::Empty => Fields::Positional(Vec::new()),
+                    EnumVariantType::Tuple(fields) => Fields::Positional(positional_fields(fields)),
+                    EnumVariantType::NewType(fields) => Fields::Named(named_fields(fields)),
+                };
+                let index = variant.index;
+
+                variants.line();
+                variants.append(gen_struct_class(
+                    &variant_name,
+                    Some((&name, index)),
+                    &fields,
+                    gen_settings,
+                ));
+
+                if fields.is_empty() {
+                    quote_in! { des_arms =>
+                        $index => const $(&variant_name)(),
+                    };
+                } else {
+                    quote_in! { des_arms =>
+                        $index => $(gen_constructor_call(&variant_name, &fields)),
+                    };
+                }
+                des_arms.push();
+            }
+
+            let ser = gen_settings.ser.then(|| {
+                quote! {
+                    $(gen_serialize_to_bytes())
+
+                    void serializeTo(PostcardWriter writer);
+                }
+            });
+
+            let des = gen_settings.des.then(|| {
+                quote! {
+                    $(gen_deserialize_from_bytes(&name))
+
+                    static $(&name) deserializeFrom(PostcardReader reader) {
+                        final index = reader.readVariantIndex();
+                        return switch (index) {
+                            $des_arms
+                            _ => throw PostcardException($("\"unknown variant index $index\"")),
+                        };
+                    }
+                }
+            });
+
+            quote! {
+                sealed class $(&name) {
+                    const $(&name)();
+                    $(for member in [ser, des].into_iter().flatten() => $['\n']$member)
+                }
+                $variants
+            }
+        }
+    }
+}
+
+/// Field of a generated class with the Dart name and the type.
+struct Field<'a> {
+    name: String,
+    value_type: &'a ValueType,
+}
+
+/// Fields of a class, passed to the constructor by name or by position.
+enum Fields<'a> {
+    Named(Vec<Field<'a>>),
+    Positional(Vec<Field<'a>>),
+}
+
+impl<'a> Fields<'a> {
+    fn all(&self) -> &[Field<'a>] {
+        match self {
+            Fields::Named(fields) | Fields::Positional(fields) => fields,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.all().is_empty()
+    }
+}
+
+fn named_fields(fields: &[StructField]) -> Vec<Field<'_>> {
+    fields
+        .iter()
+        .map(|field| Field {
+            name: field_name(&field.name),
+            value_type: &field.v_type,
+        })
+        .collect()
+}
+
+fn positional_fields(fields: &[ValueType]) -> Vec<Field<'_>> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, value_type)| Field {
+            name: format!("v{index}"),
+            value_type,
+        })
+        .collect()
+}
+
+/// Immutable class of a struct or of an enum variant, if `variant` holds the name of the
+/// enum and the index of the variant.
+fn gen_struct_class(
+    name: &str,
+    variant: Option<(&str, usize)>,
+    fields: &Fields,
+    gen_settings: &GenerationSettings,
+) -> Tokens {
+    let constructor_parameters = match fields {
+        Fields::Named(fields) => {
+            let parameters = fields
+                .iter()
+                .map(|field| quote!(required this.$(&field.name)));
+            gen_argument_list(parameters.collect(), true)
+        }
+        Fields::Positional(fields) => {
+            let parameters = fields.iter().map(|field| quote!(this.$(&field.name)));
+            gen_argument_list(parameters.collect(), false)
+        }
+    };
+
+    let declarations = (!fields.is_empty()).then(|| {
+        quote! {
+            $(for field in fields.all() join ($['\r']) => final $(dart_type(field.value_type)) $(&field.name);)
+        }
+    });
+
+    let ser = gen_settings.ser.then(|| {
+        let mut names = LocalNames::default();
+        let mut statements = Tokens::new();
+        if let Some((_, index)) = variant {
+            quote_in!(statements => writer.writeVariantIndex($index););
+            statements.push();
+        }
+        for field in fields.all() {
+            statements.append(gen_ser_value(&field.name, field.value_type, &mut names));
+            statements.push();
+        }
+
+        let serialize_to = quote!(void serializeTo(PostcardWriter writer) $(gen_block(statements)));
+        match variant {
+            Some(_) => quote! {
+                @override
+                $serialize_to
+            },
+            None => quote! {
+                $(gen_serialize_to_bytes())
+                $['\n']
+                $serialize_to
+            },
+        }
+    });
+
+    // variants are deserialized by the enum
+    let des = (gen_settings.des && variant.is_none()).then(|| {
+        quote! {
+            $(gen_deserialize_from_bytes(name))
+
+            static $name deserializeFrom(PostcardReader reader) => $(gen_constructor_call(name, fields));
+        }
+    });
+
+    let equality = if fields.is_empty() {
+        quote! {
+            @override
+            bool operator ==(Object other) => other is $name;
+
+            @override
+            int get hashCode => ($name).hashCode;
+        }
+    } else {
+        let comparisons = fields
+            .all()
+            .iter()
+            .map(|field| quote!(postcardEquals($(&field.name), other.$(&field.name))));
+        quote! {
+            @override
+            bool operator ==(Object other) =>
+                identical(this, other) ||
+                other is $name &&
+                    $(for comparison in comparisons join ( &&$['\r']) => $comparison);
+
+            @override
+            int get hashCode => postcardHash([$(for field in fields.all() join (, ) => $(&field.name))]);
+        }
+    };
+
+    let to_string = match fields {
+        Fields::Named(fields) => fields
+            .iter()
+            .map(|field| format!("{}: ${}", field.name, field.name))
+            .collect::<Vec<_>>(),
+        Fields::Positional(fields) => fields
+            .iter()
+            .map(|field| format!("${}", field.name))
+            .collect::<Vec<_>>(),
+    };
+    let to_string = if to_string.is_empty() {
+        format!("\"{name}()\"")
+    } else {
+        format!("\"{name}({})\"", to_string.join(", "))
+    };
+
+    let header = match variant {
+        Some((enum_name, _)) => quote!(final class $name extends $enum_name),
+        None => quote!(final class $name),
+    };
+
+    quote! {
+        $header {
+            const $name$constructor_parameters;
+            $(if let Some(declarations) = declarations {
+                $['\n']
+                $declarations
+            })
+            $(for member in [ser, des].into_iter().flatten() => $['\n']$member)
+
+            $equality
+
+            @override
+            String toString() => $to_string;
+        }
+    }
+}
+
+/// Argument list, with one argument per line if there is more than one.
+fn gen_argument_list(arguments: Vec<Tokens>, named: bool) -> Tokens {
+    let (open, close) = if named { ("({", "})") } else { ("(", ")") };
+    if arguments.len() > 1 {
+        quote! {
+            $open
+                $(for argument in arguments join ($['\r']) => $argument,)
+            $close
+        }
+    } else {
+        quote!($open$(for argument in arguments => $argument)$close)
+    }
+}
+
+fn gen_constructor_call(name: &str, fields: &Fields) -> Tokens {
+    let arguments = match fields {
+        Fields::Named(fields) => fields
+            .iter()
+            .map(|field| quote!($(&field.name): $(gen_des_value(field.value_type))))
+            .collect(),
+        Fields::Positional(fields) => fields
+            .iter()
+            .map(|field| gen_des_value(field.value_type))
+            .collect(),
+    };
+    quote!($name$(gen_argument_list(arguments, false)))
+}
+
+/// Block on its own lines, also if it is empty.
+fn gen_block(statements: Tokens) -> Tokens {
+    let mut tokens = Tokens::new();
+    tokens.append("{");
+    if statements.is_empty() {
+        tokens.push();
+    } else {
+        tokens.indent();
+        tokens.append(statements);
+        tokens.unindent();
+    }
+    tokens.append("}");
+    tokens
+}
+
+fn gen_serialize_to_bytes() -> Tokens {
+    quote! {
+        $(typed_data("Uint8List")) serialize() {
+            final writer = PostcardWriter();
+            serializeTo(writer);
+            return writer.toBytes();
+        }
+    }
+}
+
+fn gen_deserialize_from_bytes(name: &str) -> Tokens {
+    let uint8_list = typed_data("Uint8List");
+    quote! {
+        static ($name, $(&uint8_list)) deserialize($(&uint8_list) bytes) {
+            final reader = PostcardReader(bytes);
+            final value = deserializeFrom(reader);
+            return (value, reader.remaining());
+        }
+    }
+}
+
+/// Dart type a [ValueType] is represented with.
+fn dart_type(value_type: &ValueType) -> Tokens {
+    match value_type {
+        ValueType::Bool(_) => quote!(bool),
+        ValueType::Number(NumberMeta::FloatingPoint { .. }) => quote!(double),
+        // 64 bit integers don't fit into the integers of the web platform
+        ValueType::Number(NumberMeta::Integer { bytes, .. }) if *bytes > 4 => quote!(BigInt),
+        ValueType::Number(NumberMeta::Integer { .. }) => quote!(int),
+        ValueType::String(_) => quote!(String),
+        ValueType::Array(meta) => quote!(List<$(dart_type(&meta.items_type))>),
+        ValueType::Map(meta) => {
+            quote!(Map<$(dart_type(&meta.key_type)), $(dart_type(&meta.value_type))>)
+        }
+        // Dart has no nested nullable types, `None` and `Some(None)` are both represented by null
+        ValueType::Optional(meta) if matches!(*meta.inner, ValueType::Optional(_)) => {
+            dart_type(&meta.inner)
+        }
+        ValueType::Optional(meta) => quote!($(dart_type(&meta.inner))?),
+        ValueType::Tuple(meta) if meta.items_types.len() == 1 => dart_type(&meta.items_types[0]),
+        ValueType::Tuple(meta) => {
+            let items = meta.items_types.iter().map(dart_type);
+            quote!(($(for item in items join (, ) => $item)))
+        }
+        ValueType::Range(meta) => quote!(PostcardRange<$(dart_type(&meta.bounds_type))>),
+        ValueType::Object(meta) => quote!($(object_name(meta))),
+    }
+}
@@ -0,0 +1,178 @@
+use genco::quote;
+
+use crate::type_info::ValueType;
+
+use super::{
+    object_name,
+    ser::{
+        fixint, gen_max_length_check, gen_try, is_non_zero, number_suffix, selector, LocalNames,
+    },
+    types::TypeContext,
+    Tokens,
+};
+
+/// Statements deserializing a value with the decoder `d` into the variable `target`.
+pub(super) fn gen_des_value(
+    target: &str,
+    value_type: &ValueType,
+    context: &TypeContext,
+    names: &mut LocalNames,
+) -> Tokens {
+    match value_type {
+        ValueType::Bool(_) => gen_try(quote!(d.ReadBool($(address(target))))),
+        ValueType::Number(meta) => {
+            let read = gen_try(match fixint(meta) {
+                Some((16, signed, little_endian)) => {
+                    let method = if signed {
+                        "ReadFixintI128"
+                    } else {
+                        "ReadFixintU128"
+                    };
+                    quote!(d.$method($(address(target)), $(little_endian.to_string())))
+                }
+                Some((bytes, _, little_endian)) => quote! {
+                    readFixint(d, $(address(target)), $bytes, $(little_endian.to_string()))
+                },
+                None => quote!(d.$(format!("Read{}", number_suffix(meta)))($(address(target)))),
+            });
+            if is_non_zero(meta) {
+                quote! {
+                    $read
+                    $(gen_try(quote!(checkNonZero($target))))
+                }
+            } else {
+                read
+            }
+        }
+        ValueType::String(meta) => quote! {
+            $(gen_try(quote!(d.ReadString($(address(target))))))
+            $(gen_max_length_check(target, meta.max_length))
+        },
+        ValueType::Array(meta) if meta.length.is_some() => {
+            let index = names.next("i");
+            quote! {
+                for $(&index) := range $target {
+                    $(gen_des_value(&index_expr(target, &index), &meta.items_type, context, names))
+                }
+            }
+        }
+        ValueType::Array(meta) => {
+            let length = names.next("n");
+            let index = names.next("i");
+            let item = names.next("x");
+            quote! {
+                var $(&length) int
+                $(gen_try(quote!(d.ReadLength(&$(&length)))))
+                $(gen_max_length_check(&length, meta.max_length))
+                $target = make($(context.go_type(value_type)), 0)
+                for $(&index) := 0; $(&index) < $(&length); $(&index)++ {
+                    var $(&item) $(context.go_type(&meta.items_type))
+                    $(gen_des_value(&item, &meta.items_type, context, names))
+                    $target = append($target, $(&item))
+                }
+            }
+        }
+        ValueType::Map(meta) => {
+            let length = names.next("n");
+            let index = names.next("i");
+            let (init, entries) = if context.is_map_key(&meta.key_type) {
+                let key = names.next("k");
+                let value = names.next("v");
+                let entries = quote! {
+                    var $(&key) $(context.go_type(&meta.key_type))
+                    $(gen_des_value(&key, &meta.key_type, context, names))
+                    var $(&value) $(context.go_type(&meta.value_type))
+                    $(gen_des_value(&value, &meta.value_type, context, names))
+                    $(index_expr(target, &key)) = $(&value)
+                };
+                (quote!(make($(context.go_type(value_type)))), entries)
+            } else {
+                let entry = names.next("x");
+                let entries = quote! {
+                    var $(&entry) PostcardMapEntry[$(context.go_type(&meta.key_type)), $(context.go_type(&meta.value_type))]
+                    $(gen_des_value(&format!("{entry}.Key"), &meta.key_type, context, names))
+                    $(gen_des_value(&format!("{entry}.Value"), &meta.value_type, context, names))
+                    $target = append($target, $(&entry))
+                };
+                (quote!(make($(context.go_type(value_type)), 0)), entries)
+            };
+            quote! {
+                var $(&length) int
+                $(gen_try(quote!(d.ReadLength(&$(&length)))))
+                $(gen_max_length_check(&length, meta.max_length))
+                $target = $init
+                for $(&index) := 0; $(&index) < $(&length); $(&index)++ {
+                    $entries
+                }
+            }
+        }
+        ValueType::Optional(meta) => {
+            let some = names.next("some");
+            let inner = if context.is_interface(&meta.inner) {
+                gen_des_value(target, &meta.inner, context, names)
+            } else {
+                quote! {
+                    $target = new($(context.go_type(&meta.inner)))
+                    $(gen_des_value(&format!("*{target}"), &meta.inner, context, names))
+                }
+            };
+            quote! {
+                var $(&some) bool
+                $(gen_try(quote!(d.ReadOptionTag(&$(&some)))))
+                if $(&some) {
+                    $inner
+                } else {
+                    $target = nil
+                }
+            }
+        }
+        // a tuple with a single item is represented by the item itself
+        ValueType::Tuple(meta) if meta.items_types.len() == 1 => {
+            gen_des_value(target, &meta.items_types[0], context, names)
+        }
+        ValueType::Tuple(meta) => {
+            let mut tokens = Tokens::new();
+            for (index, ty) in meta.items_types.iter().enumerate() {
+                tokens.append(gen_des_value(
+                    &selector(target, &format!("V{index}")),
+                    ty,
+                    context,
+                    names,
+                ));
+                tokens.push();
+            }
+            tokens
+        }
+        ValueType::Range(meta) => quote! {
+            $(gen_des_value(&selector(target, "Start"), &meta.bounds_type, context, names))
+            $(gen_des_value(&selector(target, "End"), &meta.bounds_type, context, names))
+        },
+        ValueType::Object(meta) if context.is_enum(meta) => {
+            let value = names.next("x");
+            quote! {
+                $(&value), err := $(format!("Decode{}", object_name(meta)))(d)
+                if err != nil {
+                    return err
+                }
+                $target = $(&value)
+            }
+        }
+        ValueType::Object(_) => gen_try(quote!($(selector(target, "DecodePostcard"))(d))),
+    }
+}
+
+/// Pointer to the variable `target`.
+fn address(target: &str) -> String {
+    match target.strip_prefix('*') {
+        Some(pointer) => pointer.to_owned(),
+        None => format!("&{target}"),
+    }
+}
+
+fn index_expr(target: &str, index: &str) -> String {
+    if target.starts_with('*') {
+        format!("({target})[{index}]")
+    } else {
+        format!("{target}[{index}]")
+    }
+}
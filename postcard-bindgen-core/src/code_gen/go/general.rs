@@ -0,0 +1,551 @@
+use std::collections::BTreeSet;
+
+use genco::{quote, tokens::quoted};
+
+use super::{GenerationSettings, Tokens};
+
+/// Package clause of a generated file, followed by the imports of the file.
+///
+/// Go rejects unused imports, so every file lists exactly the packages it uses.
+pub(super) fn gen_header(package: &str, imports: BTreeSet<&str>) -> Tokens {
+    let mut tokens = quote!(package $package);
+    match imports.len() {
+        0 => {}
+        1 => {
+            tokens.line();
+            tokens.append(quote!(import $(for import in imports => $(quoted(import)))));
+        }
+        _ => {
+            tokens.line();
+            tokens.append(quote! {
+                import (
+                    $(for import in imports join ($['\r']) => $(quoted(import)))
+                )
+            });
+        }
+    }
+    tokens
+}
+
+/// Generates the runtime shared by all generated types.
+pub(super) fn gen_runtime(package: &str, gen_settings: &GenerationSettings) -> Tokens {
+    let mut imports = BTreeSet::new();
+    let mut functions = Tokens::new();
+
+    if gen_settings.ser || gen_settings.des {
+        imports.extend(["errors", "fmt"]);
+        functions.line();
+        functions.append(gen_checks());
+    }
+    if gen_settings.ser {
+        imports.extend(["encoding/binary", "math"]);
+        functions.line();
+        functions.append(gen_encoder());
+    }
+    if gen_settings.des {
+        imports.extend(["bytes", "encoding/binary", "io", "math", "unicode/utf8"]);
+        functions.line();
+        functions.append(gen_decoder());
+    }
+
+    quote! {
+        $(gen_header(package, imports))
+
+        $("// PostcardUint128 is an unsigned 128 bit integer split into its upper and lower half.")
+        type PostcardUint128 struct {
+            Hi uint64
+            Lo uint64
+        }
+
+        $("// PostcardInt128 is a signed 128 bit integer in two's complement split into its upper and lower half.")
+        type PostcardInt128 struct {
+            Hi int64
+            Lo uint64
+        }
+
+        $("// PostcardRange is the representation of a Rust range.")
+        type PostcardRange[T any] struct {
+            Start T
+            End T
+        }
+
+        $("// PostcardMapEntry is an entry of a map whose key type can't be the key of a Go map.")
+        type PostcardMapEntry[K any, V any] struct {
+            Key K
+            Value V
+        }
+        $functions
+    }
+}
+
+fn gen_checks() -> Tokens {
+    quote! {
+        $("// ErrZeroValue is returned for a zero value of a non-zero integer.")
+        var ErrZeroValue = errors.New("postcard: zero value for a non-zero integer")
+
+        func checkNonZero[T comparable](value T) error {
+            var zero T
+            if value == zero {
+                return ErrZeroValue
+            }
+            return nil
+        }
+
+        func checkMaxLength(length int, maxLength int) error {
+            if length > maxLength {
+                return fmt.Errorf("postcard: length %d exceeds the maximum length %d", length, maxLength)
+            }
+            return nil
+        }
+    }
+}
+
+fn gen_encoder() -> Tokens {
+    quote! {
+        $("// PostcardEncoder writes values in the postcard wire format to a byte buffer.")
+        type PostcardEncoder struct {
+            buf []byte
+        }
+
+        func NewPostcardEncoder() *PostcardEncoder {
+            return &PostcardEncoder{}
+        }
+
+        $("// Bytes returns the bytes written so far.")
+        func (e *PostcardEncoder) Bytes() []byte {
+            return e.buf
+        }
+
+        func (e *PostcardEncoder) WriteVarint(value uint64) {
+            for value >= 0x80 {
+                e.buf = append(e.buf, byte(value)|0x80)
+                value >>= 7
+            }
+            e.buf = append(e.buf, byte(value))
+        }
+
+        func (e *PostcardEncoder) WriteBool(value bool) {
+            if value {
+                e.buf = append(e.buf, 1)
+            } else {
+                e.buf = append(e.buf, 0)
+            }
+        }
+
+        func (e *PostcardEncoder) WriteU8(value uint8) {
+            e.buf = append(e.buf, value)
+        }
+
+        func (e *PostcardEncoder) WriteU16(value uint16) {
+            e.WriteVarint(uint64(value))
+        }
+
+        func (e *PostcardEncoder) WriteU32(value uint32) {
+            e.WriteVarint(uint64(value))
+        }
+
+        func (e *PostcardEncoder) WriteU64(value uint64) {
+            e.WriteVarint(value)
+        }
+
+        func (e *PostcardEncoder) WriteU128(value PostcardUint128) {
+            hi, lo := value.Hi, value.Lo
+            for hi != 0 || lo >= 0x80 {
+                e.buf = append(e.buf, byte(lo)|0x80)
+                lo = lo>>7 | hi<<57
+                hi >>= 7
+            }
+            e.buf = append(e.buf, byte(lo))
+        }
+
+        func (e *PostcardEncoder) WriteI8(value int8) {
+            e.buf = append(e.buf, byte(value))
+        }
+
+        func (e *PostcardEncoder) WriteI16(value int16) {
+            e.WriteI64(int64(value))
+        }
+
+        func (e *PostcardEncoder) WriteI32(value int32) {
+            e.WriteI64(int64(value))
+        }
+
+        func (e *PostcardEncoder) WriteI64(value int64) {
+            e.WriteVarint(uint64(value<<1) ^ uint64(value>>63))
+        }
+
+        func (e *PostcardEncoder) WriteI128(value PostcardInt128) {
+            sign := uint64(value.Hi >> 63)
+            e.WriteU128(PostcardUint128{
+                Hi: (uint64(value.Hi)<<1 | value.Lo>>63) ^ sign,
+                Lo: value.Lo<<1 ^ sign,
+            })
+        }
+
+        $("// WriteFixint writes the lower size bytes of value with a fixed width.")
+        func (e *PostcardEncoder) WriteFixint(value uint64, size int, littleEndian bool) {
+            for i := 0; i < size; i++ {
+                shift := i
+                if !littleEndian {
+                    shift = size - 1 - i
+                }
+                e.buf = append(e.buf, byte(value>>(8*shift)))
+            }
+        }
+
+        func (e *PostcardEncoder) WriteFixintU128(value PostcardUint128, littleEndian bool) {
+            if littleEndian {
+                e.WriteFixint(value.Lo, 8, true)
+                e.WriteFixint(value.Hi, 8, true)
+            } else {
+                e.WriteFixint(value.Hi, 8, false)
+                e.WriteFixint(value.Lo, 8, false)
+            }
+        }
+
+        func (e *PostcardEncoder) WriteFixintI128(value PostcardInt128, littleEndian bool) {
+            e.WriteFixintU128(PostcardUint128{Hi: uint64(value.Hi), Lo: value.Lo}, littleEndian)
+        }
+
+        func (e *PostcardEncoder) WriteF32(value float32) {
+            e.buf = binary.LittleEndian.AppendUint32(e.buf, math.Float32bits(value))
+        }
+
+        func (e *PostcardEncoder) WriteF64(value float64) {
+            e.buf = binary.LittleEndian.AppendUint64(e.buf, math.Float64bits(value))
+        }
+
+        func (e *PostcardEncoder) WriteString(value string) {
+            e.WriteLength(len(value))
+            e.buf = append(e.buf, value...)
+        }
+
+        func (e *PostcardEncoder) WriteLength(length int) {
+            e.WriteVarint(uint64(length))
+        }
+
+        func (e *PostcardEncoder) WriteOptionTag(some bool) {
+            e.WriteBool(some)
+        }
+
+        func (e *PostcardEncoder) WriteVariantIndex(index uint32) {
+            e.WriteU32(index)
+        }
+
+        type postcardEncodable interface {
+            EncodePostcard(e *PostcardEncoder) error
+        }
+
+        func marshalPostcard(value postcardEncodable) ([]byte, error) {
+            e := NewPostcardEncoder()
+            if err := value.EncodePostcard(e); err != nil {
+                return nil, err
+            }
+            return e.Bytes(), nil
+        }
+    }
+}
+
+fn gen_decoder() -> Tokens {
+    quote! {
+        $("// ErrVarintOverflow is returned for a varint exceeding the range of the decoded type.")
+        var ErrVarintOverflow = errors.New("postcard: varint overflows the decoded type")
+
+        $("// PostcardDecoder reads values in the postcard wire format from an io.Reader.")
+        $("//")
+        $("// The decoder reads exactly the bytes of the decoded values, so consecutive values can")
+        $("// be decoded from the same reader.")
+        type PostcardDecoder struct {
+            r io.Reader
+            scratch [8]byte
+        }
+
+        func NewPostcardDecoder(r io.Reader) *PostcardDecoder {
+            return &PostcardDecoder{r: r}
+        }
+
+        func (d *PostcardDecoder) readFull(buf []byte) error {
+            if _, err := io.ReadFull(d.r, buf); err != nil {
+                if err == io.EOF {
+                    return io.ErrUnexpectedEOF
+                }
+                return err
+            }
+            return nil
+        }
+
+        func (d *PostcardDecoder) readByte() (byte, error) {
+            if err := d.readFull(d.scratch[:1]); err != nil {
+                return 0, err
+            }
+            return d.scratch[0], nil
+        }
+
+        func (d *PostcardDecoder) ReadVarint(bits uint) (uint64, error) {
+            var value uint64
+            for shift := uint(0); shift < bits; shift += 7 {
+                b, err := d.readByte()
+                if err != nil {
+                    return 0, err
+                }
+                if shift+7 > bits && uint64(b&0x7f)>>(bits-shift) != 0 {
+                    return 0, ErrVarintOverflow
+                }
+                value |= uint64(b&0x7f) << shift
+                if b&0x80 == 0 {
+                    return value, nil
+                }
+            }
+            return 0, ErrVarintOverflow
+        }
+
+        func (d *PostcardDecoder) ReadBool(value *bool) error {
+            b, err := d.readByte()
+            if err != nil {
+                return err
+            }
+            switch b {
+            case 0:
+                *value = false
+            case 1:
+                *value = true
+            default:
+                return fmt.Errorf("postcard: invalid bool %d", b)
+            }
+            return nil
+        }
+
+        func (d *PostcardDecoder) ReadU8(value *uint8) error {
+            b, err := d.readByte()
+            if err != nil {
+                return err
+            }
+            *value = b
+            return nil
+        }
+
+        func (d *PostcardDecoder) ReadU16(value *uint16) error {
+            v, err := d.ReadVarint(16)
+            if err != nil {
+                return err
+            }
+            *value = uint16(v)
+            return nil
+        }
+
+        func (d *PostcardDecoder) ReadU32(value *uint32) error {
+            v, err := d.ReadVarint(32)
+            if err != nil {
+                return err
+            }
+            *value = uint32(v)
+            return nil
+        }
+
+        func (d *PostcardDecoder) ReadU64(value *uint64) error {
+            v, err := d.ReadVarint(64)
+            if err != nil {
+                return err
+            }
+            *value = v
+            return nil
+        }
+
+        func (d *PostcardDecoder) ReadU128(value *PostcardUint128) error {
+            var hi, lo uint64
+            for shift := uint(0); shift < 128; shift += 7 {
+                b, err := d.readByte()
+                if err != nil {
+                    return err
+                }
+                bits := uint64(b & 0x7f)
+                if shift+7 > 128 && bits>>(128-shift) != 0 {
+                    return ErrVarintOverflow
+                }
+                if shift < 64 {
+                    lo |= bits << shift
+                    hi |= bits >> (64 - shift)
+                } else {
+                    hi |= bits << (shift - 64)
+                }
+                if b&0x80 == 0 {
+                    *value = PostcardUint128{Hi: hi, Lo: lo}
+                    return nil
+                }
+            }
+            return ErrVarintOverflow
+        }
+
+        func (d *PostcardDecoder) ReadI8(value *int8) error {
+            b, err := d.readByte()
+            if err != nil {
+                return err
+            }
+            *value = int8(b)
+            return nil
+        }
+
+        func (d *PostcardDecoder) ReadI16(value *int16) error {
+            v, err := d.ReadVarint(16)
+            if err != nil {
+                return err
+            }
+            *value = int16(unzigzag(v))
+            return nil
+        }
+
+        func (d *PostcardDecoder) ReadI32(value *int32) error {
+            v, err := d.ReadVarint(32)
+            if err != nil {
+                return err
+            }
+            *value = int32(unzigzag(v))
+            return nil
+        }
+
+        func (d *PostcardDecoder) ReadI64(value *int64) error {
+            v, err := d.ReadVarint(64)
+            if err != nil {
+                return err
+            }
+            *value = unzigzag(v)
+            return nil
+        }
+
+        func (d *PostcardDecoder) ReadI128(value *PostcardInt128) error {
+            var v PostcardUint128
+            if err := d.ReadU128(&v); err != nil {
+                return err
+            }
+            sign := -(v.Lo & 1)
+            *value = PostcardInt128{
+                Hi: int64(v.Hi>>1 ^ sign),
+                Lo: (v.Lo>>1 | v.Hi<<63) ^ sign,
+            }
+            return nil
+        }
+
+        $("// ReadFixint reads size bytes holding an integer with a fixed width.")
+        func (d *PostcardDecoder) ReadFixint(size int, littleEndian bool) (uint64, error) {
+            if err := d.readFull(d.scratch[:size]); err != nil {
+                return 0, err
+            }
+            var value uint64
+            for i := 0; i < size; i++ {
+                index := i
+                if littleEndian {
+                    index = size - 1 - i
+                }
+                value = value<<8 | uint64(d.scratch[index])
+            }
+            return value, nil
+        }
+
+        func (d *PostcardDecoder) ReadFixintU128(value *PostcardUint128, littleEndian bool) error {
+            first, err := d.ReadFixint(8, littleEndian)
+            if err != nil {
+                return err
+            }
+            second, err := d.ReadFixint(8, littleEndian)
+            if err != nil {
+                return err
+            }
+            if littleEndian {
+                *value = PostcardUint128{Hi: second, Lo: first}
+            } else {
+                *value = PostcardUint128{Hi: first, Lo: second}
+            }
+            return nil
+        }
+
+        func (d *PostcardDecoder) ReadFixintI128(value *PostcardInt128, littleEndian bool) error {
+            var v PostcardUint128
+            if err := d.ReadFixintU128(&v, littleEndian); err != nil {
+                return err
+            }
+            *value = PostcardInt128{Hi: int64(v.Hi), Lo: v.Lo}
+            return nil
+        }
+
+        $("// readFixint reads an integer with a fixed width of size bytes into value.")
+        func readFixint[T ~int8 | ~int16 | ~int32 | ~int64 | ~uint8 | ~uint16 | ~uint32 | ~uint64](d *PostcardDecoder, value *T, size int, littleEndian bool) error {
+            v, err := d.ReadFixint(size, littleEndian)
+            if err != nil {
+                return err
+            }
+            *value = T(v)
+            return nil
+        }
+
+        func (d *PostcardDecoder) ReadF32(value *float32) error {
+            if err := d.readFull(d.scratch[:4]); err != nil {
+                return err
+            }
+            *value = math.Float32frombits(binary.LittleEndian.Uint32(d.scratch[:4]))
+            return nil
+        }
+
+        func (d *PostcardDecoder) ReadF64(value *float64) error {
+            if err := d.readFull(d.scratch[:8]); err != nil {
+                return err
+            }
+            *value = math.Float64frombits(binary.LittleEndian.Uint64(d.scratch[:8]))
+            return nil
+        }
+
+        func (d *PostcardDecoder) ReadString(value *string) error {
+            var length int
+            if err := d.ReadLength(&length); err != nil {
+                return err
+            }
+            var buf bytes.Buffer
+            if _, err := io.CopyN(&buf, d.r, int64(length)); err != nil {
+                if err == io.EOF {
+                    return io.ErrUnexpectedEOF
+                }
+                return err
+            }
+            if !utf8.Valid(buf.Bytes()) {
+                return errors.New("postcard: invalid utf-8 string")
+            }
+            *value = buf.String()
+            return nil
+        }
+
+        func (d *PostcardDecoder) ReadLength(length *int) error {
+            v, err := d.ReadVarint(64)
+            if err != nil {
+                return err
+            }
+            if v > math.MaxInt {
+                return ErrVarintOverflow
+            }
+            *length = int(v)
+            return nil
+        }
+
+        func (d *PostcardDecoder) ReadOptionTag(some *bool) error {
+            return d.ReadBool(some)
+        }
+
+        func (d *PostcardDecoder) ReadVariantIndex(index *uint32) error {
+            return d.ReadU32(index)
+        }
+
+        func unzigzag(value uint64) int64 {
+            return int64(value>>1) ^ -int64(value&1)
+        }
+
+        func errUnknownVariant(name string, index uint32) error {
+            return fmt.Errorf("postcard: unknown variant index %d of %s", index, name)
+        }
+
+        func unmarshalPostcard(data []byte, decode func(d *PostcardDecoder) error) ([]byte, error) {
+            r := bytes.NewReader(data)
+            if err := decode(NewPostcardDecoder(r)); err != nil {
+                return nil, err
+            }
+            return data[len(data)-r.Len():], nil
+        }
+    }
+}
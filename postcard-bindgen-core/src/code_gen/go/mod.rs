@@ -0,0 +1,172 @@
+mod des;
+mod general;
+mod ser;
+mod types;
+
+use core::borrow::Borrow;
+
+use convert_case::{Case, Casing};
+use genco::lang::go::Go;
+use general::gen_runtime;
+use types::gen_types;
+
+use crate::{
+    path::{Path, PathBuf},
+    registry::{Container, ContainerCollection},
+    type_info::ObjectMeta,
+    validation::ValidationSettings,
+    ExportFile, Exports,
+};
+
+use super::utils::snake_case;
+
+type Tokens = genco::lang::go::Tokens;
+
+/// Settings for Go bindings generation.
+///
+/// Structs are generated as structs, enums as interfaces implemented by a struct for each
+/// variant. Serialization and deserialization are methods of the generated structs.
+///
+/// By default, only deserialization is enabled. Serialization can be enabled by using [`GenerationSettings::serialization()`].
+/// Deserialization can be disabled with [`GenerationSettings::deserialization()`].
+/// To enable all at once use [`GenerationSettings::enable_all()`].
+#[derive(Debug)]
+pub struct GenerationSettings {
+    ser: bool,
+    des: bool,
+    module_structure: bool,
+}
+
+impl GenerationSettings {
+    /// Constructs [`GenerationSettings`] and enables all options at once.
+    pub fn enable_all() -> Self {
+        Self {
+            ser: true,
+            des: true,
+            module_structure: true,
+        }
+    }
+
+    /// Enabling or disabling of serialization code generation.
+    pub fn serialization(mut self, enabled: bool) -> Self {
+        self.ser = enabled;
+        self
+    }
+
+    /// Enabling or disabling of deserialization code generation.
+    pub fn deserialization(mut self, enabled: bool) -> Self {
+        self.des = enabled;
+        self
+    }
+
+    /// Enabling or disabling of module structure code generation.
+    ///
+    /// Go packages can't import each other in cycles, so all types are generated in a
+    /// single package. Enabling this will prefix the names of types nested in modules
+    /// with the module path (e.g. `mod_name::TypeName` becomes `ModNameTypeName`).
+    /// Different paths can still join to the same name (e.g. `a_b::C` and `a::BC`), such
+    /// clashes are reported when the package is built.
+    ///
+    /// Disabling this will generate all types with their plain names.
+    pub fn module_structure(mut self, enabled: bool) -> Self {
+        self.module_structure = enabled;
+        self
+    }
+}
+
+impl Default for GenerationSettings {
+    fn default() -> Self {
+        Self {
+            ser: false,
+            des: true,
+            module_structure: true,
+        }
+    }
+}
+
+impl From<&GenerationSettings> for ValidationSettings {
+    fn from(settings: &GenerationSettings) -> Self {
+        // all types and the structs of the enum variants share one package
+        ValidationSettings::default()
+            .flattened(!settings.module_structure)
+            .shared_namespace(type_name)
+            .variant_classes(variant_type_name)
+    }
+}
+
+/// Generates the Go code for the containers.
+///
+/// The generated files are `runtime`, holding the encoder and decoder, and `types`. Both
+/// belong to the package named after `package_name`.
+pub fn generate(
+    mut containers: ContainerCollection,
+    gen_settings: impl Borrow<GenerationSettings>,
+    package_name: String,
+) -> Exports<Go> {
+    let gen_settings = gen_settings.borrow();
+    let package = snake_case(&package_name);
+
+    if !gen_settings.module_structure {
+        containers.flatten();
+    }
+
+    Exports {
+        files: vec![
+            ExportFile {
+                content_type: "runtime".to_owned(),
+                content: gen_runtime(&package, gen_settings),
+            },
+            ExportFile {
+                content_type: "types".to_owned(),
+                content: gen_types(&package, containers.all_containers(), gen_settings),
+            },
+        ],
+    }
+}
+
+/// Name of the Go type of the container.
+fn container_name(container: &Container) -> String {
+    type_name(&container.path, &container.name)
+}
+
+/// Name of the Go type of the referenced container.
+fn object_name(meta: &ObjectMeta) -> String {
+    type_name(&meta.path, &meta.name)
+}
+
+fn type_name(path: &Path<'_, '_>, name: &str) -> String {
+    let mut path: PathBuf = path.clone().into_buf();
+    // We will skip the first part of the path, as it is the crate name.
+    path.pop_front();
+    path.parts()
+        .map(|part| pascal_case(part.as_ref()))
+        .chain([name.to_owned()])
+        .collect()
+}
+
+/// Name of the Go struct of an enum variant.
+fn variant_type_name(enum_name: &str, variant_name: &str) -> String {
+    format!("{enum_name}{variant_name}")
+}
+
+/// Exported Go name of a struct field.
+fn field_name(name: &str) -> String {
+    let name = pascal_case(name);
+    // fields can't be named like the methods of the struct
+    if METHODS.contains(&name.as_str()) {
+        format!("{name}_")
+    } else {
+        name
+    }
+}
+
+const METHODS: &[&str] = &[
+    "EncodePostcard",
+    "MarshalPostcard",
+    "DecodePostcard",
+    "UnmarshalPostcard",
+];
+
+fn pascal_case(value: &str) -> String {
+    value.to_case(Case::Pascal)
+}
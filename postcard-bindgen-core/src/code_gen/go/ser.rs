@@ -0,0 +1,201 @@
+use genco::quote;
+
+use crate::type_info::{IntegerEncoding, NumberMeta, ValueType};
+
+use super::{types::TypeContext, Tokens};
+
+/// Hands out the names of the local variables of a generated function.
+#[derive(Default)]
+pub(super) struct LocalNames(usize);
+
+impl LocalNames {
+    pub(super) fn next(&mut self, prefix: &str) -> String {
+        let name = format!("{prefix}{}", self.0);
+        self.0 += 1;
+        name
+    }
+}
+
+/// Statements serializing the value of `expr` with the encoder `e`.
+pub(super) fn gen_ser_value(
+    expr: &str,
+    value_type: &ValueType,
+    context: &TypeContext,
+    names: &mut LocalNames,
+) -> Tokens {
+    match value_type {
+        ValueType::Bool(_) => quote!(e.WriteBool($expr)),
+        ValueType::Number(meta) => {
+            let write = match fixint(meta) {
+                Some((16, signed, little_endian)) => {
+                    let method = if signed {
+                        "WriteFixintI128"
+                    } else {
+                        "WriteFixintU128"
+                    };
+                    quote!(e.$method($expr, $(little_endian.to_string())))
+                }
+                Some((bytes, _, little_endian)) => {
+                    quote!(e.WriteFixint(uint64($expr), $bytes, $(little_endian.to_string())))
+                }
+                None => quote!(e.$(format!("Write{}", number_suffix(meta)))($expr)),
+            };
+            if is_non_zero(meta) {
+                quote! {
+                    $(gen_try(quote!(checkNonZero($expr))))
+                    $write
+                }
+            } else {
+                write
+            }
+        }
+        ValueType::String(meta) => quote! {
+            $(gen_max_length_check(expr, meta.max_length))
+            e.WriteString($expr)
+        },
+        ValueType::Array(meta) => {
+            let item = names.next("x");
+            let length = meta.length.is_none().then(|| {
+                quote! {
+                    $(gen_max_length_check(expr, meta.max_length))
+                    e.WriteLength(len($expr))
+                }
+            });
+            quote! {
+                $length
+                for _, $(&item) := range $expr {
+                    $(gen_ser_value(&item, &meta.items_type, context, names))
+                }
+            }
+        }
+        ValueType::Map(meta) => {
+            let length = quote! {
+                $(gen_max_length_check(expr, meta.max_length))
+                e.WriteLength(len($expr))
+            };
+            if context.is_map_key(&meta.key_type) {
+                let key = names.next("k");
+                let value = names.next("v");
+                quote! {
+                    $length
+                    for $(&key), $(&value) := range $expr {
+                        $(gen_ser_value(&key, &meta.key_type, context, names))
+                        $(gen_ser_value(&value, &meta.value_type, context, names))
+                    }
+                }
+            } else {
+                let entry = names.next("x");
+                quote! {
+                    $length
+                    for _, $(&entry) := range $expr {
+                        $(gen_ser_value(&format!("{entry}.Key"), &meta.key_type, context, names))
+                        $(gen_ser_value(&format!("{entry}.Value"), &meta.value_type, context, names))
+                    }
+                }
+            }
+        }
+        ValueType::Optional(meta) => {
+            let item = names.next("x");
+            let inner = if context.is_interface(&meta.inner) {
+                item.clone()
+            } else {
+                format!("*{item}")
+            };
+            quote! {
+                if $(&item) := $expr; $(&item) != nil {
+                    e.WriteOptionTag(true)
+                    $(gen_ser_value(&inner, &meta.inner, context, names))
+                } else {
+                    e.WriteOptionTag(false)
+                }
+            }
+        }
+        // a tuple with a single item is represented by the item itself
+        ValueType::Tuple(meta) if meta.items_types.len() == 1 => {
+            gen_ser_value(expr, &meta.items_types[0], context, names)
+        }
+        ValueType::Tuple(meta) => {
+            let mut tokens = Tokens::new();
+            for (index, ty) in meta.items_types.iter().enumerate() {
+                tokens.append(gen_ser_value(
+                    &selector(expr, &format!("V{index}")),
+                    ty,
+                    context,
+                    names,
+                ));
+                tokens.push();
+            }
+            tokens
+        }
+        ValueType::Range(meta) => quote! {
+            $(gen_ser_value(&selector(expr, "Start"), &meta.bounds_type, context, names))
+            $(gen_ser_value(&selector(expr, "End"), &meta.bounds_type, context, names))
+        },
+        ValueType::Object(_) => gen_try(quote!($(selector(expr, "EncodePostcard"))(e))),
+    }
+}
+
+/// Calls the function returning an error and returns early if it fails.
+pub(super) fn gen_try(call: Tokens) -> Tokens {
+    quote! {
+        if err := $call; err != nil {
+            return err
+        }
+    }
+}
+
+pub(super) fn gen_max_length_check(expr: &str, max_length: Option<usize>) -> Tokens {
+    match max_length {
+        Some(max_length) => gen_try(quote!(checkMaxLength(len($expr), $max_length))),
+        None => Tokens::new(),
+    }
+}
+
+/// Selects the field or method of the value of `expr`, which may be a dereferenced pointer.
+pub(super) fn selector(expr: &str, name: &str) -> String {
+    if expr.starts_with('*') {
+        format!("({expr}).{name}")
+    } else {
+        format!("{expr}.{name}")
+    }
+}
+
+/// Suffix of the encoder and decoder methods of the number type.
+pub(super) fn number_suffix(meta: &NumberMeta) -> String {
+    match meta {
+        NumberMeta::Integer { bytes, signed, .. } => {
+            format!("{}{}", if *signed { "I" } else { "U" }, bytes * 8)
+        }
+        NumberMeta::FloatingPoint { bytes } => format!("F{}", bytes * 8),
+    }
+}
+
+/// Width, signedness and byte order (`true` for little endian) of an integer type which is
+/// encoded with a fixed width.
+pub(super) fn fixint(meta: &NumberMeta) -> Option<(usize, bool, bool)> {
+    match meta {
+        NumberMeta::Integer {
+            bytes,
+            signed,
+            encoding: IntegerEncoding::FixedLe,
+            ..
+        } => Some((*bytes, *signed, true)),
+        NumberMeta::Integer {
+            bytes,
+            signed,
+            encoding: IntegerEncoding::FixedBe,
+            ..
+        } => Some((*bytes, *signed, false)),
+        _ => None,
+    }
+}
+
+pub(super) fn is_non_zero(meta: &NumberMeta) -> bool {
+    matches!(
+        meta,
+        NumberMeta::Integer {
+            zero_able: false,
+            ..
+        }
+    )
+}
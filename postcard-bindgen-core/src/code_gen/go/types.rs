@@ -0,0 +1,402 @@
+use std::collections::BTreeSet;
+
+use genco::{quote, quote_in, tokens::quoted};
+
+use crate::{
+    registry::{BindingType, Container, EnumVariantType, StructField},
+    type_info::{NumberMeta, ObjectMeta, ValueType},
+};
+
+use super::{
+    container_name,
+    des::gen_des_value,
+    field_name,
+    general::gen_header,
+    object_name,
+    ser::{gen_ser_value, LocalNames},
+    variant_type_name, GenerationSettings, Tokens,
+};
+
+/// Knowledge about the generated types needed to reference them.
+pub(super) struct TypeContext {
+    enums: BTreeSet<String>,
+    non_comparable: BTreeSet<String>,
+}
+
+impl TypeContext {
+    fn new(containers: &[Container]) -> Self {
+        let enums = containers
+            .iter()
+            .filter(|container| matches!(container.r#type, BindingType::Enum(_)))
+            .map(container_name)
+            .collect();
+
+        let mut context = Self {
+            enums,
+            non_comparable: BTreeSet::new(),
+        };
+
+        // the comparability of a container depends on the other containers
+        loop {
+            let mut changed = false;
+            for container in containers {
+                let name = container_name(container);
+                if !context.non_comparable.contains(&name)
+                    && !container
+                        .r#type
+                        .field_types()
+                        .into_iter()
+                        .all(|ty| context.is_map_key(ty))
+                {
+                    context.non_comparable.insert(name);
+                    changed = true;
+                }
+            }
+            if !changed {
+                return context;
+            }
+        }
+    }
+
+    pub(super) fn is_enum(&self, meta: &ObjectMeta) -> bool {
+        self.enums.contains(&object_name(meta))
+    }
+
+    /// Whether the value is represented by an interface, which is nil for `None` if optional.
+    pub(super) fn is_interface(&self, value_type: &ValueType) -> bool {
+        matches!(value_type, ValueType::Object(meta) if self.is_enum(meta))
+    }
+
+    /// Whether the Go type of the value can be the key of a Go map.
+    ///
+    /// Pointers are comparable in Go, but they are compared by identity and not by the
+    /// value they point to, so optional values don't qualify.
+    pub(super) fn is_map_key(&self, value_type: &ValueType) -> bool {
+        match value_type {
+            ValueType::Bool(_) | ValueType::Number(_) | ValueType::String(_) => true,
+            ValueType::Array(meta) => meta.length.is_some() && self.is_map_key(&meta.items_type),
+            ValueType::Map(_) | ValueType::Optional(_) => false,
+            ValueType::Tuple(meta) => meta.items_types.iter().all(|ty| self.is_map_key(ty)),
+            ValueType::Range(meta) => self.is_map_key(&meta.bounds_type),
+            ValueType::Object(meta) => !self.non_comparable.contains(&object_name(meta)),
+        }
+    }
+
+    /// Go type a [ValueType] is represented with.
+    pub(super) fn go_type(&self, value_type: &ValueType) -> String {
+        match value_type {
+            ValueType::Bool(_) => "bool".to_owned(),
+            ValueType::Number(NumberMeta::FloatingPoint { bytes }) => format!("float{}", bytes * 8),
+            ValueType::Number(NumberMeta::Integer {
+                bytes: 16, signed, ..
+            }) => {
+                if *signed {
+                    "PostcardInt128".to_owned()
+                } else {
+                    "PostcardUint128".to_owned()
+                }
+            }
+            ValueType::Number(NumberMeta::Integer { bytes, signed, .. }) => {
+                format!("{}int{}", if *signed { "" } else { "u" }, bytes * 8)
+            }
+            ValueType::String(_) => "string".to_owned(),
+            ValueType::Array(meta) => match meta.length {
+                Some(length) => format!("[{length}]{}", self.go_type(&meta.items_type)),
+                None => format!("[]{}", self.go_type(&meta.items_type)),
+            },
+            ValueType::Map(meta) if self.is_map_key(&meta.key_type) => format!(
+                "map[{}]{}",
+                self.go_type(&meta.key_type),
+                self.go_type(&meta.value_type)
+            ),
+            ValueType::Map(meta) => format!(
+                "[]PostcardMapEntry[{}, {}]",
+                self.go_type(&meta.key_type),
+                self.go_type(&meta.value_type)
+            ),
+            ValueType::Optional(meta) if self.is_interface(&meta.inner) => {
+                self.go_type(&meta.inner)
+            }
+            ValueType::Optional(meta) => format!("*{}", self.go_type(&meta.inner)),
+            // a tuple with a single item is represented by the item itself
+            ValueType::Tuple(meta) if meta.items_types.len() == 1 => {
+                self.go_type(&meta.items_types[0])
+            }
+            ValueType::Tuple(meta) => {
+                let items = meta
+                    .items_types
+                    .iter()
+                    .enumerate()
+                    .map(|(index, ty)| format!("V{index} {}", self.go_type(ty)))
+                    .collect::<Vec<_>>();
+                format!("struct{{ {} }}", items.join("; "))
+            }
+            ValueType::Range(meta) => {
+                format!("PostcardRange[{}]", self.go_type(&meta.bounds_type))
+            }
+            ValueType::Object(meta) => object_name(meta),
+        }
+    }
+}
+
+/// Generates the types of all containers with their methods.
+pub(super) fn gen_types(
+    package: &str,
+    containers: impl Iterator<Item = Container>,
+    gen_settings: &GenerationSettings,
+) -> Tokens {
+    let containers = containers.collect::<Vec<_>>();
+    let context = TypeContext::new(&containers);
+
+    let mut tokens = gen_header(package, BTreeSet::new());
+    for container in containers.iter() {
+        tokens.line();
+        tokens.append(gen_container(container, &context, gen_settings));
+    }
+    tokens
+}
+
+fn gen_container(
+    container: &Container,
+    context: &TypeContext,
+    gen_settings: &GenerationSettings,
+) -> Tokens {
+    let name = container_name(container);
+    match &container.r#type {
+        BindingType::Struct(ty) => {
+            gen_struct(&name, &named_fields(&ty.fields), context, gen_settings)
+        }
+        BindingType::TupleStruct(ty) => {
+            gen_struct(&name, &unnamed_fields(&ty.fields), context, gen_settings)
+        }
+        BindingType::UnitStruct(_) => gen_struct(&name, &[], context, gen_settings),
+        BindingType::Enum(ty) => {
+            let marker = format!("is{name}");
+            let mut variants = Tokens::new();
+            let mut cases = Tokens::new();
+
+            for variant in ty.variants.iter() {
+                let variant_name = variant_type_name(&name, &variant.name);
+                let fields = match &variant.inner_type {
+                    EnumVariantType::Empty => Vec::new(),
+                    EnumVariantType::Tuple(fields) => unnamed_fields(fields),
+                    EnumVariantType::NewType(fields) => named_fields(fields),
+                };
+                let index = variant.index;
+
+                variants.line();
+                variants.append(gen_struct_definition(&variant_name, &fields, context));
+                variants.line();
+                quote_in! { variants =>
+                    func ($(&variant_name)) $(&marker)() {}
+                };
+
+                if gen_settings.ser {
+                    variants.line();
+                    variants.append(gen_encode_methods(
+                        &variant_name,
+                        Some(index),
+                        &fields,
+                        context,
+                    ));
+                }
+
+                if fields.is_empty() {
+                    quote_in! { cases =>
+                        case $index:
+                            return $(format!("{variant_name}{{}}")), nil
+                    };
+                } else {
+                    if gen_settings.des {
+                        let mut names = LocalNames::default();
+                        variants.line();
+                        quote_in! { variants =>
+                            func (v *$(&variant_name)) decodeFields(d *PostcardDecoder) error {
+                                $(gen_des_fields(&fields, context, &mut names))
+                                return nil
+                            }
+                        };
+                    }
+                    quote_in! { cases =>
+                        case $index:
+                            var v $(&variant_name)
+                            if err := v.decodeFields(d); err != nil {
+                                return nil, err
+                            }
+                            return v, nil
+                    };
+                }
+                cases.push();
+            }
+
+            let encode = gen_settings.ser.then(|| {
+                quote! {
+                    EncodePostcard(e *PostcardEncoder) error
+                    MarshalPostcard() ([]byte, error)
+                }
+            });
+
+            let decode_fn = format!("Decode{name}");
+            let decode = gen_settings.des.then(|| {
+                quote! {
+                    func $(&decode_fn)(d *PostcardDecoder) ($(&name), error) {
+                        var index uint32
+                        if err := d.ReadVariantIndex(&index); err != nil {
+                            return nil, err
+                        }
+                        switch index {
+                        $cases
+                        default:
+                            return nil, errUnknownVariant($(quoted(&name)), index)
+                        }
+                    }
+
+                    func $(format!("Unmarshal{name}"))(data []byte) ($(&name), []byte, error) {
+                        var v $(&name)
+                        rest, err := unmarshalPostcard(data, func(d *PostcardDecoder) (err error) {
+                            v, err = $(&decode_fn)(d)
+                            return err
+                        })
+                        return v, rest, err
+                    }
+                }
+            });
+
+            quote! {
+                type $(&name) interface {
+                    $(&marker)()
+                    $encode
+                }
+                $variants
+                $(if let Some(decode) = decode {
+                    $['\n']
+                    $decode
+                })
+            }
+        }
+    }
+}
+
+/// Field of a generated struct with the Go name and the type.
+struct Field<'a> {
+    name: String,
+    value_type: &'a ValueType,
+}
+
+fn named_fields(fields: &[StructField]) -> Vec<Field<'_>> {
+    fields
+        .iter()
+        .map(|field| Field {
+            name: field_name(&field.name),
+            value_type: &field.v_type,
+        })
+        .collect()
+}
+
+fn unnamed_fields(fields: &[ValueType]) -> Vec<Field<'_>> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, value_type)| Field {
+            name: format!("V{index}"),
+            value_type,
+        })
+        .collect()
+}
+
+fn gen_struct(
+    name: &str,
+    fields: &[Field],
+    context: &TypeContext,
+    gen_settings: &GenerationSettings,
+) -> Tokens {
+    let mut tokens = gen_struct_definition(name, fields, context);
+
+    if gen_settings.ser {
+        tokens.line();
+        tokens.append(gen_encode_methods(name, None, fields, context));
+    }
+
+    if gen_settings.des {
+        let mut names = LocalNames::default();
+        tokens.line();
+        quote_in! { tokens =>
+            func (v *$name) DecodePostcard(d *PostcardDecoder) error {
+                $(gen_des_fields(fields, context, &mut names))
+                return nil
+            }
+
+            func (v *$name) UnmarshalPostcard(data []byte) ([]byte, error) {
+                return unmarshalPostcard(data, v.DecodePostcard)
+            }
+        };
+    }
+
+    tokens
+}
+
+/// Struct type declaration with the field names aligned like gofmt does.
+fn gen_struct_definition(name: &str, fields: &[Field], context: &TypeContext) -> Tokens {
+    if fields.is_empty() {
+        return quote!(type $name struct{});
+    }
+
+    let width = fields
+        .iter()
+        .map(|field| field.name.len())
+        .max()
+        .unwrap_or(0);
+    quote! {
+        type $name struct {
+            $(for field in fields join ($['\r']) => $(format!("{:width$} {}", field.name, context.go_type(field.value_type))))
+        }
+    }
+}
+
+fn gen_encode_methods(
+    name: &str,
+    variant_index: Option<usize>,
+    fields: &[Field],
+    context: &TypeContext,
+) -> Tokens {
+    let mut names = LocalNames::default();
+    let mut statements = Tokens::new();
+    if let Some(index) = variant_index {
+        quote_in!(statements => e.WriteVariantIndex($index));
+        statements.push();
+    }
+    for field in fields {
+        statements.append(gen_ser_value(
+            &format!("v.{}", field.name),
+            field.value_type,
+            context,
+            &mut names,
+        ));
+        statements.push();
+    }
+
+    quote! {
+        func (v $name) EncodePostcard(e *PostcardEncoder) error {
+            $statements
+            return nil
+        }
+
+        func (v $name) MarshalPostcard() ([]byte, error) {
+            return marshalPostcard(v)
+        }
+    }
+}
+
+fn gen_des_fields(fields: &[Field], context: &TypeContext, names: &mut LocalNames) -> Tokens {
+    let mut tokens = Tokens::new();
+    for field in fields {
+        tokens.append(gen_des_value(
+            &format!("v.{}", field.name),
+            field.value_type,
+            context,
+            names,
+        ));
+        tokens.push();
+    }
+    tokens
+}
@@ -71,9 +71,9 @@ impl Ord for Package {
                 _ => std::cmp::Ordering::Less,
             },
             Self::Intern(name) => match other {
-                Self::Extern(_) => std::cmp::Ordering::Greater,
+                Self::Extern(_) | Self::Root => std::cmp::Ordering::Greater,
                 Self::Intern(other_name) => name.cmp(other_name),
-                Self::Relative(_) | Self::Root => std::cmp::Ordering::Less,
+                Self::Relative(_) => std::cmp::Ordering::Less,
             },
             Self::Relative(name) => match other {
                 Self::Relative(other_name) => name.cmp(other_name),
@@ -166,6 +166,7 @@ mod test {
         );
 
         registry.push(Package::Intern("package".into()), ImportItem::All);
+        registry.push(Package::Root, ImportItem::Single("root".into()));
 
         let (base_path, items) = registry.into_items_sorted();
 
@@ -178,6 +179,10 @@ mod test {
                     Package::Extern("extern".into()),
                     ImportMode::Single(vec![Import::new("test".into())])
                 ),
+                (
+                    Package::Root,
+                    ImportMode::Single(vec![Import::new("root".into())])
+                ),
                 (Package::Intern("package".into()), ImportMode::All),
                 (
                     Package::Relative("rel".into()),
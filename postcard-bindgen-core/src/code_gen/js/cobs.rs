@@ -0,0 +1,130 @@
+use genco::quote;
+
+use crate::function_args;
+
+use super::{
+    gen_doc_comment,
+    runtime::{RuntimeClass, RuntimeFunction, Syntax},
+    ExportRegistry, Function, GenerationSettings, Tokens,
+};
+
+/// Generates the COBS framing helpers, which rely on `serialize` and `deserialize`.
+pub fn gen_cobs_code(
+    gen_settings: &GenerationSettings,
+    export_registry: &mut ExportRegistry,
+) -> Tokens {
+    let mut tokens = gen_cobs_runtime(Syntax::JavaScript);
+
+    if gen_settings.ser {
+        export_registry.push("serialize_cobs");
+        tokens.line();
+        tokens.append(
+            Function::new_untyped(
+                "serialize_cobs",
+                function_args!["type", "value"],
+                serialize_cobs_body(),
+            )
+            .with_doc_string(
+                "Serialize a value to a COBS frame, terminated by a zero byte.\n\
+                @param {string} type - The type of the value to serialize.\n\
+                @param {Object} value - The value to serialize.\n\
+                @return {Uint8Array} The encoded frame.",
+            ),
+        );
+    }
+
+    if gen_settings.des {
+        export_registry.push("deserialize_cobs");
+        export_registry.push("CobsAccumulator");
+        tokens.line();
+        tokens.append(
+            Function::new_untyped(
+                "deserialize_cobs",
+                function_args!["type", "bytes"],
+                deserialize_cobs_body(),
+            )
+            .with_doc_string(
+                "Deserialize a value from the first COBS frame in an array of bytes.\n\
+                @param {string} type - The type of the value to deserialize.\n\
+                @param {Uint8Array | Array<number>} bytes - The array of bytes holding the frame.\n\
+                @return {Object} The deserialized value and the bytes following the frame.",
+            ),
+        );
+        tokens.line();
+        tokens.append(quote! {
+            $(gen_doc_comment(COBS_ACCUMULATOR_DOC))
+            $(gen_cobs_accumulator(Syntax::JavaScript))
+        });
+    }
+
+    tokens
+}
+
+pub(in crate::code_gen) const COBS_ACCUMULATOR_DOC: &str = "Collects COBS frames from chunks of a stream and decodes them to values of the given type.
+`feed` returns a generator over the values of all completed frames, which are decoded while iterating.
+A frame which fails to decode throws, the remaining frames are yielded by the next generator.";
+
+/// Generates the COBS encoding and decoding of byte arrays.
+pub(in crate::code_gen) fn gen_cobs_runtime(syntax: Syntax) -> Tokens {
+    let encode = RuntimeFunction::new(
+        "cobs_encode",
+        [("bytes", "Uint8Array")],
+        quote!({ const out = new Uint8Array(bytes.length + Math.floor(bytes.length / 254) + 2); let code_index = 0, out_index = 1, code = 1; for (const byte of bytes) { if (byte !== 0) { out[out_index++] = byte; code++ } if (byte === 0 || code === 0xFF) { out[code_index] = code; code_index = out_index++; code = 1 } } out[code_index] = code; out[out_index++] = 0; return out.slice(0, out_index) }),
+    )
+    .returns("Uint8Array");
+    let decode = RuntimeFunction::new(
+        "cobs_decode",
+        [("bytes", "Uint8Array")],
+        quote!({ const out = new Uint8Array(bytes.length); let index = 0, out_index = 0; while (index < bytes.length) { const code = bytes[index++]; if (code === 0 || index + code - 1 > bytes.length) { throw "invalid cobs encoding" } out.set(bytes.subarray(index, index + code - 1), out_index); out_index += code - 1; index += code - 1; if (code !== 0xFF && index < bytes.length) { out[out_index++] = 0 } } return out.subarray(0, out_index) }),
+    )
+    .returns("Uint8Array");
+
+    quote! {
+        $(encode.constant(syntax))
+        $(decode.constant(syntax))
+    }
+}
+
+/// Generates the `CobsAccumulator` class, which relies on `deserialize`.
+pub(in crate::code_gen) fn gen_cobs_accumulator(syntax: Syntax) -> Tokens {
+    RuntimeClass::new("CobsAccumulator")
+        .generics("T extends Type")
+        .field("type", "T")
+        .field("buffer", "Uint8Array")
+        .field("length", "number")
+        .field("overfull", "boolean")
+        .field("frames", "(Uint8Array | undefined)[]")
+        .constructor(
+            [("type", "T"), ("max_size = 1024", "")],
+            quote!({ this.type = type; this.buffer = new Uint8Array(max_size); this.length = 0; this.overfull = false; this.frames = [] }),
+        )
+        .method(
+            RuntimeFunction::new(
+                "feed",
+                [("chunk", "Iterable<number>")],
+                quote!({ for (const byte of chunk) { if (byte !== 0) { if (this.length < this.buffer.length) { this.buffer[this.length++] = byte } else { this.overfull = true } continue } if (this.overfull || this.length > 0) { this.frames.push(this.overfull ? undefined : this.buffer.slice(0, this.length)) } this.length = 0; this.overfull = false } return this.values() }),
+            )
+            .returns("Generator<ValueType<T>>"),
+        )
+        .method(
+            RuntimeFunction::new(
+                "*values",
+                [],
+                quote!({ while (this.frames.length > 0) { const frame = this.frames.shift(); if (frame === undefined) { throw "cobs frame exceeds the maximum size" } yield deserialize(this.type, cobs_decode(frame)).value } }),
+            )
+            .returns("Generator<ValueType<T>>"),
+        )
+        .generate(syntax)
+}
+
+pub(in crate::code_gen) fn serialize_cobs_body() -> Tokens {
+    quote!(return cobs_encode(serialize(type, value));)
+}
+
+pub(in crate::code_gen) fn deserialize_cobs_body() -> Tokens {
+    quote! {
+        const input = bytes instanceof Uint8Array ? bytes : Uint8Array.from(bytes), end = input.indexOf(0);
+        const frame = end < 0 ? input : input.subarray(0, end);
+        return { value: deserialize(type, cobs_decode(frame)).value, bytes: end < 0 ? input.subarray(input.length) : input.subarray(end + 1) };
+    }
+}
@@ -0,0 +1,132 @@
+use genco::quote;
+
+use crate::{
+    code_gen::crc::{hex, Crc32Algorithm, CRC_32_ALGORITHMS},
+    function_args,
+};
+
+use super::{
+    runtime::{RuntimeFunction, Syntax},
+    ExportRegistry, Function, GenerationSettings, Tokens,
+};
+
+/// Generates the CRC checked framing helpers, which rely on `serialize` and `deserialize`.
+pub fn gen_crc_code(
+    gen_settings: &GenerationSettings,
+    export_registry: &mut ExportRegistry,
+) -> Tokens {
+    let algorithms = CRC_32_ALGORITHMS
+        .iter()
+        .map(|algorithm| {
+            export_registry.push(algorithm.name);
+            quote!(const $(algorithm.name) = $(gen_algorithm(algorithm)))
+        })
+        .collect::<Vec<_>>();
+
+    let mut tokens = quote! {
+        $(for algorithm in algorithms join ($['\r']) => $algorithm)
+
+        $(gen_crc_runtime(Syntax::JavaScript))
+    };
+
+    if gen_settings.ser {
+        export_registry.push("serialize_crc32");
+        tokens.line();
+        tokens.append(
+            Function::new_untyped(
+                "serialize_crc32",
+                function_args!["type", "value", "algorithm"],
+                serialize_crc32_body(),
+            )
+            .with_doc_string(
+                "Serialize a value to an array of bytes followed by its CRC in little endian.\n\
+                @param {string} type - The type of the value to serialize.\n\
+                @param {Object} value - The value to serialize.\n\
+                @param {Object} algorithm - The CRC algorithm, e.g. CRC_32_ISCSI.\n\
+                @return {Uint8Array} The serialized value with the CRC.",
+            ),
+        );
+    }
+
+    if gen_settings.des {
+        export_registry.push("deserialize_crc32");
+        tokens.line();
+        tokens.append(
+            Function::new_untyped(
+                "deserialize_crc32",
+                function_args!["type", "bytes", "algorithm"],
+                deserialize_crc32_body(),
+            )
+            .with_doc_string(
+                "Deserialize a value from an array of bytes and check the CRC following it.\n\
+                @param {string} type - The type of the value to deserialize.\n\
+                @param {Uint8Array | Array<number>} bytes - The array of bytes holding the value and the CRC.\n\
+                @param {Object} algorithm - The CRC algorithm, e.g. CRC_32_ISCSI.\n\
+                @return {Object} The deserialized value and the bytes following the CRC.",
+            ),
+        );
+    }
+
+    tokens
+}
+
+/// Generates the parameters of an algorithm, typed as `Crc32Algorithm` in typescript.
+pub(in crate::code_gen) fn gen_algorithm(algorithm: &Crc32Algorithm) -> Tokens {
+    quote!({ poly: $(hex(algorithm.poly)), init: $(hex(algorithm.init)), refin: $(algorithm.refin.to_string()), refout: $(algorithm.refout.to_string()), xorout: $(hex(algorithm.xorout)) })
+}
+
+/// Generates the table driven CRC computation, the tables are created on first use.
+pub(in crate::code_gen) fn gen_crc_runtime(syntax: Syntax) -> Tokens {
+    let tables = match syntax {
+        Syntax::JavaScript => quote!(new Map()),
+        Syntax::TypeScript => quote!(new Map<Crc32Algorithm, Uint32Array>()),
+    };
+    let reflect = RuntimeFunction::new(
+        "crc32_reflect",
+        [("value", "number")],
+        quote!({ let out = 0; for (let i = 0; i < 32; i++) { out = (out << 1) | ((value >>> i) & 1) } return out >>> 0 }),
+    )
+    .returns("number");
+    let table = RuntimeFunction::new(
+        "crc32_table",
+        [("algorithm", "Crc32Algorithm")],
+        quote!({ let table = CRC32_TABLES.get(algorithm); if (table === undefined) { table = new Uint32Array(256); const poly = algorithm.refin ? crc32_reflect(algorithm.poly) : algorithm.poly; for (let i = 0; i < 256; i++) { let crc = algorithm.refin ? i : i << 24; for (let bit = 0; bit < 8; bit++) { if (algorithm.refin) { crc = crc & 1 ? (crc >>> 1) ^ poly : crc >>> 1 } else { crc = crc & 0x80000000 ? (crc << 1) ^ poly : crc << 1 } } table[i] = crc >>> 0 } CRC32_TABLES.set(algorithm, table) } return table }),
+    )
+    .returns("Uint32Array");
+    let crc32 = RuntimeFunction::new(
+        "crc32",
+        [("algorithm", "Crc32Algorithm"), ("bytes", "Uint8Array")],
+        quote!({ const table = crc32_table(algorithm); let crc = algorithm.refin ? crc32_reflect(algorithm.init) : algorithm.init; for (const byte of bytes) { crc = algorithm.refin ? table[(crc ^ byte) & 0xFF] ^ (crc >>> 8) : table[((crc >>> 24) ^ byte) & 0xFF] ^ (crc << 8) } if (algorithm.refin !== algorithm.refout) { crc = crc32_reflect(crc) } return (crc ^ algorithm.xorout) >>> 0 }),
+    )
+    .returns("number");
+
+    quote! {
+        const CRC32_TABLES = $tables
+        $(reflect.constant(syntax))
+        $(table.constant(syntax))
+        $(crc32.constant(syntax))
+    }
+}
+
+pub(in crate::code_gen) fn serialize_crc32_body() -> Tokens {
+    quote! {
+        const bytes = serialize(type, value), out = new Uint8Array(bytes.length + U32_BYTES);
+        out.set(bytes);
+        new DataView(out.buffer).setUint32(bytes.length, crc32(algorithm, bytes), true);
+        return out;
+    }
+}
+
+pub(in crate::code_gen) fn deserialize_crc32_body() -> Tokens {
+    quote! {
+        const input = bytes instanceof Uint8Array ? bytes : Uint8Array.from(bytes);
+        const { value, bytes: rest } = deserialize(type, input), used = input.length - rest.length;
+        if (rest.length < U32_BYTES) {
+            throw "input buffer too small";
+        }
+        if (new DataView(rest.buffer, rest.byteOffset, U32_BYTES).getUint32(0, true) !== crc32(algorithm, input.subarray(0, used))) {
+            throw "crc mismatch";
+        }
+        return { value, bytes: rest.subarray(U32_BYTES) };
+    }
+}
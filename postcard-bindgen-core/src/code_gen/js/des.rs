@@ -6,7 +6,11 @@ use genco::{
 
 use crate::{
     code_gen::{
-        js::{generateable::container::BindingTypeGenerateable, Tokens},
+        js::{
+            generateable::container::BindingTypeGenerateable,
+            runtime::{RuntimeClass, RuntimeFunction, Syntax},
+            Tokens,
+        },
         utils::{ContainerFullQualifiedTypeBuilder, ContainerIdentifierBuilder, TokensIterExt},
     },
     function_args,
@@ -15,24 +19,29 @@ use crate::{
 
 use super::{Case, DefaultCase, ExportRegistry, Function, SwitchCase};
 
-pub fn gen_deserializer_code() -> Tokens {
-    quote! {
-        class Deserializer {
-            constructor(bytes_in) { this.bytes = Array.from(bytes_in); }
-            pop_next = () => { const next = this.bytes.shift(); if (next === undefined) { throw "input buffer too small" } return next }
-            pop_n = (n) => { const bytes = Array(); for (let i = 0; i < n; i++) { bytes.push(this.bytes.shift()) } return bytes }
-            get_int8 = (signed) => signed ? new Int8Array([this.pop_next()])[0] : this.pop_next();
-            try_take = (n_bytes) => { let out = 0n, v_max = varint_max(n_bytes); for (let i = 0; i < v_max; i++) { const val = this.pop_next(), carry = BigInt(val & 0x7F); out |= carry << BigInt(7 * i); if ((val & 0x80) === 0) { if (i === v_max - 1 && val > max_of_last_byte(n_bytes)) { throw "Bad Variant" } else return out } } throw "Bad Variant"; }
-            deserialize_bool = () => { const byte = this.pop_next(); return byte === undefined ? undefined : byte > 0 ? true : false }
-            deserialize_number = (n_bytes, signed) => { if (n_bytes === U8_BYTES) { return this.get_int8(signed) } else if (n_bytes === U16_BYTES || n_bytes === U32_BYTES || n_bytes === U64_BYTES || n_bytes === U128_BYTES) { const val = this.try_take(n_bytes); return to_number_if_safe(signed ? de_zig_zag_signed(val) : val) } else { throw "byte count not supported" } }
-            deserialize_number_float = (n_bytes) => { const b_buffer = new ArrayBuffer(n_bytes), b_view = new DataView(b_buffer); this.pop_n(n_bytes).forEach((b, i) => b_view.setUint8(i, b)); if (n_bytes === U32_BYTES) { return b_view.getFloat32(0, true) } else if (n_bytes === U64_BYTES) { return b_view.getFloat64(0, true) } else { throw "byte count not supported" } }
-            deserialize_string = () => { const str = this.pop_n(Number(this.try_take(U32_BYTES))); return String.fromCharCode(...str) }
-            deserialize_array = (des, len) => Array.from({length: len === undefined ? Number(this.try_take(U32_BYTES)) : len}, (v, i) => des(this))
-            deserialize_string_key_map = (des) => { return [...Array(Number(this.try_take(U32_BYTES)))].reduce((prev) => { prev[this.deserialize_string()] = des(this); return prev }, {}) }
-            deserialize_map = (des) => { return [...Array(Number(this.try_take(U32_BYTES)))].reduce((prev) => { const d = des(this); prev.set(d[0], d[1]); return prev }, new Map()) }
-            release_bytes = () => { return new Uint8Array(this.bytes); }
-        }
-    }
+pub fn gen_deserializer_code(syntax: Syntax) -> Tokens {
+    let class = RuntimeClass::new("Deserializer")
+        .field("bytes", "Uint8Array")
+        .field("view", "DataView")
+        .field("offset", "number")
+        .constructor([("bytes_in", "ArrayLike<number>")], quote!({ this.bytes = bytes_in instanceof Uint8Array ? bytes_in : Uint8Array.from(bytes_in); this.view = new DataView(this.bytes.buffer, this.bytes.byteOffset, this.bytes.byteLength); this.offset = 0 }))
+        .member(RuntimeFunction::new("ensure", [("n", "number")], quote!({ if (this.offset + n > this.bytes.length) { throw "input buffer too small" } })))
+        .member(RuntimeFunction::new("pop_next", [], quote!({ this.ensure(1); return this.bytes[this.offset++] })).returns("number"))
+        .member(RuntimeFunction::new("pop_n", [("n", "number")], quote!({ this.ensure(n); const bytes = this.bytes.subarray(this.offset, this.offset + n); this.offset += n; return bytes })).returns("Uint8Array"))
+        .member(RuntimeFunction::new("get_int8", [("signed", "boolean")], quote!({ this.ensure(1); const value = signed ? this.view.getInt8(this.offset) : this.view.getUint8(this.offset); this.offset += 1; return value })).returns("number"))
+        .member(RuntimeFunction::new("try_take", [("n_bytes", "number")], quote!({ let out = 0n, v_max = varint_max(n_bytes); for (let i = 0; i < v_max; i++) { const val = this.pop_next(), carry = BigInt(val & 0x7F); out |= carry << BigInt(7 * i); if ((val & 0x80) === 0) { if (i === v_max - 1 && val > max_of_last_byte(n_bytes)) { throw "Bad Variant" } else return out } } throw "Bad Variant"; })).returns("bigint"))
+        .member(RuntimeFunction::new("deserialize_bool", [], quote!(this.pop_next() > 0)).returns("boolean"))
+        .member(RuntimeFunction::new("deserialize_number", [("n_bytes", "number"), ("signed", "boolean")], quote!({ if (n_bytes === U8_BYTES) { return this.get_int8(signed) } else if (n_bytes === U16_BYTES || n_bytes === U32_BYTES || n_bytes === U64_BYTES || n_bytes === U128_BYTES) { const val = this.try_take(n_bytes); return to_number_if_safe(signed ? de_zig_zag_signed(val) : val) } else { throw "byte count not supported" } })).returns("any"))
+        .member(RuntimeFunction::new("deserialize_fixint", [("n_bytes", "number"), ("signed", "boolean"), ("little_endian", "boolean")], quote!({ const bytes = this.pop_n(n_bytes); let val = 0n; for (let i = 0; i < n_bytes; i++) { val = (val << 8n) | BigInt(bytes[little_endian ? n_bytes - 1 - i : i]) } return to_number_if_safe(signed ? BigInt.asIntN(n_bytes * 8, val) : val) })).returns("any"))
+        .member(RuntimeFunction::new("deserialize_number_float", [("n_bytes", "number")], quote!({ let value; this.ensure(n_bytes); if (n_bytes === U32_BYTES) { value = this.view.getFloat32(this.offset, true) } else if (n_bytes === U64_BYTES) { value = this.view.getFloat64(this.offset, true) } else { throw "byte count not supported" } this.offset += n_bytes; return value })).returns("number"))
+        .member(RuntimeFunction::new("deserialize_string", [], quote!(UTF8_DECODER.decode(this.pop_n(Number(this.try_take(U32_BYTES)))))).returns("string"))
+        .member(RuntimeFunction::new("deserialize_array", [("des", "(d: Deserializer) => T"), ("len?", "number")], quote!(Array.from({length: len === undefined ? Number(this.try_take(U32_BYTES)) : len}, (v, i) => des(this)))).generics("T").returns("any"))
+        .member(RuntimeFunction::new("deserialize_byte_array", [("len?", "number")], quote!(this.pop_n(len === undefined ? Number(this.try_take(U32_BYTES)) : len).slice())).returns("Uint8Array"))
+        .member(RuntimeFunction::new("deserialize_string_key_map", [("des", "(d: Deserializer) => V")], quote!({ return [...Array(Number(this.try_take(U32_BYTES)))].reduce((prev) => { prev[this.deserialize_string()] = des(this); return prev }, {}) })).generics("V").returns("{ [key: string]: V }"))
+        .member(RuntimeFunction::new("deserialize_map", [("des", "(d: Deserializer) => [K, V]")], quote!({ return [...Array(Number(this.try_take(U32_BYTES)))].reduce((prev) => { const d = des(this); prev.set(d[0], d[1]); return prev }, new Map()) })).generics("K, V").returns("Map<K, V>"))
+        .member(RuntimeFunction::new("release_bytes", [], quote!(this.bytes.subarray(this.offset))).returns("Uint8Array"));
+
+    class.generate(syntax)
 }
 
 pub fn gen_des_functions(bindings: impl Iterator<Item = Container>) -> Tokens {
@@ -75,9 +84,9 @@ pub fn gen_deserialize_func(
     export_registry.push("deserialize");
 
     Function::new_untyped("deserialize", function_args!("type", "bytes"), body).with_doc_string(
-        "Deserialize a value from an array of bytes.
-        @param {string} type - The type of the value to deserialize.
-        @param {Uint8Array} bytes - The byte array to deserialize from.
+        "Deserialize a value from an array of bytes.\n\
+        @param {string} type - The type of the value to deserialize.\n\
+        @param {Uint8Array} bytes - The byte array to deserialize from.\n\
         @return {Object} The deserialized value and remaining bytes.",
     )
 }
@@ -0,0 +1,31 @@
+use genco::{quote, tokens::quoted};
+
+use crate::{
+    code_gen::{crc::hex, utils::ContainerFullQualifiedTypeBuilder},
+    registry::ContainerCollection,
+};
+
+use super::{ExportRegistry, Tokens};
+
+/// Generates the fingerprint constants of the schema and of every container, keyed by the
+/// type names `serialize` and `deserialize` take.
+pub fn gen_fingerprint_code(
+    containers: &ContainerCollection,
+    export_registry: &mut ExportRegistry,
+) -> Tokens {
+    export_registry.push("SCHEMA_FINGERPRINT");
+    export_registry.push("CONTAINER_FINGERPRINTS");
+
+    let fingerprints = containers.all_containers().map(|container| {
+        let fingerprint = hex(container.fingerprint());
+        let full_qualified = ContainerFullQualifiedTypeBuilder::from(&container).build();
+        quote!($(quoted(full_qualified)): $fingerprint,)
+    });
+
+    quote! {
+        const SCHEMA_FINGERPRINT = $(hex(containers.fingerprint()));
+        const CONTAINER_FINGERPRINTS = Object.freeze({
+            $(for fingerprint in fingerprints join ($['\r']) => $fingerprint)
+        });
+    }
+}
@@ -76,7 +76,7 @@ pub mod ser {
     }
 
     fn gen_case_for_variant(index: usize, variant: &EnumVariant) -> Case {
-        let variant_name = quoted(variant.name);
+        let variant_name = quoted(&variant.name);
         let variable_path = VariablePath::default()
             .modify_push(VariableAccess::Field(JS_ENUM_VARIANT_VALUE.into()));
         let body = match &variant.inner_type {
@@ -151,7 +151,7 @@ pub mod des {
     }
 
     fn gen_case_for_variant(index: usize, variant: &EnumVariant) -> Case {
-        let variant_name = quoted(variant.name);
+        let variant_name = quoted(&variant.name);
         let body = match &variant.inner_type {
             EnumVariantType::Empty => CaseBody::None,
             EnumVariantType::NewType(fields) => CaseBody::Body(des::gen_accessors_fields(fields)),
@@ -216,7 +216,7 @@ pub mod ty_check {
             None
         } else {
             let variant_checks = variants
-                .map(|(_, variant)| quote!(v.$JS_ENUM_VARIANT_KEY === $(quoted(variant.name))))
+                .map(|(_, variant)| quote!(v.$JS_ENUM_VARIANT_KEY === $(quoted(&variant.name))))
                 .join_logic_or();
             let type_check = simple_enum_type_check();
             Some(quote!(($type_check && $variant_checks)))
@@ -231,7 +231,7 @@ pub mod ty_check {
         } else {
             let variant_checks = variants.map(|(_, variant)| {
                 let inner_type_checks = gen_variant_check(variant);
-                quote!((v.$JS_ENUM_VARIANT_KEY === $(quoted(variant.name)) && $inner_type_checks))
+                quote!((v.$JS_ENUM_VARIANT_KEY === $(quoted(&variant.name)) && $inner_type_checks))
             }).join_logic_or();
             let type_check = complex_enum_type_check();
             Some(quote!(($type_check && $variant_checks)))
@@ -266,6 +266,7 @@ pub mod ts {
     use crate::{
         code_gen::{
             js::{
+                gen_doc_comment,
                 generateable::{container, types::JsTypeGenerateable},
                 JS_ENUM_VARIANT_KEY, JS_ENUM_VARIANT_VALUE,
             },
@@ -275,8 +276,22 @@ pub mod ts {
     };
 
     pub fn gen_typings(variants: impl AsRef<[EnumVariant]>) -> Tokens {
+        let variants = variants.as_ref();
+        // documented variants are declared on their own lines below their JSDoc comment
+        if variants.iter().any(|v| v.doc.is_some()) {
+            let body = variants.iter().map(|v| {
+                quote! {
+                    $(v.doc.as_deref().map(gen_doc_comment))
+                    | $(gen_variant_typings(v))
+                }
+            });
+            return quote! {
+                $['\r']
+                    $(for variant in body join ($['\r']) => $variant)
+            };
+        }
+
         let body = variants
-            .as_ref()
             .iter()
             .map(gen_variant_typings)
             .join_with_vertical_line();
@@ -284,7 +299,7 @@ pub mod ts {
     }
 
     fn gen_variant_typings(variant: &EnumVariant) -> Tokens {
-        let name = quoted(variant.name);
+        let name = quoted(&variant.name);
         match &variant.inner_type {
             EnumVariantType::Empty => quote!({ $JS_ENUM_VARIANT_KEY: $name }),
             t => {
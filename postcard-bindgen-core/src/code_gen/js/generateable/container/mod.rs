@@ -97,7 +97,7 @@ mod ser {
             .map(|field| {
                 let path = variable_path
                     .to_owned()
-                    .modify_push(VariableAccess::Field(field.name.into()));
+                    .modify_push(VariableAccess::Field(field.name.to_string()));
                 field.v_type.gen_ser_accessor(path)
             })
             .join_with([JoinType::Semicolon, JoinType::LineBreak]);
@@ -126,7 +126,7 @@ mod des {
             .map(|field| {
                 field
                     .v_type
-                    .gen_des_accessor(FieldAccessor::Object(field.name))
+                    .gen_des_accessor(FieldAccessor::Object(&field.name))
             })
             .join_with([JoinType::Comma, JoinType::LineBreak]);
         quote! {
@@ -172,7 +172,7 @@ mod ty_check {
             .map(|field| {
                 let path = variable_path
                     .to_owned()
-                    .modify_push(VariableAccess::Field(field.name.into()));
+                    .modify_push(VariableAccess::Field(field.name.to_string()));
                 field.v_type.gen_ty_check(path)
             })
             .join_logic_and();
@@ -204,7 +204,10 @@ pub mod ts {
     use genco::{prelude::js::Tokens, quote};
 
     use crate::{
-        code_gen::{js::generateable::types::JsTypeGenerateable, utils::TokensIterExt},
+        code_gen::{
+            js::{gen_doc_comment, generateable::types::JsTypeGenerateable},
+            utils::TokensIterExt,
+        },
         registry::StructField,
         type_info::ValueType,
     };
@@ -219,10 +222,25 @@ pub mod ts {
     }
 
     pub fn gen_typings_fields(fields: impl AsRef<[StructField]>) -> Tokens {
+        let fields = fields.as_ref();
+        // documented fields are declared on their own lines below their JSDoc comment
+        if fields.iter().any(|f| f.doc.is_some()) {
+            let body = fields.iter().map(|f| {
+                quote! {
+                    $(f.doc.as_deref().map(gen_doc_comment))
+                    $(&f.name): $(f.v_type.gen_ts_type()),
+                }
+            });
+            return quote! {
+                {
+                    $(for field in body join ($['\r']) => $field)
+                }
+            };
+        }
+
         let body = fields
-            .as_ref()
             .iter()
-            .map(|f| quote!($(f.name): $(f.v_type.gen_ts_type())))
+            .map(|f| quote!($(&f.name): $(f.v_type.gen_ts_type())))
             .join_with_comma();
         quote!({ $body })
     }
@@ -8,7 +8,8 @@ use genco::{quote, quote_in, tokens::quoted};
 
 use crate::{
     code_gen::{
-        js::Tokens,
+        crc::CRC_32_ALGORITHMS,
+        js::{gen_doc_comment, Tokens},
         utils::{ContainerFullQualifiedTypeBuilder, TokensIterExt},
     },
     registry::{Container, ContainerCollection, Module},
@@ -20,6 +21,27 @@ pub fn gen_ts_typings(
     containers: &ContainerCollection,
     gen_settings: impl Borrow<GenerationSettings>,
 ) -> Tokens {
+    let gen_settings = gen_settings.borrow();
+    quote!(
+        $(gen_type_decls(containers))
+
+        $(gen_fingerprint_decls())
+
+        $(gen_ser_des_decls(gen_settings.ser, gen_settings.des))
+        $(if gen_settings.cobs {
+            $['\n']
+            $(gen_cobs_decls(gen_settings.ser, gen_settings.des))
+        })
+        $(if gen_settings.crc32 {
+            $['\n']
+            $(gen_crc_decls(gen_settings.ser, gen_settings.des))
+        })
+    )
+}
+
+/// Generates the typescript type declarations of all containers together with the
+/// helper types (number aliases, `Type` and `ValueType`) they rely on.
+pub fn gen_type_decls(containers: &ContainerCollection) -> Tokens {
     quote!(
         $(gen_number_decls())
 
@@ -29,8 +51,6 @@ pub fn gen_ts_typings(
 
         $(gen_type_decl(containers.all_containers()))
         $(gen_value_type_decl(containers.all_containers()))
-
-        $(gen_ser_des_decls(gen_settings.borrow().ser, gen_settings.borrow().des))
     )
 }
 
@@ -99,6 +119,13 @@ fn gen_value_type_decl(bindings: impl Iterator<Item = Container>) -> Tokens {
     quote!(declare type ValueType<T extends Type> = $if_cases : void)
 }
 
+fn gen_fingerprint_decls() -> Tokens {
+    quote!(
+        export const SCHEMA_FINGERPRINT: number
+        export const CONTAINER_FINGERPRINTS: Readonly<Record<Type, number>>
+    )
+}
+
 fn gen_ser_des_decls(ser: bool, des: bool) -> Tokens {
     quote!(
         $(if ser {
@@ -116,6 +143,48 @@ fn gen_ser_des_decls(ser: bool, des: bool) -> Tokens {
     )
 }
 
+fn gen_cobs_decls(ser: bool, des: bool) -> Tokens {
+    quote!(
+        $(if ser {
+            export function serialize_cobs<T extends Type>(type: T, value: ValueType<T>): Uint8Array
+        })
+
+        $(if des {
+            export function deserialize_cobs<T extends Type>(type: T, bytes: Uint8Array): Result<T>
+
+            export class CobsAccumulator<T extends Type> {
+                constructor(type: T, max_size?: number)
+                feed(chunk: Uint8Array): Generator<ValueType<T>>
+            }
+        })
+    )
+}
+
+fn gen_crc_decls(ser: bool, des: bool) -> Tokens {
+    let algorithms = CRC_32_ALGORITHMS
+        .iter()
+        .map(|algorithm| quote!(export const $(algorithm.name): Crc32Algorithm));
+    quote!(
+        export interface Crc32Algorithm {
+            poly: number;
+            init: number;
+            refin: boolean;
+            refout: boolean;
+            xorout: number;
+        }
+
+        $(for algorithm in algorithms join ($['\r']) => $algorithm)
+
+        $(if ser {
+            export function serialize_crc32<T extends Type>(type: T, value: ValueType<T>, algorithm: Crc32Algorithm): Uint8Array
+        })
+
+        $(if des {
+            export function deserialize_crc32<T extends Type>(type: T, bytes: Uint8Array, algorithm: Crc32Algorithm): Result<T>
+        })
+    )
+}
+
 fn gen_bindings_types(containers: &ContainerCollection) -> Tokens {
     let (containers, mods) = containers.containers_per_module();
 
@@ -163,9 +232,12 @@ fn create_namespace(tokens: &mut Tokens, r#mod: Module<'_>) {
 }
 
 fn gen_binding_type(binding: &Container) -> Tokens {
-    let name = binding.name;
+    let name = &binding.name;
     let body = binding.r#type.gen_ts_typings_body();
-    quote!(export type $name = $body)
+    quote! {
+        $(binding.doc.as_deref().map(gen_doc_comment))
+        export type $name = $body
+    }
 }
 
 #[cfg(test)]
@@ -181,7 +253,9 @@ mod test {
         registry::{
             BindingType, Container, EnumType, EnumVariant, EnumVariantType, StructField, StructType,
         },
-        type_info::{ArrayMeta, NumberMeta, ObjectMeta, OptionalMeta, StringMeta, ValueType},
+        type_info::{
+            ArrayMeta, IntegerEncoding, NumberMeta, ObjectMeta, OptionalMeta, StringMeta, ValueType,
+        },
     };
 
     use super::gen_binding_type;
@@ -219,6 +293,7 @@ mod test {
                 bytes: assertion.0 .0,
                 signed: assertion.0 .1,
                 zero_able: true,
+                encoding: IntegerEncoding::Varint,
             });
             assert_tokens(quote!($(ty.gen_ts_type())), assertion.1);
         }
@@ -229,12 +304,18 @@ mod test {
                     bytes: assertion.0 .0,
                     signed: assertion.0 .1,
                     zero_able: true,
+                    encoding: IntegerEncoding::Varint,
                 })),
                 length: None,
                 max_length: None,
             });
 
-            assert_tokens(quote!($(ty.gen_ts_type())), quote!($(assertion.1)[]));
+            // bytes are deserialized as `Uint8Array`
+            if *assertion.0 == (1, false) {
+                assert_tokens(quote!($(ty.gen_ts_type())), quote!(Uint8Array));
+            } else {
+                assert_tokens(quote!($(ty.gen_ts_type())), quote!($(assertion.1)[]));
+            }
         }
 
         for assertion in assert_combs {
@@ -243,6 +324,7 @@ mod test {
                     bytes: assertion.0 .0,
                     signed: assertion.0 .1,
                     zero_able: true,
+                    encoding: IntegerEncoding::Varint,
                 })),
             });
 
@@ -256,7 +338,7 @@ mod test {
     #[test]
     fn test_js_type_without_number_typings() {
         let ty = ValueType::Object(ObjectMeta {
-            name: "A",
+            name: "A".into(),
             path: Path::new("", "::"),
         });
         assert_tokens(quote!($(ty.gen_ts_type())), quote!(A));
@@ -270,45 +352,53 @@ mod test {
         let tokens = StructType {
             fields: vec![
                 StructField {
-                    name: "a",
+                    name: "a".into(),
                     v_type: ValueType::Number(NumberMeta::Integer {
                         bytes: 1,
                         signed: false,
                         zero_able: true,
+                        encoding: IntegerEncoding::Varint,
                     }),
+                    doc: None,
                 },
                 StructField {
-                    name: "b",
+                    name: "b".into(),
                     v_type: ValueType::Object(ObjectMeta {
-                        name: "B",
+                        name: "B".into(),
                         path: Path::new("", "::"),
                     }),
+                    doc: None,
                 },
                 StructField {
-                    name: "c",
+                    name: "c".into(),
                     v_type: ValueType::String(StringMeta { max_length: None }),
+                    doc: None,
                 },
                 StructField {
-                    name: "d",
+                    name: "d".into(),
                     v_type: ValueType::Array(ArrayMeta {
                         items_type: Box::new(ValueType::Number(NumberMeta::Integer {
                             bytes: 1,
                             signed: false,
                             zero_able: true,
+                            encoding: IntegerEncoding::Varint,
                         })),
                         length: None,
                         max_length: None,
                     }),
+                    doc: None,
                 },
                 StructField {
-                    name: "e",
+                    name: "e".into(),
                     v_type: ValueType::Optional(OptionalMeta {
                         inner: Box::new(ValueType::Number(NumberMeta::Integer {
                             bytes: 1,
                             signed: false,
                             zero_able: true,
+                            encoding: IntegerEncoding::Varint,
                         })),
                     }),
+                    doc: None,
                 },
             ],
         }
@@ -316,25 +406,28 @@ mod test {
 
         assert_tokens(
             tokens,
-            quote!({ a: u8, b: B, c: string, d: u8[], e: u8 | undefined }),
+            quote!({ a: u8, b: B, c: string, d: Uint8Array, e: u8 | undefined }),
         )
     }
 
     #[test]
     fn test_struct_typings() {
         let test_binding = gen_binding_type(&Container {
-            name: "A",
+            name: "A".into(),
             path: Path::new("", "::"),
             r#type: BindingType::Struct(StructType {
                 fields: vec![StructField {
-                    name: "a",
+                    name: "a".into(),
                     v_type: ValueType::Number(NumberMeta::Integer {
                         bytes: 1,
                         signed: false,
                         zero_able: true,
+                        encoding: IntegerEncoding::Varint,
                     }),
+                    doc: None,
                 }],
             }),
+            doc: None,
         });
 
         assert_tokens(test_binding, quote!(export type A = { a: u8 }))
@@ -343,28 +436,32 @@ mod test {
     #[test]
     fn test_enum_typings() {
         let test_binding = gen_binding_type(&Container {
-            name: "A",
+            name: "A".into(),
             path: Path::new("", "::"),
             r#type: BindingType::Enum(EnumType {
                 variants: vec![
                     EnumVariant {
-                        name: "A",
+                        name: "A".into(),
                         index: 0,
                         inner_type: EnumVariantType::Empty,
+                        doc: None,
                     },
                     EnumVariant {
-                        name: "B",
+                        name: "B".into(),
                         index: 1,
                         inner_type: EnumVariantType::Tuple(vec![ValueType::Number(
                             NumberMeta::Integer {
                                 bytes: 1,
                                 signed: false,
                                 zero_able: true,
+                                encoding: IntegerEncoding::Varint,
                             },
                         )]),
+                        doc: None,
                     },
                 ],
             }),
+            doc: None,
         });
 
         assert_tokens(
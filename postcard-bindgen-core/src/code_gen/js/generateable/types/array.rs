@@ -2,7 +2,7 @@ use genco::{prelude::js::Tokens, quote};
 
 use crate::{
     code_gen::js::{FieldAccessor, VariablePath, JS_OBJECT_VARIABLE},
-    type_info::ArrayMeta,
+    type_info::{ArrayMeta, NumberMeta, ValueType},
 };
 
 use super::JsTypeGenerateable;
@@ -10,27 +10,32 @@ use super::JsTypeGenerateable;
 impl JsTypeGenerateable for ArrayMeta {
     fn gen_ser_accessor(&self, variable_path: VariablePath) -> Tokens {
         let inner_type_accessor = self.items_type.gen_ser_accessor(VariablePath::default());
-        let helper_func_name = quote!(lambda_$(variable_path.to_owned().into_string("_")));
-        let helper_func = quote! {
-            const $(&helper_func_name) = (s, $JS_OBJECT_VARIABLE) => {
+        // The item serializer is passed inline, so typed callers of `serialize_array` (e.g.
+        // the typescript output) can infer the parameter types from the array.
+        let item_ser = quote! {
+            (s, $JS_OBJECT_VARIABLE) => {
                 $inner_type_accessor
-            };
+            }
         };
 
         if let Some(len) = self.length {
-            quote! {
-                $helper_func
-                s.serialize_array($helper_func_name, $variable_path, $len)
-            }
+            quote!(s.serialize_array($item_ser, $variable_path, $len))
         } else {
-            quote! {
-                $helper_func
-                s.serialize_array($helper_func_name, $variable_path)
-            }
+            quote!(s.serialize_array($item_ser, $variable_path))
         }
     }
 
     fn gen_des_accessor(&self, field_accessor: FieldAccessor) -> Tokens {
+        // Bytes are copied from the input at once into a `Uint8Array` instead of being read
+        // one by one.
+        if is_byte(&self.items_type) {
+            return if let Some(len) = self.length {
+                quote!($(field_accessor)d.deserialize_byte_array($len))
+            } else {
+                quote!($(field_accessor)d.deserialize_byte_array())
+            };
+        }
+
         let inner_type_accessor = self.items_type.gen_des_accessor(FieldAccessor::Array);
         if let Some(len) = self.length {
             quote!($(field_accessor)d.deserialize_array(() => $inner_type_accessor, $len))
@@ -41,20 +46,40 @@ impl JsTypeGenerateable for ArrayMeta {
 
     fn gen_ty_check(&self, variable_path: VariablePath) -> Tokens {
         let item_ty_check = quote!($(variable_path.clone()).every(($JS_OBJECT_VARIABLE) => $(self.items_type.gen_ty_check(VariablePath::default()))));
+        // byte arrays are deserialized as `Uint8Array`, but plain arrays are accepted too
+        let array_check = if is_byte(&self.items_type) {
+            quote!(($(variable_path.clone()) instanceof Uint8Array || Array.isArray($(variable_path.clone())) && $item_ty_check))
+        } else {
+            quote!(Array.isArray($(variable_path.clone())) && $item_ty_check)
+        };
         if let Some(len) = self.length {
-            quote!(Array.isArray($(variable_path.clone())) && $item_ty_check && $variable_path.length === $len)
+            quote!($array_check && $variable_path.length === $len)
         } else if let Some(len) = self.max_length {
-            quote!(Array.isArray($(variable_path.clone())) && $item_ty_check && $variable_path.length <= $len)
+            quote!($array_check && $variable_path.length <= $len)
         } else {
-            quote!(Array.isArray($variable_path) && $item_ty_check)
+            array_check
         }
     }
 
     fn gen_ts_type(&self) -> Tokens {
-        if let Some(len) = self.length {
+        if is_byte(&self.items_type) {
+            quote!(Uint8Array)
+        } else if let Some(len) = self.length {
             quote!(FixedLengthArray<$(self.items_type.gen_ts_type()), $len>)
         } else {
             quote!($(self.items_type.gen_ts_type())[])
         }
     }
 }
+
+fn is_byte(value_type: &ValueType) -> bool {
+    matches!(
+        value_type,
+        ValueType::Number(NumberMeta::Integer {
+            bytes: 1,
+            signed: false,
+            zero_able: true,
+            ..
+        })
+    )
+}
@@ -2,7 +2,7 @@ use genco::{prelude::js::Tokens, quote};
 
 use crate::{
     code_gen::js::{FieldAccessor, VariablePath},
-    type_info::NumberMeta,
+    type_info::{IntegerEncoding, NumberMeta},
 };
 
 use super::{bool::bool_to_js_bool, JsTypeGenerateable};
@@ -14,10 +14,17 @@ impl JsTypeGenerateable for NumberMeta {
             NumberMeta::FloatingPoint { .. } => {
                 quote!(s.serialize_number_float($byte_amount_str, $variable_path))
             }
-            NumberMeta::Integer { signed, .. } => {
-                let signed = bool_to_js_bool(*signed);
-                quote!(s.serialize_number($byte_amount_str, $signed, $variable_path))
-            }
+            NumberMeta::Integer {
+                signed, encoding, ..
+            } => match fixed_width_little_endian(encoding) {
+                Some(little_endian) => {
+                    quote!(s.serialize_fixint($byte_amount_str, $little_endian, $variable_path))
+                }
+                None => {
+                    let signed = bool_to_js_bool(*signed);
+                    quote!(s.serialize_number($byte_amount_str, $signed, $variable_path))
+                }
+            },
         }
     }
 
@@ -27,9 +34,18 @@ impl JsTypeGenerateable for NumberMeta {
             NumberMeta::FloatingPoint { .. } => {
                 quote!($(field_accessor)d.deserialize_number_float($byte_amount_str))
             }
-            NumberMeta::Integer { signed, .. } => {
+            NumberMeta::Integer {
+                signed, encoding, ..
+            } => {
                 let signed = bool_to_js_bool(*signed);
-                quote!($(field_accessor)d.deserialize_number($byte_amount_str, $signed))
+                match fixed_width_little_endian(encoding) {
+                    Some(little_endian) => {
+                        quote!($(field_accessor)d.deserialize_fixint($byte_amount_str, $signed, $little_endian))
+                    }
+                    None => {
+                        quote!($(field_accessor)d.deserialize_number($byte_amount_str, $signed))
+                    }
+                }
             }
         }
     }
@@ -64,6 +80,7 @@ impl JsTypeGenerateable for NumberMeta {
                 bytes,
                 signed,
                 zero_able,
+                ..
             } => {
                 let prefix = if *signed { "i" } else { "u" };
                 let bits = match bytes {
@@ -83,3 +100,12 @@ impl JsTypeGenerateable for NumberMeta {
         }
     }
 }
+
+/// Byte order of a fixed width integer as a JavaScript boolean, `None` for varints.
+fn fixed_width_little_endian(encoding: &IntegerEncoding) -> Option<&'static str> {
+    match encoding {
+        IntegerEncoding::Varint => None,
+        IntegerEncoding::FixedLe => Some(bool_to_js_bool(true)),
+        IntegerEncoding::FixedBe => Some(bool_to_js_bool(false)),
+    }
+}
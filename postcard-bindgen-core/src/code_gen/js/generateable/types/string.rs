@@ -18,7 +18,7 @@ impl JsTypeGenerateable for StringMeta {
 
     fn gen_ty_check(&self, variable_path: VariablePath) -> Tokens {
         if let Some(len) = self.max_length {
-            quote!(typeof $(variable_path.to_owned()) === "string" && $variable_path.length <= $len)
+            quote!(typeof $(variable_path.to_owned()) === "string" && UTF8_ENCODER.encode($variable_path).length <= $len)
         } else {
             quote!(typeof $variable_path === "string")
         }
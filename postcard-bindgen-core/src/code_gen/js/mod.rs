@@ -1,29 +1,40 @@
+pub(super) mod cobs;
+pub(super) mod crc;
 mod des;
+mod fingerprint;
 mod general;
-mod generateable;
+pub(super) mod generateable;
+mod runtime;
 mod ser;
 mod type_checks;
 
 use core::borrow::Borrow;
 
-use des::{gen_des_functions, gen_deserialize_func, gen_deserializer_code};
+use cobs::gen_cobs_code;
+use crc::gen_crc_code;
+use des::{gen_des_functions, gen_deserialize_func};
+use fingerprint::gen_fingerprint_code;
 use genco::{
     prelude::js::JavaScript,
     quote_in,
     tokens::{quoted, FormatInto},
 };
-use general::gen_util;
 use generateable::gen_ts_typings;
-use ser::{gen_ser_functions, gen_serialize_func, gen_serializer_code};
+use ser::{gen_ser_functions, gen_serialize_func};
 use type_checks::gen_type_checks;
 
-use crate::{registry::ContainerCollection, ExportFile, Exports};
+pub(super) use des::gen_deserializer_code;
+pub(super) use general::gen_util;
+pub(super) use runtime::Syntax;
+pub(super) use ser::gen_serializer_code;
+
+use crate::{registry::ContainerCollection, validation::ValidationSettings, ExportFile, Exports};
 
 use super::{export_registry::ExportMode, utils::TokensIterExt};
 
 const JS_ENUM_VARIANT_KEY: &str = "tag";
 const JS_ENUM_VARIANT_VALUE: &str = "value";
-const JS_OBJECT_VARIABLE: &str = "v";
+pub(super) const JS_OBJECT_VARIABLE: &str = "v";
 const JS_LOGIC_AND: &str = "&&";
 const JS_LOGIC_OR: &str = "||";
 
@@ -57,6 +68,8 @@ pub struct GenerationSettings {
     type_script_types: bool,
     module_structure: bool,
     esm_module: bool,
+    cobs: bool,
+    crc32: bool,
 }
 
 impl GenerationSettings {
@@ -69,6 +82,8 @@ impl GenerationSettings {
             type_script_types: true,
             module_structure: true,
             esm_module: true,
+            cobs: false,
+            crc32: false,
         }
     }
 
@@ -128,6 +143,32 @@ impl GenerationSettings {
         self.esm_module = enabled;
         self
     }
+
+    /// Enabling or disabling of COBS framing code generation.
+    ///
+    /// Enabling this will generate `serialize_cobs` and `deserialize_cobs`, which encode and
+    /// decode frames like `postcard::to_slice_cobs` and `postcard::take_from_bytes_cobs`, and
+    /// a `CobsAccumulator`, whose generator method `feed` takes chunks of a stream and yields
+    /// the decoded values like `postcard::accumulator::CobsAccumulator`.
+    ///
+    /// This is not enabled by [`GenerationSettings::enable_all()`].
+    pub fn cobs(mut self, enabled: bool) -> Self {
+        self.cobs = enabled;
+        self
+    }
+
+    /// Enabling or disabling of CRC checked framing code generation.
+    ///
+    /// Enabling this will generate `serialize_crc32` and `deserialize_crc32`, which append
+    /// and check a CRC like `postcard::to_slice_crc32` and `postcard::take_from_bytes_crc32`.
+    /// The algorithm is passed to both functions, the 32 bit algorithms of the `crc` crate
+    /// catalog are exported with the same names (e.g. `CRC_32_ISCSI`).
+    ///
+    /// This is not enabled by [`GenerationSettings::enable_all()`].
+    pub fn crc32(mut self, enabled: bool) -> Self {
+        self.crc32 = enabled;
+        self
+    }
 }
 
 impl Default for GenerationSettings {
@@ -139,10 +180,18 @@ impl Default for GenerationSettings {
             type_script_types: false,
             module_structure: true,
             esm_module: false,
+            cobs: false,
+            crc32: false,
         }
     }
 }
 
+impl From<&GenerationSettings> for ValidationSettings {
+    fn from(settings: &GenerationSettings) -> Self {
+        ValidationSettings::default().flattened(!settings.module_structure)
+    }
+}
+
 /// Metadata for JS export
 ///
 /// Contains information about the exported JS package needed to
@@ -171,13 +220,23 @@ pub fn generate(
 
     export_files.push(ExportFile {
         content_type: "util".to_owned(),
-        content: gen_util(),
+        content: gen_util(Syntax::JavaScript),
+    });
+
+    let mut export_registry = ExportRegistry::new(export_mode.clone());
+    let mut tokens = gen_fingerprint_code(&containers, &mut export_registry);
+    tokens.line();
+    tokens.append(export_registry);
+
+    export_files.push(ExportFile {
+        content_type: "fingerprint".to_owned(),
+        content: tokens,
     });
 
     if gen_settings.ser {
         export_files.push(ExportFile {
             content_type: "serializer".to_owned(),
-            content: gen_serializer_code(),
+            content: gen_serializer_code(Syntax::JavaScript),
         });
 
         let mut tokens = Tokens::new();
@@ -205,7 +264,7 @@ pub fn generate(
     if gen_settings.des {
         export_files.push(ExportFile {
             content_type: "deserializer".to_owned(),
-            content: gen_deserializer_code(),
+            content: gen_deserializer_code(Syntax::JavaScript),
         });
 
         let mut tokens = Tokens::new();
@@ -213,7 +272,7 @@ pub fn generate(
         tokens.append(gen_des_functions(containers.all_containers()));
         tokens.line();
 
-        let mut export_registry = ExportRegistry::new(export_mode);
+        let mut export_registry = ExportRegistry::new(export_mode.clone());
 
         tokens.append(gen_deserialize_func(
             containers.all_containers(),
@@ -229,6 +288,30 @@ pub fn generate(
         });
     }
 
+    if gen_settings.cobs && (gen_settings.ser || gen_settings.des) {
+        let mut export_registry = ExportRegistry::new(export_mode.clone());
+        let mut tokens = gen_cobs_code(gen_settings, &mut export_registry);
+        tokens.line();
+        tokens.append(export_registry);
+
+        export_files.push(ExportFile {
+            content_type: "cobs".to_owned(),
+            content: tokens,
+        });
+    }
+
+    if gen_settings.crc32 && (gen_settings.ser || gen_settings.des) {
+        let mut export_registry = ExportRegistry::new(export_mode.clone());
+        let mut tokens = gen_crc_code(gen_settings, &mut export_registry);
+        tokens.line();
+        tokens.append(export_registry);
+
+        export_files.push(ExportFile {
+            content_type: "crc".to_owned(),
+            content: tokens,
+        });
+    }
+
     if gen_settings.runtime_type_checks {
         export_files.push(ExportFile {
             content_type: "runtime_checks".to_owned(),
@@ -326,19 +409,7 @@ impl FormatInto<JavaScript> for FunctionArg {
 
 impl FormatInto<JavaScript> for Function {
     fn format_into(self, tokens: &mut Tokens) {
-        let doc_string = self.doc_string.map(|doc| {
-            let mut tokens = Tokens::new();
-            tokens.append("/**\n");
-            tokens.append(
-                doc.lines()
-                    .map(|line| format!(" * {}", line.trim()))
-                    .collect::<Vec<_>>()
-                    .join("\n"),
-            );
-            tokens.push();
-            tokens.append(" */");
-            tokens
-        });
+        let doc_string = self.doc_string.map(gen_doc_comment);
         quote_in! { *tokens =>
             $(doc_string)
             function $(self.name)($(for arg in self.args join (, ) => $arg)) {
@@ -348,12 +419,32 @@ impl FormatInto<JavaScript> for Function {
     }
 }
 
+/// Generates a JSDoc comment, lines are pushed one by one so the comment follows the
+/// indentation of the surrounding tokens. The indentation within the text, e.g. of code
+/// examples, is kept.
+pub(super) fn gen_doc_comment(doc: impl AsRef<str>) -> Tokens {
+    let mut tokens = Tokens::new();
+    tokens.append("/**");
+    for line in doc.as_ref().lines().map(str::trim_end) {
+        tokens.push();
+        if line.is_empty() {
+            tokens.append(" *");
+        } else {
+            // a `*/` in the text would end the comment
+            tokens.append(format!(" * {}", line.replace("*/", "*\\/")));
+        }
+    }
+    tokens.push();
+    tokens.append(" */");
+    tokens
+}
+
 impl FormatInto<JavaScript> for ExportRegistry {
     fn format_into(self, tokens: &mut Tokens) {
         match self.export_mode {
             ExportMode::Cjs => {
                 quote_in! { *tokens =>
-                    $(for export in self.exports join () => exports.$(&export) = $export)
+                    $(for export in self.exports join ($['\r']) => exports.$(&export) = $export)
                 }
             }
             ExportMode::Esm => {
@@ -0,0 +1,178 @@
+use genco::{prelude::js::JavaScript, quote, quote_in, tokens::FormatInto};
+
+use super::Tokens;
+
+/// The language the runtime is generated in.
+///
+/// The javascript and the typescript output share the runtime. Typescript additionally
+/// declares the types of the class fields, the arguments and the return values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(in crate::code_gen) enum Syntax {
+    JavaScript,
+    TypeScript,
+}
+
+/// An arrow function of the runtime, generated as a `const` or as a class member.
+///
+/// An argument name ending with `?` is optional in typescript. An argument without a type
+/// is left to the inference of typescript, e.g. for an argument with a default value.
+pub(in crate::code_gen) struct RuntimeFunction {
+    name: &'static str,
+    generics: Option<&'static str>,
+    args: Vec<(&'static str, &'static str)>,
+    return_type: Option<&'static str>,
+    body: Tokens,
+}
+
+impl RuntimeFunction {
+    pub fn new<const N: usize>(
+        name: &'static str,
+        args: [(&'static str, &'static str); N],
+        body: Tokens,
+    ) -> Self {
+        Self {
+            name,
+            generics: None,
+            args: args.to_vec(),
+            return_type: None,
+            body,
+        }
+    }
+
+    pub fn generics(mut self, generics: &'static str) -> Self {
+        self.generics = Some(generics);
+        self
+    }
+
+    pub fn returns(mut self, return_type: &'static str) -> Self {
+        self.return_type = Some(return_type);
+        self
+    }
+
+    /// Generates the function as a `const` declaration.
+    pub fn constant(self, syntax: Syntax) -> Tokens {
+        quote!(const $(self.arrow(syntax)))
+    }
+
+    fn arrow(self, syntax: Syntax) -> Tokens {
+        let signature = signature(&self.args, syntax);
+        match syntax {
+            Syntax::JavaScript => quote!($(self.name) = $signature => $(self.body)),
+            Syntax::TypeScript => {
+                let generics = self.generics.map(|generics| format!("<{generics}>"));
+                let return_type = self.return_type.map(|ty| format!(": {ty}"));
+                quote!($(self.name) = $generics$signature$return_type => $(self.body))
+            }
+        }
+    }
+
+    /// Generates the function as a method, the body has to be a block.
+    fn method(self, syntax: Syntax) -> Tokens {
+        let signature = signature(&self.args, syntax);
+        match syntax {
+            Syntax::JavaScript => quote!($(self.name)$signature $(self.body)),
+            Syntax::TypeScript => {
+                let generics = self.generics.map(|generics| format!("<{generics}>"));
+                let return_type = self.return_type.map(|ty| format!(": {ty}"));
+                quote!($(self.name)$generics$signature$return_type $(self.body))
+            }
+        }
+    }
+}
+
+enum Member {
+    Arrow(RuntimeFunction),
+    Method(RuntimeFunction),
+}
+
+/// A class of the runtime with a constructor, arrow function members and methods.
+pub(in crate::code_gen) struct RuntimeClass {
+    name: &'static str,
+    generics: Option<&'static str>,
+    fields: Vec<(&'static str, &'static str)>,
+    constructor: Option<(Vec<(&'static str, &'static str)>, Tokens)>,
+    members: Vec<Member>,
+}
+
+impl RuntimeClass {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            generics: None,
+            fields: Vec::new(),
+            constructor: None,
+            members: Vec::new(),
+        }
+    }
+
+    pub fn generics(mut self, generics: &'static str) -> Self {
+        self.generics = Some(generics);
+        self
+    }
+
+    /// Adds a field, which is only declared in typescript.
+    pub fn field(mut self, name: &'static str, r#type: &'static str) -> Self {
+        self.fields.push((name, r#type));
+        self
+    }
+
+    pub fn constructor<const N: usize>(
+        mut self,
+        args: [(&'static str, &'static str); N],
+        body: Tokens,
+    ) -> Self {
+        self.constructor = Some((args.to_vec(), body));
+        self
+    }
+
+    pub fn member(mut self, member: RuntimeFunction) -> Self {
+        self.members.push(Member::Arrow(member));
+        self
+    }
+
+    /// Adds a method, a generator method is named with a leading `*`.
+    pub fn method(mut self, method: RuntimeFunction) -> Self {
+        self.members.push(Member::Method(method));
+        self
+    }
+
+    pub fn generate(self, syntax: Syntax) -> Tokens {
+        let (generics, fields) = match syntax {
+            Syntax::JavaScript => (None, Vec::new()),
+            Syntax::TypeScript => (
+                self.generics.map(|generics| format!("<{generics}>")),
+                self.fields,
+            ),
+        };
+        let constructor = self
+            .constructor
+            .map(|(args, body)| quote!(constructor$(signature(&args, syntax)) $body));
+        let members = self.members.into_iter().map(|member| match member {
+            Member::Arrow(function) => function.arrow(syntax),
+            Member::Method(function) => function.method(syntax),
+        });
+
+        let mut tokens = Tokens::new();
+        quote_in! { tokens =>
+            class $(self.name)$generics {
+                $(for (name, r#type) in fields join ($['\r']) => $name: $r#type)
+                $constructor
+                $(for member in members join ($['\r']) => $member)
+            }
+        }
+        tokens
+    }
+}
+
+fn signature(args: &[(&'static str, &'static str)], syntax: Syntax) -> impl FormatInto<JavaScript> {
+    let args = args
+        .iter()
+        .map(|(name, ty)| match syntax {
+            Syntax::JavaScript => name.trim_end_matches('?').to_owned(),
+            Syntax::TypeScript if ty.is_empty() => name.to_string(),
+            Syntax::TypeScript => format!("{name}: {ty}"),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    quote!(($args))
+}
@@ -7,7 +7,9 @@ use genco::{
 use crate::{
     code_gen::{
         js::{
-            generateable::container::BindingTypeGenerateable, Function, Tokens, JS_OBJECT_VARIABLE,
+            generateable::container::BindingTypeGenerateable,
+            runtime::{RuntimeClass, RuntimeFunction, Syntax},
+            Function, Tokens, JS_OBJECT_VARIABLE,
         },
         utils::{ContainerFullQualifiedTypeBuilder, ContainerIdentifierBuilder, TokensIterExt},
     },
@@ -17,22 +19,27 @@ use crate::{
 
 use super::{Case, DefaultCase, ExportRegistry, SwitchCase};
 
-pub fn gen_serializer_code() -> Tokens {
-    quote! {
-        class Serializer {
-            constructor() { this.bytes = [] }
-            finish = () => new Uint8Array(this.bytes)
-            push_n = (bytes) => bytes.forEach((byte) => this.bytes.push(byte))
-            serialize_bool = (value) => this.serialize_number(U8_BYTES, false, value ? 1 : 0)
-            serialize_number = (n_bytes, signed, value) => { if (n_bytes === U8_BYTES) { this.bytes.push(new Uint8Array([value])[0]) } else if (n_bytes === U16_BYTES || n_bytes === U32_BYTES || n_bytes === U64_BYTES || n_bytes === U128_BYTES) { const value_b = BigInt(value), buffer = signed ? varint(n_bytes, zig_zag(n_bytes, value_b)) : varint(n_bytes, value_b); this.push_n(buffer) } else { throw "byte count not supported" } }
-            serialize_number_float = (n_bytes, value) => { const b_buffer = new ArrayBuffer(n_bytes), b_view = new DataView(b_buffer); if (n_bytes === U32_BYTES) { b_view.setFloat32(0, value, true) } else if (n_bytes === U64_BYTES) { b_view.setFloat64(0, value, true) } else { throw "byte count not supported" } this.push_n(new Uint8Array(b_buffer)) }
-            serialize_string = (str) => { this.push_n(varint(U32_BYTES, str.length)); const bytes = []; for (const c of str) { bytes.push(c.charCodeAt(0)) } this.push_n(bytes) }
-            serialize_array = (ser, array, len) => { if (len == undefined) this.push_n(varint(U32_BYTES, array.length)); array.slice(0, len != undefined ? len : array.length).forEach((v) => ser(this, v)) }
-            serialize_string_key_map = (ser, obj) => { const entries = Object.entries(obj); this.push_n(varint(U32_BYTES, entries.length)); entries.forEach(([i, v]) => { this.serialize_string(i); ser(this, v) }) }
-            serialize_map = (ser, map) => { this.push_n(varint(U32_BYTES, map.size)); map.forEach((v, k) => ser(this, k, v)) }
-        }
+pub fn gen_serializer_code(syntax: Syntax) -> Tokens {
+    let class = RuntimeClass::new("Serializer")
+        .field("bytes", "Uint8Array")
+        .field("view", "DataView")
+        .field("offset", "number")
+        .constructor([], quote!({ this.bytes = new Uint8Array(64); this.view = new DataView(this.bytes.buffer); this.offset = 0 }))
+        .member(RuntimeFunction::new("finish", [], quote!(this.bytes.slice(0, this.offset))).returns("Uint8Array"))
+        .member(RuntimeFunction::new("reserve", [("n", "number")], quote!({ if (this.offset + n > this.bytes.length) { let capacity = this.bytes.length * 2; while (capacity < this.offset + n) { capacity *= 2 } const bytes = new Uint8Array(capacity); bytes.set(this.bytes.subarray(0, this.offset)); this.bytes = bytes; this.view = new DataView(bytes.buffer) } })))
+        .member(RuntimeFunction::new("push_byte", [("byte", "number")], quote!({ this.reserve(1); this.bytes[this.offset++] = byte })))
+        .member(RuntimeFunction::new("push_n", [("bytes", "ArrayLike<number>")], quote!({ this.reserve(bytes.length); this.bytes.set(bytes, this.offset); this.offset += bytes.length })))
+        .member(RuntimeFunction::new("push_varint", [("value", "number")], quote!({ this.reserve(varint_max(U32_BYTES)); while (value > 0x7F) { this.bytes[this.offset++] = (value & 0x7F) | 0x80; value >>>= 7 } this.bytes[this.offset++] = value })))
+        .member(RuntimeFunction::new("serialize_bool", [("value", "boolean")], quote!(this.serialize_number(U8_BYTES, false, value ? 1 : 0))))
+        .member(RuntimeFunction::new("serialize_number", [("n_bytes", "number"), ("signed", "boolean"), ("value", "number | bigint")], quote!({ if (n_bytes === U8_BYTES) { this.push_byte(Number(value) & 0xFF) } else if ((n_bytes === U16_BYTES || n_bytes === U32_BYTES) && typeof value === "number") { this.push_varint(signed ? ((value << 1) ^ (value >> 31)) >>> 0 : value) } else if (n_bytes === U16_BYTES || n_bytes === U32_BYTES || n_bytes === U64_BYTES || n_bytes === U128_BYTES) { const value_b = BigInt(value), buffer = signed ? varint(n_bytes, zig_zag(n_bytes, value_b)) : varint(n_bytes, value_b); this.push_n(buffer) } else { throw "byte count not supported" } })))
+        .member(RuntimeFunction::new("serialize_fixint", [("n_bytes", "number"), ("little_endian", "boolean"), ("value", "number | bigint")], quote!({ let value_b = BigInt.asUintN(n_bytes * 8, BigInt(value)); this.reserve(n_bytes); for (let i = 0; i < n_bytes; i++) { this.bytes[this.offset + (little_endian ? i : n_bytes - 1 - i)] = Number(value_b & 0xFFn); value_b >>= 8n } this.offset += n_bytes })))
+        .member(RuntimeFunction::new("serialize_number_float", [("n_bytes", "number"), ("value", "number")], quote!({ this.reserve(n_bytes); if (n_bytes === U32_BYTES) { this.view.setFloat32(this.offset, value, true) } else if (n_bytes === U64_BYTES) { this.view.setFloat64(this.offset, value, true) } else { throw "byte count not supported" } this.offset += n_bytes })))
+        .member(RuntimeFunction::new("serialize_string", [("str", "string")], quote!({ const bytes = UTF8_ENCODER.encode(str); this.push_varint(bytes.length); this.push_n(bytes) })))
+        .member(RuntimeFunction::new("serialize_array", [("ser", "(s: Serializer, v: T) => void"), ("array", "ArrayLike<T>"), ("len?", "number")], quote!({ if (len === undefined) this.push_varint(array.length); const n = len === undefined ? array.length : Math.min(len, array.length); for (let i = 0; i < n; i++) { ser(this, array[i]) } })).generics("T"))
+        .member(RuntimeFunction::new("serialize_string_key_map", [("ser", "(s: Serializer, v: V) => void"), ("obj", "{ [key: string]: V }")], quote!({ const entries = Object.entries(obj); this.push_varint(entries.length); entries.forEach(([i, v]) => { this.serialize_string(i); ser(this, v) }) })).generics("V"))
+        .member(RuntimeFunction::new("serialize_map", [("ser", "(s: Serializer, k: K, v: V) => void"), ("map", "Map<K, V>")], quote!({ this.push_varint(map.size); map.forEach((v, k) => ser(this, k, v)) })).generics("K, V"));
 
-    }
+    class.generate(syntax)
 }
 
 pub fn gen_ser_functions(bindings: impl Iterator<Item = Container>) -> Tokens {
@@ -78,9 +85,9 @@ pub fn gen_serialize_func(
         },
     )
     .with_doc_string(
-        "Serialize a value to an array of bytes.
-        @param {string} type - The type of the value to serialize.
-        @param {Object} value - The value to serialize.
+        "Serialize a value to an array of bytes.\n\
+        @param {string} type - The type of the value to serialize.\n\
+        @param {Object} value - The value to serialize.\n\
         @return {Uint8Array} The serialized value as an array of bytes.",
     )
 }
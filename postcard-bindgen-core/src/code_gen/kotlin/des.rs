@@ -0,0 +1,68 @@
+use genco::quote;
+
+use crate::type_info::ValueType;
+
+use super::{
+    full_qualified_object_name,
+    ser::{fixint, int_type_name, number_suffix, zero_literal},
+    types::tuple_class,
+    Tokens,
+};
+
+/// Expression deserializing a value with the deserializer `d`.
+pub(super) fn gen_des_value(package: &str, value_type: &ValueType) -> Tokens {
+    match value_type {
+        ValueType::Bool(_) => quote!(d.readBool()),
+        ValueType::Number(meta) => {
+            let read = match fixint(meta) {
+                Some((16, signed, little_endian)) => quote! {
+                    d.readFixint128($(signed.to_string()), $(little_endian.to_string()))
+                },
+                Some((bytes, signed, little_endian)) => {
+                    let convert = format!("to{}", int_type_name(bytes, signed));
+                    quote!(d.readFixint($bytes, $(little_endian.to_string())).$convert())
+                }
+                None => quote!(d.$(format!("read{}", number_suffix(meta)))()),
+            };
+            match zero_literal(meta) {
+                Some(zero) => quote!(checkNonZero($read, $zero)),
+                None => read,
+            }
+        }
+        ValueType::String(meta) => match meta.max_length {
+            Some(max_length) => quote!(d.readString(maxLength = $max_length)),
+            None => quote!(d.readString()),
+        },
+        ValueType::Array(meta) => {
+            let inner = gen_des_value(package, &meta.items_type);
+            match (meta.length, meta.max_length) {
+                (Some(length), _) => quote!(d.readArray($length) { $inner }),
+                (None, Some(max_length)) => quote!(d.readList(maxLength = $max_length) { $inner }),
+                (None, None) => quote!(d.readList { $inner }),
+            }
+        }
+        ValueType::Map(meta) => {
+            let key = gen_des_value(package, &meta.key_type);
+            let value = gen_des_value(package, &meta.value_type);
+            match meta.max_length {
+                Some(max_length) => quote! {
+                    d.readMap(maxLength = $max_length, readKey = { $key }, readValue = { $value })
+                },
+                None => quote!(d.readMap(readKey = { $key }, readValue = { $value })),
+            }
+        }
+        ValueType::Optional(meta) => {
+            quote!(d.readOption { $(gen_des_value(package, &meta.inner)) })
+        }
+        ValueType::Tuple(meta) => {
+            let items = meta.items_types.iter().map(|ty| gen_des_value(package, ty));
+            quote!($(tuple_class(package, meta.items_types.len()))($(for item in items join (, ) => $item)))
+        }
+        ValueType::Range(meta) => {
+            quote!(d.readRange { $(gen_des_value(package, &meta.bounds_type)) })
+        }
+        ValueType::Object(meta) => {
+            quote!($(full_qualified_object_name(package, meta)).deserialize(d))
+        }
+    }
+}
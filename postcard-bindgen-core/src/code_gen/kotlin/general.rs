@@ -0,0 +1,384 @@
+use genco::{quote, quote_in};
+
+use super::{Tokens, RUNTIME_PACKAGE};
+
+/// Arities of rust tuples not covered by `Pair` and `Triple`.
+pub(super) const TUPLE_CLASS_ARITIES: &[usize] = &[1, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+
+pub fn gen_runtime(package: &str) -> Tokens {
+    quote! {
+        package $package.$RUNTIME_PACKAGE
+
+        import java.math.BigInteger
+
+        class PostcardException(message: String) : Exception(message)
+
+        data class PostcardRange<T>(val start: T, val end: T)
+
+        $(gen_tuple_classes())
+
+        fun <T> checkNonZero(value: T, zero: T): T {
+            if (value == zero) {
+                throw PostcardException("non-zero value is zero")
+            }
+            return value
+        }
+
+        private val U128_MAX: BigInteger = BigInteger.ONE.shiftLeft(128).subtract(BigInteger.ONE)
+
+        $(gen_serializer())
+
+        $(gen_deserializer())
+    }
+}
+
+fn gen_tuple_classes() -> Tokens {
+    let mut tokens = Tokens::new();
+    for arity in TUPLE_CLASS_ARITIES {
+        let type_params = (1..=*arity)
+            .map(|i| format!("T{i}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let fields = (1..=*arity)
+            .map(|i| format!("val item{i}: T{i}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        quote_in!(tokens => data class $(format!("Tuple{arity}"))<$type_params>($fields));
+        tokens.push();
+    }
+    tokens
+}
+
+fn gen_serializer() -> Tokens {
+    quote! {
+        class PostcardSerializer {
+            private var bytes = ByteArray(64)
+            private var length = 0
+
+            private fun reserve(additional: Int) {
+                if (length + additional > bytes.size) {
+                    bytes = bytes.copyOf(maxOf(bytes.size * 2, length + additional))
+                }
+            }
+
+            fun writeByte(value: Int) {
+                reserve(1)
+                bytes[length++] = value.toByte()
+            }
+
+            fun writeBytes(value: ByteArray) {
+                reserve(value.size)
+                value.copyInto(bytes, length)
+                length += value.size
+            }
+
+            fun writeVarint(value: ULong) {
+                var remaining = value
+                while (remaining >= 0x80uL) {
+                    writeByte(((remaining and 0x7fuL) or 0x80uL).toInt())
+                    remaining = remaining shr 7
+                }
+                writeByte(remaining.toInt())
+            }
+
+            private fun writeVarint(value: BigInteger) {
+                var remaining = value
+                while (remaining.bitLength() > 7) {
+                    writeByte((remaining.toInt() and 0x7f) or 0x80)
+                    remaining = remaining.shiftRight(7)
+                }
+                writeByte(remaining.toInt())
+            }
+
+            private fun writeLength(length: Int, maxLength: Int?) {
+                if (maxLength != null && length > maxLength) {
+                    throw PostcardException("length exceeds maximum length")
+                }
+                writeVarint(length.toULong())
+            }
+
+            fun writeVariantIndex(index: Int) = writeVarint(index.toULong())
+
+            fun writeBool(value: Boolean) = writeByte(if (value) 1 else 0)
+
+            fun writeU8(value: UByte) = writeByte(value.toInt())
+
+            fun writeU16(value: UShort) = writeVarint(value.toULong())
+
+            fun writeU32(value: UInt) = writeVarint(value.toULong())
+
+            fun writeU64(value: ULong) = writeVarint(value)
+
+            fun writeU128(value: BigInteger) {
+                if (value.signum() < 0 || value > U128_MAX) {
+                    throw PostcardException("value out of range for u128")
+                }
+                writeVarint(value)
+            }
+
+            fun writeI8(value: Byte) = writeByte(value.toInt())
+
+            fun writeI16(value: Short) = writeI64(value.toLong())
+
+            fun writeI32(value: Int) = writeI64(value.toLong())
+
+            fun writeI64(value: Long) = writeVarint(((value shl 1) xor (value shr 63)).toULong())
+
+            fun writeI128(value: BigInteger) {
+                if (value.bitLength() > 127) {
+                    throw PostcardException("value out of range for i128")
+                }
+                if (value.signum() < 0) {
+                    writeVarint(value.negate().shiftLeft(1).subtract(BigInteger.ONE))
+                } else {
+                    writeVarint(value.shiftLeft(1))
+                }
+            }
+
+            fun writeFixint(value: Long, bytes: Int, littleEndian: Boolean) {
+                for (i in 0 until bytes) {
+                    val shift = if (littleEndian) i else bytes - 1 - i
+                    writeByte((value ushr (8 * shift)).toInt() and 0xff)
+                }
+            }
+
+            fun writeFixint128(value: BigInteger, signed: Boolean, littleEndian: Boolean) {
+                if (signed && value.bitLength() > 127) {
+                    throw PostcardException("value out of range for i128")
+                }
+                if (!signed && (value.signum() < 0 || value > U128_MAX)) {
+                    throw PostcardException("value out of range for u128")
+                }
+                val bits = value.and(U128_MAX)
+                for (i in 0 until 16) {
+                    val shift = if (littleEndian) i else 15 - i
+                    writeByte(bits.shiftRight(8 * shift).toInt() and 0xff)
+                }
+            }
+
+            fun writeF32(value: Float) {
+                val bits = value.toRawBits()
+                for (i in 0 until 4) {
+                    writeByte(bits ushr (8 * i))
+                }
+            }
+
+            fun writeF64(value: Double) {
+                val bits = value.toRawBits()
+                for (i in 0 until 8) {
+                    writeByte((bits ushr (8 * i)).toInt())
+                }
+            }
+
+            fun writeString(value: String, maxLength: Int? = null) {
+                val encoded = value.encodeToByteArray()
+                writeLength(encoded.size, maxLength)
+                writeBytes(encoded)
+            }
+
+            fun <T> writeList(value: List<T>, maxLength: Int? = null, write: (T) -> Unit) {
+                writeLength(value.size, maxLength)
+                value.forEach(write)
+            }
+
+            fun <T> writeArray(value: List<T>, length: Int, write: (T) -> Unit) {
+                if (value.size != length) {
+                    throw PostcardException("array length mismatch")
+                }
+                value.forEach(write)
+            }
+
+            fun <T> writeOption(value: T?, write: (T) -> Unit) {
+                if (value == null) {
+                    writeByte(0)
+                } else {
+                    writeByte(1)
+                    write(value)
+                }
+            }
+
+            fun <K, V> writeMap(value: Map<K, V>, maxLength: Int? = null, write: (K, V) -> Unit) {
+                writeLength(value.size, maxLength)
+                for ((key, item) in value) {
+                    write(key, item)
+                }
+            }
+
+            fun <T> writeRange(value: PostcardRange<T>, write: (T) -> Unit) {
+                write(value.start)
+                write(value.end)
+            }
+
+            fun finish(): ByteArray = bytes.copyOf(length)
+        }
+    }
+}
+
+fn gen_deserializer() -> Tokens {
+    quote! {
+        class PostcardDeserializer(private val bytes: ByteArray) {
+            private var position = 0
+
+            fun readByte(): Int {
+                if (position >= bytes.size) {
+                    throw PostcardException("unexpected end of input")
+                }
+                return bytes[position++].toInt() and 0xff
+            }
+
+            fun readBytes(length: Int): ByteArray {
+                if (length > bytes.size - position) {
+                    throw PostcardException("unexpected end of input")
+                }
+                val result = bytes.copyOfRange(position, position + length)
+                position += length
+                return result
+            }
+
+            private fun readVarint(bits: Int): ULong {
+                val maxBytes = (bits + 6) / 7
+                var value = 0uL
+                for (i in 0 until maxBytes) {
+                    val byte = readByte()
+                    if (i == maxBytes - 1 && byte > (1 shl (bits % 7)) - 1) {
+                        throw PostcardException("bad varint")
+                    }
+                    value = value or ((byte and 0x7f).toULong() shl (7 * i))
+                    if ((byte and 0x80) == 0) {
+                        return value
+                    }
+                }
+                throw PostcardException("bad varint")
+            }
+
+            private fun readVarintBig(): BigInteger {
+                var value = BigInteger.ZERO
+                for (i in 0 until 19) {
+                    val byte = readByte()
+                    if (i == 18 && byte > 3) {
+                        throw PostcardException("bad varint")
+                    }
+                    value = value.or(BigInteger.valueOf((byte and 0x7f).toLong()).shiftLeft(7 * i))
+                    if ((byte and 0x80) == 0) {
+                        return value
+                    }
+                }
+                throw PostcardException("bad varint")
+            }
+
+            private fun readZigZag(bits: Int): Long {
+                val value = readVarint(bits)
+                return (value shr 1).toLong() xor -(value and 1uL).toLong()
+            }
+
+            private fun readLength(maxLength: Int?): Int {
+                val length = readVarint(64)
+                if (length > Int.MAX_VALUE.toULong() || (maxLength != null && length > maxLength.toULong())) {
+                    throw PostcardException("length exceeds maximum length")
+                }
+                return length.toInt()
+            }
+
+            fun readVariantIndex(): Int = readVarint(32).toInt()
+
+            fun readBool(): Boolean = when (readByte()) {
+                0 -> false
+                1 -> true
+                else -> throw PostcardException("bad bool")
+            }
+
+            fun readU8(): UByte = readByte().toUByte()
+
+            fun readU16(): UShort = readVarint(16).toUShort()
+
+            fun readU32(): UInt = readVarint(32).toUInt()
+
+            fun readU64(): ULong = readVarint(64)
+
+            fun readU128(): BigInteger = readVarintBig()
+
+            fun readI8(): Byte = readByte().toByte()
+
+            fun readI16(): Short = readZigZag(16).toShort()
+
+            fun readI32(): Int = readZigZag(32).toInt()
+
+            fun readI64(): Long = readZigZag(64)
+
+            fun readI128(): BigInteger {
+                val value = readVarintBig()
+                val half = value.shiftRight(1)
+                return if (value.testBit(0)) half.add(BigInteger.ONE).negate() else half
+            }
+
+            fun readFixint(bytes: Int, littleEndian: Boolean): Long {
+                var value = 0L
+                for (i in 0 until bytes) {
+                    val shift = if (littleEndian) i else bytes - 1 - i
+                    value = value or (readByte().toLong() shl (8 * shift))
+                }
+                return value
+            }
+
+            fun readFixint128(signed: Boolean, littleEndian: Boolean): BigInteger {
+                var value = BigInteger.ZERO
+                for (i in 0 until 16) {
+                    val shift = if (littleEndian) i else 15 - i
+                    value = value.or(BigInteger.valueOf(readByte().toLong()).shiftLeft(8 * shift))
+                }
+                return if (signed && value.testBit(127)) value.subtract(U128_MAX).subtract(BigInteger.ONE) else value
+            }
+
+            fun readF32(): Float {
+                var bits = 0
+                for (i in 0 until 4) {
+                    bits = bits or (readByte() shl (8 * i))
+                }
+                return Float.fromBits(bits)
+            }
+
+            fun readF64(): Double {
+                var bits = 0L
+                for (i in 0 until 8) {
+                    bits = bits or (readByte().toLong() shl (8 * i))
+                }
+                return Double.fromBits(bits)
+            }
+
+            fun readString(maxLength: Int? = null): String =
+                readBytes(readLength(maxLength)).decodeToString(throwOnInvalidSequence = true)
+
+            fun <T> readList(maxLength: Int? = null, read: () -> T): List<T> {
+                val length = readLength(maxLength)
+                val result = ArrayList<T>(minOf(length, bytes.size - position))
+                repeat(length) { result.add(read()) }
+                return result
+            }
+
+            fun <T> readArray(length: Int, read: () -> T): List<T> = List(length) { read() }
+
+            fun <T> readOption(read: () -> T): T? = when (readByte()) {
+                0 -> null
+                1 -> read()
+                else -> throw PostcardException("bad option tag")
+            }
+
+            fun <K, V> readMap(maxLength: Int? = null, readKey: () -> K, readValue: () -> V): Map<K, V> {
+                val length = readLength(maxLength)
+                val result = LinkedHashMap<K, V>()
+                repeat(length) {
+                    val key = readKey()
+                    result[key] = readValue()
+                }
+                return result
+            }
+
+            fun <T> readRange(read: () -> T): PostcardRange<T> {
+                val start = read()
+                return PostcardRange(start, read())
+            }
+
+            fun remaining(): ByteArray = bytes.copyOfRange(position, bytes.size)
+        }
+    }
+}
@@ -0,0 +1,221 @@
+mod des;
+mod general;
+mod ser;
+mod types;
+
+use core::borrow::Borrow;
+use std::collections::BTreeMap;
+
+use genco::lang::kotlin::Kotlin;
+use general::gen_runtime;
+use types::gen_module;
+
+use crate::{
+    path::{Path, PathBuf},
+    registry::{Container, ContainerCollection},
+    type_info::ObjectMeta,
+    validation::ValidationSettings,
+    ExportFile, Exports,
+};
+
+use super::utils::snake_case;
+
+type Tokens = genco::lang::kotlin::Tokens;
+
+/// Name of the package holding the runtime, relative to the package root.
+const RUNTIME_PACKAGE: &str = "postcard";
+
+/// Settings for Kotlin bindings generation.
+///
+/// Structs are generated as data classes, enums as sealed classes. Serialization and
+/// deserialization are members of the generated classes.
+///
+/// By default, only deserialization is enabled. Serialization can be enabled by using [`GenerationSettings::serialization()`].
+/// Deserialization can be disabled with [`GenerationSettings::deserialization()`].
+/// To enable all at once use [`GenerationSettings::enable_all()`].
+#[derive(Debug)]
+pub struct GenerationSettings {
+    ser: bool,
+    des: bool,
+    module_structure: bool,
+}
+
+impl GenerationSettings {
+    /// Constructs [`GenerationSettings`] and enables all options at once.
+    pub fn enable_all() -> Self {
+        Self {
+            ser: true,
+            des: true,
+            module_structure: true,
+        }
+    }
+
+    /// Enabling or disabling of serialization code generation.
+    pub fn serialization(mut self, enabled: bool) -> Self {
+        self.ser = enabled;
+        self
+    }
+
+    /// Enabling or disabling of deserialization code generation.
+    pub fn deserialization(mut self, enabled: bool) -> Self {
+        self.des = enabled;
+        self
+    }
+
+    /// Enabling or disabling of module structure code generation.
+    ///
+    /// Enabling this will generate the types in the same module structure
+    /// as in rust. Root level types will be in the root package. Types
+    /// nested in modules will be in sub packages
+    /// (e.g. <package>.<mod_name>.<type_name>). This avoids name clashes.
+    ///
+    /// Disabling this will generate all types in the root package.
+    pub fn module_structure(mut self, enabled: bool) -> Self {
+        self.module_structure = enabled;
+        self
+    }
+}
+
+impl Default for GenerationSettings {
+    fn default() -> Self {
+        Self {
+            ser: false,
+            des: true,
+            module_structure: true,
+        }
+    }
+}
+
+impl From<&GenerationSettings> for ValidationSettings {
+    fn from(settings: &GenerationSettings) -> Self {
+        ValidationSettings::default().flattened(!settings.module_structure)
+    }
+}
+
+/// Generates the Kotlin code for the containers.
+///
+/// The content type of each export file is its path relative to the package root
+/// directory without the `.kt` extension (e.g. `Types`, `sub_module/Types` or
+/// `postcard/Runtime`). Every file declares its own package.
+pub fn generate(
+    mut containers: ContainerCollection,
+    gen_settings: impl Borrow<GenerationSettings>,
+    package_name: String,
+) -> Exports<Kotlin> {
+    let gen_settings = gen_settings.borrow();
+    let package = snake_case(&package_name);
+
+    if !gen_settings.module_structure {
+        containers.flatten();
+    }
+
+    let mut files = vec![ExportFile {
+        content_type: format!("{RUNTIME_PACKAGE}/Runtime"),
+        content: gen_runtime(&package),
+    }];
+
+    let mut modules = BTreeMap::<Vec<String>, Vec<Container>>::new();
+    for container in containers.all_containers() {
+        modules
+            .entry(module_parts(&container.path))
+            .or_default()
+            .push(container);
+    }
+
+    for (parts, containers) in modules {
+        let content_type = parts
+            .iter()
+            .map(String::as_str)
+            .chain(["Types"])
+            .collect::<Vec<_>>()
+            .join("/");
+        files.push(ExportFile {
+            content_type,
+            content: gen_module(&package, &parts, &containers, gen_settings),
+        });
+    }
+
+    Exports { files }
+}
+
+/// Module path of the container without the crate name.
+fn module_parts(path: &Path<'_, '_>) -> Vec<String> {
+    let mut path: PathBuf = path.clone().into_buf();
+    // We will skip the first part of the path, as it is the crate name.
+    path.pop_front();
+    path.parts().map(|part| part.as_ref().to_owned()).collect()
+}
+
+/// Kotlin package for the module path.
+fn package_name(package: &str, parts: &[String]) -> String {
+    parts
+        .iter()
+        .map(|part| identifier(part))
+        .fold(package.to_owned(), |package, part| {
+            format!("{package}.{part}")
+        })
+}
+
+/// Fully qualified name of the referenced class, usable from any package.
+fn full_qualified_object_name(package: &str, meta: &ObjectMeta) -> String {
+    format!(
+        "{}.{}",
+        package_name(package, &module_parts(&meta.path)),
+        identifier(&meta.name)
+    )
+}
+
+/// Fully qualified name of a runtime class.
+fn runtime_name(package: &str, name: &str) -> String {
+    format!("{package}.{RUNTIME_PACKAGE}.{name}")
+}
+
+/// Name of the class of an enum variant, nested in the sealed class.
+fn variant_identifier(enum_name: &str, variant_name: &str) -> String {
+    // nested classes can't be named like the enclosing class
+    if variant_name == enum_name || variant_name == "Companion" {
+        identifier(&format!("{variant_name}_"))
+    } else {
+        identifier(variant_name)
+    }
+}
+
+const KEYWORDS: &[&str] = &[
+    "as",
+    "break",
+    "class",
+    "continue",
+    "do",
+    "else",
+    "false",
+    "for",
+    "fun",
+    "if",
+    "in",
+    "interface",
+    "is",
+    "null",
+    "object",
+    "package",
+    "return",
+    "super",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typealias",
+    "typeof",
+    "val",
+    "var",
+    "when",
+    "while",
+];
+
+/// Escapes names which are Kotlin keywords.
+fn identifier(name: &str) -> String {
+    if KEYWORDS.contains(&name) {
+        format!("`{name}`")
+    } else {
+        name.to_owned()
+    }
+}
@@ -0,0 +1,151 @@
+use genco::quote;
+
+use crate::type_info::{IntegerEncoding, NumberMeta, ValueType};
+
+use super::{types::tuple_accessors, Tokens};
+
+/// Statement serializing the value of `expr` with the serializer `s`.
+pub(super) fn gen_ser_value(expr: &str, value_type: &ValueType, depth: usize) -> Tokens {
+    let item = format!("x{depth}");
+    match value_type {
+        ValueType::Bool(_) => quote!(s.writeBool($expr)),
+        ValueType::Number(meta) => {
+            let value = match zero_literal(meta) {
+                Some(zero) => quote!(checkNonZero($expr, $zero)),
+                None => quote!($expr),
+            };
+            match fixint(meta) {
+                Some((16, signed, little_endian)) => quote! {
+                    s.writeFixint128($value, $(signed.to_string()), $(little_endian.to_string()))
+                },
+                Some((bytes, _, little_endian)) => quote! {
+                    s.writeFixint($value.toLong(), $bytes, $(little_endian.to_string()))
+                },
+                None => quote!(s.$(format!("write{}", number_suffix(meta)))($value)),
+            }
+        }
+        ValueType::String(meta) => match meta.max_length {
+            Some(max_length) => quote!(s.writeString($expr, maxLength = $max_length)),
+            None => quote!(s.writeString($expr)),
+        },
+        ValueType::Array(meta) => {
+            let inner = gen_ser_value(&item, &meta.items_type, depth + 1);
+            let call = match (meta.length, meta.max_length) {
+                (Some(length), _) => quote!(s.writeArray($expr, $length)),
+                (None, Some(max_length)) => quote!(s.writeList($expr, maxLength = $max_length)),
+                (None, None) => quote!(s.writeList($expr)),
+            };
+            quote! {
+                $call { $(&item) ->
+                    $inner
+                }
+            }
+        }
+        ValueType::Map(meta) => {
+            let key = format!("k{depth}");
+            let value = format!("v{depth}");
+            let call = match meta.max_length {
+                Some(max_length) => quote!(s.writeMap($expr, maxLength = $max_length)),
+                None => quote!(s.writeMap($expr)),
+            };
+            quote! {
+                $call { $(&key), $(&value) ->
+                    $(gen_ser_value(&key, &meta.key_type, depth + 1))
+                    $(gen_ser_value(&value, &meta.value_type, depth + 1))
+                }
+            }
+        }
+        ValueType::Optional(meta) => quote! {
+            s.writeOption($expr) { $(&item) ->
+                $(gen_ser_value(&item, &meta.inner, depth + 1))
+            }
+        },
+        ValueType::Tuple(meta) => {
+            let items = tuple_accessors(meta.items_types.len())
+                .into_iter()
+                .zip(meta.items_types.iter())
+                .map(|(accessor, ty)| gen_ser_value(&format!("{item}.{accessor}"), ty, depth + 1));
+            quote! {
+                $expr.let { $(&item) ->
+                    $(for item in items join ($['\r']) => $item)
+                }
+            }
+        }
+        ValueType::Range(meta) => quote! {
+            s.writeRange($expr) { $(&item) ->
+                $(gen_ser_value(&item, &meta.bounds_type, depth + 1))
+            }
+        },
+        ValueType::Object(_) => quote!($expr.serialize(s)),
+    }
+}
+
+/// Suffix of the runtime read and write methods of the number type.
+pub(super) fn number_suffix(meta: &NumberMeta) -> String {
+    match meta {
+        NumberMeta::Integer { bytes, signed, .. } => {
+            format!("{}{}", if *signed { "I" } else { "U" }, bytes * 8)
+        }
+        NumberMeta::FloatingPoint { bytes } => format!("F{}", bytes * 8),
+    }
+}
+
+/// Width, signedness and byte order (`true` for little endian) of an integer type which is
+/// encoded with a fixed width.
+pub(super) fn fixint(meta: &NumberMeta) -> Option<(usize, bool, bool)> {
+    match meta {
+        NumberMeta::Integer {
+            bytes,
+            signed,
+            encoding,
+            ..
+        } => {
+            let little_endian = match encoding {
+                IntegerEncoding::Varint => return None,
+                IntegerEncoding::FixedLe => true,
+                IntegerEncoding::FixedBe => false,
+            };
+            Some((*bytes, *signed, little_endian))
+        }
+        NumberMeta::FloatingPoint { .. } => None,
+    }
+}
+
+/// Kotlin type of the integer type, e.g. `UInt` for an unsigned 32 bit integer.
+pub(super) fn int_type_name(bytes: usize, signed: bool) -> &'static str {
+    match (bytes, signed) {
+        (1, false) => "UByte",
+        (2, false) => "UShort",
+        (4, false) => "UInt",
+        (8, false) => "ULong",
+        (1, true) => "Byte",
+        (2, true) => "Short",
+        (4, true) => "Int",
+        (8, true) => "Long",
+        _ => unreachable!("unsupported integer size {bytes}"),
+    }
+}
+
+/// Zero of the number type if the type is a non-zero integer.
+pub(super) fn zero_literal(meta: &NumberMeta) -> Option<&'static str> {
+    match meta {
+        NumberMeta::Integer {
+            bytes,
+            signed,
+            zero_able: false,
+            ..
+        } => Some(match (bytes, signed) {
+            (1, false) => "0u.toUByte()",
+            (2, false) => "0u.toUShort()",
+            (4, false) => "0u",
+            (8, false) => "0uL",
+            (1, true) => "0.toByte()",
+            (2, true) => "0.toShort()",
+            (4, true) => "0",
+            (8, true) => "0L",
+            (16, _) => "java.math.BigInteger.ZERO",
+            _ => unreachable!("unsupported integer size {bytes}"),
+        }),
+        _ => None,
+    }
+}
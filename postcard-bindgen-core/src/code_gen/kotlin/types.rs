@@ -0,0 +1,365 @@
+use genco::{quote, quote_in};
+
+use crate::{
+    registry::{BindingType, Container, EnumVariantType, StructField},
+    type_info::{NumberMeta, ValueType},
+};
+
+use super::{
+    des::gen_des_value, full_qualified_object_name, general::TUPLE_CLASS_ARITIES, identifier,
+    package_name, runtime_name, ser::gen_ser_value, variant_identifier, GenerationSettings, Tokens,
+    RUNTIME_PACKAGE,
+};
+
+/// Generates the file holding the classes of one module.
+pub(super) fn gen_module(
+    package: &str,
+    parts: &[String],
+    containers: &[Container],
+    gen_settings: &GenerationSettings,
+) -> Tokens {
+    let mut imports = Tokens::new();
+    let mut runtime_imports = Vec::new();
+    if gen_settings.ser {
+        runtime_imports.push("PostcardSerializer");
+    }
+    if gen_settings.des {
+        runtime_imports.push("PostcardDeserializer");
+    }
+    if gen_settings.ser || gen_settings.des {
+        runtime_imports.push("checkNonZero");
+    }
+    for import in runtime_imports {
+        quote_in!(imports => import $package.$RUNTIME_PACKAGE.$import);
+        imports.push();
+    }
+
+    let mut classes = Tokens::new();
+    for container in containers {
+        classes.append(gen_class(package, container, gen_settings));
+        classes.line();
+    }
+
+    quote! {
+        package $(package_name(package, parts))
+
+        $imports
+
+        $classes
+    }
+}
+
+fn gen_class(package: &str, container: &Container, gen_settings: &GenerationSettings) -> Tokens {
+    let name = identifier(&container.name);
+    match &container.r#type {
+        BindingType::Struct(ty) if !ty.fields.is_empty() => {
+            let fields = named_fields(&ty.fields);
+            gen_data_class(package, &name, &fields, gen_settings)
+        }
+        BindingType::TupleStruct(ty) if !ty.fields.is_empty() => {
+            let fields = unnamed_fields(&ty.fields);
+            gen_data_class(package, &name, &fields, gen_settings)
+        }
+        BindingType::Struct(_) | BindingType::TupleStruct(_) | BindingType::UnitStruct(_) => {
+            gen_object(&name, gen_settings)
+        }
+        BindingType::Enum(ty) => {
+            let mut variants = Tokens::new();
+            let mut ser_cases = Tokens::new();
+            let mut des_cases = Tokens::new();
+
+            for variant in ty.variants.iter() {
+                let variant_name = variant_identifier(&container.name, &variant.name);
+                let fields = match &variant.inner_type {
+                    EnumVariantType::Empty => Vec::new(),
+                    EnumVariantType::Tuple(fields) => unnamed_fields(fields),
+                    EnumVariantType::NewType(fields) => named_fields(fields),
+                };
+                let index = variant.index;
+
+                if fields.is_empty() {
+                    quote_in!(variants => data object $(&variant_name) : $(&name)());
+                    quote_in!(ser_cases => is $(&variant_name) -> s.writeVariantIndex($index));
+                    quote_in!(des_cases => $index -> $(&variant_name));
+                } else {
+                    quote_in! { variants =>
+                        data class $(&variant_name)(
+                            $(gen_properties(package, &fields))
+                        ) : $(&name)()
+                    };
+                    quote_in! { ser_cases =>
+                        is $(&variant_name) -> {
+                            s.writeVariantIndex($index)
+                            $(gen_ser_fields(&fields))
+                        }
+                    };
+                    quote_in! { des_cases =>
+                        $index -> $(gen_constructor_call(package, &variant_name, &fields))
+                    };
+                }
+                variants.push();
+                ser_cases.push();
+                des_cases.push();
+            }
+
+            let ser = gen_settings.ser.then(|| {
+                quote! {
+                    $(gen_serialize_to_bytes())
+
+                    fun serialize(s: PostcardSerializer) {
+                        when (this) {
+                            $ser_cases
+                        }
+                    }
+                }
+            });
+
+            let des = gen_settings.des.then(|| {
+                quote! {
+                    companion object {
+                        $(gen_deserialize_from_bytes(&name))
+
+                        fun deserialize(d: PostcardDeserializer): $(&name) = when (val index = d.readVariantIndex()) {
+                            $des_cases
+                            else -> throw $(runtime_name(package, "PostcardException"))("unknown variant index " + index)
+                        }
+                    }
+                }
+            });
+
+            quote! {
+                sealed class $(&name) {
+                    $(join_members([Some(variants), ser, des]))
+                }
+            }
+        }
+    }
+}
+
+/// Field of a generated class with the accessor name and the type.
+struct Field<'a> {
+    name: String,
+    value_type: &'a ValueType,
+}
+
+fn named_fields(fields: &[StructField]) -> Vec<Field<'_>> {
+    fields
+        .iter()
+        .map(|field| Field {
+            name: identifier(&field.name),
+            value_type: &field.v_type,
+        })
+        .collect()
+}
+
+fn unnamed_fields(fields: &[ValueType]) -> Vec<Field<'_>> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, value_type)| Field {
+            name: format!("_{index}"),
+            value_type,
+        })
+        .collect()
+}
+
+fn gen_properties(package: &str, fields: &[Field]) -> Tokens {
+    let mut tokens = Tokens::new();
+    for field in fields {
+        quote_in!(tokens => val $(&field.name): $(kotlin_type(package, field.value_type)),);
+        tokens.push();
+    }
+    tokens
+}
+
+fn gen_ser_fields(fields: &[Field]) -> Tokens {
+    let mut tokens = Tokens::new();
+    for field in fields {
+        tokens.append(gen_ser_value(
+            &format!("this.{}", field.name),
+            field.value_type,
+            0,
+        ));
+        tokens.push();
+    }
+    tokens
+}
+
+fn gen_constructor_call(package: &str, name: &str, fields: &[Field]) -> Tokens {
+    let mut args = Tokens::new();
+    for field in fields {
+        quote_in!(args => $(&field.name) = $(gen_des_value(package, field.value_type)),);
+        args.push();
+    }
+    quote! {
+        $name(
+            $args
+        )
+    }
+}
+
+fn gen_data_class(
+    package: &str,
+    name: &str,
+    fields: &[Field],
+    gen_settings: &GenerationSettings,
+) -> Tokens {
+    let ser = gen_settings.ser.then(|| {
+        quote! {
+            $(gen_serialize_to_bytes())
+
+            fun serialize(s: PostcardSerializer) {
+                $(gen_ser_fields(fields))
+            }
+        }
+    });
+
+    let des = gen_settings.des.then(|| {
+        quote! {
+            companion object {
+                $(gen_deserialize_from_bytes(name))
+
+                fun deserialize(d: PostcardDeserializer): $name = $(gen_constructor_call(package, name, fields))
+            }
+        }
+    });
+
+    let body = join_members([ser, des]);
+
+    if body.is_empty() {
+        quote! {
+            data class $name(
+                $(gen_properties(package, fields))
+            )
+        }
+    } else {
+        quote! {
+            data class $name(
+                $(gen_properties(package, fields))
+            ) {
+                $body
+            }
+        }
+    }
+}
+
+fn gen_object(name: &str, gen_settings: &GenerationSettings) -> Tokens {
+    let ser = gen_settings.ser.then(|| {
+        quote! {
+            $(gen_serialize_to_bytes())
+
+            fun serialize($("@Suppress(\"UNUSED_PARAMETER\")") s: PostcardSerializer) {}
+        }
+    });
+
+    let des = gen_settings.des.then(|| {
+        quote! {
+            $(gen_deserialize_from_bytes(name))
+
+            fun deserialize($("@Suppress(\"UNUSED_PARAMETER\")") d: PostcardDeserializer): $name = this
+        }
+    });
+
+    let body = join_members([ser, des]);
+
+    if body.is_empty() {
+        quote!(data object $name)
+    } else {
+        quote! {
+            data object $name {
+                $body
+            }
+        }
+    }
+}
+
+fn join_members(members: impl IntoIterator<Item = Option<Tokens>>) -> Tokens {
+    let mut tokens = Tokens::new();
+    for member in members.into_iter().flatten() {
+        if !tokens.is_empty() {
+            tokens.line();
+        }
+        tokens.append(member);
+    }
+    tokens
+}
+
+fn gen_serialize_to_bytes() -> Tokens {
+    quote! {
+        fun serialize(): kotlin.ByteArray {
+            val s = PostcardSerializer()
+            serialize(s)
+            return s.finish()
+        }
+    }
+}
+
+fn gen_deserialize_from_bytes(name: &str) -> Tokens {
+    quote! {
+        fun deserialize(bytes: kotlin.ByteArray): kotlin.Pair<$name, kotlin.ByteArray> {
+            val d = PostcardDeserializer(bytes)
+            return kotlin.Pair(deserialize(d), d.remaining())
+        }
+    }
+}
+
+/// Kotlin type a [ValueType] is represented with.
+fn kotlin_type(package: &str, value_type: &ValueType) -> Tokens {
+    match value_type {
+        ValueType::Bool(_) => quote!(kotlin.Boolean),
+        ValueType::Number(NumberMeta::FloatingPoint { bytes }) => match bytes {
+            4 => quote!(kotlin.Float),
+            8 => quote!(kotlin.Double),
+            _ => unreachable!("unsupported floating point size {bytes}"),
+        },
+        ValueType::Number(NumberMeta::Integer { bytes, signed, .. }) => match (bytes, signed) {
+            (1, false) => quote!(kotlin.UByte),
+            (2, false) => quote!(kotlin.UShort),
+            (4, false) => quote!(kotlin.UInt),
+            (8, false) => quote!(kotlin.ULong),
+            (1, true) => quote!(kotlin.Byte),
+            (2, true) => quote!(kotlin.Short),
+            (4, true) => quote!(kotlin.Int),
+            (8, true) => quote!(kotlin.Long),
+            (16, _) => quote!(java.math.BigInteger),
+            _ => unreachable!("unsupported integer size {bytes}"),
+        },
+        ValueType::String(_) => quote!(kotlin.String),
+        ValueType::Array(meta) => {
+            quote!(kotlin.collections.List<$(kotlin_type(package, &meta.items_type))>)
+        }
+        ValueType::Map(meta) => quote! {
+            kotlin.collections.Map<$(kotlin_type(package, &meta.key_type)), $(kotlin_type(package, &meta.value_type))>
+        },
+        ValueType::Optional(meta) => quote!($(kotlin_type(package, &meta.inner))?),
+        ValueType::Tuple(meta) => {
+            let items = meta.items_types.iter().map(|ty| kotlin_type(package, ty));
+            quote!($(tuple_class(package, meta.items_types.len()))<$(for item in items join (, ) => $item)>)
+        }
+        ValueType::Range(meta) => quote! {
+            $(runtime_name(package, "PostcardRange"))<$(kotlin_type(package, &meta.bounds_type))>
+        },
+        ValueType::Object(meta) => quote!($(full_qualified_object_name(package, meta))),
+    }
+}
+
+/// Class a tuple with `arity` items is represented with.
+pub(super) fn tuple_class(package: &str, arity: usize) -> String {
+    match arity {
+        2 => "kotlin.Pair".to_owned(),
+        3 => "kotlin.Triple".to_owned(),
+        arity if TUPLE_CLASS_ARITIES.contains(&arity) => {
+            runtime_name(package, &format!("Tuple{arity}"))
+        }
+        _ => unreachable!("unsupported tuple size {arity}"),
+    }
+}
+
+/// Properties holding the items of a tuple with `arity` items.
+pub(super) fn tuple_accessors(arity: usize) -> Vec<String> {
+    match arity {
+        2 => vec!["first".to_owned(), "second".to_owned()],
+        3 => vec!["first".to_owned(), "second".to_owned(), "third".to_owned()],
+        arity => (1..=arity).map(|i| format!("item{i}")).collect(),
+    }
+}
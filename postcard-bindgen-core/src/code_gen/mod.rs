@@ -1,4 +1,5 @@
 mod available_check;
+mod crc;
 mod export_registry;
 mod field_accessor;
 mod function;
@@ -7,8 +8,15 @@ mod switch_case;
 mod utils;
 mod variable_path;
 
+pub mod cpp;
+pub mod csharp;
+pub mod dart;
+pub mod go;
 pub mod js;
+pub mod kotlin;
 pub mod python;
+pub mod swift;
+pub mod ts;
 
 use crate::type_info::NumberMeta;
 
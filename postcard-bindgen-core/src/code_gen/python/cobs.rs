@@ -0,0 +1,136 @@
+use genco::quote;
+
+use crate::{code_gen::utils::TokensIterExt, function_args};
+
+use super::{Function, GenerationSettings, Tokens};
+
+/// Generates the COBS framing helpers.
+///
+/// The code only uses builtins, so it is shared with the MicroPython module. It relies on
+/// `serialize` and `deserialize` being in scope.
+pub fn gen_cobs_code(gen_settings: &GenerationSettings) -> Tokens {
+    let mut sections = vec![gen_codec()];
+
+    if gen_settings.ser {
+        sections.push(quote!($(Function::new_untyped(
+            "serialize_cobs",
+            function_args!("value"),
+            quote!(return cobs_encode(serialize(value))),
+        )
+        .with_doc_string(
+            "Serialize a value to a COBS frame, terminated by a zero byte.
+
+Args:
+    value: The value to serialize.
+
+Returns:
+    The encoded frame.
+
+",
+        ))));
+    }
+
+    if gen_settings.des {
+        sections.push(quote!($(Function::new_untyped(
+            "deserialize_cobs",
+            function_args!("obj_type", "bytes_in"),
+            quote! {
+                bytes_in = bytes(bytes_in)
+                end = bytes_in.find(bytes(1))
+                frame, rest = (bytes_in, bytes()) if end < 0 else (bytes_in[:end], bytes_in[end + 1:])
+                value, _ = deserialize(obj_type, cobs_decode(frame))
+                return (value, rest)
+            },
+        )
+        .with_doc_string(
+            "Deserialize a value from the first COBS frame in an array of bytes.
+
+Args:
+    obj_type: The type of the value to deserialize.
+    bytes_in: The byte array holding the frame.
+
+Returns:
+    The deserialized value and the bytes following the frame.
+
+",
+        ))));
+        sections.push(gen_accumulator());
+    }
+
+    sections.into_iter().join_with_empty_line()
+}
+
+fn gen_codec() -> Tokens {
+    quote! {
+        def cobs_encode(data):
+            out = bytearray([0])
+            code_index = 0
+            code = 1
+            for byte in data:
+                if byte != 0:
+                    out.append(byte)
+                    code += 1
+                if byte == 0 or code == 0xFF:
+                    out[code_index] = code
+                    code_index = len(out)
+                    out.append(0)
+                    code = 1
+            out[code_index] = code
+            out.append(0)
+            return bytes(out)
+
+        def cobs_decode(data):
+            out = bytearray()
+            index = 0
+            while index < len(data):
+                code = data[index]
+                index += 1
+                if code == 0 or index + code - 1 > len(data):
+                    raise Exception("invalid cobs encoding")
+                out.extend(data[index:index + code - 1])
+                index += code - 1
+                if code != 0xFF and index < len(data):
+                    out.append(0)
+            return bytes(out)
+    }
+}
+
+fn gen_accumulator() -> Tokens {
+    quote! {
+        class CobsAccumulator:
+            $(r#""""Collects COBS frames from chunks of a stream and decodes them to values of `obj_type`.""""#)
+
+            def __init__(self, obj_type, max_size = 1024):
+                self.obj_type = obj_type
+                self.max_size = max_size
+                self.buffer = bytearray()
+                self.overfull = False
+                self.frames = []
+
+            def feed(self, chunk):
+                $(r#""""Collects the frames completed by the chunk and returns a generator over their values."#)
+                $['\n']
+                $("The frames are decoded while iterating. A frame which fails to decode raises, the")
+                $("remaining frames are yielded by the next generator.")
+                $(r#"""""#)
+                for byte in chunk:
+                    if byte != 0:
+                        if len(self.buffer) < self.max_size:
+                            self.buffer.append(byte)
+                        else:
+                            self.overfull = True
+                        continue
+                    if self.overfull or self.buffer:
+                        self.frames.append(None if self.overfull else bytes(self.buffer))
+                    self.buffer = bytearray()
+                    self.overfull = False
+                return self.values()
+
+            def values(self):
+                while self.frames:
+                    frame = self.frames.pop(0)
+                    if frame is None:
+                        raise Exception("cobs frame exceeds the maximum size")
+                    yield deserialize(self.obj_type, cobs_decode(frame))[0]
+    }
+}
@@ -0,0 +1,131 @@
+use genco::quote;
+
+use crate::{
+    code_gen::{
+        crc::{hex, CRC_32_ALGORITHMS},
+        utils::TokensIterExt,
+    },
+    function_args,
+};
+
+use super::{Function, GenerationSettings, Tokens};
+
+/// Generates the CRC checked framing helpers.
+///
+/// The code only uses builtins, so it is shared with the MicroPython module. It relies on
+/// `serialize` and `deserialize` being in scope.
+pub fn gen_crc_code(gen_settings: &GenerationSettings) -> Tokens {
+    let mut sections = vec![gen_algorithms(), gen_checksum()];
+
+    if gen_settings.ser {
+        sections.push(quote!($(Function::new_untyped(
+            "serialize_crc32",
+            function_args!("value", "algorithm"),
+            quote! {
+                bytes_out = serialize(value)
+                return bytes_out + crc32(algorithm, bytes_out).to_bytes(U32_BYTES, "little")
+            },
+        )
+        .with_doc_string(
+            "Serialize a value to an array of bytes followed by its CRC in little endian.
+
+Args:
+    value: The value to serialize.
+    algorithm: The CRC algorithm, e.g. CRC_32_ISCSI.
+
+Returns:
+    The serialized value with the CRC.
+
+",
+        ))));
+    }
+
+    if gen_settings.des {
+        sections.push(quote!($(Function::new_untyped(
+            "deserialize_crc32",
+            function_args!("obj_type", "bytes_in", "algorithm"),
+            quote! {
+                bytes_in = bytes(bytes_in)
+                value, rest = deserialize(obj_type, bytes_in)
+                used = len(bytes_in) - len(rest)
+                if len(rest) < U32_BYTES:
+                    raise Exception("input buffer too small")
+                if int.from_bytes(rest[:U32_BYTES], "little") != crc32(algorithm, bytes_in[:used]):
+                    raise Exception("crc mismatch")
+                return (value, rest[U32_BYTES:])
+            },
+        )
+        .with_doc_string(
+            "Deserialize a value from an array of bytes and check the CRC following it.
+
+Args:
+    obj_type: The type of the value to deserialize.
+    bytes_in: The byte array holding the value and the CRC.
+    algorithm: The CRC algorithm, e.g. CRC_32_ISCSI.
+
+Returns:
+    The deserialized value and the bytes following the CRC.
+
+",
+        ))));
+    }
+
+    sections.into_iter().join_with_empty_line()
+}
+
+fn gen_algorithms() -> Tokens {
+    let algorithms = CRC_32_ALGORITHMS.iter().map(|algorithm| {
+        let flag = |value: bool| if value { "True" } else { "False" };
+        quote!($(algorithm.name) = Crc32Algorithm($(hex(algorithm.poly)), $(hex(algorithm.init)), $(flag(algorithm.refin)), $(flag(algorithm.refout)), $(hex(algorithm.xorout))))
+    });
+
+    quote! {
+        class Crc32Algorithm:
+            def __init__(self, poly, init, refin, refout, xorout):
+                self.poly = poly
+                self.init = init
+                self.refin = refin
+                self.refout = refout
+                self.xorout = xorout
+                self.table = None
+
+        $(for algorithm in algorithms join ($['\r']) => $algorithm)
+    }
+}
+
+fn gen_checksum() -> Tokens {
+    quote! {
+        def crc32_reflect(value):
+            out = 0
+            for i in range(32):
+                out = (out << 1) | ((value >> i) & 1)
+            return out
+
+        def crc32_table(algorithm):
+            if algorithm.table is None:
+                poly = crc32_reflect(algorithm.poly) if algorithm.refin else algorithm.poly
+                table = []
+                for i in range(256):
+                    crc = i if algorithm.refin else i << 24
+                    for _ in range(8):
+                        if algorithm.refin:
+                            crc = (crc >> 1) ^ poly if crc & 1 else crc >> 1
+                        else:
+                            crc = ((crc << 1) ^ poly if crc & 0x80000000 else crc << 1) & 0xFFFFFFFF
+                    table.append(crc)
+                algorithm.table = table
+            return algorithm.table
+
+        def crc32(algorithm, data):
+            table = crc32_table(algorithm)
+            crc = crc32_reflect(algorithm.init) if algorithm.refin else algorithm.init
+            for byte in data:
+                if algorithm.refin:
+                    crc = table[(crc ^ byte) & 0xFF] ^ (crc >> 8)
+                else:
+                    crc = table[((crc >> 24) ^ byte) & 0xFF] ^ ((crc << 8) & 0xFFFFFFFF)
+            if algorithm.refin != algorithm.refout:
+                crc = crc32_reflect(crc)
+            return crc ^ algorithm.xorout
+    }
+}
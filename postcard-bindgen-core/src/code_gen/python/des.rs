@@ -20,21 +20,30 @@ pub fn gen_deserializer_code() -> Tokens {
 
         class Deserializer:
             def __init__(self, bytes_in):
-                self.bytes = bytearray(bytes_in)
+                if not isinstance(bytes_in, (bytes, bytearray, memoryview)):
+                    bytes_in = bytes(bytes_in)
+                self.bytes = memoryview(bytes_in)
+                self.index = 0
 
-            def pop_next(self):
-                if not self.bytes:
+            def ensure(self, n):
+                if self.index + n > len(self.bytes):
                     raise Exception("input buffer too small")
-                return self.bytes.pop(0)
+
+            def pop_next(self):
+                self.ensure(1)
+                byte = self.bytes[self.index]
+                self.index += 1
+                return byte
 
             def pop_n(self, n):
-                bytes_out = []
-                for _ in range(n):
-                    bytes_out.append(self.pop_next())
+                self.ensure(n)
+                bytes_out = self.bytes[self.index:self.index + n]
+                self.index += n
                 return bytes_out
 
             def get_int8(self, signed):
-                return int.from_bytes(bytes([self.pop_next()]), byteorder="little", signed=signed)
+                byte = self.pop_next()
+                return byte - 0x100 if signed and byte > 0x7F else byte
 
             def try_take(self, n_bytes):
                 out = 0
@@ -63,28 +72,37 @@ pub fn gen_deserializer_code() -> Tokens {
                 else:
                     raise Exception("byte count not supported")
 
+            def deserialize_fixint(self, n_bytes, signed, little_endian):
+                val = int.from_bytes(bytes(self.pop_n(n_bytes)), "little" if little_endian else "big")
+                return val - (1 << (8 * n_bytes)) if signed and val >> (8 * n_bytes - 1) else val
+
             def deserialize_number_float(self, n_bytes):
-                b_buffer = bytes(self.pop_n(n_bytes))
                 if n_bytes == U32_BYTES:
-                    return struct.unpack("<f", b_buffer)[0]
+                    fmt = "<f"
                 elif n_bytes == U64_BYTES:
-                    return struct.unpack("<d", b_buffer)[0]
+                    fmt = "<d"
                 else:
                     raise Exception("byte count not supported")
+                self.ensure(n_bytes)
+                value = struct.unpack_from(fmt, self.bytes, self.index)[0]
+                self.index += n_bytes
+                return value
 
             def deserialize_string(self):
                 str_len = self.try_take(U32_BYTES)
-                str_bytes = self.pop_n(str_len)
-                return "".join(chr(b) for b in str_bytes)
+                return str(self.pop_n(str_len), "utf-8")
 
             def deserialize_array(self, des, length = None):
                 return [des(self) for _ in range(self.try_take(U32_BYTES) if length is None else length)]
 
+            def deserialize_byte_array(self, length = None):
+                return list(self.pop_n(self.try_take(U32_BYTES) if length is None else length))
+
             def deserialize_map(self, des):
                 return {key: value for key, value in (des(self) for _ in range(self.try_take(U32_BYTES)))}
 
             def release_bytes(self):
-                return bytes(self.bytes)
+                return bytes(self.bytes[self.index:])
     }
 }
 
@@ -96,9 +114,9 @@ pub fn gen_des_functions(bindings: impl Iterator<Item = Container>) -> Tokens {
 
 fn gen_des_function_for_type(container: Container) -> Tokens {
     let container_ident =
-        ContainerIdentifierBuilder::new(container.path.clone().into_buf(), container.name).build();
+        ContainerIdentifierBuilder::new(container.path.clone().into_buf(), &container.name).build();
     let fully_qualified =
-        ContainerFullQualifiedTypeBuilder::new(container.path.clone().into_buf(), container.name)
+        ContainerFullQualifiedTypeBuilder::new(container.path.clone().into_buf(), &container.name)
             .build();
     let des_body = container.r#type.gen_des_body((&container).into());
     quote! {
@@ -110,7 +128,7 @@ fn gen_des_function_for_type(container: Container) -> Tokens {
 pub fn gen_deserialize_func(containers: impl Iterator<Item = Container> + Clone) -> Tokens {
     let all_bindings = containers
         .clone()
-        .map(|d| ContainerFullQualifiedTypeBuilder::new(d.path.clone().into_buf(), d.name).build())
+        .map(|d| ContainerFullQualifiedTypeBuilder::new(d.path.clone().into_buf(), &d.name).build())
         .collect::<Vec<_>>();
 
     let mut obj_type_types = all_bindings.iter().map(|d| quote!($d));
@@ -165,10 +183,10 @@ Returns:
 
 fn gen_des_case(container: Container) -> (Tokens, Tokens) {
     let fully_qualified =
-        ContainerFullQualifiedTypeBuilder::new(container.path.clone().into_buf(), container.name)
+        ContainerFullQualifiedTypeBuilder::new(container.path.clone().into_buf(), &container.name)
             .build();
     let container_ident =
-        ContainerIdentifierBuilder::new(container.path.clone().into_buf(), container.name).build();
+        ContainerIdentifierBuilder::new(container.path.clone().into_buf(), &container.name).build();
     (
         quote!(obj_type is $fully_qualified),
         quote!(result_value = cast(T, deserialize_$container_ident(d))),
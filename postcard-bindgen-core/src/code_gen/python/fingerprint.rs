@@ -0,0 +1,27 @@
+use genco::{quote, tokens::quoted};
+
+use crate::{
+    code_gen::{crc::hex, utils::ContainerFullQualifiedTypeBuilder},
+    registry::ContainerCollection,
+};
+
+use super::Tokens;
+
+/// Generates the fingerprint constants of the schema and of every container, keyed by the
+/// dotted path of the container in the package.
+///
+/// The code has no imports, so it is shared with the MicroPython module.
+pub fn gen_fingerprint_code(containers: &ContainerCollection) -> Tokens {
+    let fingerprints = containers.all_containers().map(|container| {
+        let fingerprint = hex(container.fingerprint());
+        let full_qualified = ContainerFullQualifiedTypeBuilder::from(&container).build();
+        quote!($(quoted(full_qualified)): $fingerprint,)
+    });
+
+    quote! {
+        SCHEMA_FINGERPRINT = $(hex(containers.fingerprint()))
+        CONTAINER_FINGERPRINTS = {
+            $(for fingerprint in fingerprints join ($['\r']) => $fingerprint)
+        }
+    }
+}
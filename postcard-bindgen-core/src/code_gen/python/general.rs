@@ -19,7 +19,7 @@ pub fn gen_util() -> Tokens {
             return (n << 1) ^ (n >> (n_bytes * BITS_PER_BYTE - 1))
 
         def varint_max(n_bytes):
-            return (n_bytes * BITS_PER_BYTE + (BITS_PER_BYTE - 1)) // BITS_PER_VARINT_BYTE
+            return (n_bytes * BITS_PER_BYTE + (BITS_PER_BYTE - 1)) $("//") BITS_PER_VARINT_BYTE
 
         def max_of_last_byte(n_bytes):
             return (1 << (n_bytes * BITS_PER_BYTE) % 7) - 1
@@ -4,8 +4,8 @@ use crate::{
     code_gen::{
         import_registry::{ImportItem, Package},
         python::{
-            generateable::types::PythonTypeGenerateable, FieldAccessor, ImportRegistry, Tokens,
-            PYTHON_OBJECT_VARIABLE,
+            gen_doc_string, gen_doc_string_or_pass, generateable::types::PythonTypeGenerateable,
+            FieldAccessor, ImportRegistry, Tokens, PYTHON_OBJECT_VARIABLE,
         },
         utils::{ContainerFullQualifiedTypeBuilder, TokensBranchedIterExt, TokensIterExt},
         variable_path::{VariableAccess, VariablePath},
@@ -21,7 +21,7 @@ impl BindingTypeGenerateable for EnumType {
         self.variants
             .iter()
             .map(|v| {
-                let variant_name = quote!($(&fully_qualified)_$(v.name));
+                let variant_name = quote!($(&fully_qualified)_$(&v.name));
 
                 let ser_fields = [quote!(s.serialize_number(U32_BYTES, False, $(v.index)))]
                     .into_iter()
@@ -32,7 +32,7 @@ impl BindingTypeGenerateable for EnumType {
                             .map(|f| {
                                 f.v_type.gen_ser_accessor(
                                     VariablePath::default()
-                                        .modify_push(VariableAccess::Field(f.name.to_owned())),
+                                        .modify_push(VariableAccess::Field(f.name.to_string())),
                                 )
                             })
                             .join_with_line_breaks(),
@@ -66,12 +66,12 @@ impl BindingTypeGenerateable for EnumType {
             .variants
             .iter()
             .map(|v| {
-                let variant_name = quote!($(&fully_qualified)_$(v.name));
+                let variant_name = quote!($(&fully_qualified)_$(&v.name));
 
                 let constructor_args = match &v.inner_type {
                     EnumVariantType::Empty => quote!(),
                     EnumVariantType::NewType(fields) => fields.iter().map(
-                        |f| quote!($(f.name) = $(f.v_type.gen_des_accessor(FieldAccessor::None))),
+                        |f| quote!($(&f.name) = $(f.v_type.gen_des_accessor(FieldAccessor::None))),
                     ).join_with_comma(),
                     EnumVariantType::Tuple(fields) => fields.iter().map(
                         |f| quote!($(f.gen_des_accessor(FieldAccessor::None))),
@@ -107,7 +107,7 @@ impl BindingTypeGenerateable for EnumType {
                         .map(|f| {
                             f.v_type.gen_ty_check(
                                 VariablePath::default()
-                                    .modify_push(VariableAccess::Field(f.name.to_owned())),
+                                    .modify_push(VariableAccess::Field(f.name.to_string())),
                             )
                         })
                         .join_with_line_breaks(),
@@ -122,7 +122,7 @@ impl BindingTypeGenerateable for EnumType {
                         .join_with_line_breaks(),
                 };
                 quote! {
-                    def assert_$(v.name)($PYTHON_OBJECT_VARIABLE):
+                    def assert_$(&v.name)($PYTHON_OBJECT_VARIABLE):
                         $body
 
                 }
@@ -133,10 +133,10 @@ impl BindingTypeGenerateable for EnumType {
             .variants
             .iter()
             .map(|v| {
-                let variant_name = quote!($(&fully_qualified)_$(v.name));
+                let variant_name = quote!($(&fully_qualified)_$(&v.name));
                 (
                     Some(quote!(isinstance($PYTHON_OBJECT_VARIABLE, $variant_name))),
-                    quote!(assert_$(v.name)($PYTHON_OBJECT_VARIABLE)),
+                    quote!(assert_$(&v.name)($PYTHON_OBJECT_VARIABLE)),
                 )
             })
             .chain([(
@@ -164,8 +164,8 @@ impl BindingTypeGenerateable for EnumType {
             .join_with_empty_line();
 
         quote! {
-            class $(container_info.name):
-                pass
+            class $(container_info.name.as_ref()):
+                $(gen_doc_string_or_pass(container_info.doc))
 
             $variants
         }
@@ -180,17 +180,23 @@ fn gen_variant_typings(
     let enum_name = enum_name.as_ref();
     let variant = variant.as_ref();
 
-    let variant_name = quote!($(enum_name)_$(variant.name));
+    let variant_name = quote!($(enum_name)_$(&variant.name));
+    let doc = variant.doc.as_deref().map(gen_doc_string);
 
     match &variant.inner_type {
         EnumVariantType::Empty => quote! {
             class $variant_name($enum_name):
-                pass
+                $(gen_doc_string_or_pass(variant.doc.as_deref()))
         },
         EnumVariantType::NewType(fields) => {
             let fields = fields
                 .iter()
-                .map(|f| quote!($(f.name): $(f.v_type.gen_typings(import_registry))))
+                .map(|f| {
+                    quote! {
+                        $(&f.name): $(f.v_type.gen_typings(import_registry))
+                        $(f.doc.as_deref().map(gen_doc_string))
+                    }
+                })
                 .join_with_line_breaks();
 
             import_registry.push(
@@ -200,6 +206,7 @@ fn gen_variant_typings(
             quote! {
                 @dataclass
                 class $variant_name($enum_name):
+                    $doc
                     $fields
             }
         }
@@ -224,6 +231,7 @@ fn gen_variant_typings(
 
             quote! {
                 class $(&variant_name)($enum_name, tuple[$types_comma_chained]):
+                    $doc
 
                     def __new__(cls, $(&constructor_args)):
                         return super($(&variant_name), cls).__new__(cls, ($pass_on_args))
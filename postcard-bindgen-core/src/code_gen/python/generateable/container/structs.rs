@@ -4,8 +4,8 @@ use crate::{
     code_gen::{
         import_registry::{ImportItem, Package},
         python::{
-            generateable::types::PythonTypeGenerateable, FieldAccessor, ImportRegistry, Tokens,
-            VariableAccess, VariablePath,
+            gen_doc_string, generateable::types::PythonTypeGenerateable, FieldAccessor,
+            ImportRegistry, Tokens, VariableAccess, VariablePath,
         },
         utils::{ContainerFullQualifiedTypeBuilder, TokensIterExt},
     },
@@ -20,7 +20,8 @@ impl BindingTypeGenerateable for StructType {
             .iter()
             .map(|field| {
                 field.v_type.gen_ser_accessor(
-                    VariablePath::default().modify_push(VariableAccess::Field(field.name.into())),
+                    VariablePath::default()
+                        .modify_push(VariableAccess::Field(field.name.to_string())),
                 )
             })
             .join_with_line_breaks()
@@ -34,7 +35,7 @@ impl BindingTypeGenerateable for StructType {
             .map(|field| {
                 field
                     .v_type
-                    .gen_des_accessor(FieldAccessor::Object(field.name))
+                    .gen_des_accessor(FieldAccessor::Object(&field.name))
             })
             .join_with_comma();
         quote!(return $fully_qualified($body))
@@ -51,7 +52,7 @@ impl BindingTypeGenerateable for StructType {
                 field.v_type.gen_ty_check(
                     variable_path
                         .to_owned()
-                        .modify_push(VariableAccess::Field(field.name.into())),
+                        .modify_push(VariableAccess::Field(field.name.to_string())),
                 )
             })
             .join_with_line_breaks();
@@ -72,7 +73,12 @@ impl BindingTypeGenerateable for StructType {
         let body = self
             .fields
             .iter()
-            .map(|field| quote!($(field.name): $(field.v_type.gen_typings(import_registry))))
+            .map(|field| {
+                quote! {
+                    $(&field.name): $(field.v_type.gen_typings(import_registry))
+                    $(field.doc.as_deref().map(gen_doc_string))
+                }
+            })
             .join_with_line_breaks();
         import_registry.push(
             Package::Extern("dataclasses".into()),
@@ -81,6 +87,7 @@ impl BindingTypeGenerateable for StructType {
         quote! {
             @dataclass
             class $(container_info.name.as_ref()):
+                $(container_info.doc.map(gen_doc_string))
                 $body
         }
     }
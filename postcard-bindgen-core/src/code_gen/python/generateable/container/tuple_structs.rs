@@ -3,8 +3,8 @@ use genco::{quote, tokens::quoted};
 use crate::{
     code_gen::{
         python::{
-            generateable::types::PythonTypeGenerateable, FieldAccessor, ImportRegistry, Tokens,
-            VariableAccess, VariablePath, PYTHON_OBJECT_VARIABLE,
+            gen_doc_string, generateable::types::PythonTypeGenerateable, FieldAccessor,
+            ImportRegistry, Tokens, VariableAccess, VariablePath, PYTHON_OBJECT_VARIABLE,
         },
         utils::{ContainerFullQualifiedTypeBuilder, TokensIterExt},
     },
@@ -85,9 +85,11 @@ impl BindingTypeGenerateable for TupleStructType {
             .join_with_comma_min_one();
 
         let class_name = container_info.name.as_ref();
+        let doc = container_info.doc.map(gen_doc_string);
 
         quote! {
             class $class_name(tuple[$types_comma_chained]):
+                $doc
 
                 def __new__(cls, $(&constructor_args)):
                     return super($class_name, cls).__new__(cls, ($pass_on_args))
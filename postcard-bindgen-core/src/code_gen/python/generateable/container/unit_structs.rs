@@ -3,7 +3,7 @@ use genco::quote;
 use crate::{
     code_gen::{
         import_registry::{ImportItem, Package},
-        python::{ImportRegistry, Tokens, PYTHON_OBJECT_VARIABLE},
+        python::{gen_doc_string_or_pass, ImportRegistry, Tokens, PYTHON_OBJECT_VARIABLE},
         utils::ContainerFullQualifiedTypeBuilder,
     },
     registry::{ContainerInfo, UnitStructType},
@@ -38,7 +38,7 @@ impl BindingTypeGenerateable for UnitStructType {
         quote! {
             @dataclass
             class $(container_info.name):
-                pass
+                $(gen_doc_string_or_pass(container_info.doc))
         }
     }
 }
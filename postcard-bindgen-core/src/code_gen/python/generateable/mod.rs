@@ -1,14 +1,11 @@
-use std::{
-    borrow::Cow,
-    path::{Path, PathBuf},
-};
+use std::path::{Path, PathBuf};
 
 use container::BindingTypeGenerateable;
 use genco::{quote, quote_in, tokens::quoted};
 
 use crate::{
     code_gen::{
-        python::ImportRegistry,
+        python::{variant_class_name, ImportRegistry},
         utils::{snake_case, TokensIterExt},
     },
     registry::{BindingType, Container, ContainerCollection, Module},
@@ -46,12 +43,12 @@ fn generate_typings_for_mod<'a>(
     generate_package_name: String,
 ) {
     let container_exports = containers.clone().map(|f| {
-        let mut l = vec![Cow::from(f.name)];
+        let mut l = vec![f.name.clone()];
         if let BindingType::Enum(e) = f.r#type {
             l.extend(
                 e.variants
                     .iter()
-                    .map(|v| format!("{}_{}", f.name, v.name).into()),
+                    .map(|v| variant_class_name(&f.name, &v.name).into()),
             );
         }
         l
@@ -101,7 +98,7 @@ fn generate_typings_for_mod<'a>(
 
         files.push(ExportFile {
             content_type: path
-                .join(format!("_{}", snake_case(container.name)))
+                .join(format!("_{}", snake_case(&container.name)))
                 .to_string_lossy()
                 .into_owned(),
             content: quote! {
@@ -5,7 +5,7 @@ use crate::{
         python::{FieldAccessor, ImportRegistry, Tokens, VariablePath, PYTHON_OBJECT_VARIABLE},
         utils::TokensIterExt,
     },
-    type_info::ArrayMeta,
+    type_info::{ArrayMeta, NumberMeta, ValueType},
 };
 
 use super::PythonTypeGenerateable;
@@ -33,6 +33,14 @@ impl PythonTypeGenerateable for ArrayMeta {
     }
 
     fn gen_des_accessor(&self, field_accessor: FieldAccessor) -> Tokens {
+        // bytes are sliced from the input instead of being read one by one
+        if is_byte(&self.items_type) {
+            return match self.length {
+                Some(len) => quote!($(field_accessor)d.deserialize_byte_array($len)),
+                None => quote!($(field_accessor)d.deserialize_byte_array()),
+            };
+        }
+
         let inner_type_accessor = self.items_type.gen_des_accessor(FieldAccessor::Array);
         if let Some(len) = self.length {
             quote!($(field_accessor)d.deserialize_array(lambda d: $inner_type_accessor, $len))
@@ -67,3 +75,15 @@ impl PythonTypeGenerateable for ArrayMeta {
         quote!(list[$(self.items_type.gen_typings(import_registry))])
     }
 }
+
+fn is_byte(value_type: &ValueType) -> bool {
+    matches!(
+        value_type,
+        ValueType::Number(NumberMeta::Integer {
+            bytes: 1,
+            signed: false,
+            zero_able: true,
+            ..
+        })
+    )
+}
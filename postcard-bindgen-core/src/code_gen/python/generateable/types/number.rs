@@ -6,7 +6,7 @@ use crate::{
         python::{generateable::types::bool::bool_to_python_bool, ImportRegistry, Tokens},
         utils::TokensIterExt,
     },
-    type_info::NumberMeta,
+    type_info::{IntegerEncoding, NumberMeta},
 };
 
 use super::PythonTypeGenerateable;
@@ -18,10 +18,17 @@ impl PythonTypeGenerateable for NumberMeta {
             NumberMeta::FloatingPoint { .. } => {
                 quote!(s.serialize_number_float($byte_amount_str, $variable_path))
             }
-            NumberMeta::Integer { signed, .. } => {
-                let signed = bool_to_python_bool(*signed);
-                quote!(s.serialize_number($byte_amount_str, $signed, $variable_path))
-            }
+            NumberMeta::Integer {
+                signed, encoding, ..
+            } => match fixed_width_little_endian(encoding) {
+                Some(little_endian) => {
+                    quote!(s.serialize_fixint($byte_amount_str, $little_endian, $variable_path))
+                }
+                None => {
+                    let signed = bool_to_python_bool(*signed);
+                    quote!(s.serialize_number($byte_amount_str, $signed, $variable_path))
+                }
+            },
         }
     }
 
@@ -31,9 +38,18 @@ impl PythonTypeGenerateable for NumberMeta {
             NumberMeta::FloatingPoint { .. } => {
                 quote!($(field_accessor)d.deserialize_number_float($byte_amount_str))
             }
-            NumberMeta::Integer { signed, .. } => {
+            NumberMeta::Integer {
+                signed, encoding, ..
+            } => {
                 let signed = bool_to_python_bool(*signed);
-                quote!($(field_accessor)d.deserialize_number($byte_amount_str, $signed))
+                match fixed_width_little_endian(encoding) {
+                    Some(little_endian) => {
+                        quote!($(field_accessor)d.deserialize_fixint($byte_amount_str, $signed, $little_endian))
+                    }
+                    None => {
+                        quote!($(field_accessor)d.deserialize_number($byte_amount_str, $signed))
+                    }
+                }
             }
         }
     }
@@ -68,6 +84,7 @@ impl PythonTypeGenerateable for NumberMeta {
                 bytes,
                 signed,
                 zero_able,
+                ..
             } => {
                 let int_type = rust_int_to_python_type(*bytes, *signed, !*zero_able);
                 import_registry.push(Package::Root, ImportItem::Single("basic_types".into()));
@@ -77,6 +94,15 @@ impl PythonTypeGenerateable for NumberMeta {
     }
 }
 
+/// Byte order of a fixed width integer as a Python boolean, `None` for varints.
+fn fixed_width_little_endian(encoding: &IntegerEncoding) -> Option<&'static str> {
+    match encoding {
+        IntegerEncoding::Varint => None,
+        IntegerEncoding::FixedLe => Some(bool_to_python_bool(true)),
+        IntegerEncoding::FixedBe => Some(bool_to_python_bool(false)),
+    }
+}
+
 fn rust_int_to_python_type(bytes: usize, signed: bool, non_zero: bool) -> String {
     let bits = match bytes {
         1 => "8",
@@ -14,19 +14,19 @@ use super::PythonTypeGenerateable;
 impl PythonTypeGenerateable for ObjectMeta {
     fn gen_ser_accessor(&self, variable_path: VariablePath) -> Tokens {
         let container_ident =
-            ContainerIdentifierBuilder::new(self.path.clone().into_buf(), self.name).build();
+            ContainerIdentifierBuilder::new(self.path.clone().into_buf(), &self.name).build();
         quote!(serialize_$container_ident(s, $variable_path))
     }
 
     fn gen_des_accessor(&self, field_accessor: FieldAccessor) -> Tokens {
         let container_ident =
-            ContainerIdentifierBuilder::new(self.path.clone().into_buf(), self.name).build();
+            ContainerIdentifierBuilder::new(self.path.clone().into_buf(), &self.name).build();
         quote!($(field_accessor)deserialize_$container_ident(d))
     }
 
     fn gen_ty_check(&self, variable_path: VariablePath) -> Tokens {
         let container_ident =
-            ContainerIdentifierBuilder::new(self.path.clone().into_buf(), self.name).build();
+            ContainerIdentifierBuilder::new(self.path.clone().into_buf(), &self.name).build();
         quote!(assert_$container_ident($variable_path))
     }
 
@@ -36,17 +36,17 @@ impl PythonTypeGenerateable for ObjectMeta {
         container_path.pop_front();
 
         let mut import_path = container_path.clone();
-        import_path.push(format!("_{}", snake_case(self.name)));
+        import_path.push(format!("_{}", snake_case(&self.name)));
 
         import_path.push_front("types");
 
-        container_path.push(self.name);
+        container_path.push(self.name.clone());
         let type_alias = format!("_{}", String::from(container_path.into_path("_")));
 
         import_registry.push(
             Package::Intern(import_path),
             ImportItem::Aliased {
-                item_name: self.name.into(),
+                item_name: self.name.clone(),
                 alias: type_alias.clone().into(),
             },
         );
@@ -23,7 +23,7 @@ impl PythonTypeGenerateable for StringMeta {
         let mut checks = vec![];
         checks.push(quote!(assert isinstance($(variable_path.to_owned()), str), "{} is not a string".format($(variable_path.to_owned()))));
         if let Some(len) = self.max_length {
-            checks.push(quote!(assert len($(variable_path.to_owned())) <= $len, "{} has a length greater than {}".format($variable_path, $len)));
+            checks.push(quote!(assert len($(variable_path.to_owned()).encode("utf-8")) <= $len, "{} has a length greater than {}".format($variable_path, $len)));
         }
         checks.into_iter().join_with_line_breaks()
     }
@@ -0,0 +1,435 @@
+use genco::{quote, tokens::quoted};
+
+use crate::{
+    code_gen::{
+        python::{
+            cobs::gen_cobs_code,
+            crc::gen_crc_code,
+            des::gen_des_functions,
+            fingerprint::gen_fingerprint_code,
+            general::gen_util,
+            generateable::{container::BindingTypeGenerateable, types::PythonTypeGenerateable},
+            ser::{gen_ser_case, gen_ser_functions},
+            type_checks::gen_type_check_func,
+            variant_class_name, Function, GenerationSettings, Tokens, VariableAccess, VariablePath,
+            PYTHON_OBJECT_VARIABLE,
+        },
+        utils::{
+            ContainerFullQualifiedTypeBuilder, ContainerIdentifierBuilder, TokensBranchedIterExt,
+            TokensIterExt,
+        },
+    },
+    function_args,
+    registry::{BindingType, Container, ContainerCollection, EnumVariantType},
+};
+
+/// Generates a single module with the types and the serialization and deserialization code.
+///
+/// The module only uses builtins which are available in MicroPython. The containers must
+/// be flattened, as the types are referenced by their plain names.
+pub fn gen_micropython_module(
+    containers: &ContainerCollection,
+    gen_settings: &GenerationSettings,
+) -> Tokens {
+    let mut sections = vec![
+        gen_util(),
+        gen_fingerprint_code(containers),
+        gen_classes(containers.all_containers()),
+    ];
+
+    if gen_settings.runtime_type_checks {
+        sections.push(gen_type_checks(containers.all_containers()));
+    }
+
+    if gen_settings.ser {
+        sections.push(gen_serializer_code());
+        sections.push(gen_ser_functions(containers.all_containers()));
+        sections.push(gen_serialize_func(
+            containers.all_containers(),
+            gen_settings.runtime_type_checks,
+        ));
+    }
+
+    if gen_settings.des {
+        sections.push(gen_deserializer_code());
+        sections.push(gen_des_functions(containers.all_containers()));
+        sections.push(gen_deserialize_func(containers.all_containers()));
+    }
+
+    if gen_settings.ser || gen_settings.des {
+        if gen_settings.cobs {
+            sections.push(gen_cobs_code(gen_settings));
+        }
+        if gen_settings.crc32 {
+            sections.push(gen_crc_code(gen_settings));
+        }
+    }
+
+    let mut tokens = Tokens::new();
+    if gen_settings.ser || gen_settings.des {
+        tokens.append("import struct");
+        tokens.line();
+    }
+    tokens.append(sections.into_iter().join_with_empty_line());
+    tokens
+}
+
+fn gen_classes(containers: impl Iterator<Item = Container>) -> Tokens {
+    containers.map(gen_class).join_with_empty_line()
+}
+
+fn gen_class(container: Container) -> Tokens {
+    let name = container.name;
+    match &container.r#type {
+        BindingType::Struct(ty) => {
+            let fields = ty
+                .fields
+                .iter()
+                .map(|f| f.name.as_ref())
+                .collect::<Vec<_>>();
+            gen_plain_class(&name, None, &fields)
+        }
+        BindingType::TupleStruct(ty) => gen_tuple_class(&name, None, ty.fields.len()),
+        BindingType::UnitStruct(_) => gen_plain_class(&name, None, &[]),
+        BindingType::Enum(ty) => {
+            let variants = ty
+                .variants
+                .iter()
+                .map(|v| {
+                    let variant_name = variant_class_name(&name, &v.name);
+                    match &v.inner_type {
+                        EnumVariantType::Empty => gen_plain_class(&variant_name, Some(&name), &[]),
+                        EnumVariantType::NewType(fields) => {
+                            let fields = fields.iter().map(|f| f.name.as_ref()).collect::<Vec<_>>();
+                            gen_plain_class(&variant_name, Some(&name), &fields)
+                        }
+                        EnumVariantType::Tuple(fields) => {
+                            gen_tuple_class(&variant_name, Some(&name), fields.len())
+                        }
+                    }
+                })
+                .join_with_empty_line();
+
+            quote! {
+                class $name:
+                    pass
+
+                $variants
+            }
+        }
+    }
+}
+
+/// Plain class with the fields as attributes, replacing a dataclass.
+fn gen_plain_class(name: &str, base: Option<&str>, fields: &[&str]) -> Tokens {
+    let mut members = Vec::new();
+
+    if !fields.is_empty() {
+        members.push(quote! {
+            def __init__(self, $(for field in fields.iter() join (, ) => $(*field))):
+                $(for field in fields.iter() join ($['\r']) => self.$(*field) = $(*field))
+        });
+    }
+
+    let comparisons = fields
+        .iter()
+        .map(|field| quote!(self.$(*field) == other.$(*field)));
+    members.push(quote! {
+        def __eq__(self, other):
+            return type(self) is type(other)$(for comparison in comparisons => $[' ']and $comparison)
+    });
+
+    let repr = if fields.is_empty() {
+        quote!(return $(quoted(format!("{name}()"))))
+    } else {
+        let format = fields
+            .iter()
+            .map(|field| format!("{field}={{!r}}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        quote!(return $(quoted(format!("{name}({format})"))).format($(for field in fields.iter() join (, ) => self.$(*field))))
+    };
+    members.push(quote! {
+        def __repr__(self):
+            $repr
+    });
+
+    gen_class_definition(name, base, members)
+}
+
+/// Plain class holding the fields in a tuple and supporting indexed access like a tuple,
+/// replacing a subclass of `tuple`.
+fn gen_tuple_class(name: &str, base: Option<&str>, field_count: usize) -> Tokens {
+    let args = (0..field_count)
+        .map(|i| format!("_{i}"))
+        .collect::<Vec<_>>();
+
+    let members = vec![
+        quote! {
+            def __init__(self, $(for arg in args.iter() join (, ) => $arg)):
+                self._values = ($(args.iter().join_with_comma_min_one()))
+        },
+        quote! {
+            def __getitem__(self, index):
+                return self._values[index]
+        },
+        quote! {
+            def __len__(self):
+                return len(self._values)
+        },
+        quote! {
+            def __eq__(self, other):
+                return type(self) is type(other) and self._values == other._values
+        },
+        quote! {
+            def __repr__(self):
+                return $(quoted(name)) + repr(self._values)
+        },
+    ];
+
+    gen_class_definition(name, base, members)
+}
+
+fn gen_class_definition(name: &str, base: Option<&str>, members: Vec<Tokens>) -> Tokens {
+    let header = match base {
+        Some(base) => quote!(class $name($base):),
+        None => quote!(class $name:),
+    };
+    quote! {
+        $header
+            $(members.into_iter().join_with_empty_line())
+    }
+}
+
+fn gen_type_checks(containers: impl Iterator<Item = Container>) -> Tokens {
+    containers
+        .map(|container| {
+            let body = match &container.r#type {
+                // tuple structs are not tuples in MicroPython
+                BindingType::TupleStruct(ty) => {
+                    let fully_qualified =
+                        ContainerFullQualifiedTypeBuilder::from(&container).build();
+                    let field_checks = ty.fields.iter().enumerate().map(|(i, v)| {
+                        v.gen_ty_check(VariablePath::default().modify_push(VariableAccess::Indexed(i)))
+                    });
+                    [quote!(assert isinstance($PYTHON_OBJECT_VARIABLE, $(&fully_qualified)), "{} is not a {}".format($PYTHON_OBJECT_VARIABLE, $(quoted(&fully_qualified))))]
+                        .into_iter()
+                        .chain(field_checks)
+                        .join_with_line_breaks()
+                }
+                ty => ty.gen_ty_check_body((&container).into()),
+            };
+            gen_type_check_func(&container, body)
+        })
+        .join_with_empty_line()
+}
+
+fn gen_serializer_code() -> Tokens {
+    quote! {
+        class Serializer:
+            def __init__(self):
+                self.bytes = bytearray()
+
+            def finish(self):
+                return bytes(self.bytes)
+
+            def push_n(self, bytes_in):
+                self.bytes.extend(bytes(bytes_in))
+
+            def serialize_bool(self, value):
+                self.serialize_number(U8_BYTES, False, 1 if value else 0)
+
+            def serialize_number(self, n_bytes, signed, value):
+                if n_bytes == U8_BYTES:
+                    self.bytes.append(value & 0xFF)
+                elif n_bytes in (U16_BYTES, U32_BYTES, U64_BYTES, U128_BYTES):
+                    value_b = int(value)
+                    self.push_n(varint(n_bytes, zig_zag(n_bytes, value_b) if signed else value_b))
+                else:
+                    raise Exception("byte count not supported")
+
+            def serialize_fixint(self, n_bytes, little_endian, value):
+                value_b = int(value) & ((1 << (8 * n_bytes)) - 1)
+                self.bytes.extend(value_b.to_bytes(n_bytes, "little" if little_endian else "big"))
+
+            def serialize_number_float(self, n_bytes, value):
+                if n_bytes == U32_BYTES:
+                    self.bytes.extend(struct.pack("<f", value))
+                elif n_bytes == U64_BYTES:
+                    self.bytes.extend(struct.pack("<d", value))
+                else:
+                    raise Exception("byte count not supported")
+
+            def serialize_string(self, s):
+                encoded = s.encode("utf-8")
+                self.push_n(varint(U32_BYTES, len(encoded)))
+                self.bytes.extend(encoded)
+
+            def serialize_array(self, ser, array, length):
+                if length is None:
+                    self.push_n(varint(U32_BYTES, len(array)))
+                for i in range(len(array) if length is None else length):
+                    ser(self, array[i])
+
+            def serialize_map(self, ser, map_obj):
+                self.push_n(varint(U32_BYTES, len(map_obj)))
+                for k, v in map_obj.items():
+                    ser(self, k, v)
+    }
+}
+
+fn gen_serialize_func(
+    containers: impl Iterator<Item = Container>,
+    runtime_type_checks: bool,
+) -> Tokens {
+    let ser_switch = containers
+        .map(|t| gen_ser_case(t, runtime_type_checks))
+        .map(|(condition, body)| (Some(condition), body))
+        .chain([(
+            None,
+            quote!(raise TypeError("{} not serializable".format(type(value)))),
+        )])
+        .join_if_branched();
+
+    quote! {
+        $(Function::new_untyped(
+            "serialize",
+            function_args!("value"),
+            quote! {
+                s = Serializer()
+
+                $ser_switch
+
+                return s.finish()
+            },
+        ))
+    }
+}
+
+fn gen_deserializer_code() -> Tokens {
+    quote! {
+        class Deserializer:
+            def __init__(self, bytes_in):
+                self.bytes = bytes(bytes_in)
+                self.index = 0
+
+            def pop_next(self):
+                if self.index >= len(self.bytes):
+                    raise Exception("input buffer too small")
+                byte = self.bytes[self.index]
+                self.index += 1
+                return byte
+
+            def pop_n(self, n):
+                if self.index + n > len(self.bytes):
+                    raise Exception("input buffer too small")
+                bytes_out = self.bytes[self.index:self.index + n]
+                self.index += n
+                return bytes_out
+
+            def get_int8(self, signed):
+                byte = self.pop_next()
+                return byte - 0x100 if signed and byte > 0x7F else byte
+
+            def try_take(self, n_bytes):
+                out = 0
+                v_max = varint_max(n_bytes)
+                for i in range(v_max):
+                    val = self.pop_next()
+                    carry = val & 0x7F
+                    out |= carry << (7 * i)
+                    if (val & 0x80) == 0:
+                        if i == v_max - 1 and val > max_of_last_byte(n_bytes):
+                            raise Exception("Bad Variant")
+                        else:
+                            return out
+                raise Exception("Bad Variant")
+
+            def deserialize_bool(self):
+                return self.pop_next() > 0
+
+            def deserialize_number(self, n_bytes, signed):
+                if n_bytes == U8_BYTES:
+                    return self.get_int8(signed)
+                elif n_bytes in (U16_BYTES, U32_BYTES, U64_BYTES, U128_BYTES):
+                    val = self.try_take(n_bytes)
+                    return de_zig_zag_signed(val) if signed else val
+                else:
+                    raise Exception("byte count not supported")
+
+            def deserialize_fixint(self, n_bytes, signed, little_endian):
+                val = int.from_bytes(bytes(self.pop_n(n_bytes)), "little" if little_endian else "big")
+                return val - (1 << (8 * n_bytes)) if signed and val >> (8 * n_bytes - 1) else val
+
+            def deserialize_number_float(self, n_bytes):
+                if n_bytes == U32_BYTES:
+                    return struct.unpack("<f", self.pop_n(n_bytes))[0]
+                elif n_bytes == U64_BYTES:
+                    return struct.unpack("<d", self.pop_n(n_bytes))[0]
+                else:
+                    raise Exception("byte count not supported")
+
+            def deserialize_string(self):
+                str_len = self.try_take(U32_BYTES)
+                return self.pop_n(str_len).decode("utf-8")
+
+            def deserialize_array(self, des, length = None):
+                return [des(self) for _ in range(self.try_take(U32_BYTES) if length is None else length)]
+
+            def deserialize_byte_array(self, length = None):
+                return list(self.pop_n(self.try_take(U32_BYTES) if length is None else length))
+
+            def deserialize_map(self, des):
+                out = {}
+                for _ in range(self.try_take(U32_BYTES)):
+                    key, value = des(self)
+                    out[key] = value
+                return out
+
+            def release_bytes(self):
+                return self.bytes[self.index:]
+    }
+}
+
+fn gen_deserialize_func(containers: impl Iterator<Item = Container>) -> Tokens {
+    let des_switch = containers
+        .map(|container| {
+            let fully_qualified = ContainerFullQualifiedTypeBuilder::from(&container).build();
+            let container_ident = ContainerIdentifierBuilder::from(&container).build();
+            (
+                Some(quote!(obj_type is $fully_qualified)),
+                quote!(result_value = deserialize_$container_ident(d)),
+            )
+        })
+        .chain([(
+            None,
+            quote!(raise TypeError("{} not deserializable".format(obj_type))),
+        )])
+        .join_if_branched();
+
+    let des_func = Function::new_untyped(
+        "deserialize",
+        function_args!("obj_type", "bytes"),
+        quote! {
+            d = Deserializer(bytes)
+
+            $des_switch
+
+            return (result_value, d.release_bytes())
+        },
+    )
+    .with_doc_string(
+        "Deserialize a value from an array of bytes.
+
+Args:
+    obj_type: The type of the value to deserialize.
+    bytes: The byte array to deserialize from.
+
+Returns:
+    The deserialized value and the remaining bytes.
+
+",
+    );
+
+    quote!($des_func)
+}
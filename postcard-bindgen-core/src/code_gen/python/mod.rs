@@ -1,23 +1,33 @@
+mod cobs;
+mod crc;
 mod des;
+mod fingerprint;
 mod general;
 mod generateable;
+mod micropython;
 mod ser;
 mod type_checks;
 
 use core::borrow::Borrow;
 
+use cobs::gen_cobs_code;
+use crc::gen_crc_code;
 use des::{gen_des_functions, gen_deserialize_func, gen_deserializer_code};
+use fingerprint::gen_fingerprint_code;
 use genco::{lang::python::Python, quote, quote_in, tokens::FormatInto};
 use general::gen_util;
 use generateable::{gen_basic_typings, gen_typings};
+use micropython::gen_micropython_module;
 use ser::{gen_ser_functions, gen_serialize_func, gen_serializer_code};
 use type_checks::gen_type_checks;
 
 use crate::{
-    code_gen::import_registry::ImportMode, path::PathBuf, registry::ContainerCollection, Exports,
+    code_gen::import_registry::ImportMode, path::PathBuf, registry::ContainerCollection,
+    validation::ValidationSettings, Exports,
 };
 
 use super::{
+    crc::CRC_32_ALGORITHMS,
     import_registry::{ImportItem, Package},
     utils::{snake_case, IfBranchedTemplate, TokensBranchedIterExt, TokensIterExt},
 };
@@ -52,6 +62,9 @@ pub struct GenerationSettings {
     des: bool,
     runtime_type_checks: bool,
     module_structure: bool,
+    micropython: bool,
+    cobs: bool,
+    crc32: bool,
 }
 
 impl GenerationSettings {
@@ -62,6 +75,9 @@ impl GenerationSettings {
             des: true,
             runtime_type_checks: true,
             module_structure: true,
+            micropython: false,
+            cobs: false,
+            crc32: false,
         }
     }
 
@@ -97,6 +113,45 @@ impl GenerationSettings {
         self.module_structure = enabled;
         self
     }
+
+    /// Enabling or disabling of MicroPython compatible code generation.
+    ///
+    /// Enabling this will generate a single flat module (`__init__`) instead of a package
+    /// with subpackages. The types are plain classes instead of dataclasses and tuple
+    /// subclasses, nothing is imported from `typing` and the serializer and deserializer
+    /// only use builtins available in MicroPython, like `struct` and `bytearray`.
+    ///
+    /// As all types are placed in the same module, the module structure is ignored.
+    pub fn micropython(mut self, enabled: bool) -> Self {
+        self.micropython = enabled;
+        self
+    }
+
+    /// Enabling or disabling of COBS framing code generation.
+    ///
+    /// Enabling this will generate `serialize_cobs` and `deserialize_cobs`, which encode and
+    /// decode frames like `postcard::to_slice_cobs` and `postcard::take_from_bytes_cobs`, and
+    /// a `CobsAccumulator`, which is fed with chunks of a stream and yields the decoded values
+    /// like `postcard::accumulator::CobsAccumulator`.
+    ///
+    /// This is not enabled by [`GenerationSettings::enable_all()`].
+    pub fn cobs(mut self, enabled: bool) -> Self {
+        self.cobs = enabled;
+        self
+    }
+
+    /// Enabling or disabling of CRC checked framing code generation.
+    ///
+    /// Enabling this will generate `serialize_crc32` and `deserialize_crc32`, which append
+    /// and check a CRC like `postcard::to_slice_crc32` and `postcard::take_from_bytes_crc32`.
+    /// The algorithm is passed to both functions, the 32 bit algorithms of the `crc` crate
+    /// catalog are generated with the same names (e.g. `CRC_32_ISCSI`).
+    ///
+    /// This is not enabled by [`GenerationSettings::enable_all()`].
+    pub fn crc32(mut self, enabled: bool) -> Self {
+        self.crc32 = enabled;
+        self
+    }
 }
 
 impl Default for GenerationSettings {
@@ -106,10 +161,26 @@ impl Default for GenerationSettings {
             des: true,
             runtime_type_checks: false,
             module_structure: true,
+            micropython: false,
+            cobs: false,
+            crc32: false,
         }
     }
 }
 
+impl From<&GenerationSettings> for ValidationSettings {
+    fn from(settings: &GenerationSettings) -> Self {
+        ValidationSettings::default()
+            .flattened(!settings.module_structure || settings.micropython)
+            .variant_classes(variant_class_name)
+    }
+}
+
+/// Name of the class generated for an enum variant next to the class of the enum.
+pub(crate) fn variant_class_name(enum_name: &str, variant_name: &str) -> String {
+    format!("{enum_name}_{variant_name}")
+}
+
 pub fn generate(
     mut containers: ContainerCollection,
     gen_settings: impl Borrow<GenerationSettings>,
@@ -118,10 +189,19 @@ pub fn generate(
     let generate_package_name = snake_case(&generate_package_name);
     let gen_settings = gen_settings.borrow();
 
-    if !gen_settings.module_structure {
+    if !gen_settings.module_structure || gen_settings.micropython {
         containers.flatten();
     }
 
+    if gen_settings.micropython {
+        return Exports {
+            files: vec![ExportFile {
+                content_type: "__init__".to_owned(),
+                content: gen_micropython_module(&containers, gen_settings),
+            }],
+        };
+    }
+
     let mut files = Vec::new();
 
     files.push(ExportFile {
@@ -134,6 +214,11 @@ pub fn generate(
         content: gen_basic_typings(),
     });
 
+    files.push(ExportFile {
+        content_type: "fingerprint".to_owned(),
+        content: gen_fingerprint_code(&containers),
+    });
+
     files.extend(gen_typings(&containers, generate_package_name.clone()));
 
     if gen_settings.runtime_type_checks {
@@ -200,9 +285,68 @@ pub fn generate(
         });
     }
 
+    let cobs = gen_settings.cobs && (gen_settings.ser || gen_settings.des);
+    if cobs {
+        let mut import_registry = ImportRegistry::new("".to_owned());
+        if gen_settings.ser {
+            import_registry.push(
+                Package::Relative("ser".into()),
+                ImportItem::Single("serialize".into()),
+            );
+        }
+        if gen_settings.des {
+            import_registry.push(
+                Package::Relative("des".into()),
+                ImportItem::Single("deserialize".into()),
+            );
+        }
+
+        files.push(ExportFile {
+            content_type: "cobs".to_owned(),
+            content: quote! {
+                $import_registry
+
+                $(gen_cobs_code(gen_settings))
+            },
+        });
+    }
+
+    let crc32 = gen_settings.crc32 && (gen_settings.ser || gen_settings.des);
+    if crc32 {
+        let mut import_registry = ImportRegistry::new("".to_owned());
+        import_registry.push(Package::Relative("util".into()), ImportItem::All);
+        if gen_settings.ser {
+            import_registry.push(
+                Package::Relative("ser".into()),
+                ImportItem::Single("serialize".into()),
+            );
+        }
+        if gen_settings.des {
+            import_registry.push(
+                Package::Relative("des".into()),
+                ImportItem::Single("deserialize".into()),
+            );
+        }
+
+        files.push(ExportFile {
+            content_type: "crc".to_owned(),
+            content: quote! {
+                $import_registry
+
+                $(gen_crc_code(gen_settings))
+            },
+        });
+    }
+
     let mut import_registry = ImportRegistry::new(generate_package_name);
     import_registry.push(Package::Relative("types".into()), ImportItem::All);
     import_registry.push(Package::Relative("basic_types".into()), ImportItem::All);
+    for item in ["SCHEMA_FINGERPRINT", "CONTAINER_FINGERPRINTS"] {
+        import_registry.push(
+            Package::Relative("fingerprint".into()),
+            ImportItem::Single(item.into()),
+        );
+    }
 
     if gen_settings.des {
         import_registry.push(
@@ -218,6 +362,39 @@ pub fn generate(
         );
     }
 
+    if cobs {
+        let mut items = Vec::new();
+        if gen_settings.ser {
+            items.push("serialize_cobs");
+        }
+        if gen_settings.des {
+            items.extend(["deserialize_cobs", "CobsAccumulator"]);
+        }
+        for item in items {
+            import_registry.push(
+                Package::Relative("cobs".into()),
+                ImportItem::Single(item.into()),
+            );
+        }
+    }
+
+    if crc32 {
+        let mut items = vec!["Crc32Algorithm"];
+        items.extend(CRC_32_ALGORITHMS.iter().map(|algorithm| algorithm.name));
+        if gen_settings.ser {
+            items.push("serialize_crc32");
+        }
+        if gen_settings.des {
+            items.push("deserialize_crc32");
+        }
+        for item in items {
+            import_registry.push(
+                Package::Relative("crc".into()),
+                ImportItem::Single(item.into()),
+            );
+        }
+    }
+
     files.push(ExportFile {
         content_type: "__init__".to_owned(),
         content: quote!($import_registry),
@@ -402,6 +579,47 @@ impl FormatInto<Python> for Function {
     }
 }
 
+/// Generates a docstring, lines are pushed one by one so the docstring follows the
+/// indentation of the surrounding tokens.
+pub(super) fn gen_doc_string(doc: impl AsRef<str>) -> Tokens {
+    // escaped, so the text can't end the docstring
+    let doc = doc
+        .as_ref()
+        .replace('\\', "\\\\")
+        .replace("\"\"\"", "\\\"\"\"");
+    let doc = match doc.strip_suffix('"') {
+        Some(doc) => format!("{doc}\\\""),
+        None => doc,
+    };
+
+    let lines = doc.lines().collect::<Vec<_>>();
+    let mut tokens = Tokens::new();
+    tokens.append(format!("\"\"\"{}", lines.first().unwrap_or(&"")));
+    for line in lines.iter().skip(1) {
+        if line.is_empty() {
+            tokens.line();
+        } else {
+            tokens.push();
+            tokens.append(line.to_string());
+        }
+    }
+    // the closing quotes of multi line docstrings go on their own line
+    if lines.len() > 1 {
+        tokens.push();
+    }
+    tokens.append("\"\"\"");
+    tokens
+}
+
+/// Generates the docstring of a class body without other members, or `pass` if there is no
+/// doc comment.
+pub(super) fn gen_doc_string_or_pass(doc: Option<&str>) -> Tokens {
+    match doc {
+        Some(doc) => gen_doc_string(doc),
+        None => quote!(pass),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use genco::tokens::FormatInto;
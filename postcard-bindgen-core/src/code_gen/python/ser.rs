@@ -50,6 +50,10 @@ pub fn gen_serializer_code() -> Tokens {
                 else:
                     raise Exception("byte count not supported")
 
+            def serialize_fixint(self, n_bytes, little_endian, value):
+                value_b = int(value) & ((1 << (8 * n_bytes)) - 1)
+                self.bytes.extend(value_b.to_bytes(n_bytes, "little" if little_endian else "big"))
+
             def serialize_number_float(self, n_bytes, value):
                 if n_bytes == U32_BYTES:
                     b_buffer = struct.pack("<f", value)
@@ -60,8 +64,9 @@ pub fn gen_serializer_code() -> Tokens {
                 self.push_n(b_buffer)
 
             def serialize_string(self, s):
-                self.push_n(varint(U32_BYTES, len(s)))
-                self.push_n([ord(c) for c in s])
+                encoded = s.encode("utf-8")
+                self.push_n(varint(U32_BYTES, len(encoded)))
+                self.push_n(encoded)
 
             def serialize_array(self, ser, array, length):
                 if length is None:
@@ -84,7 +89,7 @@ pub fn gen_ser_functions(bindings: impl Iterator<Item = Container>) -> Tokens {
 
 fn gen_ser_function_for_type(container: Container) -> impl FormatInto<Python> {
     let container_ident =
-        ContainerIdentifierBuilder::new(container.path.clone().into_buf(), container.name).build();
+        ContainerIdentifierBuilder::new(container.path.clone().into_buf(), &container.name).build();
     let body = container.r#type.gen_ser_body((&container).into());
 
     Function::new_untyped(
@@ -150,7 +155,7 @@ pub fn gen_serialize_func(
     tokens
 }
 
-fn gen_ser_case(container: Container, runtime_type_checks: bool) -> (Tokens, Tokens) {
+pub fn gen_ser_case(container: Container, runtime_type_checks: bool) -> (Tokens, Tokens) {
     let case_str = ContainerFullQualifiedTypeBuilder::from(&container).build();
     let container_ident = ContainerIdentifierBuilder::from(&container).build();
 
@@ -0,0 +1,392 @@
+use genco::{quote, quote_in};
+
+use crate::{
+    registry::{BindingType, Container},
+    type_info::ValueType,
+};
+
+use super::{
+    container_name, full_qualified_object_name, identifier,
+    ser::{fixint, is_non_zero, number_suffix},
+    types::{container_fields, swift_type, variant_fields, Field},
+    Tokens,
+};
+
+/// Generates the decoder and the `PostcardDecodable` protocol.
+pub(super) fn gen_decoder_code() -> Tokens {
+    quote! {
+        public protocol PostcardDecodable {
+            init(from decoder: PostcardDecoder) throws
+        }
+
+        extension PostcardDecodable {
+            public static func deserialize(_ bytes: [UInt8]) throws -> (Self, [UInt8]) {
+                let decoder = PostcardDecoder(bytes)
+                let value = try Self(from: decoder)
+                return (value, decoder.remaining())
+            }
+        }
+
+        public final class PostcardDecoder {
+            private let bytes: [UInt8]
+            private var position = 0
+
+            public init(_ bytes: [UInt8]) {
+                self.bytes = bytes
+            }
+
+            public func remaining() -> [UInt8] {
+                Array(bytes[position...])
+            }
+
+            public func readByte() throws -> UInt8 {
+                guard position < bytes.count else {
+                    throw PostcardError.unexpectedEnd
+                }
+                defer { position += 1 }
+                return bytes[position]
+            }
+
+            public func readBytes(_ count: Int) throws -> [UInt8] {
+                guard bytes.count - position >= count else {
+                    throw PostcardError.unexpectedEnd
+                }
+                defer { position += count }
+                return Array(bytes[position ..< position + count])
+            }
+
+            public func readVarint(bits: Int) throws -> UInt64 {
+                let maxBytes = (bits + 6) / 7
+                var value: UInt64 = 0
+                for index in 0 ..< maxBytes {
+                    let byte = try readByte()
+                    if index == maxBytes - 1 && Int(byte) >= (1 << (bits - 7 * index)) {
+                        throw PostcardError.invalidVarint
+                    }
+                    value |= UInt64(byte & 0x7f) << (7 * index)
+                    if (byte & 0x80) == 0 {
+                        return value
+                    }
+                }
+                throw PostcardError.invalidVarint
+            }
+
+            public func readBool() throws -> Bool {
+                switch try readByte() {
+                case 0:
+                    return false
+                case 1:
+                    return true
+                case let byte:
+                    throw PostcardError.invalidBool(byte)
+                }
+            }
+
+            public func readU8() throws -> UInt8 {
+                try readByte()
+            }
+
+            public func readU16() throws -> UInt16 {
+                UInt16(try readVarint(bits: 16))
+            }
+
+            public func readU32() throws -> UInt32 {
+                UInt32(try readVarint(bits: 32))
+            }
+
+            public func readU64() throws -> UInt64 {
+                try readVarint(bits: 64)
+            }
+
+            public func readU128() throws -> PostcardUInt128 {
+                var high: UInt64 = 0
+                var low: UInt64 = 0
+                for index in 0 ..< 19 {
+                    let byte = try readByte()
+                    if index == 18 && byte >= 4 {
+                        throw PostcardError.invalidVarint
+                    }
+                    let bits = UInt64(byte & 0x7f)
+                    // smart shifts, bits shifted out of a half end up in the other one
+                    low |= bits << (7 * index)
+                    high |= bits << (7 * index - 64)
+                    if (byte & 0x80) == 0 {
+                        return PostcardUInt128(high: high, low: low)
+                    }
+                }
+                throw PostcardError.invalidVarint
+            }
+
+            public func readI8() throws -> Int8 {
+                Int8(bitPattern: try readByte())
+            }
+
+            public func readI16() throws -> Int16 {
+                Int16(truncatingIfNeeded: zigZagDecode(try readVarint(bits: 16)))
+            }
+
+            public func readI32() throws -> Int32 {
+                Int32(truncatingIfNeeded: zigZagDecode(try readVarint(bits: 32)))
+            }
+
+            public func readI64() throws -> Int64 {
+                zigZagDecode(try readVarint(bits: 64))
+            }
+
+            public func readI128() throws -> PostcardInt128 {
+                let value = try readU128()
+                let sign: UInt64 = (value.low & 1) == 1 ? UInt64.max : 0
+                let high = (value.high >> 1) ^ sign
+                let low = ((value.low >> 1) | (value.high << 63)) ^ sign
+                return PostcardInt128(high: Int64(bitPattern: high), low: low)
+            }
+
+            public func readFixint<T: FixedWidthInteger>(_ type: T.Type, littleEndian: Bool) throws -> T {
+                let bytes = try readBytes(MemoryLayout<T>.size)
+                var bits: UInt64 = 0
+                for byte in littleEndian ? bytes.reversed() : bytes {
+                    bits = (bits << 8) | UInt64(byte)
+                }
+                return T(truncatingIfNeeded: bits)
+            }
+
+            public func readFixint(_ type: PostcardUInt128.Type, littleEndian: Bool) throws -> PostcardUInt128 {
+                let first = try readFixint(UInt64.self, littleEndian: littleEndian)
+                let second = try readFixint(UInt64.self, littleEndian: littleEndian)
+                return littleEndian ? PostcardUInt128(high: second, low: first) : PostcardUInt128(high: first, low: second)
+            }
+
+            public func readFixint(_ type: PostcardInt128.Type, littleEndian: Bool) throws -> PostcardInt128 {
+                let value = try readFixint(PostcardUInt128.self, littleEndian: littleEndian)
+                return PostcardInt128(high: Int64(bitPattern: value.high), low: value.low)
+            }
+
+            public func readF32() throws -> Float {
+                var bits: UInt32 = 0
+                for (index, byte) in try readBytes(4).enumerated() {
+                    bits |= UInt32(byte) << (8 * index)
+                }
+                return Float(bitPattern: bits)
+            }
+
+            public func readF64() throws -> Double {
+                var bits: UInt64 = 0
+                for (index, byte) in try readBytes(8).enumerated() {
+                    bits |= UInt64(byte) << (8 * index)
+                }
+                return Double(bitPattern: bits)
+            }
+
+            public func readString(maxLength: Int? = nil) throws -> String {
+                let length = try readLength(maxLength: maxLength)
+                var iterator = try readBytes(length).makeIterator()
+                var utf8 = UTF8()
+                var scalars = String.UnicodeScalarView()
+                while true {
+                    switch utf8.decode(&iterator) {
+                    case let .scalarValue(scalar):
+                        scalars.append(scalar)
+                    case .emptyInput:
+                        return String(scalars)
+                    case .error:
+                        throw PostcardError.invalidUtf8
+                    }
+                }
+            }
+
+            public func readList<T>(maxLength: Int? = nil, _ read: () throws -> T) throws -> [T] {
+                let length = try readLength(maxLength: maxLength)
+                var value: [T] = []
+                value.reserveCapacity(min(length, bytes.count - position))
+                for _ in 0 ..< length {
+                    value.append(try read())
+                }
+                return value
+            }
+
+            public func readArray<T>(length: Int, _ read: () throws -> T) throws -> [T] {
+                var value: [T] = []
+                value.reserveCapacity(length)
+                for _ in 0 ..< length {
+                    value.append(try read())
+                }
+                return value
+            }
+
+            public func readMap<Key: Hashable, Value>(maxLength: Int? = nil, readKey: () throws -> Key, readValue: () throws -> Value) throws -> [Key: Value] {
+                let length = try readLength(maxLength: maxLength)
+                var value: [Key: Value] = [:]
+                for _ in 0 ..< length {
+                    let key = try readKey()
+                    value[key] = try readValue()
+                }
+                return value
+            }
+
+            public func readOption<T>(_ read: () throws -> T) throws -> T? {
+                switch try readByte() {
+                case 0:
+                    return nil
+                case 1:
+                    return try read()
+                case let tag:
+                    throw PostcardError.invalidOptionTag(tag)
+                }
+            }
+
+            public func readRange<T>(_ read: () throws -> T) throws -> PostcardRange<T> {
+                let start = try read()
+                let end = try read()
+                return PostcardRange(start: start, end: end)
+            }
+
+            public func readVariantIndex() throws -> UInt32 {
+                UInt32(try readVarint(bits: 32))
+            }
+
+            private func readLength(maxLength: Int?) throws -> Int {
+                let length = Int(clamping: try readVarint(bits: 64))
+                if let maxLength = maxLength, length > maxLength {
+                    throw PostcardError.lengthExceeded(length: length, maxLength: maxLength)
+                }
+                return length
+            }
+
+            private func zigZagDecode(_ value: UInt64) -> Int64 {
+                Int64(bitPattern: (value >> 1) ^ (0 &- (value & 1)))
+            }
+        }
+    }
+}
+
+/// Generates the `PostcardDecodable` conformances of the containers.
+pub(super) fn gen_des_extensions(
+    module: &str,
+    containers: impl Iterator<Item = Container>,
+) -> Tokens {
+    let mut tokens = Tokens::new();
+    for container in containers {
+        quote_in! { tokens =>
+            extension $(container_name(&container)): PostcardDecodable {
+                public init(from decoder: PostcardDecoder) throws {
+                    $(gen_init(module, &container))
+                }
+            }
+        };
+        tokens.line();
+    }
+    tokens
+}
+
+fn gen_init(module: &str, container: &Container) -> Tokens {
+    match &container.r#type {
+        BindingType::Enum(ty) => {
+            let mut cases = Tokens::new();
+            for variant in ty.variants.iter() {
+                let name = identifier(&variant.name);
+                let fields = variant_fields(&variant.inner_type);
+                quote_in!(cases => case $(variant.index):);
+                cases.indent();
+                if fields.is_empty() {
+                    quote_in!(cases => self = .$name);
+                } else {
+                    quote_in!(cases => self = $(gen_call(&format!(".{name}"), module, &fields)));
+                }
+                cases.unindent();
+                cases.push();
+            }
+            quote! {
+                switch try decoder.readVariantIndex() {
+                $cases
+                case let index:
+                    throw PostcardError.unknownVariant(index)
+                }
+            }
+        }
+        ty => gen_call("self.init", module, &container_fields(ty)),
+    }
+}
+
+fn gen_call(callee: &str, module: &str, fields: &[Field]) -> Tokens {
+    let arguments = fields
+        .iter()
+        .map(|field| {
+            let value = gen_des_value(module, field.value_type);
+            if field.labeled {
+                quote!($(&field.name): $value)
+            } else {
+                value
+            }
+        })
+        .collect::<Vec<_>>();
+    if arguments.len() > 1 {
+        quote! {
+            $callee(
+                $(for argument in arguments join (,$['\r']) => $argument)
+            )
+        }
+    } else {
+        quote!($callee($(for argument in arguments => $argument)))
+    }
+}
+
+/// Expression decoding a value with the decoder `decoder`.
+fn gen_des_value(module: &str, value_type: &ValueType) -> Tokens {
+    match value_type {
+        ValueType::Bool(_) => quote!(try decoder.readBool()),
+        ValueType::Number(meta) => {
+            let read = match fixint(meta) {
+                Some(little_endian) => quote! {
+                    decoder.readFixint($(swift_type(module, value_type)).self, littleEndian: $(little_endian.to_string()))
+                },
+                None => quote!(decoder.$(format!("read{}", number_suffix(meta)))()),
+            };
+            if is_non_zero(meta) {
+                quote!(try checkNonZero($read))
+            } else {
+                quote!(try $read)
+            }
+        }
+        ValueType::String(meta) => match meta.max_length {
+            Some(max_length) => quote!(try decoder.readString(maxLength: $max_length)),
+            None => quote!(try decoder.readString()),
+        },
+        ValueType::Array(meta) => {
+            let inner = gen_des_value(module, &meta.items_type);
+            match (meta.length, meta.max_length) {
+                (Some(length), _) => quote!(try decoder.readArray(length: $length) { $inner }),
+                (None, Some(max_length)) => {
+                    quote!(try decoder.readList(maxLength: $max_length) { $inner })
+                }
+                (None, None) => quote!(try decoder.readList { $inner }),
+            }
+        }
+        ValueType::Map(meta) => {
+            let key = gen_des_value(module, &meta.key_type);
+            let value = gen_des_value(module, &meta.value_type);
+            match meta.max_length {
+                Some(max_length) => quote! {
+                    try decoder.readMap(maxLength: $max_length, readKey: { $key }, readValue: { $value })
+                },
+                None => quote!(try decoder.readMap(readKey: { $key }, readValue: { $value })),
+            }
+        }
+        ValueType::Optional(meta) => {
+            quote!(try decoder.readOption { $(gen_des_value(module, &meta.inner)) })
+        }
+        // a tuple with a single item is represented by the item itself
+        ValueType::Tuple(meta) if meta.items_types.len() == 1 => {
+            gen_des_value(module, &meta.items_types[0])
+        }
+        ValueType::Tuple(meta) => {
+            let items = meta.items_types.iter().map(|ty| gen_des_value(module, ty));
+            quote!(($(for item in items join (, ) => $item)))
+        }
+        ValueType::Range(meta) => {
+            quote!(try decoder.readRange { $(gen_des_value(module, &meta.bounds_type)) })
+        }
+        ValueType::Object(meta) => {
+            quote!(try $(full_qualified_object_name(module, meta))(from: decoder))
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use genco::quote;
+
+use super::Tokens;
+
+/// Generates the types shared by the encoder, the decoder and the generated types.
+pub(super) fn gen_runtime() -> Tokens {
+    quote! {
+        public enum PostcardError: Error, Equatable {
+            case unexpectedEnd
+            case invalidVarint
+            case invalidOptionTag(UInt8)
+            case invalidBool(UInt8)
+            case invalidUtf8
+            case unknownVariant(UInt32)
+            case lengthExceeded(length: Int, maxLength: Int)
+            case lengthMismatch(length: Int, expected: Int)
+            case zeroValue
+        }
+
+        public struct PostcardUInt128: Hashable {
+            public var high: UInt64
+            public var low: UInt64
+
+            public init(high: UInt64, low: UInt64) {
+                self.high = high
+                self.low = low
+            }
+
+            public init(_ value: UInt64) {
+                self.init(high: 0, low: value)
+            }
+
+            public var isZero: Bool {
+                high == 0 && low == 0
+            }
+        }
+
+        public struct PostcardInt128: Hashable {
+            public var high: Int64
+            public var low: UInt64
+
+            public init(high: Int64, low: UInt64) {
+                self.high = high
+                self.low = low
+            }
+
+            public init(_ value: Int64) {
+                self.init(high: value < 0 ? -1 : 0, low: UInt64(bitPattern: value))
+            }
+
+            public var isZero: Bool {
+                high == 0 && low == 0
+            }
+        }
+
+        public struct PostcardRange<Bound> {
+            public var start: Bound
+            public var end: Bound
+
+            public init(start: Bound, end: Bound) {
+                self.start = start
+                self.end = end
+            }
+        }
+
+        extension PostcardRange: Equatable where Bound: Equatable {}
+
+        extension PostcardRange: Hashable where Bound: Hashable {}
+
+        public func checkNonZero<T: BinaryInteger>(_ value: T) throws -> T {
+            if value == 0 {
+                throw PostcardError.zeroValue
+            }
+            return value
+        }
+
+        public func checkNonZero(_ value: PostcardUInt128) throws -> PostcardUInt128 {
+            if value.isZero {
+                throw PostcardError.zeroValue
+            }
+            return value
+        }
+
+        public func checkNonZero(_ value: PostcardInt128) throws -> PostcardInt128 {
+            if value.isZero {
+                throw PostcardError.zeroValue
+            }
+            return value
+        }
+    }
+}
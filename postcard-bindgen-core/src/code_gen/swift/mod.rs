@@ -0,0 +1,240 @@
+mod des;
+mod general;
+mod ser;
+mod types;
+
+use core::borrow::Borrow;
+
+use des::{gen_decoder_code, gen_des_extensions};
+use genco::lang::swift::Swift;
+use general::gen_runtime;
+use ser::{gen_encoder_code, gen_ser_extensions};
+use types::gen_type_definitions;
+
+use crate::{
+    path::{Path, PathBuf},
+    registry::{Container, ContainerCollection},
+    type_info::ObjectMeta,
+    validation::ValidationSettings,
+    ExportFile, Exports,
+};
+
+type Tokens = genco::lang::swift::Tokens;
+
+/// Settings for Swift bindings generation.
+///
+/// Structs are generated as structs, enums as enums with associated values. Serialization
+/// and deserialization are added as conformances to `PostcardEncodable` and
+/// `PostcardDecodable`.
+///
+/// By default, only deserialization is enabled. Serialization can be enabled by using [`GenerationSettings::serialization()`].
+/// Deserialization can be disabled with [`GenerationSettings::deserialization()`].
+/// To enable all at once use [`GenerationSettings::enable_all()`].
+#[derive(Debug)]
+pub struct GenerationSettings {
+    ser: bool,
+    des: bool,
+    module_structure: bool,
+}
+
+impl GenerationSettings {
+    /// Constructs [`GenerationSettings`] and enables all options at once.
+    pub fn enable_all() -> Self {
+        Self {
+            ser: true,
+            des: true,
+            module_structure: true,
+        }
+    }
+
+    /// Enabling or disabling of serialization code generation.
+    pub fn serialization(mut self, enabled: bool) -> Self {
+        self.ser = enabled;
+        self
+    }
+
+    /// Enabling or disabling of deserialization code generation.
+    pub fn deserialization(mut self, enabled: bool) -> Self {
+        self.des = enabled;
+        self
+    }
+
+    /// Enabling or disabling of module structure code generation.
+    ///
+    /// Enabling this will generate the types in the same module structure
+    /// as in rust. Root level types will be at the top level of the Swift module.
+    /// Types nested in modules will be nested in namespace enums
+    /// (e.g. <mod_name>.<type_name>). This avoids name clashes.
+    ///
+    /// Disabling this will generate all types at the top level.
+    pub fn module_structure(mut self, enabled: bool) -> Self {
+        self.module_structure = enabled;
+        self
+    }
+}
+
+impl Default for GenerationSettings {
+    fn default() -> Self {
+        Self {
+            ser: false,
+            des: true,
+            module_structure: true,
+        }
+    }
+}
+
+impl From<&GenerationSettings> for ValidationSettings {
+    fn from(settings: &GenerationSettings) -> Self {
+        ValidationSettings::default().flattened(!settings.module_structure)
+    }
+}
+
+/// Generates the Swift code for the containers.
+///
+/// `package_name` has to be the name of the Swift module the code is compiled in, as it
+/// qualifies the references between the generated types.
+pub fn generate(
+    mut containers: ContainerCollection,
+    gen_settings: impl Borrow<GenerationSettings>,
+    package_name: String,
+) -> Exports<Swift> {
+    let gen_settings = gen_settings.borrow();
+    let module = package_name;
+
+    if !gen_settings.module_structure {
+        containers.flatten();
+    }
+
+    let mut files = vec![
+        ExportFile {
+            content_type: "Runtime".to_owned(),
+            content: gen_runtime(),
+        },
+        ExportFile {
+            content_type: "Types".to_owned(),
+            content: gen_type_definitions(&module, containers.all_containers()),
+        },
+    ];
+
+    if gen_settings.ser {
+        files.push(ExportFile {
+            content_type: "Encoder".to_owned(),
+            content: gen_encoder_code(),
+        });
+        files.push(ExportFile {
+            content_type: "Ser".to_owned(),
+            content: gen_ser_extensions(containers.all_containers()),
+        });
+    }
+
+    if gen_settings.des {
+        files.push(ExportFile {
+            content_type: "Decoder".to_owned(),
+            content: gen_decoder_code(),
+        });
+        files.push(ExportFile {
+            content_type: "Des".to_owned(),
+            content: gen_des_extensions(&module, containers.all_containers()),
+        });
+    }
+
+    Exports { files }
+}
+
+/// Module path of the container without the crate name.
+fn module_parts(path: &Path<'_, '_>) -> Vec<String> {
+    let mut path: PathBuf = path.clone().into_buf();
+    // We will skip the first part of the path, as it is the crate name.
+    path.pop_front();
+    path.parts().map(|part| identifier(part.as_ref())).collect()
+}
+
+/// Name of the container relative to the Swift module.
+fn container_name(container: &Container) -> String {
+    relative_name(&container.path, &container.name)
+}
+
+/// Name of the referenced type relative to the Swift module.
+fn object_name(meta: &ObjectMeta) -> String {
+    relative_name(&meta.path, &meta.name)
+}
+
+/// Name of the referenced type qualified with the Swift module, usable from any namespace.
+fn full_qualified_object_name(module: &str, meta: &ObjectMeta) -> String {
+    format!("{module}.{}", object_name(meta))
+}
+
+fn relative_name(path: &Path<'_, '_>, name: &str) -> String {
+    module_parts(path)
+        .into_iter()
+        .chain([identifier(name)])
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+const KEYWORDS: &[&str] = &[
+    "Any",
+    "Self",
+    "as",
+    "associatedtype",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "continue",
+    "default",
+    "defer",
+    "deinit",
+    "do",
+    "else",
+    "enum",
+    "extension",
+    "fallthrough",
+    "false",
+    "fileprivate",
+    "for",
+    "func",
+    "guard",
+    "if",
+    "import",
+    "in",
+    "init",
+    "inout",
+    "internal",
+    "is",
+    "let",
+    "nil",
+    "open",
+    "operator",
+    "precedencegroup",
+    "private",
+    "protocol",
+    "public",
+    "repeat",
+    "rethrows",
+    "return",
+    "self",
+    "static",
+    "struct",
+    "subscript",
+    "super",
+    "switch",
+    "throw",
+    "throws",
+    "true",
+    "try",
+    "typealias",
+    "var",
+    "where",
+    "while",
+];
+
+/// Escapes names which are Swift keywords.
+fn identifier(name: &str) -> String {
+    if KEYWORDS.contains(&name) {
+        format!("`{name}`")
+    } else {
+        name.to_owned()
+    }
+}
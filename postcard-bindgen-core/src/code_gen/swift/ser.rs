@@ -0,0 +1,371 @@
+use genco::{quote, quote_in};
+
+use crate::{
+    registry::{BindingType, Container},
+    type_info::{IntegerEncoding, NumberMeta, ValueType},
+};
+
+use super::{
+    container_name, identifier,
+    types::{container_fields, variant_fields},
+    Tokens,
+};
+
+/// Generates the encoder and the `PostcardEncodable` protocol.
+pub(super) fn gen_encoder_code() -> Tokens {
+    quote! {
+        public protocol PostcardEncodable {
+            func encode(to encoder: PostcardEncoder) throws
+        }
+
+        extension PostcardEncodable {
+            public func serialize() throws -> [UInt8] {
+                let encoder = PostcardEncoder()
+                try encode(to: encoder)
+                return encoder.bytes
+            }
+        }
+
+        public final class PostcardEncoder {
+            public private(set) var bytes: [UInt8] = []
+
+            public init() {}
+
+            public func writeByte(_ value: UInt8) {
+                bytes.append(value)
+            }
+
+            public func writeVarint(_ value: UInt64) {
+                var value = value
+                while value >= 0x80 {
+                    writeByte(UInt8(truncatingIfNeeded: value) | 0x80)
+                    value >>= 7
+                }
+                writeByte(UInt8(value))
+            }
+
+            public func writeBool(_ value: Bool) {
+                writeByte(value ? 1 : 0)
+            }
+
+            public func writeU8(_ value: UInt8) {
+                writeByte(value)
+            }
+
+            public func writeU16(_ value: UInt16) {
+                writeVarint(UInt64(value))
+            }
+
+            public func writeU32(_ value: UInt32) {
+                writeVarint(UInt64(value))
+            }
+
+            public func writeU64(_ value: UInt64) {
+                writeVarint(value)
+            }
+
+            public func writeU128(_ value: PostcardUInt128) {
+                var high = value.high
+                var low = value.low
+                while high != 0 || low >= 0x80 {
+                    writeByte(UInt8(truncatingIfNeeded: low) | 0x80)
+                    low = (low >> 7) | (high << 57)
+                    high >>= 7
+                }
+                writeByte(UInt8(low))
+            }
+
+            public func writeI8(_ value: Int8) {
+                writeByte(UInt8(bitPattern: value))
+            }
+
+            public func writeI16(_ value: Int16) {
+                writeI64(Int64(value))
+            }
+
+            public func writeI32(_ value: Int32) {
+                writeI64(Int64(value))
+            }
+
+            public func writeI64(_ value: Int64) {
+                writeVarint(UInt64(bitPattern: (value << 1) ^ (value >> 63)))
+            }
+
+            public func writeI128(_ value: PostcardInt128) {
+                let sign: UInt64 = value.high < 0 ? UInt64.max : 0
+                let high = ((UInt64(bitPattern: value.high) << 1) | (value.low >> 63)) ^ sign
+                let low = (value.low << 1) ^ sign
+                writeU128(PostcardUInt128(high: high, low: low))
+            }
+
+            public func writeFixint<T: FixedWidthInteger>(_ value: T, littleEndian: Bool) {
+                let count = MemoryLayout<T>.size
+                let bits = UInt64(truncatingIfNeeded: value)
+                for index in 0 ..< count {
+                    let shift = littleEndian ? index : count - 1 - index
+                    writeByte(UInt8(truncatingIfNeeded: bits >> (8 * shift)))
+                }
+            }
+
+            public func writeFixint(_ value: PostcardUInt128, littleEndian: Bool) {
+                for half in littleEndian ? [value.low, value.high] : [value.high, value.low] {
+                    writeFixint(half, littleEndian: littleEndian)
+                }
+            }
+
+            public func writeFixint(_ value: PostcardInt128, littleEndian: Bool) {
+                writeFixint(PostcardUInt128(high: UInt64(bitPattern: value.high), low: value.low), littleEndian: littleEndian)
+            }
+
+            public func writeF32(_ value: Float) {
+                var bits = value.bitPattern
+                for _ in 0 ..< 4 {
+                    writeByte(UInt8(truncatingIfNeeded: bits))
+                    bits >>= 8
+                }
+            }
+
+            public func writeF64(_ value: Double) {
+                var bits = value.bitPattern
+                for _ in 0 ..< 8 {
+                    writeByte(UInt8(truncatingIfNeeded: bits))
+                    bits >>= 8
+                }
+            }
+
+            public func writeString(_ value: String, maxLength: Int? = nil) throws {
+                let utf8 = Array(value.utf8)
+                try writeLength(utf8.count, maxLength: maxLength)
+                bytes.append(contentsOf: utf8)
+            }
+
+            public func writeList<T>(_ value: [T], maxLength: Int? = nil, _ write: (T) throws -> Void) throws {
+                try writeLength(value.count, maxLength: maxLength)
+                for item in value {
+                    try write(item)
+                }
+            }
+
+            public func writeArray<T>(_ value: [T], length: Int, _ write: (T) throws -> Void) throws {
+                guard value.count == length else {
+                    throw PostcardError.lengthMismatch(length: value.count, expected: length)
+                }
+                for item in value {
+                    try write(item)
+                }
+            }
+
+            public func writeMap<Key, Value>(_ value: [Key: Value], maxLength: Int? = nil, _ write: (Key, Value) throws -> Void) throws {
+                try writeLength(value.count, maxLength: maxLength)
+                for (key, item) in value {
+                    try write(key, item)
+                }
+            }
+
+            public func writeOption<T>(_ value: T?, _ write: (T) throws -> Void) throws {
+                if let value = value {
+                    writeByte(1)
+                    try write(value)
+                } else {
+                    writeByte(0)
+                }
+            }
+
+            public func writeRange<T>(_ value: PostcardRange<T>, _ write: (T) throws -> Void) throws {
+                try write(value.start)
+                try write(value.end)
+            }
+
+            public func writeVariantIndex(_ index: UInt32) {
+                writeVarint(UInt64(index))
+            }
+
+            private func writeLength(_ length: Int, maxLength: Int?) throws {
+                if let maxLength = maxLength, length > maxLength {
+                    throw PostcardError.lengthExceeded(length: length, maxLength: maxLength)
+                }
+                writeVarint(UInt64(length))
+            }
+        }
+    }
+}
+
+/// Generates the `PostcardEncodable` conformances of the containers.
+pub(super) fn gen_ser_extensions(containers: impl Iterator<Item = Container>) -> Tokens {
+    let mut tokens = Tokens::new();
+    for container in containers {
+        quote_in! { tokens =>
+            extension $(container_name(&container)): PostcardEncodable {
+                $(gen_encode(&container))
+            }
+        };
+        tokens.line();
+    }
+    tokens
+}
+
+fn gen_encode(container: &Container) -> Tokens {
+    let body = match &container.r#type {
+        BindingType::Enum(ty) => {
+            let mut cases = Tokens::new();
+            for variant in ty.variants.iter() {
+                let name = identifier(&variant.name);
+                let index = variant.index;
+                let fields = variant_fields(&variant.inner_type);
+                if fields.is_empty() {
+                    quote_in!(cases => case .$name:);
+                } else {
+                    let bindings = fields.iter().map(|field| field.name.as_str());
+                    quote_in!(cases => case let .$name($(for binding in bindings join (, ) => $binding)):);
+                }
+                cases.indent();
+                quote_in!(cases => encoder.writeVariantIndex($index));
+                for field in fields.iter() {
+                    cases.push();
+                    cases.append(gen_ser_value(&field.name, field.value_type, 0));
+                }
+                cases.unindent();
+                cases.push();
+            }
+            quote! {
+                switch self {
+                $cases
+                }
+            }
+        }
+        ty => {
+            let mut statements = Tokens::new();
+            for field in container_fields(ty) {
+                statements.append(gen_ser_value(
+                    &format!("self.{}", field.name),
+                    field.value_type,
+                    0,
+                ));
+                statements.push();
+            }
+            statements
+        }
+    };
+
+    if body.is_empty() {
+        quote!(public func encode(to encoder: PostcardEncoder) throws {})
+    } else {
+        quote! {
+            public func encode(to encoder: PostcardEncoder) throws {
+                $body
+            }
+        }
+    }
+}
+
+/// Statement encoding the value of `expr` with the encoder `encoder`.
+fn gen_ser_value(expr: &str, value_type: &ValueType, depth: usize) -> Tokens {
+    let item = format!("x{depth}");
+    match value_type {
+        ValueType::Bool(_) => quote!(encoder.writeBool($expr)),
+        ValueType::Number(meta) => {
+            let (value, call) = if is_non_zero(meta) {
+                (quote!(checkNonZero($expr)), "try encoder")
+            } else {
+                (quote!($expr), "encoder")
+            };
+            match fixint(meta) {
+                Some(little_endian) => {
+                    quote!($call.writeFixint($value, littleEndian: $(little_endian.to_string())))
+                }
+                None => quote!($call.$(format!("write{}", number_suffix(meta)))($value)),
+            }
+        }
+        ValueType::String(meta) => match meta.max_length {
+            Some(max_length) => quote!(try encoder.writeString($expr, maxLength: $max_length)),
+            None => quote!(try encoder.writeString($expr)),
+        },
+        ValueType::Array(meta) => {
+            let call = match (meta.length, meta.max_length) {
+                (Some(length), _) => quote!(try encoder.writeArray($expr, length: $length)),
+                (None, Some(max_length)) => {
+                    quote!(try encoder.writeList($expr, maxLength: $max_length))
+                }
+                (None, None) => quote!(try encoder.writeList($expr)),
+            };
+            quote! {
+                $call { $(&item) in
+                    $(gen_ser_value(&item, &meta.items_type, depth + 1))
+                }
+            }
+        }
+        ValueType::Map(meta) => {
+            let key = format!("k{depth}");
+            let value = format!("v{depth}");
+            let call = match meta.max_length {
+                Some(max_length) => quote!(try encoder.writeMap($expr, maxLength: $max_length)),
+                None => quote!(try encoder.writeMap($expr)),
+            };
+            quote! {
+                $call { $(&key), $(&value) in
+                    $(gen_ser_value(&key, &meta.key_type, depth + 1))
+                    $(gen_ser_value(&value, &meta.value_type, depth + 1))
+                }
+            }
+        }
+        ValueType::Optional(meta) => quote! {
+            try encoder.writeOption($expr) { $(&item) in
+                $(gen_ser_value(&item, &meta.inner, depth + 1))
+            }
+        },
+        // a tuple with a single item is represented by the item itself
+        ValueType::Tuple(meta) if meta.items_types.len() == 1 => {
+            gen_ser_value(expr, &meta.items_types[0], depth)
+        }
+        ValueType::Tuple(meta) => {
+            let items = meta
+                .items_types
+                .iter()
+                .enumerate()
+                .map(|(index, ty)| gen_ser_value(&format!("{expr}.{index}"), ty, depth));
+            quote!($(for item in items join ($['\r']) => $item))
+        }
+        ValueType::Range(meta) => quote! {
+            try encoder.writeRange($expr) { $(&item) in
+                $(gen_ser_value(&item, &meta.bounds_type, depth + 1))
+            }
+        },
+        ValueType::Object(_) => quote!(try $expr.encode(to: encoder)),
+    }
+}
+
+/// Suffix of the encoder and decoder methods of the number type.
+pub(super) fn number_suffix(meta: &NumberMeta) -> String {
+    match meta {
+        NumberMeta::Integer { bytes, signed, .. } => {
+            format!("{}{}", if *signed { "I" } else { "U" }, bytes * 8)
+        }
+        NumberMeta::FloatingPoint { bytes } => format!("F{}", bytes * 8),
+    }
+}
+
+/// Byte order (`true` for little endian) of an integer type which is encoded with a fixed
+/// width.
+pub(super) fn fixint(meta: &NumberMeta) -> Option<bool> {
+    match meta {
+        NumberMeta::Integer {
+            encoding: IntegerEncoding::FixedLe,
+            ..
+        } => Some(true),
+        NumberMeta::Integer {
+            encoding: IntegerEncoding::FixedBe,
+            ..
+        } => Some(false),
+        _ => None,
+    }
+}
+
+pub(super) fn is_non_zero(meta: &NumberMeta) -> bool {
+    matches!(
+        meta,
+        NumberMeta::Integer {
+            zero_able: false,
+            ..
+        }
+    )
+}
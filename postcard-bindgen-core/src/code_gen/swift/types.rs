@@ -0,0 +1,292 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use genco::{quote, quote_in};
+
+use crate::{
+    registry::{BindingType, Container, EnumVariantType, StructField},
+    type_info::{NumberMeta, ValueType},
+};
+
+use super::{
+    container_name, full_qualified_object_name, identifier, module_parts, object_name, Tokens,
+};
+
+/// Generates the type definitions of all containers.
+///
+/// Rust modules are represented by caseless enums, the types of a module are declared in an
+/// extension of its enum.
+pub(super) fn gen_type_definitions(
+    module: &str,
+    containers: impl Iterator<Item = Container>,
+) -> Tokens {
+    let containers = containers.collect::<Vec<_>>();
+    let non_hashable = non_hashable_containers(&containers);
+
+    let mut modules = BTreeMap::<Vec<String>, Vec<&Container>>::new();
+    for container in containers.iter() {
+        modules
+            .entry(module_parts(&container.path))
+            .or_default()
+            .push(container);
+    }
+
+    let mut namespaces = BTreeSet::new();
+    for parts in modules.keys() {
+        for len in 1..=parts.len() {
+            namespaces.insert(&parts[..len]);
+        }
+    }
+
+    let mut tokens = Tokens::new();
+    for parts in namespaces {
+        let (name, parents) = parts.split_last().unwrap();
+        if parents.is_empty() {
+            quote_in!(tokens => public enum $name {});
+        } else {
+            quote_in! { tokens =>
+                extension $(parents.join(".")) {
+                    public enum $name {}
+                }
+            };
+        }
+        tokens.line();
+    }
+
+    for (parts, containers) in modules {
+        let mut definitions = Tokens::new();
+        for container in containers {
+            definitions.append(gen_definition(module, container, &non_hashable));
+            definitions.line();
+        }
+
+        if parts.is_empty() {
+            tokens.append(definitions);
+        } else {
+            quote_in! { tokens =>
+                extension $(parts.join(".")) {
+                    $definitions
+                }
+            };
+        }
+        tokens.line();
+    }
+
+    tokens
+}
+
+fn gen_definition(module: &str, container: &Container, non_hashable: &BTreeSet<String>) -> Tokens {
+    let name = identifier(&container.name);
+    let conformance = (!non_hashable.contains(&container_name(container))).then_some(": Hashable");
+
+    match &container.r#type {
+        BindingType::Enum(ty) => {
+            let mut cases = Tokens::new();
+            for variant in ty.variants.iter() {
+                let fields = variant_fields(&variant.inner_type);
+                let variant_name = identifier(&variant.name);
+                if fields.is_empty() {
+                    quote_in!(cases => case $variant_name);
+                } else {
+                    let fields = fields.iter().map(|field| {
+                        let ty = swift_type(module, field.value_type);
+                        if field.labeled {
+                            quote!($(&field.name): $ty)
+                        } else {
+                            ty
+                        }
+                    });
+                    quote_in!(cases => case $variant_name($(for field in fields join (, ) => $field)));
+                }
+                cases.push();
+            }
+
+            // enums holding themselves need to be boxed
+            let recursive = container
+                .r#type
+                .referenced_objects()
+                .iter()
+                .any(|object| object.name == container.name && object.path == container.path);
+            let keyword = if recursive { "indirect enum" } else { "enum" };
+
+            quote! {
+                public $keyword $name$conformance {
+                    $cases
+                }
+            }
+        }
+        ty => {
+            let fields = container_fields(ty);
+
+            let mut properties = Tokens::new();
+            let mut parameters = Vec::new();
+            let mut assignments = Tokens::new();
+            for field in fields.iter() {
+                let ty = swift_type(module, field.value_type);
+                quote_in!(properties => public var $(&field.name): $(&ty));
+                properties.push();
+                parameters.push(if field.labeled {
+                    quote!($(&field.name): $ty)
+                } else {
+                    quote!(_ $(&field.name): $ty)
+                });
+                quote_in!(assignments => self.$(&field.name) = $(&field.name));
+                assignments.push();
+            }
+
+            if fields.is_empty() {
+                quote! {
+                    public struct $name$conformance {
+                        public init() {}
+                    }
+                }
+            } else {
+                quote! {
+                    public struct $name$conformance {
+                        $properties
+
+                        public init(
+                            $(for parameter in parameters join (,$['\r']) => $parameter)
+                        ) {
+                            $assignments
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Field of a generated type with the property name and the type.
+///
+/// Fields of tuple structs and tuple variants are passed without argument labels.
+pub(super) struct Field<'a> {
+    pub(super) name: String,
+    pub(super) labeled: bool,
+    pub(super) value_type: &'a ValueType,
+}
+
+/// Fields of a struct type.
+pub(super) fn container_fields(ty: &BindingType) -> Vec<Field<'_>> {
+    match ty {
+        BindingType::Struct(ty) => named_fields(&ty.fields),
+        BindingType::TupleStruct(ty) => unnamed_fields(&ty.fields),
+        BindingType::UnitStruct(_) => Vec::new(),
+        BindingType::Enum(_) => unreachable!("enums have no fields"),
+    }
+}
+
+/// Associated values of an enum variant.
+pub(super) fn variant_fields(ty: &EnumVariantType) -> Vec<Field<'_>> {
+    match ty {
+        EnumVariantType::Empty => Vec::new(),
+        EnumVariantType::Tuple(fields) => unnamed_fields(fields),
+        EnumVariantType::NewType(fields) => named_fields(fields),
+    }
+}
+
+fn named_fields(fields: &[StructField]) -> Vec<Field<'_>> {
+    fields
+        .iter()
+        .map(|field| Field {
+            name: identifier(&field.name),
+            labeled: true,
+            value_type: &field.v_type,
+        })
+        .collect()
+}
+
+fn unnamed_fields(fields: &[ValueType]) -> Vec<Field<'_>> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, value_type)| Field {
+            name: format!("_{index}"),
+            labeled: false,
+            value_type,
+        })
+        .collect()
+}
+
+/// Names of the containers which can't conform to `Hashable`.
+///
+/// Swift tuples can't conform to protocols, so every container holding a tuple, directly or
+/// through other containers, is excluded.
+fn non_hashable_containers(containers: &[Container]) -> BTreeSet<String> {
+    let mut non_hashable = BTreeSet::new();
+    loop {
+        let mut changed = false;
+        for container in containers {
+            let name = container_name(container);
+            if !non_hashable.contains(&name)
+                && !container
+                    .r#type
+                    .field_types()
+                    .into_iter()
+                    .all(|ty| is_hashable(ty, &non_hashable))
+            {
+                non_hashable.insert(name);
+                changed = true;
+            }
+        }
+        if !changed {
+            return non_hashable;
+        }
+    }
+}
+
+fn is_hashable(value_type: &ValueType, non_hashable: &BTreeSet<String>) -> bool {
+    match value_type {
+        ValueType::Tuple(meta) if meta.items_types.len() == 1 => {
+            is_hashable(&meta.items_types[0], non_hashable)
+        }
+        ValueType::Tuple(_) => false,
+        ValueType::Array(meta) => is_hashable(&meta.items_type, non_hashable),
+        ValueType::Map(meta) => is_hashable(&meta.value_type, non_hashable),
+        ValueType::Optional(meta) => is_hashable(&meta.inner, non_hashable),
+        ValueType::Range(meta) => is_hashable(&meta.bounds_type, non_hashable),
+        ValueType::Object(meta) => !non_hashable.contains(&object_name(meta)),
+        ValueType::Bool(_) | ValueType::Number(_) | ValueType::String(_) => true,
+    }
+}
+
+/// Swift type a [ValueType] is represented with.
+pub(super) fn swift_type(module: &str, value_type: &ValueType) -> Tokens {
+    match value_type {
+        ValueType::Bool(_) => quote!(Bool),
+        ValueType::Number(NumberMeta::FloatingPoint { bytes }) => match bytes {
+            4 => quote!(Float),
+            8 => quote!(Double),
+            _ => unreachable!("unsupported floating point size {bytes}"),
+        },
+        ValueType::Number(NumberMeta::Integer { bytes, signed, .. }) => match (bytes, signed) {
+            (1, false) => quote!(UInt8),
+            (2, false) => quote!(UInt16),
+            (4, false) => quote!(UInt32),
+            (8, false) => quote!(UInt64),
+            (16, false) => quote!(PostcardUInt128),
+            (1, true) => quote!(Int8),
+            (2, true) => quote!(Int16),
+            (4, true) => quote!(Int32),
+            (8, true) => quote!(Int64),
+            (16, true) => quote!(PostcardInt128),
+            _ => unreachable!("unsupported integer size {bytes}"),
+        },
+        ValueType::String(_) => quote!(String),
+        ValueType::Array(meta) => quote!([$(swift_type(module, &meta.items_type))]),
+        ValueType::Map(meta) => quote! {
+            [$(swift_type(module, &meta.key_type)): $(swift_type(module, &meta.value_type))]
+        },
+        ValueType::Optional(meta) => quote!($(swift_type(module, &meta.inner))?),
+        ValueType::Tuple(meta) if meta.items_types.len() == 1 => {
+            swift_type(module, &meta.items_types[0])
+        }
+        ValueType::Tuple(meta) => {
+            let items = meta.items_types.iter().map(|ty| swift_type(module, ty));
+            quote!(($(for item in items join (, ) => $item)))
+        }
+        ValueType::Range(meta) => {
+            quote!(PostcardRange<$(swift_type(module, &meta.bounds_type))>)
+        }
+        ValueType::Object(meta) => quote!($(full_qualified_object_name(module, meta))),
+    }
+}
@@ -0,0 +1,70 @@
+use genco::quote;
+
+use crate::{
+    code_gen::js::{
+        cobs::{
+            deserialize_cobs_body, gen_cobs_accumulator, gen_cobs_runtime, serialize_cobs_body,
+            COBS_ACCUMULATOR_DOC,
+        },
+        Syntax,
+    },
+    function_args,
+};
+
+use super::{gen_doc_comment, Function, GenerationSettings, Tokens, TypedFunction};
+
+/// Generates the COBS framing helpers, which rely on `serialize` and `deserialize`.
+pub fn gen_cobs_code(gen_settings: &GenerationSettings) -> Tokens {
+    let mut tokens = gen_cobs_runtime(Syntax::TypeScript);
+
+    if gen_settings.ser {
+        tokens.line();
+        tokens.append(
+            TypedFunction::new(
+                Function::new(
+                    "serialize_cobs",
+                    function_args![("type", "T"), ("value", "ValueType<T>")],
+                    serialize_cobs_body(),
+                    "Uint8Array",
+                )
+                .with_doc_string(
+                    "Serialize a value to a COBS frame, terminated by a zero byte.\n\
+                    @param type - The type of the value to serialize.\n\
+                    @param value - The value to serialize.\n\
+                    @return The encoded frame.",
+                ),
+            )
+            .with_generics("T extends Type")
+            .exported(),
+        );
+    }
+
+    if gen_settings.des {
+        tokens.line();
+        tokens.append(
+            TypedFunction::new(
+                Function::new(
+                    "deserialize_cobs",
+                    function_args![("type", "T"), ("bytes", "ArrayLike<number>")],
+                    deserialize_cobs_body(),
+                    "Result<T>",
+                )
+                .with_doc_string(
+                    "Deserialize a value from the first COBS frame in an array of bytes.\n\
+                    @param type - The type of the value to deserialize.\n\
+                    @param bytes - The array of bytes holding the frame.\n\
+                    @return The deserialized value and the bytes following the frame.",
+                ),
+            )
+            .with_generics("T extends Type")
+            .exported(),
+        );
+        tokens.line();
+        tokens.append(quote! {
+            $(gen_doc_comment(COBS_ACCUMULATOR_DOC))
+            export $(gen_cobs_accumulator(Syntax::TypeScript))
+        });
+    }
+
+    tokens
+}
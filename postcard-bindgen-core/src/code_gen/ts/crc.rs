@@ -0,0 +1,85 @@
+use genco::quote;
+
+use crate::{
+    code_gen::{
+        crc::CRC_32_ALGORITHMS,
+        js::{
+            crc::{deserialize_crc32_body, gen_algorithm, gen_crc_runtime, serialize_crc32_body},
+            Syntax,
+        },
+    },
+    function_args,
+};
+
+use super::{Function, GenerationSettings, Tokens, TypedFunction};
+
+/// Generates the CRC checked framing helpers, which rely on `serialize` and `deserialize`.
+pub fn gen_crc_code(gen_settings: &GenerationSettings) -> Tokens {
+    let algorithms = CRC_32_ALGORITHMS.iter().map(|algorithm| {
+        quote!(export const $(algorithm.name): Crc32Algorithm = $(gen_algorithm(algorithm)))
+    });
+
+    let mut tokens = quote! {
+        export type Crc32Algorithm = { poly: number, init: number, refin: boolean, refout: boolean, xorout: number }
+
+        $(for algorithm in algorithms join ($['\r']) => $algorithm)
+
+        $(gen_crc_runtime(Syntax::TypeScript))
+    };
+
+    if gen_settings.ser {
+        tokens.line();
+        tokens.append(
+            TypedFunction::new(
+                Function::new(
+                    "serialize_crc32",
+                    function_args![
+                        ("type", "T"),
+                        ("value", "ValueType<T>"),
+                        ("algorithm", "Crc32Algorithm")
+                    ],
+                    serialize_crc32_body(),
+                    "Uint8Array",
+                )
+                .with_doc_string(
+                    "Serialize a value to an array of bytes followed by its CRC in little endian.\n\
+                    @param type - The type of the value to serialize.\n\
+                    @param value - The value to serialize.\n\
+                    @param algorithm - The CRC algorithm, e.g. CRC_32_ISCSI.\n\
+                    @return The serialized value with the CRC.",
+                ),
+            )
+            .with_generics("T extends Type")
+            .exported(),
+        );
+    }
+
+    if gen_settings.des {
+        tokens.line();
+        tokens.append(
+            TypedFunction::new(
+                Function::new(
+                    "deserialize_crc32",
+                    function_args![
+                        ("type", "T"),
+                        ("bytes", "ArrayLike<number>"),
+                        ("algorithm", "Crc32Algorithm")
+                    ],
+                    deserialize_crc32_body(),
+                    "Result<T>",
+                )
+                .with_doc_string(
+                    "Deserialize a value from an array of bytes and check the CRC following it.\n\
+                    @param type - The type of the value to deserialize.\n\
+                    @param bytes - The array of bytes holding the value and the CRC.\n\
+                    @param algorithm - The CRC algorithm, e.g. CRC_32_ISCSI.\n\
+                    @return The deserialized value and the bytes following the CRC.",
+                ),
+            )
+            .with_generics("T extends Type")
+            .exported(),
+        );
+    }
+
+    tokens
+}
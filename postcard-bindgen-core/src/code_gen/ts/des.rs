@@ -0,0 +1,88 @@
+use genco::{
+    lang::JavaScript,
+    quote,
+    tokens::{quoted, FormatInto},
+};
+
+use crate::{
+    code_gen::{
+        js::generateable::container::BindingTypeGenerateable,
+        utils::{ContainerFullQualifiedTypeBuilder, ContainerIdentifierBuilder, TokensIterExt},
+    },
+    function_args,
+    registry::Container,
+};
+
+use super::{Case, DefaultCase, Function, SwitchCase, Tokens, TypedFunction};
+
+pub fn gen_des_functions(bindings: impl Iterator<Item = Container>) -> Tokens {
+    bindings
+        .map(gen_des_function_for_type)
+        .join_with_empty_line()
+}
+
+fn gen_des_function_for_type(container: Container) -> impl FormatInto<JavaScript> {
+    let container_ident = ContainerIdentifierBuilder::from(&container).build();
+    let full_qualified = ContainerFullQualifiedTypeBuilder::from(&container).build();
+    let des_body = container.r#type.gen_des_body();
+
+    TypedFunction::new(Function::new(
+        quote!(deserialize_$container_ident),
+        function_args![("d", "Deserializer")],
+        des_body,
+        full_qualified,
+    ))
+}
+
+pub fn gen_result_interface() -> Tokens {
+    quote! {
+        export interface Result<T extends Type> {
+            value: ValueType<T>;
+            bytes: Uint8Array;
+        }
+    }
+}
+
+pub fn gen_deserialize_func(
+    defines: impl Iterator<Item = Container>,
+) -> impl FormatInto<JavaScript> {
+    let mut switch_case = SwitchCase::new("type");
+    switch_case.extend_cases(defines.map(gen_des_case));
+    switch_case.default_case(DefaultCase::new_without_break(
+        quote!(throw "type not implemented";),
+    ));
+
+    let body = quote! {
+        const d = new Deserializer(bytes);
+        let return_value: unknown;
+        $switch_case
+        return { value: return_value as ValueType<T>, bytes: d.release_bytes() };
+    };
+
+    TypedFunction::new(
+        Function::new(
+            "deserialize",
+            function_args![("type", "T"), ("bytes", "Uint8Array")],
+            body,
+            "Result<T>",
+        )
+        .with_doc_string(
+            "Deserialize a value from an array of bytes.\n\
+            @param type - The type of the value to deserialize.\n\
+            @param bytes - The byte array to deserialize from.\n\
+            @return The deserialized value and remaining bytes.",
+        ),
+    )
+    .with_generics("T extends Type")
+    .exported()
+}
+
+fn gen_des_case(container: Container) -> Case {
+    let fully_qualified = ContainerFullQualifiedTypeBuilder::from(&container).build();
+    let container_ident = ContainerIdentifierBuilder::from(&container).build();
+
+    Case::new(
+        quoted(fully_qualified),
+        quote!(return_value = deserialize_$container_ident(d);),
+    )
+}
@@ -0,0 +1,258 @@
+mod cobs;
+mod crc;
+mod des;
+mod ser;
+
+use core::borrow::Borrow;
+
+use cobs::gen_cobs_code;
+use crc::gen_crc_code;
+use des::{gen_des_functions, gen_deserialize_func, gen_result_interface};
+use genco::{prelude::js::JavaScript, quote, quote_in, tokens::FormatInto};
+use ser::{gen_ser_functions, gen_serialize_func};
+
+use crate::{registry::ContainerCollection, validation::ValidationSettings, ExportFile, Exports};
+
+use super::js::{
+    gen_deserializer_code, gen_doc_comment, gen_serializer_code, gen_util,
+    generateable::gen_type_decls, Syntax,
+};
+
+type Tokens = genco::Tokens<JavaScript>;
+
+type Function = super::function::Function<JavaScript>;
+type Case = super::switch_case::Case<JavaScript>;
+type DefaultCase = super::switch_case::DefaultCase<JavaScript>;
+type SwitchCase = super::switch_case::SwitchCase<JavaScript>;
+
+/// Settings for typescript bindings generation.
+///
+/// The generated code is typescript source, so the static type checks replace the runtime
+/// type checks of the javascript generation.
+///
+/// By default, only deserialization is enabled. Serialization can be enabled by using [`GenerationSettings::serialization()`].
+/// Deserialization can be disabled with [`GenerationSettings::deserialization()`].
+/// To enable all at once use [`GenerationSettings::enable_all()`].
+#[derive(Debug)]
+pub struct GenerationSettings {
+    ser: bool,
+    des: bool,
+    module_structure: bool,
+    cobs: bool,
+    crc32: bool,
+}
+
+impl GenerationSettings {
+    /// Constructs [`GenerationSettings`] and enables all options at once.
+    pub fn enable_all() -> Self {
+        Self {
+            ser: true,
+            des: true,
+            module_structure: true,
+            cobs: false,
+            crc32: false,
+        }
+    }
+
+    /// Enabling or disabling of serialization code generation.
+    pub fn serialization(mut self, enabled: bool) -> Self {
+        self.ser = enabled;
+        self
+    }
+
+    /// Enabling or disabling of deserialization code generation.
+    pub fn deserialization(mut self, enabled: bool) -> Self {
+        self.des = enabled;
+        self
+    }
+
+    /// Enabling or disabling of module structure code generation.
+    ///
+    /// Enabling this will generate the types in the same module structure
+    /// as in rust. Root level types will be in the root of the generated
+    /// package. Types nested in modules will be in namespaces
+    /// (e.g. <mod_name>.<type_name>). This avoids name clashes.
+    ///
+    /// Disabling this will generate all types in the root module.
+    pub fn module_structure(mut self, enabled: bool) -> Self {
+        self.module_structure = enabled;
+        self
+    }
+
+    /// Enabling or disabling of COBS framing code generation.
+    ///
+    /// Enabling this will generate `serialize_cobs` and `deserialize_cobs`, which encode and
+    /// decode frames like `postcard::to_slice_cobs` and `postcard::take_from_bytes_cobs`, and
+    /// a `CobsAccumulator`, whose generator method `feed` takes chunks of a stream and yields
+    /// the decoded values like `postcard::accumulator::CobsAccumulator`.
+    ///
+    /// This is not enabled by [`GenerationSettings::enable_all()`].
+    pub fn cobs(mut self, enabled: bool) -> Self {
+        self.cobs = enabled;
+        self
+    }
+
+    /// Enabling or disabling of CRC checked framing code generation.
+    ///
+    /// Enabling this will generate `serialize_crc32` and `deserialize_crc32`, which append
+    /// and check a CRC like `postcard::to_slice_crc32` and `postcard::take_from_bytes_crc32`.
+    /// The algorithm is passed to both functions, the 32 bit algorithms of the `crc` crate
+    /// catalog are exported with the same names (e.g. `CRC_32_ISCSI`).
+    ///
+    /// This is not enabled by [`GenerationSettings::enable_all()`].
+    pub fn crc32(mut self, enabled: bool) -> Self {
+        self.crc32 = enabled;
+        self
+    }
+}
+
+impl Default for GenerationSettings {
+    fn default() -> Self {
+        Self {
+            ser: false,
+            des: true,
+            module_structure: true,
+            cobs: false,
+            crc32: false,
+        }
+    }
+}
+
+impl From<&GenerationSettings> for ValidationSettings {
+    fn from(settings: &GenerationSettings) -> Self {
+        ValidationSettings::default().flattened(!settings.module_structure)
+    }
+}
+
+pub fn generate(
+    mut containers: ContainerCollection,
+    gen_settings: impl Borrow<GenerationSettings>,
+) -> Exports<JavaScript> {
+    let gen_settings = gen_settings.borrow();
+
+    if !gen_settings.module_structure {
+        containers.flatten();
+    }
+
+    let mut export_files = Vec::new();
+
+    export_files.push(ExportFile {
+        content_type: "util".to_owned(),
+        content: gen_util(Syntax::TypeScript),
+    });
+
+    export_files.push(ExportFile {
+        content_type: "types".to_owned(),
+        content: gen_type_decls(&containers),
+    });
+
+    if gen_settings.ser {
+        export_files.push(ExportFile {
+            content_type: "serializer".to_owned(),
+            content: gen_serializer_code(Syntax::TypeScript),
+        });
+
+        let mut tokens = Tokens::new();
+
+        tokens.append(gen_ser_functions(containers.all_containers()));
+        tokens.line();
+        tokens.append(gen_serialize_func(containers.all_containers()));
+
+        export_files.push(ExportFile {
+            content_type: "ser".to_owned(),
+            content: tokens,
+        });
+    }
+
+    if gen_settings.des {
+        export_files.push(ExportFile {
+            content_type: "deserializer".to_owned(),
+            content: gen_deserializer_code(Syntax::TypeScript),
+        });
+
+        let mut tokens = Tokens::new();
+
+        tokens.append(gen_des_functions(containers.all_containers()));
+        tokens.line();
+        tokens.append(gen_result_interface());
+        tokens.line();
+        tokens.append(gen_deserialize_func(containers.all_containers()));
+
+        export_files.push(ExportFile {
+            content_type: "des".to_owned(),
+            content: tokens,
+        });
+    }
+
+    if gen_settings.cobs && (gen_settings.ser || gen_settings.des) {
+        export_files.push(ExportFile {
+            content_type: "cobs".to_owned(),
+            content: gen_cobs_code(gen_settings),
+        });
+    }
+
+    if gen_settings.crc32 && (gen_settings.ser || gen_settings.des) {
+        export_files.push(ExportFile {
+            content_type: "crc".to_owned(),
+            content: gen_crc_code(gen_settings),
+        });
+    }
+
+    Exports {
+        files: export_files,
+    }
+}
+
+/// A [`Function`] which is formatted with its argument and return types.
+struct TypedFunction {
+    function: Function,
+    generics: Option<Tokens>,
+    exported: bool,
+}
+
+impl TypedFunction {
+    fn new(function: Function) -> Self {
+        Self {
+            function,
+            generics: None,
+            exported: false,
+        }
+    }
+
+    fn with_generics(mut self, generics: impl FormatInto<JavaScript>) -> Self {
+        self.generics = Some(quote!($generics));
+        self
+    }
+
+    fn exported(mut self) -> Self {
+        self.exported = true;
+        self
+    }
+}
+
+impl FormatInto<JavaScript> for TypedFunction {
+    fn format_into(self, tokens: &mut Tokens) {
+        let Function {
+            args,
+            name,
+            body,
+            return_type,
+            doc_string,
+        } = self.function;
+
+        let doc_string = doc_string.map(gen_doc_comment);
+        let generics = self.generics.map(|generics| quote!(<$generics>));
+        let args = args.into_iter().map(|arg| match arg.r#type {
+            Some(r#type) => quote!($(arg.name): $r#type),
+            None => arg.name,
+        });
+        let return_type = return_type.map(|r#type| quote!(: $r#type));
+
+        quote_in! { *tokens =>
+            $(doc_string)
+            $(if self.exported { export$[' '] })function $name$(generics)($(for arg in args join (, ) => $arg))$(return_type) {
+                $body
+            }
+        }
+    }
+}
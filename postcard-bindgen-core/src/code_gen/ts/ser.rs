@@ -0,0 +1,75 @@
+use genco::{
+    lang::JavaScript,
+    quote,
+    tokens::{quoted, FormatInto},
+};
+
+use crate::{
+    code_gen::{
+        js::{generateable::container::BindingTypeGenerateable, JS_OBJECT_VARIABLE},
+        utils::{ContainerFullQualifiedTypeBuilder, ContainerIdentifierBuilder, TokensIterExt},
+    },
+    function_args,
+    registry::Container,
+};
+
+use super::{Case, DefaultCase, Function, SwitchCase, Tokens, TypedFunction};
+
+pub fn gen_ser_functions(bindings: impl Iterator<Item = Container>) -> Tokens {
+    bindings
+        .map(gen_ser_function_for_type)
+        .join_with_empty_line()
+}
+
+fn gen_ser_function_for_type(container: Container) -> impl FormatInto<JavaScript> {
+    let container_ident = ContainerIdentifierBuilder::from(&container).build();
+    let full_qualified = ContainerFullQualifiedTypeBuilder::from(&container).build();
+    let ser_body = container.r#type.gen_ser_body();
+
+    TypedFunction::new(Function::new(
+        quote!(serialize_$container_ident),
+        function_args![("s", "Serializer"), (JS_OBJECT_VARIABLE, full_qualified)],
+        ser_body,
+        "void",
+    ))
+}
+
+pub fn gen_serialize_func(defines: impl Iterator<Item = Container>) -> impl FormatInto<JavaScript> {
+    let mut switch_case = SwitchCase::new("type");
+    switch_case.extend_cases(defines.map(gen_ser_case));
+    switch_case.default_case(DefaultCase::new_without_break(
+        quote!(throw "type not implemented";),
+    ));
+
+    TypedFunction::new(
+        Function::new(
+            "serialize",
+            function_args![("type", "T"), ("value", "ValueType<T>")],
+            quote! {
+                const s = new Serializer();
+                const $JS_OBJECT_VARIABLE: unknown = value;
+                $switch_case
+                return s.finish();
+            },
+            "Uint8Array",
+        )
+        .with_doc_string(
+            "Serialize a value to an array of bytes.\n\
+            @param type - The type of the value to serialize.\n\
+            @param value - The value to serialize.\n\
+            @return The serialized value as an array of bytes.",
+        ),
+    )
+    .with_generics("T extends Type")
+    .exported()
+}
+
+fn gen_ser_case(container: Container) -> Case {
+    let full_qualified = ContainerFullQualifiedTypeBuilder::from(&container).build();
+    let container_ident = ContainerIdentifierBuilder::from(&container).build();
+
+    Case::new(
+        quoted(full_qualified.as_str()),
+        quote!(serialize_$(container_ident)(s, $JS_OBJECT_VARIABLE as $(&full_qualified));),
+    )
+}
@@ -273,15 +273,15 @@ impl<'a> ContainerIdentifierBuilder<'a> {
     }
 }
 
-impl From<&Container> for ContainerIdentifierBuilder<'_> {
-    fn from(container: &Container) -> Self {
-        Self::new(container.path.clone().into_buf(), container.name)
+impl<'a> From<&'a Container> for ContainerIdentifierBuilder<'a> {
+    fn from(container: &'a Container) -> Self {
+        Self::new(container.path.clone().into_buf(), &container.name)
     }
 }
 
-impl From<&ObjectMeta> for ContainerIdentifierBuilder<'_> {
-    fn from(meta: &ObjectMeta) -> Self {
-        Self::new(meta.path.clone().into_buf(), meta.name)
+impl<'a> From<&'a ObjectMeta> for ContainerIdentifierBuilder<'a> {
+    fn from(meta: &'a ObjectMeta) -> Self {
+        Self::new(meta.path.clone().into_buf(), &meta.name)
     }
 }
 
@@ -310,15 +310,15 @@ impl<'a> From<&'a ContainerInfo<'a>> for ContainerFullQualifiedTypeBuilder<'a> {
     }
 }
 
-impl From<&Container> for ContainerFullQualifiedTypeBuilder<'_> {
-    fn from(container: &Container) -> Self {
-        Self::new(container.path.clone().into_buf(), container.name)
+impl<'a> From<&'a Container> for ContainerFullQualifiedTypeBuilder<'a> {
+    fn from(container: &'a Container) -> Self {
+        Self::new(container.path.clone().into_buf(), &container.name)
     }
 }
 
-impl From<&ObjectMeta> for ContainerFullQualifiedTypeBuilder<'_> {
-    fn from(meta: &ObjectMeta) -> Self {
-        Self::new(meta.path.clone().into_buf(), meta.name)
+impl<'a> From<&'a ObjectMeta> for ContainerFullQualifiedTypeBuilder<'a> {
+    fn from(meta: &'a ObjectMeta) -> Self {
+        Self::new(meta.path.clone().into_buf(), &meta.name)
     }
 }
 
@@ -347,6 +347,7 @@ mod test {
         let container = ContainerInfo {
             name: "Test".into(),
             path: PathBuf::new().into_path("::"),
+            doc: None,
         };
 
         let builder =
@@ -357,6 +358,7 @@ mod test {
         let container = ContainerInfo {
             name: "Test".into(),
             path: PathBuf::from_iter(["crate".into(), "submodule".into()]).into_path("::"),
+            doc: None,
         };
 
         let builder =
@@ -370,6 +372,7 @@ mod test {
         let container = ContainerInfo {
             name: "Test".into(),
             path: PathBuf::new().into_path("::"),
+            doc: None,
         };
 
         let builder: ContainerFullQualifiedTypeBuilder = (&container).into();
@@ -379,6 +382,7 @@ mod test {
         let container = ContainerInfo {
             name: "Test".into(),
             path: PathBuf::from_iter(["crate".into(), "submodule".into()]).into_path("::"),
+            doc: None,
         };
 
         let builder: ContainerFullQualifiedTypeBuilder = (&container).into();
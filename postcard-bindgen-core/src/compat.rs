@@ -0,0 +1,958 @@
+use core::fmt::Display;
+use std::{borrow::Cow, collections::BTreeSet};
+
+use crate::{
+    path::Path,
+    registry::{BindingType, Container, ContainerCollection, EnumVariant, EnumVariantType},
+    type_info::{IntegerEncoding, NumberMeta, ObjectMeta, ValueType},
+};
+
+/// How a change affects peers which still use the old bindings.
+///
+/// Ordered from the most to the least compatible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Compatibility {
+    /// Both versions read and write the same bytes.
+    WireCompatible,
+    /// The new bindings read all bytes of the old bindings and write the values both can
+    /// represent the same, but they can also write values the old bindings can't read (e.g.
+    /// an appended enum variant).
+    ForwardCompatible,
+    /// Bytes written by one version are misparsed or rejected by the other.
+    Breaking,
+}
+
+impl Display for Compatibility {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Compatibility::WireCompatible => write!(f, "wire-compatible"),
+            Compatibility::ForwardCompatible => write!(f, "forward-compatible"),
+            Compatibility::Breaking => write!(f, "breaking"),
+        }
+    }
+}
+
+/// A difference between two versions of the bindings found by [check()].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    /// Module path of the container the change was found in.
+    pub path: Path<'static, 'static>,
+    /// Name of the container the change was found in.
+    pub container: Cow<'static, str>,
+    /// Field or variant of the container the change is in, e.g. `.name` or `::Move.x`.
+    /// Empty for changes of the container itself.
+    pub location: String,
+    pub kind: ChangeKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeKind {
+    ContainerAdded,
+    ContainerRemoved,
+    /// A struct became an enum or the other way around.
+    ContainerKindChanged {
+        old: &'static str,
+        new: &'static str,
+    },
+    FieldAdded,
+    FieldRemoved,
+    FieldRenamed {
+        new: String,
+    },
+    /// The field was moved to another position, which changes the order of the bytes.
+    FieldMoved {
+        old_position: usize,
+        new_position: usize,
+    },
+    VariantAdded {
+        index: usize,
+    },
+    VariantRemoved,
+    VariantRenamed {
+        new: String,
+    },
+    /// The variant got another index, which is what postcard writes to identify it.
+    VariantMoved {
+        old_index: usize,
+        new_index: usize,
+    },
+    /// The type holds all values of the old type, encoded the same (e.g. `u16` to `u32`).
+    TypeWidened {
+        old: String,
+        new: String,
+    },
+    TypeChanged {
+        old: String,
+        new: String,
+    },
+}
+
+impl Change {
+    pub fn compatibility(&self) -> Compatibility {
+        match self.kind {
+            ChangeKind::ContainerAdded
+            | ChangeKind::FieldRenamed { .. }
+            | ChangeKind::VariantRenamed { .. } => Compatibility::WireCompatible,
+            ChangeKind::VariantAdded { .. } | ChangeKind::TypeWidened { .. } => {
+                Compatibility::ForwardCompatible
+            }
+            ChangeKind::ContainerRemoved
+            | ChangeKind::ContainerKindChanged { .. }
+            | ChangeKind::FieldAdded
+            | ChangeKind::FieldRemoved
+            | ChangeKind::FieldMoved { .. }
+            | ChangeKind::VariantRemoved
+            | ChangeKind::VariantMoved { .. }
+            | ChangeKind::TypeChanged { .. } => Compatibility::Breaking,
+        }
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}::{}{}: ", self.path, self.container, self.location)?;
+        match &self.kind {
+            ChangeKind::ContainerAdded => write!(f, "added"),
+            ChangeKind::ContainerRemoved => write!(f, "removed"),
+            ChangeKind::ContainerKindChanged { old, new } => {
+                write!(f, "changed from {old} to {new}")
+            }
+            ChangeKind::FieldAdded => write!(f, "field added"),
+            ChangeKind::FieldRemoved => write!(f, "field removed"),
+            ChangeKind::FieldRenamed { new } => write!(f, "field renamed to `{new}`"),
+            ChangeKind::FieldMoved {
+                old_position,
+                new_position,
+            } => write!(
+                f,
+                "field moved from position {old_position} to {new_position}"
+            ),
+            ChangeKind::VariantAdded { index } => write!(f, "variant added with index {index}"),
+            ChangeKind::VariantRemoved => write!(f, "variant removed"),
+            ChangeKind::VariantRenamed { new } => write!(f, "variant renamed to `{new}`"),
+            ChangeKind::VariantMoved {
+                old_index,
+                new_index,
+            } => write!(f, "variant moved from index {old_index} to {new_index}"),
+            ChangeKind::TypeWidened { old, new } => {
+                write!(f, "type widened from `{old}` to `{new}`")
+            }
+            ChangeKind::TypeChanged { old, new } => {
+                write!(f, "type changed from `{old}` to `{new}`")
+            }
+        }
+    }
+}
+
+/// All changes found by [check()], in the order of the old containers followed by the added
+/// containers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report(pub Vec<Change>);
+
+impl Report {
+    /// Compatibility of the least compatible change.
+    pub fn compatibility(&self) -> Compatibility {
+        self.0
+            .iter()
+            .map(Change::compatibility)
+            .max()
+            .unwrap_or(Compatibility::WireCompatible)
+    }
+
+    pub fn is_breaking(&self) -> bool {
+        self.compatibility() == Compatibility::Breaking
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        writeln!(f, "{}:", self.compatibility())?;
+        for change in self.0.iter() {
+            writeln!(f, "  {}: {change}", change.compatibility())?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares two versions of the bindings and classifies how each change affects the bytes.
+///
+/// Containers are matched by their path and name. Postcard writes neither field nor variant
+/// names, so fields are compared by their position and enum variants by their index.
+pub fn check(old: &ContainerCollection, new: &ContainerCollection) -> Report {
+    let old = old.all_containers().collect::<Vec<_>>();
+    let new = new.all_containers().collect::<Vec<_>>();
+    let mut checker = Checker {
+        old: &old,
+        new: &new,
+        comparing: BTreeSet::new(),
+    };
+    let mut changes = Vec::new();
+
+    for old_container in old.iter() {
+        match find(&new, &old_container.path, &old_container.name) {
+            Some(new_container) => checker.container(old_container, new_container, &mut changes),
+            None => changes.push(change(old_container, "", ChangeKind::ContainerRemoved)),
+        }
+    }
+    for new_container in new.iter() {
+        if find(&old, &new_container.path, &new_container.name).is_none() {
+            changes.push(change(new_container, "", ChangeKind::ContainerAdded));
+        }
+    }
+
+    Report(changes)
+}
+
+struct Checker<'c> {
+    old: &'c [Container],
+    new: &'c [Container],
+    /// Pairs of differently named containers being compared, to stop at recursive types.
+    comparing: BTreeSet<(String, String)>,
+}
+
+/// A field of a struct or an enum variant, tuple fields are named by their position.
+struct Field<'a> {
+    name: String,
+    v_type: &'a ValueType,
+}
+
+impl Checker<'_> {
+    fn container(&mut self, old: &Container, new: &Container, changes: &mut Vec<Change>) {
+        match (&old.r#type, &new.r#type) {
+            (BindingType::Enum(old_ty), BindingType::Enum(new_ty)) => {
+                self.variants(old, &old_ty.variants, &new_ty.variants, changes)
+            }
+            (BindingType::Enum(_), _) | (_, BindingType::Enum(_)) => changes.push(change(
+                old,
+                "",
+                ChangeKind::ContainerKindChanged {
+                    old: kind(&old.r#type),
+                    new: kind(&new.r#type),
+                },
+            )),
+            // structs, tuple structs and unit structs are written the same
+            (old_ty, new_ty) => self.fields(old, "", &fields(old_ty), &fields(new_ty), changes),
+        }
+    }
+
+    fn variants(
+        &mut self,
+        container: &Container,
+        old: &[EnumVariant],
+        new: &[EnumVariant],
+        changes: &mut Vec<Change>,
+    ) {
+        for old_variant in old {
+            let location = format!("::{}", old_variant.name);
+            let moved_to = new
+                .iter()
+                .find(|v| v.name == old_variant.name && v.index != old_variant.index);
+            if let Some(new_variant) = moved_to {
+                changes.push(change(
+                    container,
+                    location,
+                    ChangeKind::VariantMoved {
+                        old_index: old_variant.index,
+                        new_index: new_variant.index,
+                    },
+                ));
+                continue;
+            }
+            let Some(new_variant) = new.iter().find(|v| v.index == old_variant.index) else {
+                changes.push(change(container, location, ChangeKind::VariantRemoved));
+                continue;
+            };
+            if new_variant.name != old_variant.name {
+                changes.push(change(
+                    container,
+                    location.clone(),
+                    ChangeKind::VariantRenamed {
+                        new: new_variant.name.to_string(),
+                    },
+                ));
+            }
+            self.fields(
+                container,
+                &location,
+                &variant_fields(&old_variant.inner_type),
+                &variant_fields(&new_variant.inner_type),
+                changes,
+            );
+        }
+
+        for new_variant in new {
+            let known = old
+                .iter()
+                .any(|v| v.index == new_variant.index || v.name == new_variant.name);
+            if !known {
+                changes.push(change(
+                    container,
+                    format!("::{}", new_variant.name),
+                    ChangeKind::VariantAdded {
+                        index: new_variant.index,
+                    },
+                ));
+            }
+        }
+    }
+
+    fn fields(
+        &mut self,
+        container: &Container,
+        prefix: &str,
+        old: &[Field],
+        new: &[Field],
+        changes: &mut Vec<Change>,
+    ) {
+        let in_old = |name: &str| old.iter().any(|field| field.name == name);
+        let in_new = |name: &str| new.iter().any(|field| field.name == name);
+        // a field is renamed if neither its old nor its new name is found in the other version
+        let renamed = |position: usize| {
+            let (Some(old_field), Some(new_field)) = (old.get(position), new.get(position)) else {
+                return false;
+            };
+            !in_new(&old_field.name) && !in_old(&new_field.name)
+        };
+
+        for (position, old_field) in old.iter().enumerate() {
+            let location = format!("{prefix}.{}", old_field.name);
+            let new_field = match new.iter().position(|field| field.name == old_field.name) {
+                Some(new_position) if new_position != position => {
+                    changes.push(change(
+                        container,
+                        location,
+                        ChangeKind::FieldMoved {
+                            old_position: position,
+                            new_position,
+                        },
+                    ));
+                    continue;
+                }
+                Some(_) => &new[position],
+                None if renamed(position) => {
+                    changes.push(change(
+                        container,
+                        location.clone(),
+                        ChangeKind::FieldRenamed {
+                            new: new[position].name.to_owned(),
+                        },
+                    ));
+                    &new[position]
+                }
+                None => {
+                    changes.push(change(container, location, ChangeKind::FieldRemoved));
+                    continue;
+                }
+            };
+            let kind = match self.value(old_field.v_type, new_field.v_type) {
+                Compatibility::WireCompatible => continue,
+                Compatibility::ForwardCompatible => ChangeKind::TypeWidened {
+                    old: type_name(old_field.v_type),
+                    new: type_name(new_field.v_type),
+                },
+                Compatibility::Breaking => ChangeKind::TypeChanged {
+                    old: type_name(old_field.v_type),
+                    new: type_name(new_field.v_type),
+                },
+            };
+            changes.push(change(container, location, kind));
+        }
+
+        for (position, new_field) in new.iter().enumerate() {
+            if !in_old(&new_field.name) && !renamed(position) {
+                changes.push(change(
+                    container,
+                    format!("{prefix}.{}", new_field.name),
+                    ChangeKind::FieldAdded,
+                ));
+            }
+        }
+    }
+
+    fn value(&mut self, old: &ValueType, new: &ValueType) -> Compatibility {
+        match (old, new) {
+            (ValueType::Bool(_), ValueType::Bool(_)) => Compatibility::WireCompatible,
+            (
+                ValueType::Number(NumberMeta::Integer {
+                    bytes: old_bytes,
+                    signed: old_signed,
+                    zero_able: old_zero_able,
+                    encoding: old_encoding,
+                }),
+                ValueType::Number(NumberMeta::Integer {
+                    bytes: new_bytes,
+                    signed: new_signed,
+                    zero_able: new_zero_able,
+                    encoding: new_encoding,
+                }),
+            ) => {
+                if old_signed != new_signed || old_encoding != new_encoding {
+                    return Compatibility::Breaking;
+                }
+                // single bytes are written as they are, only varints can grow
+                let width = match (old_bytes, new_bytes) {
+                    (old, new) if old == new => Compatibility::WireCompatible,
+                    (old, new)
+                        if *old > 1 && new > old && *old_encoding == IntegerEncoding::Varint =>
+                    {
+                        Compatibility::ForwardCompatible
+                    }
+                    _ => Compatibility::Breaking,
+                };
+                let zero = match (old_zero_able, new_zero_able) {
+                    (old, new) if old == new => Compatibility::WireCompatible,
+                    (false, true) => Compatibility::ForwardCompatible,
+                    _ => Compatibility::Breaking,
+                };
+                width.max(zero)
+            }
+            (
+                ValueType::Number(NumberMeta::FloatingPoint { bytes: old_bytes }),
+                ValueType::Number(NumberMeta::FloatingPoint { bytes: new_bytes }),
+            ) if old_bytes == new_bytes => Compatibility::WireCompatible,
+            (ValueType::String(old), ValueType::String(new)) => {
+                max_length(old.max_length, new.max_length)
+            }
+            (ValueType::Array(old), ValueType::Array(new)) if old.length == new.length => {
+                max_length(old.max_length, new.max_length)
+                    .max(self.value(&old.items_type, &new.items_type))
+            }
+            (ValueType::Tuple(old), ValueType::Tuple(new))
+                if old.items_types.len() == new.items_types.len() =>
+            {
+                old.items_types
+                    .iter()
+                    .zip(new.items_types.iter())
+                    .map(|(old, new)| self.value(old, new))
+                    .max()
+                    .unwrap_or(Compatibility::WireCompatible)
+            }
+            (ValueType::Optional(old), ValueType::Optional(new)) => {
+                self.value(&old.inner, &new.inner)
+            }
+            (ValueType::Map(old), ValueType::Map(new)) => {
+                max_length(old.max_length, new.max_length)
+                    .max(self.value(&old.key_type, &new.key_type))
+                    .max(self.value(&old.value_type, &new.value_type))
+            }
+            (ValueType::Range(old), ValueType::Range(new)) => {
+                self.value(&old.bounds_type, &new.bounds_type)
+            }
+            (ValueType::Object(old), ValueType::Object(new)) => self.object(old, new),
+            _ => Compatibility::Breaking,
+        }
+    }
+
+    fn object(&mut self, old: &ObjectMeta, new: &ObjectMeta) -> Compatibility {
+        // changes of the same container are reported for the container itself
+        if old.path == new.path && old.name == new.name {
+            return Compatibility::WireCompatible;
+        }
+
+        let (Some(old_container), Some(new_container)) = (
+            find(self.old, &old.path, &old.name),
+            find(self.new, &new.path, &new.name),
+        ) else {
+            return Compatibility::Breaking;
+        };
+        let key = (
+            format!("{}::{}", old.path, old.name),
+            format!("{}::{}", new.path, new.name),
+        );
+        if !self.comparing.insert(key.clone()) {
+            return Compatibility::WireCompatible;
+        }
+        let mut changes = Vec::new();
+        self.container(old_container, new_container, &mut changes);
+        self.comparing.remove(&key);
+
+        changes
+            .iter()
+            .map(Change::compatibility)
+            .max()
+            .unwrap_or(Compatibility::WireCompatible)
+    }
+}
+
+fn find<'c>(containers: &'c [Container], path: &Path, name: &str) -> Option<&'c Container> {
+    containers
+        .iter()
+        .find(|container| container.name == name && container.path == *path)
+}
+
+fn change(container: &Container, location: impl Into<String>, kind: ChangeKind) -> Change {
+    Change {
+        path: container.path.clone(),
+        container: container.name.clone(),
+        location: location.into(),
+        kind,
+    }
+}
+
+fn kind(ty: &BindingType) -> &'static str {
+    match ty {
+        BindingType::Struct(_) => "struct",
+        BindingType::TupleStruct(_) => "tuple struct",
+        BindingType::UnitStruct(_) => "unit struct",
+        BindingType::Enum(_) => "enum",
+    }
+}
+
+fn fields(ty: &BindingType) -> Vec<Field<'_>> {
+    match ty {
+        BindingType::Struct(ty) => ty
+            .fields
+            .iter()
+            .map(|field| Field {
+                name: field.name.to_string(),
+                v_type: &field.v_type,
+            })
+            .collect(),
+        BindingType::TupleStruct(ty) => tuple_fields(&ty.fields),
+        BindingType::UnitStruct(_) | BindingType::Enum(_) => Vec::new(),
+    }
+}
+
+fn variant_fields(ty: &EnumVariantType) -> Vec<Field<'_>> {
+    match ty {
+        EnumVariantType::Empty => Vec::new(),
+        EnumVariantType::Tuple(fields) => tuple_fields(fields),
+        EnumVariantType::NewType(fields) => fields
+            .iter()
+            .map(|field| Field {
+                name: field.name.to_string(),
+                v_type: &field.v_type,
+            })
+            .collect(),
+    }
+}
+
+fn tuple_fields(fields: &[ValueType]) -> Vec<Field<'_>> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(position, v_type)| Field {
+            name: position.to_string(),
+            v_type,
+        })
+        .collect()
+}
+
+fn max_length(old: Option<usize>, new: Option<usize>) -> Compatibility {
+    match (old, new) {
+        (old, new) if old == new => Compatibility::WireCompatible,
+        (Some(_), None) => Compatibility::ForwardCompatible,
+        (Some(old), Some(new)) if new > old => Compatibility::ForwardCompatible,
+        _ => Compatibility::Breaking,
+    }
+}
+
+/// Name of the type in Rust syntax, including the maximum lengths.
+fn type_name(ty: &ValueType) -> String {
+    let with_max_length = |name: String, max_length: Option<usize>| match max_length {
+        Some(max_length) => format!("{name}<max {max_length}>"),
+        None => name,
+    };
+    match ty {
+        ValueType::Number(NumberMeta::Integer {
+            bytes,
+            signed,
+            zero_able,
+            encoding,
+        }) => {
+            let name = format!("{}{}", if *signed { "i" } else { "u" }, bytes * 8);
+            let name = if *zero_able {
+                name
+            } else {
+                format!("NonZero<{name}>")
+            };
+            match encoding {
+                IntegerEncoding::Varint => name,
+                IntegerEncoding::FixedLe => format!("{name} (fixint le)"),
+                IntegerEncoding::FixedBe => format!("{name} (fixint be)"),
+            }
+        }
+        ValueType::Number(NumberMeta::FloatingPoint { bytes }) => format!("f{}", bytes * 8),
+        ValueType::Bool(_) => "bool".to_owned(),
+        ValueType::String(meta) => with_max_length("String".to_owned(), meta.max_length),
+        ValueType::Object(meta) => format!("{}::{}", meta.path, meta.name),
+        ValueType::Optional(meta) => format!("Option<{}>", type_name(&meta.inner)),
+        ValueType::Array(meta) => match meta.length {
+            Some(length) => format!("[{}; {length}]", type_name(&meta.items_type)),
+            None => with_max_length(
+                format!("Vec<{}>", type_name(&meta.items_type)),
+                meta.max_length,
+            ),
+        },
+        ValueType::Range(meta) => format!("Range<{}>", type_name(&meta.bounds_type)),
+        ValueType::Map(meta) => with_max_length(
+            format!(
+                "Map<{}, {}>",
+                type_name(&meta.key_type),
+                type_name(&meta.value_type)
+            ),
+            meta.max_length,
+        ),
+        ValueType::Tuple(meta) => format!(
+            "({})",
+            meta.items_types
+                .iter()
+                .map(type_name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        path::Path,
+        registry::{BindingsRegistry, ContainerCollection, EnumType, StructType, TupleFields},
+        type_info::{GenBinding, ObjectMeta, ValueType},
+    };
+
+    use super::{check, ChangeKind, Compatibility};
+
+    struct Item;
+
+    impl GenBinding for Item {
+        fn get_type() -> ValueType {
+            ValueType::Object(ObjectMeta {
+                name: "Item".into(),
+                path: Path::new("crate", "::"),
+            })
+        }
+    }
+
+    struct Entry;
+
+    impl GenBinding for Entry {
+        fn get_type() -> ValueType {
+            ValueType::Object(ObjectMeta {
+                name: "Entry".into(),
+                path: Path::new("crate", "::"),
+            })
+        }
+    }
+
+    fn collection(register: impl FnOnce(&mut BindingsRegistry)) -> ContainerCollection {
+        let mut registry = BindingsRegistry::default();
+        register(&mut registry);
+        registry.into_entries()
+    }
+
+    fn enum_collection(variants: &[&'static str]) -> ContainerCollection {
+        collection(|registry| {
+            let mut ty = EnumType::new();
+            for variant in variants {
+                ty.register_variant(variant);
+            }
+            registry.register_enum_binding("Command", "crate", ty);
+        })
+    }
+
+    fn struct_collection(register: impl FnOnce(&mut StructType)) -> ContainerCollection {
+        collection(|registry| {
+            let mut ty = StructType::new();
+            register(&mut ty);
+            registry.register_struct_binding("Test", "crate", ty);
+        })
+    }
+
+    fn kinds(old: &ContainerCollection, new: &ContainerCollection) -> Vec<(String, ChangeKind)> {
+        check(old, new)
+            .0
+            .into_iter()
+            .map(|change| (change.location, change.kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_unchanged_bindings() {
+        let old = enum_collection(&["Stop", "Start"]);
+        let report = check(&old, &old);
+
+        assert!(report.0.is_empty());
+        assert_eq!(report.compatibility(), Compatibility::WireCompatible);
+    }
+
+    #[test]
+    fn test_enum_variants() {
+        let old = enum_collection(&["Stop", "Start"]);
+
+        let appended = check(&old, &enum_collection(&["Stop", "Start", "Reset"]));
+        assert_eq!(appended.compatibility(), Compatibility::ForwardCompatible);
+        assert_eq!(appended.0[0].kind, ChangeKind::VariantAdded { index: 2 });
+
+        assert_eq!(
+            kinds(&old, &enum_collection(&["Start", "Stop"])),
+            vec![
+                (
+                    "::Stop".into(),
+                    ChangeKind::VariantMoved {
+                        old_index: 0,
+                        new_index: 1
+                    }
+                ),
+                (
+                    "::Start".into(),
+                    ChangeKind::VariantMoved {
+                        old_index: 1,
+                        new_index: 0
+                    }
+                ),
+            ]
+        );
+        assert_eq!(
+            kinds(&old, &enum_collection(&["Stop"])),
+            vec![("::Start".into(), ChangeKind::VariantRemoved)]
+        );
+        assert_eq!(
+            kinds(&old, &enum_collection(&["Halt", "Start"])),
+            vec![(
+                "::Stop".into(),
+                ChangeKind::VariantRenamed { new: "Halt".into() }
+            )]
+        );
+    }
+
+    #[test]
+    fn test_variant_fields() {
+        let variant = |register: fn(&mut TupleFields)| {
+            collection(|registry| {
+                let mut fields = TupleFields::default();
+                register(&mut fields);
+                let mut ty = EnumType::new();
+                ty.register_variant_tuple("Move", fields);
+                registry.register_enum_binding("Command", "crate", ty);
+            })
+        };
+        let old = variant(|fields| fields.register_field::<u8>());
+
+        assert_eq!(
+            kinds(&old, &variant(|fields| fields.register_field::<i8>())),
+            vec![(
+                "::Move.0".into(),
+                ChangeKind::TypeChanged {
+                    old: "u8".into(),
+                    new: "i8".into()
+                }
+            )]
+        );
+        assert_eq!(
+            kinds(
+                &old,
+                &variant(|fields| {
+                    fields.register_field::<u8>();
+                    fields.register_field::<u8>();
+                })
+            ),
+            vec![("::Move.1".into(), ChangeKind::FieldAdded)]
+        );
+    }
+
+    #[test]
+    fn test_field_types() {
+        let old = struct_collection(|ty| {
+            ty.register_field::<u16>("a");
+            ty.register_field::<u8>("b");
+            ty.register_field::<core::num::NonZeroU32>("c");
+            ty.register_field::<Option<Vec<i32>>>("d");
+            ty.register_field::<f32>("e");
+        });
+        let new = struct_collection(|ty| {
+            ty.register_field::<u32>("a");
+            ty.register_field::<u16>("b");
+            ty.register_field::<u32>("c");
+            ty.register_field::<Option<Vec<u32>>>("d");
+            ty.register_field::<f32>("e");
+        });
+
+        let report = check(&old, &new);
+        assert!(report.is_breaking());
+        assert_eq!(
+            kinds(&old, &new),
+            vec![
+                (
+                    ".a".into(),
+                    ChangeKind::TypeWidened {
+                        old: "u16".into(),
+                        new: "u32".into()
+                    }
+                ),
+                (
+                    ".b".into(),
+                    ChangeKind::TypeChanged {
+                        old: "u8".into(),
+                        new: "u16".into()
+                    }
+                ),
+                (
+                    ".c".into(),
+                    ChangeKind::TypeWidened {
+                        old: "NonZero<u32>".into(),
+                        new: "u32".into()
+                    }
+                ),
+                (
+                    ".d".into(),
+                    ChangeKind::TypeChanged {
+                        old: "Option<Vec<i32>>".into(),
+                        new: "Option<Vec<u32>>".into()
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_struct_fields() {
+        let old = struct_collection(|ty| {
+            ty.register_field::<u8>("a");
+            ty.register_field::<u8>("b");
+            ty.register_field::<u8>("c");
+        });
+
+        assert_eq!(
+            kinds(
+                &old,
+                &struct_collection(|ty| {
+                    ty.register_field::<u8>("a");
+                    ty.register_field::<u8>("c");
+                    ty.register_field::<u8>("b");
+                })
+            ),
+            vec![
+                (
+                    ".b".into(),
+                    ChangeKind::FieldMoved {
+                        old_position: 1,
+                        new_position: 2
+                    }
+                ),
+                (
+                    ".c".into(),
+                    ChangeKind::FieldMoved {
+                        old_position: 2,
+                        new_position: 1
+                    }
+                ),
+            ]
+        );
+        assert_eq!(
+            kinds(
+                &old,
+                &struct_collection(|ty| {
+                    ty.register_field::<u8>("a");
+                    ty.register_field::<u8>("x");
+                    ty.register_field::<u8>("b");
+                    ty.register_field::<u8>("c");
+                })
+            ),
+            vec![
+                (
+                    ".b".into(),
+                    ChangeKind::FieldMoved {
+                        old_position: 1,
+                        new_position: 2
+                    }
+                ),
+                (
+                    ".c".into(),
+                    ChangeKind::FieldMoved {
+                        old_position: 2,
+                        new_position: 3
+                    }
+                ),
+                (".x".into(), ChangeKind::FieldAdded),
+            ]
+        );
+        assert_eq!(
+            kinds(
+                &old,
+                &struct_collection(|ty| {
+                    ty.register_field::<u8>("a");
+                    ty.register_field::<u8>("renamed");
+                })
+            ),
+            vec![
+                (
+                    ".b".into(),
+                    ChangeKind::FieldRenamed {
+                        new: "renamed".into()
+                    }
+                ),
+                (".c".into(), ChangeKind::FieldRemoved),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_containers() {
+        let old = collection(|registry| {
+            registry.register_struct_binding("Test", "crate", StructType::new());
+            registry.register_struct_binding("Removed", "crate", StructType::new());
+        });
+        let new = collection(|registry| {
+            registry.register_enum_binding("Test", "crate", EnumType::new());
+            registry.register_struct_binding("Added", "crate::other", StructType::new());
+        });
+
+        let report = check(&old, &new);
+        assert_eq!(
+            report.to_string(),
+            "breaking:
+  breaking: crate::Test: changed from struct to enum
+  breaking: crate::Removed: removed
+  wire-compatible: crate::other::Added: added
+"
+        );
+    }
+
+    #[test]
+    fn test_referenced_containers() {
+        let item = |registry: &mut BindingsRegistry, name, field_type: fn(&mut StructType)| {
+            let mut ty = StructType::new();
+            field_type(&mut ty);
+            registry.register_struct_binding(name, "crate", ty);
+        };
+        let old = collection(|registry| {
+            item(registry, "Item", |ty| {
+                ty.register_field::<Vec<Item>>("children")
+            });
+            item(registry, "Test", |ty| ty.register_field::<Item>("item"));
+        });
+        // the same layout under another name, and a changed layout
+        let renamed = collection(|registry| {
+            item(registry, "Entry", |ty| {
+                ty.register_field::<Vec<Entry>>("items")
+            });
+            item(registry, "Test", |ty| ty.register_field::<Entry>("item"));
+        });
+        let changed = collection(|registry| {
+            item(registry, "Entry", |ty| {
+                ty.register_field::<Vec<u8>>("children")
+            });
+            item(registry, "Test", |ty| ty.register_field::<Entry>("item"));
+        });
+
+        assert_eq!(
+            kinds(&old, &renamed),
+            vec![
+                ("".into(), ChangeKind::ContainerRemoved),
+                ("".into(), ChangeKind::ContainerAdded),
+            ]
+        );
+        assert_eq!(
+            kinds(&old, &changed)[1],
+            (
+                ".item".into(),
+                ChangeKind::TypeChanged {
+                    old: "crate::Item".into(),
+                    new: "crate::Entry".into()
+                }
+            )
+        );
+    }
+}
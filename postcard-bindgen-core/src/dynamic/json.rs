@@ -0,0 +1,301 @@
+use serde_json::{Map, Number, Value as Json};
+
+use super::{integer, integer_value, Error, Fields, Integer, Result, Schema, Value};
+use crate::{
+    registry::{BindingType, Container, ContainerCollection, EnumVariantType, StructField},
+    type_info::{NumberMeta, ValueType},
+};
+
+impl Value {
+    /// Converts the value to JSON in the layout `serde_json` uses for the Rust type.
+    ///
+    /// 128 bit integers which don't fit into a JSON number are written as strings, maps with
+    /// keys other than strings or integers as arrays of key value pairs.
+    pub fn to_json(&self) -> Json {
+        match self {
+            Value::Bool(value) => Json::Bool(*value),
+            Value::U8(value) => Json::from(*value),
+            Value::U16(value) => Json::from(*value),
+            Value::U32(value) => Json::from(*value),
+            Value::U64(value) => Json::from(*value),
+            Value::U128(value) => u64::try_from(*value)
+                .map(Json::from)
+                .unwrap_or_else(|_| Json::String(value.to_string())),
+            Value::I8(value) => Json::from(*value),
+            Value::I16(value) => Json::from(*value),
+            Value::I32(value) => Json::from(*value),
+            Value::I64(value) => Json::from(*value),
+            Value::I128(value) => i64::try_from(*value)
+                .map(Json::from)
+                .unwrap_or_else(|_| Json::String(value.to_string())),
+            Value::F32(value) => Json::from(*value),
+            Value::F64(value) => Number::from_f64(*value).map_or(Json::Null, Json::Number),
+            Value::String(value) => Json::String(value.to_owned()),
+            Value::Array(items) | Value::Tuple(items) => {
+                Json::Array(items.iter().map(Value::to_json).collect())
+            }
+            Value::Option(None) => Json::Null,
+            Value::Option(Some(value)) => value.to_json(),
+            Value::Map(entries) => entries
+                .iter()
+                .map(|(key, value)| Some((key.to_json_key()?, value.to_json())))
+                .collect::<Option<Map<_, _>>>()
+                .map(Json::Object)
+                .unwrap_or_else(|| {
+                    Json::Array(
+                        entries
+                            .iter()
+                            .map(|(key, value)| Json::Array(vec![key.to_json(), value.to_json()]))
+                            .collect(),
+                    )
+                }),
+            Value::Range { start, end } => Json::Object(Map::from_iter([
+                ("start".to_owned(), start.to_json()),
+                ("end".to_owned(), end.to_json()),
+            ])),
+            Value::Struct(fields) => fields.to_json(),
+            Value::Enum {
+                variant,
+                fields: Fields::Unit,
+            } => Json::String(variant.to_owned()),
+            Value::Enum { variant, fields } => {
+                Json::Object(Map::from_iter([(variant.to_owned(), fields.to_json())]))
+            }
+        }
+    }
+
+    fn to_json_key(&self) -> Option<String> {
+        match self {
+            Value::String(key) => Some(key.to_owned()),
+            key => match integer(key)? {
+                Integer::Unsigned(key) => Some(key.to_string()),
+                Integer::Signed(key) => Some(key.to_string()),
+            },
+        }
+    }
+}
+
+impl Fields {
+    fn to_json(&self) -> Json {
+        match self {
+            Fields::Unit => Json::Null,
+            // newtype structs and variants are serialized as their inner value
+            Fields::Unnamed(values) if values.len() == 1 => values[0].to_json(),
+            Fields::Unnamed(values) => Json::Array(values.iter().map(Value::to_json).collect()),
+            Fields::Named(fields) => Json::Object(
+                fields
+                    .iter()
+                    .map(|(name, value)| (name.to_owned(), value.to_json()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Converts JSON in the layout of [Value::to_json()] to a value of the container registered
+/// under `type_path`, which can be passed to [encode()](super::encode()).
+///
+/// Missing fields of an optional type are `None` and integers may also be given as strings.
+pub fn from_json(containers: &ContainerCollection, type_path: &str, json: &Json) -> Result<Value> {
+    let schema = Schema::new(containers);
+    let container = schema.find(type_path)?;
+    JsonReader { schema: &schema }.container(container, json)
+}
+
+struct JsonReader<'s> {
+    schema: &'s Schema,
+}
+
+impl JsonReader<'_> {
+    fn container(&self, container: &Container, json: &Json) -> Result<Value> {
+        let mismatch = || Error::TypeMismatch {
+            expected: container.name.to_string(),
+        };
+        match &container.r#type {
+            BindingType::Struct(ty) => self
+                .named_fields(&ty.fields, json, mismatch)
+                .map(Value::Struct),
+            BindingType::TupleStruct(ty) => self
+                .unnamed_fields(&ty.fields, json, mismatch)
+                .map(Value::Struct),
+            BindingType::UnitStruct(_) => match json {
+                Json::Null => Ok(Value::Struct(Fields::Unit)),
+                _ => Err(mismatch()),
+            },
+            BindingType::Enum(ty) => {
+                let (variant, content) = match json {
+                    Json::String(variant) => (variant, None),
+                    Json::Object(object) if object.len() == 1 => {
+                        let (variant, content) = object.iter().next().unwrap();
+                        (variant, Some(content))
+                    }
+                    _ => return Err(mismatch()),
+                };
+                let schema_variant = ty
+                    .variants
+                    .iter()
+                    .find(|v| v.name == variant.as_str())
+                    .ok_or_else(|| Error::UnknownVariant {
+                        name: container.name.clone(),
+                        variant: variant.to_owned(),
+                    })?;
+                let fields = match (&schema_variant.inner_type, content) {
+                    (EnumVariantType::Empty, None) => Fields::Unit,
+                    (EnumVariantType::Tuple(types), Some(content)) => {
+                        self.unnamed_fields(types, content, mismatch)?
+                    }
+                    (EnumVariantType::NewType(types), Some(content)) => {
+                        self.named_fields(types, content, mismatch)?
+                    }
+                    _ => return Err(mismatch()),
+                };
+                Ok(Value::Enum {
+                    variant: variant.to_owned(),
+                    fields,
+                })
+            }
+        }
+    }
+
+    fn named_fields(
+        &self,
+        types: &[StructField],
+        json: &Json,
+        mismatch: impl Fn() -> Error,
+    ) -> Result<Fields> {
+        let Json::Object(object) = json else {
+            return Err(mismatch());
+        };
+        if let Some(name) = object
+            .keys()
+            .find(|name| !types.iter().any(|ty| ty.name == name.as_str()))
+        {
+            return Err(Error::UnknownField(name.to_owned()));
+        }
+        let fields = types
+            .iter()
+            .map(|ty| {
+                let value = match (object.get(ty.name.as_ref()), &ty.v_type) {
+                    (Some(json), v_type) => self.value(v_type, json)?,
+                    (None, ValueType::Optional(_)) => Value::Option(None),
+                    (None, _) => return Err(Error::MissingField(ty.name.clone())),
+                };
+                Ok((ty.name.to_string(), value))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Fields::Named(fields))
+    }
+
+    fn unnamed_fields(
+        &self,
+        types: &[ValueType],
+        json: &Json,
+        mismatch: impl Fn() -> Error,
+    ) -> Result<Fields> {
+        if let [ty] = types {
+            return Ok(Fields::Unnamed(vec![self.value(ty, json)?]));
+        }
+        let Json::Array(items) = json else {
+            return Err(mismatch());
+        };
+        self.items(types, items).map(Fields::Unnamed)
+    }
+
+    fn items(&self, types: &[ValueType], items: &[Json]) -> Result<Vec<Value>> {
+        if types.len() != items.len() {
+            return Err(Error::LengthMismatch {
+                expected: types.len(),
+                found: items.len(),
+            });
+        }
+        types
+            .iter()
+            .zip(items)
+            .map(|(ty, item)| self.value(ty, item))
+            .collect()
+    }
+
+    fn value(&self, ty: &ValueType, json: &Json) -> Result<Value> {
+        let mismatch = || Error::TypeMismatch {
+            expected: ty.name(),
+        };
+        match (ty, json) {
+            (ValueType::Bool(_), Json::Bool(value)) => Ok(Value::Bool(*value)),
+            (ValueType::Number(NumberMeta::FloatingPoint { bytes: 4 }), Json::Number(value)) => {
+                Ok(Value::F32(value.as_f64().ok_or_else(mismatch)? as f32))
+            }
+            (ValueType::Number(NumberMeta::FloatingPoint { .. }), Json::Number(value)) => {
+                Ok(Value::F64(value.as_f64().ok_or_else(mismatch)?))
+            }
+            (ValueType::Number(NumberMeta::Integer { bytes, signed, .. }), json) => {
+                let value = match json {
+                    Json::Number(value) => value
+                        .as_u64()
+                        .map(|value| Integer::Unsigned(value as u128))
+                        .or_else(|| value.as_i64().map(|value| Integer::Signed(value as i128))),
+                    Json::String(value) => value
+                        .parse()
+                        .map(Integer::Unsigned)
+                        .or_else(|_| value.parse().map(Integer::Signed))
+                        .ok(),
+                    _ => None,
+                }
+                .ok_or_else(mismatch)?;
+                if !value.fits(*bytes, *signed) {
+                    return Err(Error::IntegerOutOfRange {
+                        expected: ty.name(),
+                    });
+                }
+                Ok(integer_value(*bytes, *signed, value.pattern()))
+            }
+            (ValueType::String(_), Json::String(value)) => Ok(Value::String(value.to_owned())),
+            (ValueType::Array(meta), Json::Array(items)) => items
+                .iter()
+                .map(|item| self.value(&meta.items_type, item))
+                .collect::<Result<_>>()
+                .map(Value::Array),
+            (ValueType::Tuple(meta), Json::Array(items)) => {
+                self.items(&meta.items_types, items).map(Value::Tuple)
+            }
+            (ValueType::Optional(_), Json::Null) => Ok(Value::Option(None)),
+            (ValueType::Optional(meta), json) => Ok(Value::Option(Some(Box::new(
+                self.value(&meta.inner, json)?,
+            )))),
+            (ValueType::Map(meta), Json::Object(object)) => object
+                .iter()
+                .map(|(key, value)| {
+                    // keys are strings in JSON, integer keys are parsed from them
+                    let key = self.value(&meta.key_type, &Json::String(key.to_owned()))?;
+                    Ok((key, self.value(&meta.value_type, value)?))
+                })
+                .collect::<Result<_>>()
+                .map(Value::Map),
+            (ValueType::Map(meta), Json::Array(entries)) => entries
+                .iter()
+                .map(|entry| match entry {
+                    Json::Array(pair) if pair.len() == 2 => Ok((
+                        self.value(&meta.key_type, &pair[0])?,
+                        self.value(&meta.value_type, &pair[1])?,
+                    )),
+                    _ => Err(mismatch()),
+                })
+                .collect::<Result<_>>()
+                .map(Value::Map),
+            (ValueType::Range(meta), Json::Object(object)) => {
+                let bound = |name: &'static str| {
+                    let json = object.get(name).ok_or(Error::MissingField(name.into()))?;
+                    self.value(&meta.bounds_type, json).map(Box::new)
+                };
+                Ok(Value::Range {
+                    start: bound("start")?,
+                    end: bound("end")?,
+                })
+            }
+            (ValueType::Object(meta), json) => {
+                let container = self.schema.resolve(meta)?;
+                self.container(container, json)
+            }
+            _ => Err(mismatch()),
+        }
+    }
+}
@@ -0,0 +1,665 @@
+mod json;
+
+use core::fmt::Display;
+use std::borrow::Cow;
+use std::error;
+
+use crate::{
+    registry::{BindingType, Container, ContainerCollection, EnumVariantType, StructField},
+    type_info::{IntegerEncoding, NumberMeta, ObjectMeta, ValueType},
+};
+
+/// A value of a type with bindings, decoded from postcard bytes without the Rust type.
+///
+/// Integers keep their width and sign, containers keep the names of fields and enum variants.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Bool(bool),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+    F32(f32),
+    F64(f64),
+    String(String),
+    /// Items of a `Vec`, a slice or an array.
+    Array(Vec<Value>),
+    Tuple(Vec<Value>),
+    Option(Option<Box<Value>>),
+    /// Entries of a map in the order they are serialized in.
+    Map(Vec<(Value, Value)>),
+    Range {
+        start: Box<Value>,
+        end: Box<Value>,
+    },
+    Struct(Fields),
+    Enum {
+        variant: String,
+        fields: Fields,
+    },
+}
+
+/// Fields of a struct or of an enum variant.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Fields {
+    Unit,
+    Unnamed(Vec<Value>),
+    Named(Vec<(String, Value)>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// No container is registered under the type path.
+    UnknownType(String),
+    /// A type references a container which is not part of the collection.
+    DanglingReference {
+        path: String,
+        name: Cow<'static, str>,
+    },
+    /// The bytes end before the value is complete.
+    UnexpectedEnd,
+    /// A varint is longer than its integer type allows.
+    InvalidVarint,
+    InvalidBool(u8),
+    InvalidOptionTag(u8),
+    InvalidUtf8,
+    /// The variant index is not a variant of the enum.
+    InvalidVariantIndex {
+        name: Cow<'static, str>,
+        index: u32,
+    },
+    /// Zero for an integer type which can't be zero.
+    Zero,
+    /// The value doesn't have the shape of the type, which is named like [ValueType::name()].
+    TypeMismatch {
+        expected: String,
+    },
+    /// The integer doesn't fit into the integer type.
+    IntegerOutOfRange {
+        expected: String,
+    },
+    /// The array has a different length than the fixed length of the type.
+    LengthMismatch {
+        expected: usize,
+        found: usize,
+    },
+    /// The array, string or map is longer than the maximum length of the type.
+    LengthExceeded {
+        max: usize,
+        found: usize,
+    },
+    UnknownVariant {
+        name: Cow<'static, str>,
+        variant: String,
+    },
+    MissingField(Cow<'static, str>),
+    UnknownField(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::UnknownType(path) => write!(f, "no type `{path}` is registered"),
+            Error::DanglingReference { path, name } => {
+                write!(f, "`{path}::{name}` is referenced but not registered")
+            }
+            Error::UnexpectedEnd => write!(f, "unexpected end of the bytes"),
+            Error::InvalidVarint => write!(f, "invalid varint"),
+            Error::InvalidBool(byte) => write!(f, "invalid bool {byte:#04x}"),
+            Error::InvalidOptionTag(byte) => write!(f, "invalid option tag {byte:#04x}"),
+            Error::InvalidUtf8 => write!(f, "string is not valid UTF-8"),
+            Error::InvalidVariantIndex { name, index } => {
+                write!(f, "enum `{name}` has no variant with index {index}")
+            }
+            Error::Zero => write!(f, "zero for a non-zero integer"),
+            Error::TypeMismatch { expected } => write!(f, "expected a value of type `{expected}`"),
+            Error::IntegerOutOfRange { expected } => {
+                write!(f, "integer doesn't fit into `{expected}`")
+            }
+            Error::LengthMismatch { expected, found } => {
+                write!(f, "expected {expected} items, found {found}")
+            }
+            Error::LengthExceeded { max, found } => {
+                write!(f, "length {found} exceeds the maximum length {max}")
+            }
+            Error::UnknownVariant { name, variant } => {
+                write!(f, "enum `{name}` has no variant `{variant}`")
+            }
+            Error::MissingField(name) => write!(f, "missing field `{name}`"),
+            Error::UnknownField(name) => write!(f, "unknown field `{name}`"),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+pub use json::from_json;
+
+/// Decodes a value of the container registered under `type_path` from the start of `bytes`.
+///
+/// `type_path` is the Rust path of the container, with or without the crate name, e.g.
+/// `shapes::Shape`. Returns the value and the bytes following it.
+pub fn decode<'b>(
+    containers: &ContainerCollection,
+    type_path: &str,
+    bytes: &'b [u8],
+) -> Result<(Value, &'b [u8])> {
+    let schema = Schema::new(containers);
+    let container = schema.find(type_path)?;
+    let mut decoder = Decoder {
+        schema: &schema,
+        bytes,
+    };
+    let value = decoder.container(container)?;
+    Ok((value, decoder.bytes))
+}
+
+/// Encodes a value of the container registered under `type_path`, the inverse of [decode()].
+///
+/// Numbers are converted to the types of the bindings, integers only if the value fits.
+pub fn encode(containers: &ContainerCollection, type_path: &str, value: &Value) -> Result<Vec<u8>> {
+    let schema = Schema::new(containers);
+    let container = schema.find(type_path)?;
+    let mut encoder = Encoder {
+        schema: &schema,
+        bytes: Vec::new(),
+    };
+    encoder.container(container, value)?;
+    Ok(encoder.bytes)
+}
+
+struct Schema {
+    containers: Vec<Container>,
+}
+
+impl Schema {
+    fn new(containers: &ContainerCollection) -> Self {
+        Self {
+            containers: containers.all_containers().collect(),
+        }
+    }
+
+    fn find(&self, type_path: &str) -> Result<&Container> {
+        self.containers
+            .iter()
+            .find(|container| {
+                let parts = container
+                    .path
+                    .parts()
+                    .filter(|part| !part.is_empty())
+                    .chain([container.name.as_ref()])
+                    .collect::<Vec<_>>();
+                // the first part of the path is the crate name
+                parts.join("::") == type_path
+                    || (parts.len() > 1 && parts[1..].join("::") == type_path)
+            })
+            .ok_or_else(|| Error::UnknownType(type_path.to_owned()))
+    }
+
+    fn resolve(&self, meta: &ObjectMeta) -> Result<&Container> {
+        self.containers
+            .iter()
+            .find(|container| container.name == meta.name && container.path == meta.path)
+            .ok_or_else(|| Error::DanglingReference {
+                path: meta.path.to_string(),
+                name: meta.name.clone(),
+            })
+    }
+}
+
+struct Decoder<'s, 'b> {
+    schema: &'s Schema,
+    bytes: &'b [u8],
+}
+
+impl<'b> Decoder<'_, 'b> {
+    fn take(&mut self, n: usize) -> Result<&'b [u8]> {
+        if self.bytes.len() < n {
+            return Err(Error::UnexpectedEnd);
+        }
+        let (taken, rest) = self.bytes.split_at(n);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn byte(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn varint(&mut self, bits: usize) -> Result<u128> {
+        let max_bytes = bits.div_ceil(7);
+        let mut value = 0u128;
+        for i in 0..max_bytes {
+            let byte = self.byte()?;
+            value |= ((byte & 0x7F) as u128) << (7 * i);
+            if byte & 0x80 == 0 {
+                if i == max_bytes - 1 && (byte as u128) >> (bits - 7 * i) != 0 {
+                    return Err(Error::InvalidVarint);
+                }
+                return Ok(value);
+            }
+        }
+        Err(Error::InvalidVarint)
+    }
+
+    fn length(&mut self, max_length: Option<usize>) -> Result<usize> {
+        let length = self.varint(64)? as usize;
+        check_max_length(length, max_length)?;
+        Ok(length)
+    }
+
+    fn container(&mut self, container: &Container) -> Result<Value> {
+        match &container.r#type {
+            BindingType::Struct(ty) => Ok(Value::Struct(self.named_fields(&ty.fields)?)),
+            BindingType::TupleStruct(ty) => Ok(Value::Struct(self.unnamed_fields(&ty.fields)?)),
+            BindingType::UnitStruct(_) => Ok(Value::Struct(Fields::Unit)),
+            BindingType::Enum(ty) => {
+                let index = self.varint(32)? as u32;
+                let variant = ty
+                    .variants
+                    .iter()
+                    .find(|variant| variant.index as u32 == index)
+                    .ok_or(Error::InvalidVariantIndex {
+                        name: container.name.clone(),
+                        index,
+                    })?;
+                let fields = match &variant.inner_type {
+                    EnumVariantType::Empty => Fields::Unit,
+                    EnumVariantType::Tuple(fields) => self.unnamed_fields(fields)?,
+                    EnumVariantType::NewType(fields) => self.named_fields(fields)?,
+                };
+                Ok(Value::Enum {
+                    variant: variant.name.to_string(),
+                    fields,
+                })
+            }
+        }
+    }
+
+    fn named_fields(&mut self, fields: &[StructField]) -> Result<Fields> {
+        let values = fields
+            .iter()
+            .map(|field| Ok((field.name.to_string(), self.value(&field.v_type)?)))
+            .collect::<Result<_>>()?;
+        Ok(Fields::Named(values))
+    }
+
+    fn unnamed_fields(&mut self, fields: &[ValueType]) -> Result<Fields> {
+        let values = fields
+            .iter()
+            .map(|ty| self.value(ty))
+            .collect::<Result<_>>()?;
+        Ok(Fields::Unnamed(values))
+    }
+
+    fn value(&mut self, ty: &ValueType) -> Result<Value> {
+        match ty {
+            ValueType::Bool(_) => match self.byte()? {
+                0 => Ok(Value::Bool(false)),
+                1 => Ok(Value::Bool(true)),
+                byte => Err(Error::InvalidBool(byte)),
+            },
+            ValueType::Number(NumberMeta::FloatingPoint { bytes: 4 }) => Ok(Value::F32(
+                f32::from_le_bytes(self.take(4)?.try_into().unwrap()),
+            )),
+            ValueType::Number(NumberMeta::FloatingPoint { .. }) => Ok(Value::F64(
+                f64::from_le_bytes(self.take(8)?.try_into().unwrap()),
+            )),
+            ValueType::Number(NumberMeta::Integer {
+                bytes,
+                signed,
+                zero_able,
+                encoding,
+            }) => {
+                let bits = bytes * 8;
+                let pattern = match encoding {
+                    _ if *bytes == 1 => self.byte()? as u128,
+                    IntegerEncoding::Varint if *signed => {
+                        let zigzag = self.varint(bits)?;
+                        ((zigzag >> 1) as i128 ^ -((zigzag & 1) as i128)) as u128
+                    }
+                    IntegerEncoding::Varint => self.varint(bits)?,
+                    IntegerEncoding::FixedLe => self
+                        .take(*bytes)?
+                        .iter()
+                        .rev()
+                        .fold(0, |value, byte| (value << 8) | *byte as u128),
+                    IntegerEncoding::FixedBe => self
+                        .take(*bytes)?
+                        .iter()
+                        .fold(0, |value, byte| (value << 8) | *byte as u128),
+                };
+                if pattern == 0 && !zero_able {
+                    return Err(Error::Zero);
+                }
+                Ok(integer_value(*bytes, *signed, pattern))
+            }
+            ValueType::String(meta) => {
+                let length = self.length(meta.max_length)?;
+                let string =
+                    core::str::from_utf8(self.take(length)?).map_err(|_| Error::InvalidUtf8)?;
+                Ok(Value::String(string.to_owned()))
+            }
+            ValueType::Array(meta) => {
+                let length = match meta.length {
+                    Some(length) => length,
+                    None => self.length(meta.max_length)?,
+                };
+                let items = (0..length)
+                    .map(|_| self.value(&meta.items_type))
+                    .collect::<Result<_>>()?;
+                Ok(Value::Array(items))
+            }
+            ValueType::Tuple(meta) => {
+                let items = meta
+                    .items_types
+                    .iter()
+                    .map(|ty| self.value(ty))
+                    .collect::<Result<_>>()?;
+                Ok(Value::Tuple(items))
+            }
+            ValueType::Optional(meta) => match self.byte()? {
+                0 => Ok(Value::Option(None)),
+                1 => Ok(Value::Option(Some(Box::new(self.value(&meta.inner)?)))),
+                byte => Err(Error::InvalidOptionTag(byte)),
+            },
+            ValueType::Map(meta) => {
+                let length = self.length(meta.max_length)?;
+                let entries = (0..length)
+                    .map(|_| Ok((self.value(&meta.key_type)?, self.value(&meta.value_type)?)))
+                    .collect::<Result<_>>()?;
+                Ok(Value::Map(entries))
+            }
+            ValueType::Range(meta) => Ok(Value::Range {
+                start: Box::new(self.value(&meta.bounds_type)?),
+                end: Box::new(self.value(&meta.bounds_type)?),
+            }),
+            ValueType::Object(meta) => {
+                let container = self.schema.resolve(meta)?;
+                self.container(container)
+            }
+        }
+    }
+}
+
+struct Encoder<'s> {
+    schema: &'s Schema,
+    bytes: Vec<u8>,
+}
+
+impl Encoder<'_> {
+    fn varint(&mut self, mut value: u128) {
+        while value > 0x7F {
+            self.bytes.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        self.bytes.push(value as u8);
+    }
+
+    fn length(&mut self, length: usize, max_length: Option<usize>) -> Result<()> {
+        check_max_length(length, max_length)?;
+        self.varint(length as u128);
+        Ok(())
+    }
+
+    fn container(&mut self, container: &Container, value: &Value) -> Result<()> {
+        let mismatch = || Error::TypeMismatch {
+            expected: container.name.to_string(),
+        };
+        match (&container.r#type, value) {
+            (BindingType::Struct(ty), Value::Struct(fields)) => {
+                self.named_fields(&ty.fields, fields, mismatch)
+            }
+            (BindingType::TupleStruct(ty), Value::Struct(fields)) => {
+                self.unnamed_fields(&ty.fields, fields, mismatch)
+            }
+            (BindingType::UnitStruct(_), Value::Struct(Fields::Unit)) => Ok(()),
+            (BindingType::Enum(ty), Value::Enum { variant, fields }) => {
+                let schema_variant = ty
+                    .variants
+                    .iter()
+                    .find(|v| v.name == variant.as_str())
+                    .ok_or_else(|| Error::UnknownVariant {
+                        name: container.name.clone(),
+                        variant: variant.to_owned(),
+                    })?;
+                self.varint(schema_variant.index as u128);
+                match (&schema_variant.inner_type, fields) {
+                    (EnumVariantType::Empty, Fields::Unit) => Ok(()),
+                    (EnumVariantType::Tuple(types), fields) => {
+                        self.unnamed_fields(types, fields, mismatch)
+                    }
+                    (EnumVariantType::NewType(types), fields) => {
+                        self.named_fields(types, fields, mismatch)
+                    }
+                    _ => Err(mismatch()),
+                }
+            }
+            _ => Err(mismatch()),
+        }
+    }
+
+    fn named_fields(
+        &mut self,
+        types: &[StructField],
+        fields: &Fields,
+        mismatch: impl Fn() -> Error,
+    ) -> Result<()> {
+        let Fields::Named(fields) = fields else {
+            return Err(mismatch());
+        };
+        if let Some((name, _)) = fields
+            .iter()
+            .find(|(name, _)| !types.iter().any(|ty| ty.name == name.as_str()))
+        {
+            return Err(Error::UnknownField(name.to_owned()));
+        }
+        for ty in types {
+            let (_, value) = fields
+                .iter()
+                .find(|(name, _)| ty.name == name.as_str())
+                .ok_or(Error::MissingField(ty.name.clone()))?;
+            self.value(&ty.v_type, value)?;
+        }
+        Ok(())
+    }
+
+    fn unnamed_fields(
+        &mut self,
+        types: &[ValueType],
+        fields: &Fields,
+        mismatch: impl Fn() -> Error,
+    ) -> Result<()> {
+        let Fields::Unnamed(values) = fields else {
+            return Err(mismatch());
+        };
+        self.items(types.iter(), values)
+    }
+
+    fn items<'t>(
+        &mut self,
+        types: impl ExactSizeIterator<Item = &'t ValueType>,
+        values: &[Value],
+    ) -> Result<()> {
+        if types.len() != values.len() {
+            return Err(Error::LengthMismatch {
+                expected: types.len(),
+                found: values.len(),
+            });
+        }
+        for (ty, value) in types.zip(values) {
+            self.value(ty, value)?;
+        }
+        Ok(())
+    }
+
+    fn value(&mut self, ty: &ValueType, value: &Value) -> Result<()> {
+        let mismatch = || Error::TypeMismatch {
+            expected: ty.name(),
+        };
+        match (ty, value) {
+            (ValueType::Bool(_), Value::Bool(value)) => self.bytes.push(*value as u8),
+            (ValueType::Number(NumberMeta::FloatingPoint { bytes }), value) => {
+                let float = match value {
+                    Value::F32(value) => *value as f64,
+                    Value::F64(value) => *value,
+                    _ => return Err(mismatch()),
+                };
+                if *bytes == 4 {
+                    self.bytes.extend((float as f32).to_le_bytes());
+                } else {
+                    self.bytes.extend(float.to_le_bytes());
+                }
+            }
+            (
+                ValueType::Number(NumberMeta::Integer {
+                    bytes,
+                    signed,
+                    zero_able,
+                    encoding,
+                }),
+                value,
+            ) => {
+                let value = integer(value).ok_or_else(mismatch)?;
+                if !value.fits(*bytes, *signed) {
+                    return Err(Error::IntegerOutOfRange {
+                        expected: ty.name(),
+                    });
+                }
+                let pattern = value.pattern();
+                if pattern == 0 && !zero_able {
+                    return Err(Error::Zero);
+                }
+                let le_bytes = pattern.to_le_bytes();
+                match encoding {
+                    _ if *bytes == 1 => self.bytes.push(pattern as u8),
+                    IntegerEncoding::Varint if *signed => {
+                        let value = pattern as i128;
+                        self.varint((value.wrapping_shl(1) ^ (value >> 127)) as u128)
+                    }
+                    IntegerEncoding::Varint => self.varint(pattern),
+                    IntegerEncoding::FixedLe => self.bytes.extend(&le_bytes[..*bytes]),
+                    IntegerEncoding::FixedBe => self.bytes.extend(le_bytes[..*bytes].iter().rev()),
+                }
+            }
+            (ValueType::String(meta), Value::String(value)) => {
+                self.length(value.len(), meta.max_length)?;
+                self.bytes.extend(value.as_bytes());
+            }
+            (ValueType::Array(meta), Value::Array(items)) => {
+                match meta.length {
+                    Some(length) if length != items.len() => {
+                        return Err(Error::LengthMismatch {
+                            expected: length,
+                            found: items.len(),
+                        })
+                    }
+                    Some(_) => {}
+                    None => self.length(items.len(), meta.max_length)?,
+                }
+                for item in items {
+                    self.value(&meta.items_type, item)?;
+                }
+            }
+            (ValueType::Tuple(meta), Value::Tuple(items)) => {
+                self.items(meta.items_types.iter(), items)?
+            }
+            (ValueType::Optional(_), Value::Option(None)) => self.bytes.push(0),
+            (ValueType::Optional(meta), Value::Option(Some(value))) => {
+                self.bytes.push(1);
+                self.value(&meta.inner, value)?;
+            }
+            (ValueType::Map(meta), Value::Map(entries)) => {
+                self.length(entries.len(), meta.max_length)?;
+                for (key, value) in entries {
+                    self.value(&meta.key_type, key)?;
+                    self.value(&meta.value_type, value)?;
+                }
+            }
+            (ValueType::Range(meta), Value::Range { start, end }) => {
+                self.value(&meta.bounds_type, start)?;
+                self.value(&meta.bounds_type, end)?;
+            }
+            (ValueType::Object(meta), value) => {
+                let container = self.schema.resolve(meta)?;
+                self.container(container, value)?;
+            }
+            _ => return Err(mismatch()),
+        }
+        Ok(())
+    }
+}
+
+fn check_max_length(length: usize, max_length: Option<usize>) -> Result<()> {
+    match max_length {
+        Some(max) if length > max => Err(Error::LengthExceeded { max, found: length }),
+        _ => Ok(()),
+    }
+}
+
+/// Value of an integer type from the bits of its two's complement.
+fn integer_value(bytes: usize, signed: bool, pattern: u128) -> Value {
+    match (bytes, signed) {
+        (1, false) => Value::U8(pattern as u8),
+        (2, false) => Value::U16(pattern as u16),
+        (4, false) => Value::U32(pattern as u32),
+        (8, false) => Value::U64(pattern as u64),
+        (1, true) => Value::I8(pattern as i8),
+        (2, true) => Value::I16(pattern as i16),
+        (4, true) => Value::I32(pattern as i32),
+        (8, true) => Value::I64(pattern as i64),
+        (_, false) => Value::U128(pattern),
+        (_, true) => Value::I128(pattern as i128),
+    }
+}
+
+enum Integer {
+    Unsigned(u128),
+    Signed(i128),
+}
+
+impl Integer {
+    fn fits(&self, bytes: usize, signed: bool) -> bool {
+        let bits = (bytes * 8) as u32;
+        match *self {
+            Integer::Unsigned(value) if signed => value < 1 << (bits - 1),
+            Integer::Unsigned(value) => bits == 128 || value < 1 << bits,
+            Integer::Signed(value) if signed => {
+                bits == 128 || (-(1 << (bits - 1))..1 << (bits - 1)).contains(&value)
+            }
+            Integer::Signed(value) => value >= 0 && (bits == 128 || (value as u128) < 1 << bits),
+        }
+    }
+
+    /// Bits of the integer in two's complement.
+    fn pattern(&self) -> u128 {
+        match *self {
+            Integer::Unsigned(value) => value,
+            Integer::Signed(value) => value as u128,
+        }
+    }
+}
+
+fn integer(value: &Value) -> Option<Integer> {
+    Some(match value {
+        Value::U8(value) => Integer::Unsigned(*value as u128),
+        Value::U16(value) => Integer::Unsigned(*value as u128),
+        Value::U32(value) => Integer::Unsigned(*value as u128),
+        Value::U64(value) => Integer::Unsigned(*value as u128),
+        Value::U128(value) => Integer::Unsigned(*value),
+        Value::I8(value) => Integer::Signed(*value as i128),
+        Value::I16(value) => Integer::Signed(*value as i128),
+        Value::I32(value) => Integer::Signed(*value as i128),
+        Value::I64(value) => Integer::Signed(*value as i128),
+        Value::I128(value) => Integer::Signed(*value),
+        _ => return None,
+    })
+}
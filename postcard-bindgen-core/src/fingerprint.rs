@@ -0,0 +1,288 @@
+use crate::{
+    registry::{BindingType, Container, ContainerCollection, EnumVariantType, StructField},
+    type_info::{IntegerEncoding, NumberMeta, ValueType},
+};
+
+const FNV_OFFSET_BASIS: u32 = 0x811c9dc5;
+const FNV_PRIME: u32 = 0x01000193;
+
+impl Container {
+    /// Returns a stable 32 bit hash over the structure of the container.
+    ///
+    /// The hash covers the name of the container, the names and order of its fields, the
+    /// names and indices of its variants and the full [ValueType] of every field. Containers
+    /// referenced by fields are included by name only, their structure is part of their own
+    /// fingerprint. Module paths are left out, so moving a type to another module or crate
+    /// keeps its fingerprint.
+    ///
+    /// The hash is FNV-1a over a fixed encoding of the structure and doesn't change between
+    /// builds, platforms or versions of this crate.
+    pub fn fingerprint(&self) -> u32 {
+        let mut hasher = Fingerprint::new();
+        hasher.container(self);
+        hasher.finish()
+    }
+}
+
+impl ContainerCollection {
+    /// Returns a stable 32 bit hash over all containers of the collection.
+    ///
+    /// The hash is built from the [Container::fingerprint()] of every container, independent
+    /// of the order the containers were registered in.
+    pub fn fingerprint(&self) -> u32 {
+        let mut fingerprints = self
+            .all_containers()
+            .map(|container| container.fingerprint())
+            .collect::<Vec<_>>();
+        fingerprints.sort_unstable();
+
+        let mut hasher = Fingerprint::new();
+        hasher.len(fingerprints.len());
+        for fingerprint in fingerprints {
+            hasher.bytes(&fingerprint.to_le_bytes());
+        }
+        hasher.finish()
+    }
+}
+
+/// FNV-1a hasher writing the structure of a container as tagged, length prefixed tokens, so
+/// different structures can't produce the same input.
+struct Fingerprint(u32);
+
+impl Fingerprint {
+    fn new() -> Self {
+        Self(FNV_OFFSET_BASIS)
+    }
+
+    fn finish(self) -> u32 {
+        self.0
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ *byte as u32).wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn tag(&mut self, tag: u8) {
+        self.bytes(&[tag]);
+    }
+
+    fn len(&mut self, len: usize) {
+        self.bytes(&(len as u64).to_le_bytes());
+    }
+
+    fn str(&mut self, value: &str) {
+        self.len(value.len());
+        self.bytes(value.as_bytes());
+    }
+
+    fn bool(&mut self, value: bool) {
+        self.tag(value as u8);
+    }
+
+    fn option_len(&mut self, len: Option<usize>) {
+        match len {
+            Some(len) => {
+                self.tag(1);
+                self.len(len);
+            }
+            None => self.tag(0),
+        }
+    }
+
+    fn container(&mut self, container: &Container) {
+        self.str(&container.name);
+        match &container.r#type {
+            BindingType::Struct(ty) => {
+                self.tag(0);
+                self.named_fields(&ty.fields);
+            }
+            BindingType::TupleStruct(ty) => {
+                self.tag(1);
+                self.unnamed_fields(&ty.fields);
+            }
+            BindingType::UnitStruct(_) => self.tag(2),
+            BindingType::Enum(ty) => {
+                self.tag(3);
+                self.len(ty.variants.len());
+                for variant in &ty.variants {
+                    self.len(variant.index);
+                    self.str(&variant.name);
+                    match &variant.inner_type {
+                        EnumVariantType::Empty => self.tag(0),
+                        EnumVariantType::Tuple(fields) => {
+                            self.tag(1);
+                            self.unnamed_fields(fields);
+                        }
+                        EnumVariantType::NewType(fields) => {
+                            self.tag(2);
+                            self.named_fields(fields);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn named_fields(&mut self, fields: &[StructField]) {
+        self.len(fields.len());
+        for field in fields {
+            self.str(&field.name);
+            self.value_type(&field.v_type);
+        }
+    }
+
+    fn unnamed_fields(&mut self, fields: &[ValueType]) {
+        self.len(fields.len());
+        for field in fields {
+            self.value_type(field);
+        }
+    }
+
+    fn value_type(&mut self, ty: &ValueType) {
+        match ty {
+            ValueType::Number(NumberMeta::Integer {
+                bytes,
+                signed,
+                zero_able,
+                encoding,
+            }) => {
+                self.tag(0);
+                self.len(*bytes);
+                self.bool(*signed);
+                self.bool(*zero_able);
+                self.tag(match encoding {
+                    IntegerEncoding::Varint => 0,
+                    IntegerEncoding::FixedLe => 1,
+                    IntegerEncoding::FixedBe => 2,
+                });
+            }
+            ValueType::Number(NumberMeta::FloatingPoint { bytes }) => {
+                self.tag(1);
+                self.len(*bytes);
+            }
+            ValueType::Array(meta) => {
+                self.tag(2);
+                self.value_type(&meta.items_type);
+                self.option_len(meta.length);
+                self.option_len(meta.max_length);
+            }
+            ValueType::String(meta) => {
+                self.tag(3);
+                self.option_len(meta.max_length);
+            }
+            ValueType::Object(meta) => {
+                self.tag(4);
+                self.str(&meta.name);
+            }
+            ValueType::Optional(meta) => {
+                self.tag(5);
+                self.value_type(&meta.inner);
+            }
+            ValueType::Range(meta) => {
+                self.tag(6);
+                self.value_type(&meta.bounds_type);
+            }
+            ValueType::Map(meta) => {
+                self.tag(7);
+                self.value_type(&meta.key_type);
+                self.value_type(&meta.value_type);
+                self.option_len(meta.max_length);
+            }
+            ValueType::Tuple(meta) => {
+                self.tag(8);
+                self.unnamed_fields(&meta.items_types);
+            }
+            ValueType::Bool(_) => self.tag(9),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        path::Path,
+        registry::{
+            BindingType, Container, EnumType, EnumVariant, EnumVariantType, StructField, StructType,
+        },
+        type_info::{GenBinding, ValueType},
+    };
+
+    use super::Fingerprint;
+
+    fn record(fields: &[(&'static str, ValueType)]) -> Container {
+        Container {
+            path: Path::new("crate", "::"),
+            name: "Record".into(),
+            r#type: BindingType::Struct(StructType {
+                fields: fields
+                    .iter()
+                    .map(|(name, v_type)| StructField {
+                        name: (*name).into(),
+                        v_type: v_type.clone(),
+                        doc: None,
+                    })
+                    .collect(),
+            }),
+            doc: None,
+        }
+    }
+
+    #[test]
+    fn test_fnv_1a_reference_values() {
+        let hash = |bytes: &[u8]| {
+            let mut hasher = Fingerprint::new();
+            hasher.bytes(bytes);
+            hasher.finish()
+        };
+        assert_eq!(hash(b""), 0x811c9dc5);
+        assert_eq!(hash(b"a"), 0xe40c292c);
+        assert_eq!(hash(b"foobar"), 0xbf9cf968);
+    }
+
+    #[test]
+    fn test_fingerprint_covers_structure() {
+        let base = record(&[("a", u8::get_type()), ("b", u16::get_type())]);
+        let fingerprint = base.fingerprint();
+
+        // field order, names and types
+        let changed = [
+            record(&[("b", u16::get_type()), ("a", u8::get_type())]),
+            record(&[("a", u8::get_type()), ("c", u16::get_type())]),
+            record(&[("a", u8::get_type()), ("b", u32::get_type())]),
+            record(&[("a", u8::get_type()), ("b", i16::get_type())]),
+            record(&[("a", u8::get_type()), ("b", Option::<u16>::get_type())]),
+            record(&[("a", u8::get_type())]),
+        ];
+        for container in changed {
+            assert_ne!(container.fingerprint(), fingerprint, "{container:?}");
+        }
+
+        // the container name, but not its module
+        let mut renamed = base.clone();
+        renamed.name = "Other".into();
+        assert_ne!(renamed.fingerprint(), fingerprint);
+        let mut moved = base.clone();
+        moved.path = Path::new("other::module", "::");
+        assert_eq!(moved.fingerprint(), fingerprint);
+    }
+
+    #[test]
+    fn test_fingerprint_covers_variant_indices() {
+        let status = |index| Container {
+            path: Path::new("crate", "::"),
+            name: "Status".into(),
+            r#type: BindingType::Enum(EnumType {
+                variants: vec![EnumVariant {
+                    index,
+                    name: "Idle".into(),
+                    inner_type: EnumVariantType::Empty,
+                    doc: None,
+                }],
+            }),
+            doc: None,
+        };
+        assert_ne!(status(0).fingerprint(), status(1).fingerprint());
+    }
+}
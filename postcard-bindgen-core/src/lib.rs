@@ -5,11 +5,21 @@ extern crate alloc;
 #[cfg(feature = "generating")]
 pub mod code_gen;
 #[cfg(feature = "generating")]
+pub mod compat;
+#[cfg(feature = "generating")]
+pub mod dynamic;
+#[cfg(feature = "generating")]
+mod fingerprint;
+#[cfg(feature = "generating")]
 pub mod path;
 #[cfg(feature = "generating")]
 pub mod registry;
 #[cfg(feature = "generating")]
+pub mod schema;
+#[cfg(feature = "generating")]
 pub mod type_info;
+#[cfg(feature = "generating")]
+pub mod validation;
 
 #[cfg(feature = "generating")]
 pub use genco::lang;
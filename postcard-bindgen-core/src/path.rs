@@ -2,6 +2,7 @@ use core::fmt::Display;
 use std::borrow::Cow;
 
 use genco::{lang::Lang, tokens::FormatInto};
+use serde::{Deserialize, Serialize};
 
 /// A part of a path which can either be [str] or [String].
 pub type Part<'a> = Cow<'a, str>;
@@ -150,7 +151,7 @@ impl<'a> From<&'a str> for PathBuf<'a> {
 /// let path = Path::new("foo/bar/baz", "/");
 ///
 /// assert_eq!(path.parts().collect::<Vec<&str>>(), vec!["foo", "bar", "baz"]);
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Path<'a, 'b> {
     path: Option<FullPath<'a>>,
     joiner: Cow<'b, str>,
@@ -1,19 +1,25 @@
 use core::fmt::Display;
-use std::{borrow::Cow, collections::VecDeque};
+use std::{
+    borrow::Cow,
+    collections::{BTreeSet, VecDeque},
+};
 
 use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
 use tree_ds::prelude::{Node, NodeRemovalStrategy, Tree};
 
 use crate::{
     path::Path,
-    type_info::{GenBinding, ValueType},
+    type_info::{GenBinding, ObjectMeta, ValueType},
 };
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Container {
     pub path: Path<'static, 'static>,
-    pub name: &'static str,
+    pub name: Cow<'static, str>,
     pub r#type: BindingType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<Cow<'static, str>>,
 }
 
 impl Container {
@@ -32,18 +38,20 @@ impl Container {
 pub struct ContainerInfo<'a> {
     pub name: Cow<'a, str>,
     pub path: Path<'a, 'a>,
+    pub doc: Option<&'a str>,
 }
 
-impl From<&Container> for ContainerInfo<'_> {
-    fn from(container: &Container) -> Self {
+impl<'a> From<&'a Container> for ContainerInfo<'a> {
+    fn from(container: &'a Container) -> Self {
         Self {
-            name: Cow::Borrowed(container.name),
+            name: Cow::Borrowed(&container.name),
             path: container.path.clone(),
+            doc: container.doc.as_deref(),
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BindingType {
     Struct(StructType),
     TupleStruct(TupleStructType),
@@ -51,7 +59,36 @@ pub enum BindingType {
     Enum(EnumType),
 }
 
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
+impl BindingType {
+    /// Returns the types of all fields of the type, including the fields of all enum variants.
+    pub(crate) fn field_types(&self) -> Vec<&ValueType> {
+        match self {
+            BindingType::Struct(ty) => ty.fields.iter().map(|f| &f.v_type).collect(),
+            BindingType::TupleStruct(ty) => ty.fields.iter().collect(),
+            BindingType::UnitStruct(_) => Vec::new(),
+            BindingType::Enum(ty) => ty
+                .variants
+                .iter()
+                .flat_map(|variant| match &variant.inner_type {
+                    EnumVariantType::Empty => Vec::new(),
+                    EnumVariantType::Tuple(fields) => fields.iter().collect(),
+                    EnumVariantType::NewType(fields) => fields.iter().map(|f| &f.v_type).collect(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns all containers referenced by the fields of the type.
+    pub(crate) fn referenced_objects(&self) -> Vec<&ObjectMeta> {
+        let mut objects = Vec::new();
+        for ty in self.field_types() {
+            ty.referenced_objects(&mut objects);
+        }
+        objects
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 // encoded into | variant index | (inner)
 pub struct EnumType {
     pub variants: Vec<EnumVariant>,
@@ -66,27 +103,37 @@ impl EnumType {
     pub fn register_variant(&mut self, name: &'static str) {
         self.variants.push(EnumVariant {
             index: self.variants.len(),
-            name,
+            name: name.into(),
             inner_type: EnumVariantType::Empty,
+            doc: None,
         });
     }
 
     pub fn register_variant_tuple(&mut self, name: &'static str, fields: TupleFields) {
         self.variants.push(EnumVariant {
             index: self.variants.len(),
-            name,
+            name: name.into(),
             inner_type: EnumVariantType::Tuple(fields.into_inner()),
+            doc: None,
         });
     }
 
     pub fn register_unnamed_struct(&mut self, name: &'static str, fields: StructFields) {
         self.variants.push(EnumVariant {
             index: self.variants.len(),
-            name,
+            name: name.into(),
             inner_type: EnumVariantType::NewType(fields.into_inner()),
+            doc: None,
         })
     }
 
+    /// Sets the doc comment of the registered variant with the given name.
+    pub fn document_variant(&mut self, name: &str, doc: &'static str) {
+        if let Some(variant) = self.variants.iter_mut().find(|v| v.name == name) {
+            variant.doc = Some(doc.into());
+        }
+    }
+
     fn flatten_paths(&mut self) {
         for variant in &mut self.variants {
             match &mut variant.inner_type {
@@ -106,11 +153,13 @@ impl EnumType {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct EnumVariant {
     pub index: usize,
-    pub name: &'static str,
+    pub name: Cow<'static, str>,
     pub inner_type: EnumVariantType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<Cow<'static, str>>,
 }
 
 impl AsRef<EnumVariant> for EnumVariant {
@@ -119,7 +168,7 @@ impl AsRef<EnumVariant> for EnumVariant {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EnumVariantType {
     Empty,
     Tuple(Vec<ValueType>),
@@ -127,7 +176,7 @@ pub enum EnumVariantType {
     NewType(Vec<StructField>),
 }
 
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StructType {
     pub fields: Vec<StructField>,
 }
@@ -139,11 +188,17 @@ impl StructType {
 
     pub fn register_field<T: GenBinding>(&mut self, name: &'static str) {
         self.fields.push(StructField {
-            name,
+            name: name.into(),
             v_type: T::get_type(),
+            doc: None,
         })
     }
 
+    /// Sets the doc comment of the registered field with the given name.
+    pub fn document_field(&mut self, name: &str, doc: &'static str) {
+        document_field(&mut self.fields, name, doc)
+    }
+
     fn flatten_paths(&mut self) {
         for field in &mut self.fields {
             field.v_type.flatten_paths();
@@ -151,7 +206,7 @@ impl StructType {
     }
 }
 
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TupleStructType {
     pub fields: Vec<ValueType>,
 }
@@ -172,7 +227,7 @@ impl TupleStructType {
     }
 }
 
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct UnitStructType;
 
 impl UnitStructType {
@@ -181,10 +236,19 @@ impl UnitStructType {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StructField {
-    pub name: &'static str,
+    pub name: Cow<'static, str>,
     pub v_type: ValueType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<Cow<'static, str>>,
+}
+
+/// Sets the doc comment of the field with the given name.
+fn document_field(fields: &mut [StructField], name: &str, doc: &'static str) {
+    if let Some(field) = fields.iter_mut().find(|f| f.name == name) {
+        field.doc = Some(doc.into());
+    }
 }
 
 #[derive(Debug, Default)]
@@ -193,11 +257,17 @@ pub struct StructFields(Vec<StructField>);
 impl StructFields {
     pub fn register_field<T: GenBinding>(&mut self, name: &'static str) {
         self.0.push(StructField {
-            name,
+            name: name.into(),
             v_type: T::get_type(),
+            doc: None,
         })
     }
 
+    /// Sets the doc comment of the registered field with the given name.
+    pub fn document_field(&mut self, name: &str, doc: &'static str) {
+        document_field(&mut self.0, name, doc)
+    }
+
     fn into_inner(self) -> Vec<StructField> {
         self.0
     }
@@ -439,67 +509,104 @@ impl NodeType {
 }
 
 #[derive(Debug)]
-pub struct BindingsRegistry(Tree<NodeId, NodeType>);
+pub struct BindingsRegistry {
+    tree: Tree<NodeId, NodeType>,
+    // the (path, name) pairs of the registered containers
+    registered: BTreeSet<(Path<'static, 'static>, Cow<'static, str>)>,
+}
 
 impl BindingsRegistry {
     pub fn register_struct_binding(
         &mut self,
-        name: &'static str,
+        name: impl Into<Cow<'static, str>>,
         path: impl Into<Cow<'static, str>>,
         value: StructType,
     ) {
         self.insert_container(Container {
             path: Path::new(path, "::"),
-            name,
+            name: name.into(),
             r#type: BindingType::Struct(value),
+            doc: None,
         });
     }
 
     pub fn register_tuple_struct_binding(
         &mut self,
-        name: &'static str,
+        name: impl Into<Cow<'static, str>>,
         path: impl Into<Cow<'static, str>>,
         value: TupleStructType,
     ) {
         self.insert_container(Container {
             path: Path::new(path, "::"),
-            name,
+            name: name.into(),
             r#type: BindingType::TupleStruct(value),
+            doc: None,
         });
     }
 
     pub fn register_unit_struct_binding(
         &mut self,
-        name: &'static str,
+        name: impl Into<Cow<'static, str>>,
         path: impl Into<Cow<'static, str>>,
         value: UnitStructType,
     ) {
         self.insert_container(Container {
             path: Path::new(path, "::"),
-            name,
+            name: name.into(),
             r#type: BindingType::UnitStruct(value),
+            doc: None,
         });
     }
 
     pub fn register_enum_binding(
         &mut self,
-        name: &'static str,
+        name: impl Into<Cow<'static, str>>,
         path: impl Into<Cow<'static, str>>,
         value: EnumType,
     ) {
         self.insert_container(Container {
             path: Path::new(path, "::"),
-            name,
+            name: name.into(),
             r#type: BindingType::Enum(value),
+            doc: None,
         });
     }
 
+    /// Returns `true` if a container with the given name is already registered at the path.
+    pub fn contains_binding(&self, name: &str, path: impl Into<Cow<'static, str>>) -> bool {
+        self.registered
+            .contains(&(Path::new(path, "::"), Cow::Owned(name.to_owned())))
+    }
+
+    /// Sets the doc comment of the container registered with the given name at the path.
+    pub fn document_binding(
+        &mut self,
+        name: &str,
+        path: impl Into<Cow<'static, str>>,
+        doc: &'static str,
+    ) {
+        let path = Path::new(path, "::");
+        for node in self.tree.get_nodes().iter() {
+            node.update_value(|value| {
+                if let Some(NodeType::Container(c)) = value {
+                    if c.name == name && c.path == path {
+                        c.doc = Some(doc.into());
+                    }
+                }
+            })
+            .unwrap();
+        }
+    }
+
     pub fn into_entries(self) -> ContainerCollection {
-        ContainerCollection(self.0)
+        ContainerCollection(self.tree)
     }
 
-    fn insert_container(&mut self, container: Container) {
-        let mut node = self.0.get_root_node().unwrap();
+    pub(crate) fn insert_container(&mut self, container: Container) {
+        self.registered
+            .insert((container.path.clone(), container.name.clone()));
+
+        let mut node = self.tree.get_root_node().unwrap();
         let node_id = {
             let container_path = &container.path;
             let mut parts = container_path
@@ -514,7 +621,7 @@ impl BindingsRegistry {
                 if let Some(part) = part {
                     let node_ids = node.get_children_ids().unwrap();
                     let child = node_ids.iter().find(|child| {
-                        let node = self.0.get_node_by_id(child).unwrap();
+                        let node = self.tree.get_node_by_id(child).unwrap();
                         matches!(node.get_value(), Ok(Some(NodeType::Module(p))) if p == part)
                     });
 
@@ -523,7 +630,7 @@ impl BindingsRegistry {
                             break PathExists::Full(*child);
                         }
 
-                        node = self.0.get_node_by_id(child).unwrap();
+                        node = self.tree.get_node_by_id(child).unwrap();
                     } else {
                         break PathExists::Partly(node.get_node_id().unwrap(), part.into());
                     }
@@ -536,7 +643,7 @@ impl BindingsRegistry {
                 PathExists::Full(node_id) => node_id,
                 PathExists::Partly(node_id, part) => {
                     let mut node = self
-                        .0
+                        .tree
                         .add_node(
                             Node::new_with_auto_id(Some(NodeType::Module(part.clone()))),
                             Some(&node_id),
@@ -545,7 +652,7 @@ impl BindingsRegistry {
 
                     for part in parts {
                         node = self
-                            .0
+                            .tree
                             .add_node(
                                 Node::new_with_auto_id(Some(NodeType::Module(part.clone().into()))),
                                 Some(&node),
@@ -558,7 +665,7 @@ impl BindingsRegistry {
             }
         };
 
-        self.0
+        self.tree
             .add_node(
                 Node::new_with_auto_id(Some(NodeType::Container(container))),
                 Some(&node_id),
@@ -575,7 +682,10 @@ impl Default for BindingsRegistry {
             None,
         )
         .unwrap();
-        Self(tree)
+        Self {
+            tree,
+            registered: BTreeSet::new(),
+        }
     }
 }
 
@@ -586,7 +696,7 @@ pub trait JsBindings {
 #[cfg(test)]
 mod test {
     use crate::registry::{
-        BindingsRegistry, EnumType, JsBindings, StructFields, StructType, TupleFields,
+        BindingType, BindingsRegistry, EnumType, JsBindings, StructFields, StructType, TupleFields,
         TupleStructType,
     };
 
@@ -667,4 +777,45 @@ mod test {
         let mut registry = BindingsRegistry::default();
         Test::create_bindings(&mut registry);
     }
+
+    #[test]
+    fn test_registry_contains_binding() {
+        let mut registry = BindingsRegistry::default();
+        registry.register_struct_binding("Test", "crate::a", StructType::new());
+
+        assert!(registry.contains_binding("Test", "crate::a"));
+        assert!(!registry.contains_binding("Test", "crate::b"));
+        assert!(!registry.contains_binding("Other", "crate::a"));
+    }
+
+    #[test]
+    fn test_registry_document_binding() {
+        let mut registry = BindingsRegistry::default();
+        let mut ty = StructType::new();
+        ty.register_field::<u8>("a");
+        ty.register_field::<u8>("b");
+        ty.document_field("a", "Field a.");
+        registry.register_struct_binding("Test", "crate::a", ty);
+        registry.register_struct_binding("Test", "crate::b", StructType::new());
+        registry.document_binding("Test", "crate::a", "A test.");
+
+        let containers = registry.into_entries();
+        let docs = containers
+            .all_containers()
+            .map(|container| match container.r#type {
+                BindingType::Struct(ty) => (
+                    container.doc,
+                    ty.fields.iter().map(|f| f.doc.clone()).collect::<Vec<_>>(),
+                ),
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            docs,
+            [
+                (Some("A test.".into()), vec![Some("Field a.".into()), None]),
+                (None, vec![])
+            ]
+        );
+    }
 }
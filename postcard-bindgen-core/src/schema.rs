@@ -0,0 +1,101 @@
+use core::fmt::Display;
+use std::error::Error;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::registry::{BindingsRegistry, Container, ContainerCollection};
+
+/// Version of the schema documents written by [ContainerCollection::to_schema()].
+///
+/// Documents of other versions are rejected when loading.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Portable JSON document holding the containers of a [ContainerCollection].
+#[derive(Serialize, Deserialize)]
+struct SchemaDocument {
+    version: u32,
+    containers: Vec<Container>,
+}
+
+#[derive(Deserialize)]
+struct SchemaVersion {
+    version: u32,
+}
+
+/// A schema document which can't be loaded by [ContainerCollection::from_schema()].
+#[derive(Debug)]
+pub enum SchemaError {
+    UnsupportedVersion(u32),
+    Invalid(serde_json::Error),
+}
+
+impl Display for SchemaError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SchemaError::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported schema version {version}, expected {SCHEMA_VERSION}"
+            ),
+            SchemaError::Invalid(err) => write!(f, "invalid schema: {err}"),
+        }
+    }
+}
+
+impl Error for SchemaError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SchemaError::UnsupportedVersion(_) => None,
+            SchemaError::Invalid(err) => Some(err),
+        }
+    }
+}
+
+impl ContainerCollection {
+    /// Exports the containers to a versioned JSON schema document.
+    ///
+    /// The document can be loaded with [ContainerCollection::from_schema()] to generate
+    /// bindings without compiling the Rust types.
+    pub fn to_schema(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    /// Loads the containers from a JSON schema document written by
+    /// [ContainerCollection::to_schema()].
+    pub fn from_schema(schema: &str) -> Result<Self, SchemaError> {
+        // checked first, so documents of other versions don't fail with a confusing error
+        let SchemaVersion { version } =
+            serde_json::from_str(schema).map_err(SchemaError::Invalid)?;
+        if version != SCHEMA_VERSION {
+            return Err(SchemaError::UnsupportedVersion(version));
+        }
+        serde_json::from_str(schema).map_err(SchemaError::Invalid)
+    }
+}
+
+impl Serialize for ContainerCollection {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SchemaDocument {
+            version: SCHEMA_VERSION,
+            containers: self.all_containers().collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ContainerCollection {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let document = SchemaDocument::deserialize(deserializer)?;
+        if document.version != SCHEMA_VERSION {
+            return Err(de::Error::custom(SchemaError::UnsupportedVersion(
+                document.version,
+            )));
+        }
+
+        // inserting the containers in their original order rebuilds the same module tree
+        let mut registry = BindingsRegistry::default();
+        for container in document.containers {
+            registry.insert_container(container);
+        }
+        Ok(registry.into_entries())
+    }
+}
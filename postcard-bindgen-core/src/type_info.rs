@@ -1,13 +1,23 @@
+use core::marker::PhantomData;
 use core::num::{
     NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU128, NonZeroU16,
     NonZeroU32, NonZeroU64, NonZeroU8,
 };
 
-use alloc::{boxed::Box, vec, vec::Vec};
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt::{Display, Write};
+use serde::{Deserialize, Serialize};
 
-use crate::path::Path;
+use crate::{path::Path, registry::BindingsRegistry};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ValueType {
     Number(NumberMeta),
     Array(ArrayMeta),
@@ -44,6 +54,86 @@ impl ValueType {
             _ => {}
         }
     }
+
+    pub(crate) fn referenced_objects<'a>(&'a self, objects: &mut Vec<&'a ObjectMeta>) {
+        match self {
+            ValueType::Object(meta) => objects.push(meta),
+            ValueType::Array(meta) => meta.items_type.referenced_objects(objects),
+            ValueType::Optional(meta) => meta.inner.referenced_objects(objects),
+            ValueType::Range(meta) => meta.bounds_type.referenced_objects(objects),
+            ValueType::Map(meta) => {
+                meta.key_type.referenced_objects(objects);
+                meta.value_type.referenced_objects(objects);
+            }
+            ValueType::Tuple(meta) => {
+                for item in meta.items_types.iter() {
+                    item.referenced_objects(objects);
+                }
+            }
+            ValueType::Number(_) | ValueType::String(_) | ValueType::Bool(_) => {}
+        }
+    }
+
+    /// Returns a name for the type which is a valid identifier in all target languages.
+    ///
+    /// Used to name instantiations of generic containers.
+    pub fn name(&self) -> String {
+        match self {
+            ValueType::Number(NumberMeta::Integer {
+                bytes,
+                signed,
+                zero_able,
+                encoding,
+            }) => {
+                let name = format!("{}{}", if *signed { "i" } else { "u" }, bytes * 8);
+                let name = match encoding {
+                    IntegerEncoding::Varint => name,
+                    IntegerEncoding::FixedLe => format!("{name}_le"),
+                    IntegerEncoding::FixedBe => format!("{name}_be"),
+                };
+                if *zero_able {
+                    name
+                } else {
+                    format!("NonZero_{name}")
+                }
+            }
+            ValueType::Number(NumberMeta::FloatingPoint { bytes }) => format!("f{}", bytes * 8),
+            ValueType::String(_) => "String".to_string(),
+            ValueType::Bool(_) => "bool".to_string(),
+            ValueType::Object(meta) => meta
+                .path
+                .parts()
+                .skip(1)
+                .chain([meta.name.as_ref()])
+                .collect::<Vec<_>>()
+                .join("_"),
+            ValueType::Optional(meta) => format!("Option_{}", meta.inner.name()),
+            ValueType::Array(meta) => match meta.length {
+                Some(length) => format!("Array{length}_{}", meta.items_type.name()),
+                None => format!("Vec_{}", meta.items_type.name()),
+            },
+            ValueType::Range(meta) => format!("Range_{}", meta.bounds_type.name()),
+            ValueType::Map(meta) => {
+                format!("Map_{}_{}", meta.key_type.name(), meta.value_type.name())
+            }
+            ValueType::Tuple(meta) => meta
+                .items_types
+                .iter()
+                .fold("Tuple".to_string(), |name, item| name + "_" + &item.name()),
+        }
+    }
+}
+
+/// Returns the name of an instantiation of a generic container, e.g. `Frame_u8` for `Frame<u8>`.
+///
+/// Containers passed as arguments are prefixed with their module path below the crate,
+/// so `Frame<a::Status>` and `Frame<b::Status>` are named `Frame_a_Status` and `Frame_b_Status`.
+pub fn generic_container_name(name: &'static str, args: &[&dyn Display]) -> Cow<'static, str> {
+    let mut full_name = name.to_string();
+    for arg in args {
+        write!(full_name, "_{arg}").unwrap();
+    }
+    Cow::Owned(full_name)
 }
 
 impl AsRef<ValueType> for ValueType {
@@ -52,36 +142,49 @@ impl AsRef<ValueType> for ValueType {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MapMeta {
     pub(crate) key_type: Box<ValueType>,
     pub(crate) value_type: Box<ValueType>,
     pub(crate) max_length: Option<usize>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RangeMeta {
     pub(crate) bounds_type: Box<ValueType>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct OptionalMeta {
     pub(crate) inner: Box<ValueType>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum NumberMeta {
     Integer {
         bytes: usize,
         signed: bool,
         zero_able: bool,
+        encoding: IntegerEncoding,
     },
     FloatingPoint {
         bytes: usize,
     },
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// How postcard puts an integer on the wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IntegerEncoding {
+    /// Single byte for 8 bit integers, zigzag encoded varint for signed and varint for
+    /// unsigned integers otherwise.
+    Varint,
+    /// Fixed width little endian, as written by `postcard::fixint::le`.
+    FixedLe,
+    /// Fixed width big endian, as written by `postcard::fixint::be`.
+    FixedBe,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ArrayMeta {
     // Boxed to avoid infinite recursion
     pub(crate) items_type: Box<ValueType>,
@@ -89,33 +192,43 @@ pub struct ArrayMeta {
     pub(crate) max_length: Option<usize>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StringMeta {
     pub(crate) max_length: Option<usize>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ObjectMeta {
-    pub name: &'static str,
+    pub name: Cow<'static, str>,
     pub path: Path<'static, 'static>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TupleMeta {
     pub(crate) items_types: Vec<ValueType>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BoolMeta;
 
 pub trait GenBinding {
     fn get_type() -> ValueType;
+
+    /// Registers all containers reachable from this type.
+    ///
+    /// Containers register themselves and the types of their fields, wrapper types forward
+    /// to the types they wrap.
+    fn register_bindings(_registry: &mut BindingsRegistry) {}
 }
 
 impl<T: GenBinding> GenBinding for &mut T {
     fn get_type() -> ValueType {
         T::get_type()
     }
+
+    fn register_bindings(registry: &mut BindingsRegistry) {
+        T::register_bindings(registry)
+    }
 }
 
 macro_rules! impl_gen_js_binding_numbers_ints {
@@ -127,6 +240,7 @@ macro_rules! impl_gen_js_binding_numbers_ints {
                         bytes: $bytes,
                         signed: $signed,
                         zero_able: $zero_able,
+                        encoding: IntegerEncoding::Varint,
                     })
                 }
             }
@@ -181,12 +295,51 @@ impl_gen_js_binding_numbers_floats![
     f64: 8
 ];
 
+/// Binding of an integer serialized with `#[serde(with = "postcard::fixint::le")]`.
+pub struct FixintLe<T>(PhantomData<T>);
+
+/// Binding of an integer serialized with `#[serde(with = "postcard::fixint::be")]`.
+pub struct FixintBe<T>(PhantomData<T>);
+
+fn fixed_width(ty: ValueType, encoding: IntegerEncoding) -> ValueType {
+    match ty {
+        ValueType::Number(NumberMeta::Integer {
+            bytes,
+            signed,
+            zero_able,
+            ..
+        }) => ValueType::Number(NumberMeta::Integer {
+            bytes,
+            signed,
+            zero_able,
+            encoding,
+        }),
+        _ => panic!("`postcard::fixint` can only encode integers"),
+    }
+}
+
+impl<T: GenBinding> GenBinding for FixintLe<T> {
+    fn get_type() -> ValueType {
+        fixed_width(T::get_type(), IntegerEncoding::FixedLe)
+    }
+}
+
+impl<T: GenBinding> GenBinding for FixintBe<T> {
+    fn get_type() -> ValueType {
+        fixed_width(T::get_type(), IntegerEncoding::FixedBe)
+    }
+}
+
 impl<T: GenBinding> GenBinding for Option<T> {
     fn get_type() -> ValueType {
         ValueType::Optional(OptionalMeta {
             inner: Box::new(T::get_type()),
         })
     }
+
+    fn register_bindings(registry: &mut BindingsRegistry) {
+        T::register_bindings(registry)
+    }
 }
 
 impl<T: GenBinding> GenBinding for &[T] {
@@ -197,6 +350,10 @@ impl<T: GenBinding> GenBinding for &[T] {
             max_length: None,
         })
     }
+
+    fn register_bindings(registry: &mut BindingsRegistry) {
+        T::register_bindings(registry)
+    }
 }
 
 impl<T: GenBinding> GenBinding for [T] {
@@ -207,6 +364,10 @@ impl<T: GenBinding> GenBinding for [T] {
             max_length: None,
         })
     }
+
+    fn register_bindings(registry: &mut BindingsRegistry) {
+        T::register_bindings(registry)
+    }
 }
 
 impl<T: GenBinding, const S: usize> GenBinding for [T; S] {
@@ -217,6 +378,10 @@ impl<T: GenBinding, const S: usize> GenBinding for [T; S] {
             max_length: Some(S),
         })
     }
+
+    fn register_bindings(registry: &mut BindingsRegistry) {
+        T::register_bindings(registry)
+    }
 }
 
 impl GenBinding for &str {
@@ -231,6 +396,10 @@ impl<T: GenBinding> GenBinding for core::ops::Range<T> {
             bounds_type: Box::new(T::get_type()),
         })
     }
+
+    fn register_bindings(registry: &mut BindingsRegistry) {
+        T::register_bindings(registry)
+    }
 }
 
 macro_rules! tuple_impls {
@@ -242,6 +411,10 @@ macro_rules! tuple_impls {
                         items_types: vec![$($name::get_type()),+],
                     })
                 }
+
+                fn register_bindings(registry: &mut BindingsRegistry) {
+                    $($name::register_bindings(registry);)+
+                }
             }
         )+
     };
@@ -253,6 +426,10 @@ impl<T: GenBinding> GenBinding for (T,) {
             items_types: vec![T::get_type()],
         })
     }
+
+    fn register_bindings(registry: &mut BindingsRegistry) {
+        T::register_bindings(registry)
+    }
 }
 
 tuple_impls! {
@@ -287,6 +464,11 @@ impl<K: GenBinding, V: GenBinding> GenBinding for alloc::collections::BTreeMap<K
             max_length: None,
         })
     }
+
+    fn register_bindings(registry: &mut BindingsRegistry) {
+        K::register_bindings(registry);
+        V::register_bindings(registry);
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -305,6 +487,10 @@ impl<T: GenBinding> GenBinding for alloc::vec::Vec<T> {
             max_length: None,
         })
     }
+
+    fn register_bindings(registry: &mut BindingsRegistry) {
+        T::register_bindings(registry)
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -312,6 +498,10 @@ impl<T: GenBinding> GenBinding for alloc::rc::Rc<T> {
     fn get_type() -> ValueType {
         T::get_type()
     }
+
+    fn register_bindings(registry: &mut BindingsRegistry) {
+        T::register_bindings(registry)
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -319,6 +509,10 @@ impl<T: GenBinding> GenBinding for alloc::sync::Arc<T> {
     fn get_type() -> ValueType {
         T::get_type()
     }
+
+    fn register_bindings(registry: &mut BindingsRegistry) {
+        T::register_bindings(registry)
+    }
 }
 
 #[cfg(feature = "std")]
@@ -330,6 +524,11 @@ impl<K: GenBinding, V: GenBinding> GenBinding for std::collections::HashMap<K, V
             max_length: None,
         })
     }
+
+    fn register_bindings(registry: &mut BindingsRegistry) {
+        K::register_bindings(registry);
+        V::register_bindings(registry);
+    }
 }
 
 #[cfg(feature = "std")]
@@ -337,6 +536,10 @@ impl<T: GenBinding> GenBinding for std::sync::RwLock<T> {
     fn get_type() -> ValueType {
         T::get_type()
     }
+
+    fn register_bindings(registry: &mut BindingsRegistry) {
+        T::register_bindings(registry)
+    }
 }
 
 #[cfg(feature = "heapless")]
@@ -348,6 +551,10 @@ impl<T: GenBinding, const N: usize> GenBinding for heapless::Vec<T, N> {
             max_length: Some(N),
         })
     }
+
+    fn register_bindings(registry: &mut BindingsRegistry) {
+        T::register_bindings(registry)
+    }
 }
 
 #[cfg(feature = "heapless")]
@@ -368,4 +575,9 @@ impl<K: GenBinding, V: GenBinding, const N: usize> GenBinding for heapless::Line
             max_length: Some(N),
         })
     }
+
+    fn register_bindings(registry: &mut BindingsRegistry) {
+        K::register_bindings(registry);
+        V::register_bindings(registry);
+    }
 }
@@ -0,0 +1,462 @@
+use core::fmt::Display;
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    error::Error,
+};
+
+use crate::{
+    path::Path,
+    registry::{BindingType, Container, ContainerCollection},
+};
+
+/// A problem in a [ContainerCollection] that would produce broken generated code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// Module path of the container the error was found in.
+    pub path: Path<'static, 'static>,
+    /// Name of the container the error was found in.
+    pub container: Cow<'static, str>,
+    pub reason: ValidationErrorReason,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationErrorReason {
+    /// The container references a container which is not part of the collection.
+    DanglingReference {
+        path: Path<'static, 'static>,
+        name: Cow<'static, str>,
+    },
+    /// Another container with the same name is registered in the same module.
+    DuplicateName,
+    /// The type generated for the container is named like the type of another container in
+    /// the same namespace.
+    TypeNameClash { type_name: String },
+    /// The class generated for an enum variant collides with another generated class in the
+    /// same namespace.
+    VariantNameClash {
+        variant: Cow<'static, str>,
+        class_name: String,
+    },
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}::{}: ", self.path, self.container)?;
+        match &self.reason {
+            ValidationErrorReason::DanglingReference { path, name } => {
+                write!(f, "references `{path}::{name}` which is not registered")
+            }
+            ValidationErrorReason::DuplicateName => {
+                write!(f, "registered more than once in the same module")
+            }
+            ValidationErrorReason::TypeNameClash { type_name } => {
+                write!(f, "generated type `{type_name}` collides with another type")
+            }
+            ValidationErrorReason::VariantNameClash {
+                variant,
+                class_name,
+            } => write!(
+                f,
+                "class `{class_name}` generated for variant `{variant}` collides with another type"
+            ),
+        }
+    }
+}
+
+/// All problems found by [ContainerCollection::validate()].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationErrors(pub Vec<ValidationError>);
+
+impl Display for ValidationErrors {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        writeln!(f, "invalid bindings:")?;
+        for error in self.0.iter() {
+            writeln!(f, "  {error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ValidationErrors {}
+
+/// Describes the generated code which [ContainerCollection::validate()] checks the collection for.
+///
+/// Each generator converts its `GenerationSettings` into the matching settings.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidationSettings {
+    flattened: bool,
+    type_name: Option<fn(&Path<'_, '_>, &str) -> String>,
+    variant_class_name: Option<fn(&str, &str) -> String>,
+}
+
+impl ValidationSettings {
+    /// Validates the collection as generated without module structure, where all containers
+    /// share one module.
+    pub fn flattened(mut self, enabled: bool) -> Self {
+        self.flattened = enabled;
+        self
+    }
+
+    /// Validates the collection as generated into a single namespace, where the types are
+    /// named by `type_name` from the module path and the name of the container.
+    pub fn shared_namespace(mut self, type_name: fn(&Path<'_, '_>, &str) -> String) -> Self {
+        self.type_name = Some(type_name);
+        self
+    }
+
+    /// Checks the classes generated for enum variants next to the other types, named by
+    /// `class_name` from the generated name of the enum and the name of the variant.
+    pub fn variant_classes(mut self, class_name: fn(&str, &str) -> String) -> Self {
+        self.variant_class_name = Some(class_name);
+        self
+    }
+
+    // the module a container is generated in
+    fn module(&self, path: &Path) -> String {
+        if self.flattened {
+            String::new()
+        } else {
+            path.to_string()
+        }
+    }
+
+    // the namespace the type of a container is generated in
+    fn namespace(&self, path: &Path) -> String {
+        match self.type_name {
+            Some(_) => String::new(),
+            None => self.module(path),
+        }
+    }
+
+    // the name of the type generated for a container
+    fn type_name(&self, container: &Container) -> String {
+        let Some(type_name) = self.type_name else {
+            return container.name.to_string();
+        };
+        let mut path = container.path.clone();
+        if self.flattened {
+            path.flatten();
+        }
+        type_name(&path, &container.name)
+    }
+}
+
+impl ContainerCollection {
+    /// Checks that code can be generated for the collection.
+    ///
+    /// Reports references to containers which are not registered, containers registered
+    /// more than once in the same module, generated type names clashing within a namespace
+    /// and, if enabled in the settings, enum variant classes clashing with other generated
+    /// classes.
+    pub fn validate(&self, settings: ValidationSettings) -> Result<(), ValidationErrors> {
+        let containers = self.all_containers().collect::<Vec<_>>();
+        let mut errors = Vec::new();
+
+        let mut registered = BTreeSet::new();
+        for container in containers.iter() {
+            if !registered.insert((settings.module(&container.path), &container.name)) {
+                errors.push(error(container, ValidationErrorReason::DuplicateName));
+            }
+        }
+
+        for container in containers.iter() {
+            let mut reported = BTreeSet::new();
+            for object in container.r#type.referenced_objects() {
+                let key = (settings.module(&object.path), &object.name);
+                if !registered.contains(&key) && reported.insert(key) {
+                    errors.push(error(
+                        container,
+                        ValidationErrorReason::DanglingReference {
+                            path: object.path.clone(),
+                            name: object.name.clone(),
+                        },
+                    ));
+                }
+            }
+        }
+
+        errors.extend(type_name_clashes(&containers, settings));
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationErrors(errors))
+        }
+    }
+}
+
+fn type_name_clashes(
+    containers: &[Container],
+    settings: ValidationSettings,
+) -> Vec<ValidationError> {
+    // the generated names in each namespace with the module and name of their container
+    let mut type_names = BTreeMap::<_, BTreeMap<_, _>>::new();
+    let mut errors = Vec::new();
+    for container in containers.iter() {
+        let type_name = settings.type_name(container);
+        let key = (settings.module(&container.path), &container.name);
+        let namespace = type_names
+            .entry(settings.namespace(&container.path))
+            .or_default();
+        match namespace.get(&type_name) {
+            // registered more than once, already reported
+            Some(other) if *other == key => {}
+            Some(_) => errors.push(error(
+                container,
+                ValidationErrorReason::TypeNameClash { type_name },
+            )),
+            None => {
+                namespace.insert(type_name, key);
+            }
+        }
+    }
+
+    let Some(variant_class_name) = settings.variant_class_name else {
+        return errors;
+    };
+    let mut class_names = type_names
+        .into_iter()
+        .map(|(namespace, names)| (namespace, names.into_keys().collect::<BTreeSet<_>>()))
+        .collect::<BTreeMap<_, _>>();
+    for container in containers.iter() {
+        let BindingType::Enum(ty) = &container.r#type else {
+            continue;
+        };
+        let type_name = settings.type_name(container);
+        let namespace_classes = class_names
+            .get_mut(&settings.namespace(&container.path))
+            .unwrap();
+        for variant in ty.variants.iter() {
+            let class_name = variant_class_name(&type_name, &variant.name);
+            if !namespace_classes.insert(class_name.clone()) {
+                errors.push(error(
+                    container,
+                    ValidationErrorReason::VariantNameClash {
+                        variant: variant.name.clone(),
+                        class_name,
+                    },
+                ));
+            }
+        }
+    }
+    errors
+}
+
+fn error(container: &Container, reason: ValidationErrorReason) -> ValidationError {
+    ValidationError {
+        path: container.path.clone(),
+        container: container.name.clone(),
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        path::Path,
+        registry::{BindingsRegistry, EnumType, StructFields, StructType, TupleStructType},
+        type_info::{GenBinding, ObjectMeta, ValueType},
+    };
+
+    use super::{ValidationError, ValidationErrorReason, ValidationSettings};
+
+    struct Missing;
+
+    impl GenBinding for Missing {
+        fn get_type() -> ValueType {
+            ValueType::Object(ObjectMeta {
+                name: "Missing".into(),
+                path: Path::new("crate::other", "::"),
+            })
+        }
+    }
+
+    fn underscored(enum_name: &str, variant_name: &str) -> String {
+        format!("{enum_name}_{variant_name}")
+    }
+
+    // joins the module path without the crate and the name like the go and dart generators
+    fn joined(path: &Path<'_, '_>, name: &str) -> String {
+        path.to_string()
+            .split("::")
+            .skip(1)
+            .map(|part| part.to_uppercase())
+            .chain([name.to_owned()])
+            .collect()
+    }
+
+    fn validation_errors(
+        registry: BindingsRegistry,
+        settings: ValidationSettings,
+    ) -> Vec<ValidationError> {
+        registry.into_entries().validate(settings).unwrap_err().0
+    }
+
+    #[test]
+    fn test_valid_collection() {
+        let mut registry = BindingsRegistry::default();
+
+        let mut ty = EnumType::new();
+        ty.register_variant("A");
+        registry.register_enum_binding("Test", "crate", ty);
+        registry.register_struct_binding("Test", "crate::other", StructType::new());
+
+        assert!(registry
+            .into_entries()
+            .validate(ValidationSettings::default().variant_classes(underscored))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_dangling_reference() {
+        let mut registry = BindingsRegistry::default();
+
+        let mut ty = TupleStructType::new();
+        ty.register_field::<Option<Missing>>();
+        ty.register_field::<Vec<Missing>>();
+        registry.register_tuple_struct_binding("Test", "crate", ty);
+
+        assert_eq!(
+            validation_errors(registry, ValidationSettings::default()),
+            vec![ValidationError {
+                path: Path::new("crate", "::"),
+                container: "Test".into(),
+                reason: ValidationErrorReason::DanglingReference {
+                    path: Path::new("crate::other", "::"),
+                    name: "Missing".into(),
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn test_duplicate_name() {
+        let mut registry = BindingsRegistry::default();
+        registry.register_struct_binding("Test", "crate", StructType::new());
+        registry.register_struct_binding("Test", "crate", StructType::new());
+
+        assert_eq!(
+            validation_errors(registry, ValidationSettings::default()),
+            vec![ValidationError {
+                path: Path::new("crate", "::"),
+                container: "Test".into(),
+                reason: ValidationErrorReason::DuplicateName,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_duplicate_name_when_flattened() {
+        let mut registry = BindingsRegistry::default();
+        registry.register_struct_binding("Test", "crate::a", StructType::new());
+        registry.register_struct_binding("Test", "crate::b", StructType::new());
+        let containers = registry.into_entries();
+
+        assert!(containers.validate(ValidationSettings::default()).is_ok());
+        let errors = containers
+            .validate(ValidationSettings::default().flattened(true))
+            .unwrap_err()
+            .0;
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].reason, ValidationErrorReason::DuplicateName);
+    }
+
+    #[test]
+    fn test_variant_name_clash() {
+        let mut registry = BindingsRegistry::default();
+
+        let mut ty = EnumType::new();
+        ty.register_variant("A");
+        ty.register_unnamed_struct("B_C", StructFields::default());
+        registry.register_enum_binding("Test", "crate", ty);
+        registry.register_struct_binding("Test_A", "crate", StructType::new());
+
+        let mut ty = EnumType::new();
+        ty.register_variant("C");
+        registry.register_enum_binding("Test_B", "crate", ty);
+
+        let containers = registry.into_entries();
+        assert!(containers.validate(ValidationSettings::default()).is_ok());
+
+        let errors = containers
+            .validate(ValidationSettings::default().variant_classes(underscored))
+            .unwrap_err()
+            .0;
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0].reason,
+            ValidationErrorReason::VariantNameClash {
+                variant: "A".into(),
+                class_name: "Test_A".into(),
+            }
+        );
+        assert_eq!(errors[1].container, "Test_B");
+    }
+
+    #[test]
+    fn test_type_name_clash_in_shared_namespace() {
+        let mut registry = BindingsRegistry::default();
+        registry.register_struct_binding("C", "crate::a_b", StructType::new());
+        registry.register_struct_binding("BC", "crate::a", StructType::new());
+        let containers = registry.into_entries();
+
+        assert!(containers.validate(ValidationSettings::default()).is_ok());
+        let errors = containers
+            .validate(
+                ValidationSettings::default()
+                    .shared_namespace(|path, name| joined(path, name).replace('_', "")),
+            )
+            .unwrap_err()
+            .0;
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].reason,
+            ValidationErrorReason::TypeNameClash {
+                type_name: "ABC".into()
+            }
+        );
+    }
+
+    #[test]
+    fn test_variant_name_clash_in_shared_namespace() {
+        let mut registry = BindingsRegistry::default();
+
+        let mut ty = EnumType::new();
+        ty.register_variant("Circle");
+        registry.register_enum_binding("Shape", "crate::a", ty);
+        registry.register_struct_binding("AShapeCircle", "crate", StructType::new());
+        registry.register_struct_binding("ShapeCircle", "crate::a", StructType::new());
+        let containers = registry.into_entries();
+
+        let settings = ValidationSettings::default()
+            .shared_namespace(joined)
+            .variant_classes(|enum_name, variant_name| format!("{enum_name}{variant_name}"));
+        let errors = containers.validate(settings).unwrap_err().0;
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0].reason,
+            ValidationErrorReason::TypeNameClash {
+                type_name: "AShapeCircle".into()
+            }
+        );
+        assert_eq!(
+            errors[1].reason,
+            ValidationErrorReason::VariantNameClash {
+                variant: "Circle".into(),
+                class_name: "AShapeCircle".into(),
+            }
+        );
+
+        // without module structure, the containers are named by their plain names
+        let errors = containers.validate(settings.flattened(true)).unwrap_err().0;
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].reason,
+            ValidationErrorReason::VariantNameClash {
+                variant: "Circle".into(),
+                class_name: "ShapeCircle".into(),
+            }
+        );
+    }
+}
@@ -0,0 +1,38 @@
+mod registry;
+
+use postcard_bindgen_core::code_gen::cpp::{generate, GenerationSettings};
+
+use registry::init_registry;
+
+#[test]
+fn test_types() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::enable_all();
+    let exports = generate(containers, gen_settings, "test".to_owned());
+
+    let types_file = exports.file("types").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!(types_file);
+}
+
+#[test]
+fn test_ser() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::enable_all();
+    let exports = generate(containers, gen_settings, "test".to_owned());
+
+    let ser_file = exports.file("ser").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!(ser_file);
+}
+
+#[test]
+fn test_des() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::enable_all();
+    let exports = generate(containers, gen_settings, "test".to_owned());
+
+    let des_file = exports.file("des").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!(des_file);
+}
@@ -0,0 +1,38 @@
+mod registry;
+
+use postcard_bindgen_core::code_gen::csharp::{generate, GenerationSettings};
+
+use registry::init_registry;
+
+#[test]
+fn test_types() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::enable_all();
+    let exports = generate(containers, gen_settings, "test".to_owned());
+
+    let types_file = exports.file("Types").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!(types_file);
+}
+
+#[test]
+fn test_runtime() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::enable_all();
+    let exports = generate(containers, gen_settings, "test".to_owned());
+
+    let runtime_file = exports.file("Runtime").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!(runtime_file);
+}
+
+#[test]
+fn test_types_without_ser_des() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::default().deserialization(false);
+    let exports = generate(containers, gen_settings, "test".to_owned());
+
+    let types_file = exports.file("Types").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!(types_file);
+}
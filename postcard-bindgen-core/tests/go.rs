@@ -0,0 +1,51 @@
+mod registry;
+
+use postcard_bindgen_core::code_gen::go::{generate, GenerationSettings};
+
+use registry::init_registry;
+
+#[test]
+fn test_types() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::enable_all();
+    let exports = generate(containers, gen_settings, "test".to_owned());
+
+    let types_file = exports.file("types").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!(types_file);
+}
+
+#[test]
+fn test_runtime() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::enable_all();
+    let exports = generate(containers, gen_settings, "test".to_owned());
+
+    let runtime_file = exports.file("runtime").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!(runtime_file);
+}
+
+#[test]
+fn test_types_without_ser_des() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::default().deserialization(false);
+    let exports = generate(containers, gen_settings, "test".to_owned());
+
+    let types_file = exports.file("types").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!(types_file);
+}
+
+#[test]
+fn test_flattened_types() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::default()
+        .deserialization(false)
+        .module_structure(false);
+    let exports = generate(containers, gen_settings, "test".to_owned());
+
+    let types_file = exports.file("types").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!(types_file);
+}
@@ -1,6 +1,9 @@
 mod registry;
 
-use postcard_bindgen_core::code_gen::js::{generate, GenerationSettings};
+use postcard_bindgen_core::{
+    code_gen::js::{generate, GenerationSettings},
+    registry::{BindingsRegistry, StructType},
+};
 
 use registry::init_registry;
 
@@ -52,6 +55,21 @@ fn test_des() {
     insta::assert_snapshot!(des_file);
 }
 
+#[test]
+fn test_des_byte_arrays() {
+    let mut registry = BindingsRegistry::default();
+    let mut struct_type = StructType::new();
+    struct_type.register_field::<[u8; 4]>("fixed");
+    struct_type.register_field::<Vec<u8>>("payload");
+    struct_type.register_field::<Vec<i8>>("signed");
+    registry.register_struct_binding("Bytes", "main_crate", struct_type);
+
+    let (exports, _meta) = generate(registry.into_entries(), GenerationSettings::enable_all());
+
+    let des_file = exports.file("des").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!(des_file);
+}
+
 #[test]
 fn test_ts_types() {
     let containers = init_registry().into_entries();
@@ -84,3 +102,36 @@ fn test_des_cjf_module() {
     let des_file = exports.file("des").unwrap().to_file_string().unwrap();
     insta::assert_snapshot!(des_file);
 }
+
+#[test]
+fn test_cobs() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::enable_all().cobs(true);
+    let (exports, _meta) = generate(containers, gen_settings);
+
+    let cobs_file = exports.file("cobs").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!(cobs_file);
+}
+
+#[test]
+fn test_cobs_des_only() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::default().cobs(true);
+    let (exports, _meta) = generate(containers, gen_settings);
+
+    let cobs_file = exports.file("cobs").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!(cobs_file);
+}
+
+#[test]
+fn test_crc() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::enable_all().crc32(true);
+    let (exports, _meta) = generate(containers, gen_settings);
+
+    let crc_file = exports.file("crc").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!(crc_file);
+}
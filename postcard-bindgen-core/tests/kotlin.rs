@@ -0,0 +1,42 @@
+mod registry;
+
+use postcard_bindgen_core::code_gen::kotlin::{generate, GenerationSettings};
+
+use registry::init_registry;
+
+#[test]
+fn test_types() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::enable_all();
+    let exports = generate(containers, gen_settings, "test".to_owned());
+
+    let types_file = exports.file("Types").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!(types_file);
+}
+
+#[test]
+fn test_sub_module_types() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::enable_all();
+    let exports = generate(containers, gen_settings, "test".to_owned());
+
+    let types_file = exports
+        .file("sub_module/Types")
+        .unwrap()
+        .to_file_string()
+        .unwrap();
+    insta::assert_snapshot!(types_file);
+}
+
+#[test]
+fn test_types_without_ser_des() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::default().deserialization(false);
+    let exports = generate(containers, gen_settings, "test".to_owned());
+
+    let types_file = exports.file("Types").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!(types_file);
+}
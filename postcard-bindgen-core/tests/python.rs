@@ -51,3 +51,40 @@ fn test_des() {
     let des_file = exports.file("des").unwrap().to_file_string().unwrap();
     insta::assert_snapshot!(des_file);
 }
+
+#[test]
+fn test_micropython_module() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::enable_all().micropython(true);
+    let exports = generate(containers, gen_settings, "test".to_owned());
+
+    assert_eq!(exports.files.len(), 1);
+    let module_file = exports.file("__init__").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!(module_file);
+}
+
+#[test]
+fn test_cobs() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::enable_all().cobs(true);
+    let exports = generate(containers, gen_settings, "test".to_owned());
+
+    let cobs_file = exports.file("cobs").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!(cobs_file);
+
+    let init_file = exports.file("__init__").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!("cobs_init", init_file);
+}
+
+#[test]
+fn test_crc() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::enable_all().crc32(true);
+    let exports = generate(containers, gen_settings, "test".to_owned());
+
+    let crc_file = exports.file("crc").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!(crc_file);
+}
@@ -0,0 +1,108 @@
+mod registry;
+
+use postcard_bindgen_core::{
+    code_gen::{js, python},
+    lang::Lang,
+    registry::ContainerCollection,
+    schema::{SchemaError, SCHEMA_VERSION},
+    Exports,
+};
+
+use registry::init_registry;
+
+fn reload(containers: &ContainerCollection) -> ContainerCollection {
+    ContainerCollection::from_schema(&containers.to_schema()).unwrap()
+}
+
+fn files<L: Lang>(exports: Exports<L>) -> Vec<(String, String)>
+where
+    L::Config: Default,
+{
+    exports
+        .files
+        .into_iter()
+        .map(|file| (file.content_type, file.content.to_file_string().unwrap()))
+        .collect()
+}
+
+#[test]
+fn test_schema_document() {
+    let containers = init_registry().into_entries();
+
+    insta::assert_snapshot!(containers.to_schema());
+}
+
+#[test]
+fn test_reloaded_schema_generates_the_same_bindings() {
+    let containers = init_registry().into_entries();
+    let reloaded = reload(&containers);
+
+    assert_eq!(
+        reloaded.all_containers().collect::<Vec<_>>(),
+        containers.all_containers().collect::<Vec<_>>()
+    );
+
+    let (js_exports, _) = js::generate(containers.clone(), js::GenerationSettings::enable_all());
+    let (js_reloaded, _) = js::generate(reloaded.clone(), js::GenerationSettings::enable_all());
+    assert_eq!(files(js_exports), files(js_reloaded));
+
+    let python_exports = python::generate(
+        containers,
+        python::GenerationSettings::enable_all(),
+        "test".to_owned(),
+    );
+    let python_reloaded = python::generate(
+        reloaded,
+        python::GenerationSettings::enable_all(),
+        "test".to_owned(),
+    );
+    assert_eq!(files(python_exports), files(python_reloaded));
+}
+
+#[test]
+fn test_reloaded_schema_keeps_the_module_tree() {
+    let containers = init_registry().into_entries();
+    let reloaded = reload(&containers);
+
+    let module_tree = |containers: &ContainerCollection| {
+        let (root, modules) = containers.containers_per_module();
+        let modules = modules
+            .iter()
+            .map(|module| {
+                let (containers, _) = module.entries();
+                let names = containers
+                    .iter()
+                    .map(|c| c.name.clone())
+                    .collect::<Vec<_>>();
+                (module.path(), module.name().to_owned(), names)
+            })
+            .collect::<Vec<_>>();
+        (
+            root.iter().map(|c| c.name.clone()).collect::<Vec<_>>(),
+            modules,
+        )
+    };
+    assert_eq!(module_tree(&reloaded), module_tree(&containers));
+
+    let mut flattened = init_registry().into_entries();
+    flattened.flatten();
+    assert_eq!(module_tree(&reload(&flattened)), module_tree(&flattened));
+}
+
+#[test]
+fn test_unsupported_schema_version() {
+    let schema = init_registry().into_entries().to_schema().replacen(
+        &format!("\"version\": {SCHEMA_VERSION}"),
+        "\"version\": 99",
+        1,
+    );
+
+    assert!(matches!(
+        ContainerCollection::from_schema(&schema),
+        Err(SchemaError::UnsupportedVersion(99))
+    ));
+    assert!(matches!(
+        ContainerCollection::from_schema("{}"),
+        Err(SchemaError::Invalid(_))
+    ));
+}
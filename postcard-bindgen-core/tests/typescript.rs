@@ -0,0 +1,60 @@
+mod registry;
+
+use postcard_bindgen_core::code_gen::ts::{generate, GenerationSettings};
+
+use registry::init_registry;
+
+#[test]
+fn test_ser() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::enable_all();
+    let exports = generate(containers, gen_settings);
+
+    let ser_file = exports.file("ser").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!(ser_file);
+}
+
+#[test]
+fn test_des() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::enable_all();
+    let exports = generate(containers, gen_settings);
+
+    let des_file = exports.file("des").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!(des_file);
+}
+
+#[test]
+fn test_types() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::enable_all();
+    let exports = generate(containers, gen_settings);
+
+    let types_file = exports.file("types").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!(types_file);
+}
+
+#[test]
+fn test_cobs() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::enable_all().cobs(true);
+    let exports = generate(containers, gen_settings);
+
+    let cobs_file = exports.file("cobs").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!(cobs_file);
+}
+
+#[test]
+fn test_crc() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::enable_all().crc32(true);
+    let exports = generate(containers, gen_settings);
+
+    let crc_file = exports.file("crc").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!(crc_file);
+}
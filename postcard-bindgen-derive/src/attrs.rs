@@ -0,0 +1,297 @@
+use quote::ToTokens;
+use serde_derive_internals::{ast, attr::TagType, Ctxt};
+
+const ATTR_NAME: &str = "postcard_bindgen";
+
+/// A field as it appears on the wire.
+pub struct BindingField {
+    pub name: String,
+    pub ty: syn::Type,
+    pub doc: Option<String>,
+}
+
+/// Reports container attributes that change the wire format in a way postcard can't represent.
+pub fn check_container(cx: &Ctxt, container: &ast::Container) {
+    let msg = match container.attrs.tag() {
+        TagType::External => return,
+        TagType::Internal { .. } => {
+            "internally tagged enums (`#[serde(tag = \"...\")]`) are not supported by postcard"
+        }
+        TagType::Adjacent { .. } => {
+            "adjacently tagged enums (`#[serde(tag = \"...\", content = \"...\")]`) are not supported by postcard"
+        }
+        TagType::None => "untagged enums (`#[serde(untagged)]`) are not supported by postcard",
+    };
+    cx.error_spanned_by(&container.ident, msg);
+}
+
+/// Returns the type serde serializes the container as, if it is not serialized by its own layout.
+///
+/// This is the case for `#[serde(into = "...")]` and `#[serde(transparent)]` containers. `into`
+/// needs a matching `from` or `try_from` and the other way around, as the bindings describe a
+/// single layout for both directions.
+pub fn proxy_type(cx: &Ctxt, container: &ast::Container) -> Option<syn::Type> {
+    let from = container
+        .attrs
+        .type_from()
+        .or(container.attrs.type_try_from());
+    match (container.attrs.type_into(), from) {
+        (Some(into), Some(from)) => {
+            if into.to_token_stream().to_string() != from.to_token_stream().to_string() {
+                cx.error_spanned_by(
+                    from,
+                    "`into` and `from` types differ, so serialized and deserialized values have different layouts",
+                );
+            }
+            return Some(into.to_owned());
+        }
+        (Some(into), None) => {
+            cx.error_spanned_by(
+                into,
+                "`into` without a matching `from` or `try_from` gives different serialized and deserialized layouts",
+            );
+            return None;
+        }
+        (None, Some(from)) => {
+            cx.error_spanned_by(
+                from,
+                "`from` or `try_from` without a matching `into` gives different serialized and deserialized layouts",
+            );
+            return None;
+        }
+        (None, None) => (),
+    }
+
+    if container.attrs.transparent() {
+        if let ast::Data::Struct(_, fields) = &container.data {
+            return fields
+                .iter()
+                .find(|field| field.attrs.transparent())
+                .map(|field| binding_type(cx, field));
+        }
+    }
+
+    None
+}
+
+/// Returns the fields which are serialized, with their wire types.
+pub fn binding_fields(cx: &Ctxt, fields: &[ast::Field]) -> Vec<BindingField> {
+    fields
+        .iter()
+        .filter_map(|field| {
+            let attrs = &field.attrs;
+            if attrs.flatten() {
+                cx.error_spanned_by(
+                    field.original,
+                    "`#[serde(flatten)]` is not supported by postcard",
+                );
+                return None;
+            }
+            if attrs.skip_serializing_if().is_some() {
+                cx.error_spanned_by(
+                    field.original,
+                    "`#[serde(skip_serializing_if = \"...\")]` is not supported by postcard, the field would be missing from the serialized bytes",
+                );
+                return None;
+            }
+            match (attrs.skip_serializing(), attrs.skip_deserializing()) {
+                (true, true) => return None,
+                (false, false) => (),
+                _ => {
+                    cx.error_spanned_by(
+                        field.original,
+                        "skipping a field in only one direction gives different serialized and deserialized layouts, use `#[serde(skip)]`",
+                    );
+                    return None;
+                }
+            }
+
+            Some(BindingField {
+                name: attrs.name().serialize_name().value.to_owned(),
+                ty: binding_type(cx, field),
+                doc: doc_comment(&field.original.attrs),
+            })
+        })
+        .collect()
+}
+
+/// Returns the variants which are serialized.
+///
+/// Skipped variants must be skipped in both directions and declared after all other variants.
+/// Otherwise the indices serde serializes and deserializes the following variants with don't
+/// match.
+pub fn binding_variants<'a>(
+    cx: &Ctxt,
+    variants: &'a [ast::Variant<'a>],
+) -> Vec<&'a ast::Variant<'a>> {
+    let is_skipped = |variant: &ast::Variant| {
+        variant.attrs.skip_serializing() || variant.attrs.skip_deserializing()
+    };
+
+    for (index, variant) in variants.iter().enumerate() {
+        if variant.attrs.untagged() {
+            cx.error_spanned_by(
+                variant.original,
+                "untagged variants (`#[serde(untagged)]`) are not supported by postcard",
+            );
+        }
+        if variant.attrs.serialize_with().is_some() || variant.attrs.deserialize_with().is_some() {
+            cx.error_spanned_by(
+                variant.original,
+                "`#[serde(with = \"...\")]` on variants is not supported",
+            );
+        }
+        if variant.attrs.skip_serializing() != variant.attrs.skip_deserializing() {
+            cx.error_spanned_by(
+                variant.original,
+                "skipping a variant in only one direction gives different serialized and deserialized layouts, use `#[serde(skip)]`",
+            );
+        }
+        if is_skipped(variant) && variants[index + 1..].iter().any(|v| !is_skipped(v)) {
+            cx.error_spanned_by(
+                variant.original,
+                "skipped variants must be declared after all serialized variants",
+            );
+        }
+    }
+
+    variants.iter().filter(|v| !is_skipped(v)).collect()
+}
+
+/// Returns the text of the `///` comments or `#[doc = "..."]` attributes.
+///
+/// Only the single leading space rustdoc keeps from `///` comments is removed, deeper
+/// indentation like the one of code examples is part of the text.
+pub fn doc_comment(attrs: &[syn::Attribute]) -> Option<String> {
+    let docs = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(doc),
+                        ..
+                    }),
+                ..
+            }) => Some(doc.value()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let mut lines = Vec::new();
+    for doc in &docs {
+        let mut doc_lines = doc.split('\n').map(str::trim_end).collect::<Vec<_>>();
+        match doc_lines.as_mut_slice() {
+            [line] => *line = line.strip_prefix(' ').unwrap_or(line),
+            // the continuation lines of `/** */` comments are indented like the code
+            [first, rest @ ..] => {
+                *first = first.trim_start();
+                unindent(rest);
+            }
+            [] => {}
+        }
+        lines.extend(doc_lines);
+    }
+
+    let doc = lines.join("\n");
+    let doc = doc.trim_matches('\n');
+    (!doc.is_empty()).then(|| doc.to_owned())
+}
+
+/// Removes the indentation all non-empty lines share.
+fn unindent(lines: &mut [&str]) {
+    let indent = lines
+        .iter()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or_default();
+    for line in lines.iter_mut() {
+        *line = line.get(indent..).unwrap_or_default();
+    }
+}
+
+fn binding_type(cx: &Ctxt, field: &ast::Field) -> syn::Type {
+    let field_attrs = parse_field_attrs(cx, &field.original.attrs);
+    let with = field
+        .attrs
+        .serialize_with()
+        .or(field.attrs.deserialize_with());
+    let fixint = field_attrs.fixint.or_else(|| with.and_then(fixint_with));
+
+    let ty = match field_attrs.as_type {
+        Some(ty) => ty,
+        None => {
+            if with.is_some() && fixint.is_none() {
+                cx.error_spanned_by(
+                    field.original,
+                    "`#[serde(with = \"...\")]` changes the serialized type of the field, declare it with `#[postcard_bindgen(as_type = \"...\")]`",
+                );
+            }
+            field.ty.to_owned()
+        }
+    };
+
+    match fixint {
+        Some(Fixint::Le) => syn::parse_quote!(_pb::__private::FixintLe<#ty>),
+        Some(Fixint::Be) => syn::parse_quote!(_pb::__private::FixintBe<#ty>),
+        None => ty,
+    }
+}
+
+/// Byte order of an integer serialized with a fixed width.
+#[derive(Clone, Copy)]
+enum Fixint {
+    Le,
+    Be,
+}
+
+#[derive(Default)]
+struct FieldAttrs {
+    as_type: Option<syn::Type>,
+    fixint: Option<Fixint>,
+}
+
+/// Detects `#[serde(with = "postcard::fixint::le")]` and `::be`, also if `fixint` is imported.
+fn fixint_with(path: &syn::ExprPath) -> Option<Fixint> {
+    let segments = path
+        .path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>();
+    // serde appends the name of the function to the path
+    match segments.as_slice() {
+        [.., module, order, _] if module == "fixint" && order == "le" => Some(Fixint::Le),
+        [.., module, order, _] if module == "fixint" && order == "be" => Some(Fixint::Be),
+        _ => None,
+    }
+}
+
+fn parse_field_attrs(cx: &Ctxt, attrs: &[syn::Attribute]) -> FieldAttrs {
+    let mut field_attrs = FieldAttrs::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident(ATTR_NAME)) {
+        let res = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("as_type") {
+                let lit: syn::LitStr = meta.value()?.parse()?;
+                field_attrs.as_type = Some(lit.parse::<syn::Type>()?);
+                Ok(())
+            } else if meta.path.is_ident("fixint") {
+                let lit: syn::LitStr = meta.value()?.parse()?;
+                field_attrs.fixint = match lit.value().as_str() {
+                    "le" => Some(Fixint::Le),
+                    "be" => Some(Fixint::Be),
+                    _ => return Err(meta.error("expected `fixint = \"le\"` or `fixint = \"be\"`")),
+                };
+                Ok(())
+            } else {
+                Err(meta.error("unknown postcard_bindgen attribute"))
+            }
+        });
+        if let Err(err) = res {
+            cx.syn_error(err);
+        }
+    }
+    field_attrs
+}
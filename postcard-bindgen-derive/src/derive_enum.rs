@@ -1,29 +1,47 @@
-use proc_macro2::{Ident, TokenStream};
+use proc_macro2::TokenStream;
 use quote::quote;
-use serde_derive_internals::ast::{self, Style};
+use serde_derive_internals::{
+    ast::{self, Style},
+    Ctxt,
+};
 
-pub fn derive_enum<'a>(ident: Ident, variants: impl AsRef<[ast::Variant<'a>]>) -> TokenStream {
-    let enum_name = ident.to_string();
-    let body = variants.as_ref().iter().map(|variant| {
+use crate::{
+    attrs::{binding_fields, binding_variants, doc_comment, BindingField},
+    derive_struct::{document_fields, register_field_types},
+};
+
+pub fn derive_enum(cx: &Ctxt, name: TokenStream, variants: &[ast::Variant]) -> TokenStream {
+    let variants = binding_variants(cx, variants)
+        .into_iter()
+        .map(|variant| (variant, binding_fields(cx, &variant.fields)))
+        .collect::<Vec<_>>();
+    let body = variants.iter().map(|(variant, fields)| {
+        let variant_name = &variant.attrs.name().serialize_name().value;
+        derive_variant_style(&variant.style, variant_name, fields)
+    });
+    let docs = variants.iter().filter_map(|(variant, _)| {
         let variant_name = &variant.attrs.name().serialize_name().value;
-        derive_variant_style(&variant.style, variant_name, &variant.fields)
+        let doc = doc_comment(&variant.original.attrs)?;
+        Some(quote!(ty.document_variant(#variant_name, #doc);))
     });
+    let field_registrations = register_field_types(variants.iter().flat_map(|(_, fields)| fields));
     quote!(
         let mut ty = _pb::__private::EnumType::new();
         #(#body);*;
-        reg.register_enum_binding(#enum_name.into(), module_path!(), ty);
+        #(#docs)*
+        reg.register_enum_binding(#name, module_path!(), ty);
+        #field_registrations
     )
 }
 
-fn derive_variant_style<'a>(
+fn derive_variant_style(
     style: &Style,
     variant_name: impl AsRef<str>,
-    fields: impl AsRef<[ast::Field<'a>]>,
+    fields: impl AsRef<[BindingField]>,
 ) -> TokenStream {
     match style {
         ast::Style::Struct => derive_struct_variant(variant_name, fields),
-        ast::Style::Newtype => derive_newtype_variant(variant_name, &fields.as_ref()[0]),
-        ast::Style::Tuple => derive_tuple_variant(variant_name, fields),
+        ast::Style::Newtype | ast::Style::Tuple => derive_tuple_variant(variant_name, fields),
         ast::Style::Unit => derive_unit_variant(variant_name),
     }
 }
@@ -33,40 +51,32 @@ fn derive_unit_variant(variant_name: impl AsRef<str>) -> TokenStream {
     quote!(ty.register_variant(#variant_name.into());)
 }
 
-fn derive_newtype_variant(variant_name: impl AsRef<str>, field: &ast::Field<'_>) -> TokenStream {
-    let variant_name = variant_name.as_ref();
-    let ty = field.ty;
-    quote!(
-        let mut fields = _pb::__private::TupleFields::default();
-        fields.register_field::<#ty>();
-        ty.register_variant_tuple(#variant_name.into(), fields);
-    )
-}
-
-fn derive_struct_variant<'a>(
+fn derive_struct_variant(
     variant_name: impl AsRef<str>,
-    fields: impl AsRef<[ast::Field<'a>]>,
+    fields: impl AsRef<[BindingField]>,
 ) -> TokenStream {
     let variant_name = variant_name.as_ref();
     let body = fields.as_ref().iter().map(|field| {
-        let ty = field.ty;
-        let field_name = field.attrs.name().serialize_name();
+        let ty = &field.ty;
+        let field_name = &field.name;
         quote!(fields.register_field::<#ty>(#field_name.into());)
     });
+    let docs = document_fields(quote!(fields), &fields);
     quote!(
         let mut fields = _pb::__private::StructFields::default();
         #(#body);*;
+        #docs
         ty.register_unnamed_struct(#variant_name.into(), fields);
     )
 }
 
-fn derive_tuple_variant<'a>(
+fn derive_tuple_variant(
     variant_name: impl AsRef<str>,
-    fields: impl AsRef<[ast::Field<'a>]>,
+    fields: impl AsRef<[BindingField]>,
 ) -> TokenStream {
     let variant_name = variant_name.as_ref();
     let body = fields.as_ref().iter().map(|field| {
-        let ty = field.ty;
+        let ty = &field.ty;
         quote!(fields.register_field::<#ty>();)
     });
     quote!(
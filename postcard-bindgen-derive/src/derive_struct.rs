@@ -1,20 +1,35 @@
-use proc_macro2::{Ident, TokenStream};
+use proc_macro2::TokenStream;
 use quote::quote;
-use serde_derive_internals::ast::{Field, Style};
-
-pub fn derive_struct(style: Style, ident: Ident, fields: Vec<Field>) -> TokenStream {
-    let fields = fields
-        .into_iter()
-        .filter(|field| !(field.attrs.skip_serializing() || field.attrs.skip_deserializing()))
-        .collect::<Vec<_>>();
-    let struct_name = ident.to_string();
-    derive_struct_style(style, struct_name, fields)
+use serde_derive_internals::{
+    ast::{Field, Style},
+    Ctxt,
+};
+
+use crate::attrs::{binding_fields, BindingField};
+
+pub fn derive_struct(cx: &Ctxt, style: Style, name: TokenStream, fields: &[Field]) -> TokenStream {
+    let fields = binding_fields(cx, fields);
+    let field_registrations = register_field_types(&fields);
+    let body = derive_struct_style(style, name, fields);
+    quote!(
+        #body
+        #field_registrations
+    )
+}
+
+/// Registers the containers referenced by the fields, so they don't need to be listed by hand.
+pub fn register_field_types<'a>(fields: impl IntoIterator<Item = &'a BindingField>) -> TokenStream {
+    let body = fields.into_iter().map(|field| {
+        let ty = &field.ty;
+        quote!(<#ty as _pb::__private::GenBinding>::register_bindings(reg);)
+    });
+    quote!(#(#body)*)
 }
 
-fn derive_struct_style<'a>(
+fn derive_struct_style(
     style: Style,
-    struct_name: String,
-    fields: impl AsRef<[Field<'a>]>,
+    struct_name: TokenStream,
+    fields: impl AsRef<[BindingField]>,
 ) -> TokenStream {
     match style {
         Style::Struct => derive_struct_type(struct_name, fields),
@@ -24,34 +39,46 @@ fn derive_struct_style<'a>(
     }
 }
 
-fn derive_unit_struct_type(name: String) -> TokenStream {
+fn derive_unit_struct_type(name: TokenStream) -> TokenStream {
     quote!(
         let mut ty = _pb::__private::UnitStructType::new();
-        reg.register_unit_struct_binding(#name.into(), module_path!(), ty);
+        reg.register_unit_struct_binding(#name, module_path!(), ty);
     )
 }
 
-fn derive_tuple_struct_type<'a>(name: String, fields: impl AsRef<[Field<'a>]>) -> TokenStream {
+fn derive_tuple_struct_type(name: TokenStream, fields: impl AsRef<[BindingField]>) -> TokenStream {
     let body = fields.as_ref().iter().map(|field| {
-        let ty = field.ty;
+        let ty = &field.ty;
         quote!(ty.register_field::<#ty>())
     });
     quote!(
         let mut ty = _pb::__private::TupleStructType::new();
         #(#body);*;
-        reg.register_tuple_struct_binding(#name.into(), module_path!(), ty);
+        reg.register_tuple_struct_binding(#name, module_path!(), ty);
     )
 }
 
-fn derive_struct_type<'a>(name: String, fields: impl AsRef<[Field<'a>]>) -> TokenStream {
+fn derive_struct_type(name: TokenStream, fields: impl AsRef<[BindingField]>) -> TokenStream {
     let body = fields.as_ref().iter().map(|field| {
-        let ident_str = field.attrs.name().serialize_name();
-        let ty = field.ty;
+        let ident_str = &field.name;
+        let ty = &field.ty;
         quote!(ty.register_field::<#ty>(#ident_str.into()))
     });
+    let docs = document_fields(quote!(ty), &fields);
     quote!(
         let mut ty = _pb::__private::StructType::new();
         #(#body);*;
-        reg.register_struct_binding(#name.into(), module_path!(), ty);
+        #docs
+        reg.register_struct_binding(#name, module_path!(), ty);
     )
 }
+
+/// Sets the doc comments of the fields registered on the receiver.
+pub fn document_fields(receiver: TokenStream, fields: impl AsRef<[BindingField]>) -> TokenStream {
+    let body = fields.as_ref().iter().filter_map(|field| {
+        let ident_str = &field.name;
+        let doc = field.doc.as_ref()?;
+        Some(quote!(#receiver.document_field(#ident_str, #doc);))
+    });
+    quote!(#(#body)*)
+}
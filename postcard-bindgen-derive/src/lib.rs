@@ -3,12 +3,13 @@ use derive_struct::derive_struct;
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
 use serde_derive_internals::{ast, Ctxt, Derive};
-use syn::DeriveInput;
+use syn::{parse_quote, DeriveInput, GenericParam, Generics, WherePredicate};
 
+mod attrs;
 mod derive_enum;
 mod derive_struct;
 
-#[proc_macro_derive(PostcardBindings)]
+#[proc_macro_derive(PostcardBindings, attributes(postcard_bindgen))]
 pub fn postcard_bindings(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     derive_js_implementation(input).into()
 }
@@ -19,45 +20,118 @@ fn derive_js_implementation(input: proc_macro::TokenStream) -> TokenStream {
     let cx = Ctxt::new();
     // `serde_derive_internals` uses this only when parsing deserialization helpers.
     let private = Ident::new("__private", Span::call_site());
-    let container = ast::Container::from_ast(&cx, &input, Derive::Serialize, &private).unwrap();
+    let Some(container) = ast::Container::from_ast(&cx, &input, Derive::Serialize, &private) else {
+        return cx.check().unwrap_err().to_compile_error();
+    };
+
+    attrs::check_container(&cx, &container);
+
+    // containers serialized as another type have no own layout to register
+    let proxy_type = attrs::proxy_type(&cx, &container);
+
+    let ident = container.ident;
+    let name = container_name(&ident, container.generics);
 
-    let body = match container.data {
-        ast::Data::Enum(variants) => derive_enum(container.ident.to_owned(), variants),
-        ast::Data::Struct(style, fields) => {
-            derive_struct(style, container.ident.to_owned(), fields)
+    let body = match (&proxy_type, &container.data) {
+        (Some(ty), _) => quote!(<#ty as _pb::__private::GenBinding>::register_bindings(reg);),
+        (None, ast::Data::Enum(variants)) => derive_enum(&cx, name.to_owned(), variants),
+        (None, ast::Data::Struct(style, fields)) => {
+            derive_struct(&cx, *style, name.to_owned(), fields)
+        }
+    };
+    // containers are registered before their fields, which stops the recursion for
+    // self-referencing types
+    let body = match proxy_type {
+        Some(_) => body,
+        None => {
+            let doc = attrs::doc_comment(&input.attrs)
+                .map(|doc| quote!(reg.document_binding(&#name, module_path!(), #doc);));
+            quote!(
+                if reg.contains_binding(&#name, module_path!()) {
+                    return;
+                }
+                #body
+                #doc
+            )
         }
     };
 
-    let ident = container.ident;
-    let generics = container.generics;
-    let container_name = ident.to_string();
+    if let Err(err) = cx.check() {
+        return err.to_compile_error();
+    }
 
-    let expanded = if cfg!(feature = "expanding") {
+    let get_type = match proxy_type {
+        Some(ty) => quote!(<#ty as _pb::__private::GenBinding>::get_type()),
+        None => quote!(
+            _pb::__private::ValueType::Object(_pb::__private::ObjectMeta {
+                name: ::core::convert::Into::into(#name),
+                path: _pb::__private::Path::new(module_path!(), "::"),
+            })
+        ),
+    };
+
+    // every instantiation of a generic container is generated as its own type, so all
+    // type parameters need bindings
+    let mut generics = container.generics.to_owned();
+    let bounds = generics
+        .type_params()
+        .map(|param| &param.ident)
+        .map(|param| -> WherePredicate { parse_quote!(#param: _pb::__private::GenBinding) })
+        .collect::<Vec<_>>();
+    generics.make_where_clause().predicates.extend(bounds);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    if cfg!(feature = "expanding") {
         quote!(
             const _: () = {
                 #[allow(unused_extern_crates, clippy::useless_attribute)]
                 extern crate postcard_bindgen as _pb;
-                impl #generics _pb::__private::JsBindings for #ident #generics {
+                impl #impl_generics _pb::__private::JsBindings for #ident #ty_generics #where_clause {
                     fn create_bindings(reg: &mut _pb::__private::BindingsRegistry) {
                         #body
                     }
                 }
 
-                impl #generics _pb::__private::GenBinding for #ident #generics {
+                impl #impl_generics _pb::__private::GenBinding for #ident #ty_generics #where_clause {
                     fn get_type() -> _pb::__private::ValueType {
-                        _pb::__private::ValueType::Object(_pb::__private::ObjectMeta {
-                            name: #container_name.into(),
-                            path: _pb::__private::Path::new(module_path!(), "::"),
-                        })
+                        #get_type
+                    }
+
+                    fn register_bindings(reg: &mut _pb::__private::BindingsRegistry) {
+                        <Self as _pb::__private::JsBindings>::create_bindings(reg)
                     }
                 }
             };
         )
     } else {
         TokenStream::new()
-    };
+    }
+}
 
-    cx.check().unwrap();
+/// Returns an expression evaluating to the name the container is registered with.
+///
+/// Generic containers are named after their arguments, e.g. `Frame<u8>` becomes `Frame_u8`.
+fn container_name(ident: &Ident, generics: &Generics) -> TokenStream {
+    let name = ident.to_string();
+    let args = generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(param) => {
+                let ident = &param.ident;
+                Some(quote!(&<#ident as _pb::__private::GenBinding>::get_type().name()))
+            }
+            GenericParam::Const(param) => {
+                let ident = &param.ident;
+                Some(quote!(&#ident))
+            }
+            GenericParam::Lifetime(_) => None,
+        })
+        .collect::<Vec<_>>();
 
-    expanded
+    if args.is_empty() {
+        quote!(#name)
+    } else {
+        quote!(_pb::__private::generic_container_name(#name, &[#(#args),*]))
+    }
 }
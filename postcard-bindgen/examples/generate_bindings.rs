@@ -1,4 +1,4 @@
-use std::{collections::HashMap, io::Write, num::NonZero, ops::Range, str::FromStr, u8};
+use std::{collections::HashMap, io::Write, num::NonZero, ops::Range, str::FromStr};
 
 use postcard_bindgen::{generate_bindings, javascript, python, PackageInfo, PostcardBindings};
 use serde::Serialize;
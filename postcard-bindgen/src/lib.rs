@@ -11,7 +11,9 @@
 //! will be placed in it.
 //!
 //! ```rust
-//! # use postcard_bindgen::{PostcardBindings, generate_bindings, javascript::{build_package, GenerationSettings}, PackageInfo};
+//! # #[cfg(feature = "generating")]
+//! # use postcard_bindgen::{generate_bindings, javascript::{build_package, GenerationSettings}, PackageInfo};
+//! # use postcard_bindgen::PostcardBindings;
 //! # use serde::Serialize;
 //! # extern crate alloc;
 //! #[derive(Serialize, PostcardBindings)]
@@ -30,6 +32,7 @@
 //!     D { a: &'static str, b: B },
 //! }
 //!
+//! # #[cfg(feature = "generating")]
 //! fn main() {
 //!     build_package(
 //!         std::env::current_dir().unwrap().as_path(),
@@ -42,6 +45,8 @@
 //!     )
 //!     .unwrap();
 //! }
+//! # #[cfg(not(feature = "generating"))]
+//! # fn main() {}
 //! ```
 //!
 //! ```text
@@ -75,6 +80,13 @@ pub mod javascript {
     pub use postcard_bindgen_core::code_gen::js::GenerationSettings;
 }
 
+#[cfg(feature = "generating")]
+#[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
+pub mod typescript {
+    pub use super::package::ts_package::build_ts_package as build_package;
+    pub use postcard_bindgen_core::code_gen::ts::GenerationSettings;
+}
+
 #[cfg(feature = "generating")]
 #[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
 pub mod python {
@@ -82,10 +94,73 @@ pub mod python {
     pub use postcard_bindgen_core::code_gen::python::GenerationSettings;
 }
 
+#[cfg(feature = "generating")]
+#[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
+pub mod cpp {
+    pub use super::package::cpp_package::build_cpp_package as build_package;
+    pub use postcard_bindgen_core::code_gen::cpp::GenerationSettings;
+}
+
+#[cfg(feature = "generating")]
+#[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
+pub mod csharp {
+    pub use super::package::nuget_package::build_nuget_package as build_package;
+    pub use postcard_bindgen_core::code_gen::csharp::GenerationSettings;
+}
+
+#[cfg(feature = "generating")]
+#[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
+pub mod dart {
+    pub use super::package::pub_package::build_pub_package as build_package;
+    pub use postcard_bindgen_core::code_gen::dart::GenerationSettings;
+}
+
+#[cfg(feature = "generating")]
+#[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
+pub mod go {
+    pub use super::package::go_module::build_go_module as build_package;
+    pub use postcard_bindgen_core::code_gen::go::GenerationSettings;
+}
+
+#[cfg(feature = "generating")]
+#[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
+pub mod kotlin {
+    pub use super::package::gradle_package::build_gradle_package as build_package;
+    pub use postcard_bindgen_core::code_gen::kotlin::GenerationSettings;
+}
+
+#[cfg(feature = "generating")]
+#[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
+pub mod swift {
+    pub use super::package::swift_package::build_swift_package as build_package;
+    pub use postcard_bindgen_core::code_gen::swift::GenerationSettings;
+}
+
+#[cfg(feature = "generating")]
+#[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
+pub use postcard_bindgen_core::compat;
+
+#[cfg(feature = "generating")]
+#[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
+pub use postcard_bindgen_core::dynamic;
+
+#[cfg(feature = "generating")]
+#[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
+pub use postcard_bindgen_core::{
+    registry::ContainerCollection,
+    schema::{SchemaError, SCHEMA_VERSION},
+};
+
 #[cfg(feature = "generating")]
 #[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
 pub use package::{PackageInfo, Version, VersionFromStrError};
 
+#[cfg(feature = "generating")]
+#[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
+pub use postcard_bindgen_core::validation::{
+    ValidationError, ValidationErrorReason, ValidationErrors, ValidationSettings,
+};
+
 /// Macro to annotate structs or enums for which bindings should be generated.
 ///
 /// For this macro to work, the [`serde::Serialize`] macro must be derived as well.
@@ -99,6 +174,49 @@ pub use package::{PackageInfo, Version, VersionFromStrError};
 ///    a: u32
 /// }
 /// ```
+///
+/// # Serde attributes
+///
+/// Serde attributes which change the serialized layout are taken into account,
+/// such as `rename`, `skip`, `transparent` and `into`. Attributes postcard can't
+/// represent (`untagged`, `tag`, `flatten`, `skip_serializing_if`) are rejected at
+/// compile time, as are attributes giving different serialized and deserialized layouts
+/// (`into` without a matching `from`, fields and variants skipped in only one direction).
+/// Skipped enum variants must be declared after all other variants.
+///
+/// Fields using `#[serde(with = "...")]` need to declare the type they are serialized as.
+///
+/// ```rust
+/// # use serde::Serialize;
+/// # use postcard_bindgen_derive::PostcardBindings;
+/// # mod as_u8 {
+/// #     pub fn serialize<S: serde::Serializer>(v: &bool, s: S) -> Result<S::Ok, S::Error> {
+/// #         s.serialize_u8(*v as u8)
+/// #     }
+/// # }
+/// #[derive(Serialize, PostcardBindings)]
+/// struct Test {
+///     #[serde(serialize_with = "as_u8::serialize")]
+///     #[postcard_bindgen(as_type = "u8")]
+///     enabled: bool,
+/// }
+/// ```
+///
+/// # Generics
+///
+/// Every instantiation of a generic container passed to [`generate_bindings!`] is generated
+/// as its own type, named after its arguments. `Frame<u8>` becomes `Frame_u8`, and
+/// `Frame<Status>` becomes `Frame_Status`.
+///
+/// ```rust
+/// # use serde::Serialize;
+/// # use postcard_bindgen_derive::PostcardBindings;
+/// #[derive(Serialize, PostcardBindings)]
+/// struct Frame<T> {
+///     id: u8,
+///     payload: T,
+/// }
+/// ```
 pub use postcard_bindgen_derive::PostcardBindings;
 
 #[cfg(feature = "generating")]
@@ -107,7 +225,9 @@ pub mod __private {
     pub use postcard_bindgen_core::{
         path::Path,
         registry::*,
-        type_info::{GenBinding, ObjectMeta, ValueType},
+        type_info::{
+            generic_container_name, FixintBe, FixintLe, GenBinding, ObjectMeta, ValueType,
+        },
     };
 }
 
@@ -118,6 +238,9 @@ pub mod __private {
 /// This `trait` is automatically implemented when deriving the
 /// [`postcard_bindgen_derive::PostcardBindings`] on the types.
 ///
+/// Containers referenced by the supplied types, directly or through fields of other
+/// containers, are registered as well, so only the top level types need to be listed.
+///
 /// # Example
 /// ```rust
 /// # use serde::Serialize;
@@ -0,0 +1,114 @@
+use core::borrow::Borrow;
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
+use postcard_bindgen_core::{
+    code_gen::cpp::{generate, GenerationSettings},
+    lang::c::Tokens,
+    registry::ContainerCollection,
+};
+
+use super::{PackageInfo, Version};
+
+/// Builds a header only C++ library from [ContainerCollection].
+///
+/// The package contains a single header `include/<name>.hpp` and a `CMakeLists.txt`
+/// defining an interface library target, so it can be added with `add_subdirectory()`
+/// or `FetchContent`.
+///
+/// Fails with [io::ErrorKind::InvalidInput] before writing any files if the bindings don't
+/// pass [ContainerCollection::validate()].
+///
+/// # Example
+/// ```
+/// # use postcard_bindgen::{cpp::{build_package, GenerationSettings}, PackageInfo, PostcardBindings, generate_bindings};
+/// # use serde::Serialize;
+/// #[derive(Serialize, PostcardBindings)]
+/// struct Test {
+///    field: u8
+/// }
+///
+/// # fn main() {
+/// let parent_dir = std::env::current_dir().unwrap();
+/// let package_info = PackageInfo {
+///    name: "test_package".into(),
+///    version: "0.1.0".try_into().unwrap()
+/// };
+///
+/// build_package(parent_dir.as_path(), package_info, GenerationSettings::enable_all(), generate_bindings!(Test));
+/// # }
+/// ```
+pub fn build_cpp_package(
+    parent_dir: &Path,
+    package_info: PackageInfo,
+    gen_settings: impl Borrow<GenerationSettings>,
+    containers: ContainerCollection,
+) -> io::Result<()> {
+    containers
+        .validate(gen_settings.borrow().into())
+        .map_err(|errors| io::Error::new(io::ErrorKind::InvalidInput, errors))?;
+
+    let mut dir = parent_dir.to_path_buf();
+    dir.push(package_info.name.as_str());
+    std::fs::create_dir_all(&dir)?;
+
+    let package_name = package_info.name.replace("-", "_");
+
+    let cmake_lists = cmake_file_src(&package_name, &package_info.version);
+
+    let mut cmake_lists_path = dir.to_owned();
+    cmake_lists_path.push("CMakeLists.txt");
+    File::create(cmake_lists_path.as_path())?.write_all(cmake_lists.as_bytes())?;
+
+    dir.push("include");
+    std::fs::create_dir_all(&dir)?;
+
+    let mut exports = generate(containers, gen_settings, package_info.name);
+
+    let mut header = Tokens::new();
+    for content_type in [
+        "runtime",
+        "types",
+        "serializer",
+        "deserializer",
+        "ser",
+        "des",
+    ] {
+        if let Some(file) = exports.pop_file(content_type) {
+            header.append(file);
+            header.line();
+        }
+    }
+
+    let mut header_path = dir.to_owned();
+    header_path.push(format!("{package_name}.hpp"));
+    File::create(header_path.as_path())?
+        .write_all(format!("#pragma once\n\n{}", header.to_file_string().unwrap()).as_bytes())?;
+
+    Ok(())
+}
+
+fn cmake_file_src(package_name: impl AsRef<str>, package_version: &Version) -> String {
+    let package_name = package_name.as_ref();
+    let package_version = package_version.to_string();
+
+    format!(
+        "cmake_minimum_required(VERSION 3.14)
+
+project({package_name} VERSION {package_version} LANGUAGES CXX)
+
+add_library({package_name} INTERFACE)
+add_library({package_name}::{package_name} ALIAS {package_name})
+
+target_include_directories({package_name} INTERFACE
+    $<BUILD_INTERFACE:${{CMAKE_CURRENT_SOURCE_DIR}}/include>
+    $<INSTALL_INTERFACE:include>
+)
+
+target_compile_features({package_name} INTERFACE cxx_std_17)
+",
+    )
+}
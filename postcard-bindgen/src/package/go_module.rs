@@ -0,0 +1,83 @@
+use core::borrow::Borrow;
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
+use postcard_bindgen_core::{
+    code_gen::go::{generate, GenerationSettings},
+    registry::ContainerCollection,
+};
+
+use super::PackageInfo;
+
+const GO_VERSION: &str = "1.21";
+
+/// Builds a Go module from [ContainerCollection].
+///
+/// The module path and the package are named after the package. Go modules are versioned
+/// by the tags of their repository, so the version of the package isn't written.
+///
+/// Fails with [io::ErrorKind::InvalidInput] before writing any files if the bindings don't
+/// pass [ContainerCollection::validate()].
+///
+/// # Example
+/// ```
+/// # use postcard_bindgen::{go::{build_package, GenerationSettings}, PackageInfo, PostcardBindings, generate_bindings};
+/// # use serde::Serialize;
+/// #[derive(Serialize, PostcardBindings)]
+/// struct Test {
+///    field: u8
+/// }
+///
+/// # fn main() {
+/// let parent_dir = std::env::current_dir().unwrap();
+/// let package_info = PackageInfo {
+///    name: "test_package".into(),
+///    version: "0.1.0".try_into().unwrap()
+/// };
+///
+/// build_package(parent_dir.as_path(), package_info, GenerationSettings::enable_all(), generate_bindings!(Test));
+/// # }
+/// ```
+pub fn build_go_module(
+    parent_dir: &Path,
+    package_info: PackageInfo,
+    gen_settings: impl Borrow<GenerationSettings>,
+    containers: ContainerCollection,
+) -> io::Result<()> {
+    containers
+        .validate(gen_settings.borrow().into())
+        .map_err(|errors| io::Error::new(io::ErrorKind::InvalidInput, errors))?;
+
+    let mut dir = parent_dir.to_path_buf();
+    dir.push(package_info.name.as_str());
+    std::fs::create_dir_all(&dir)?;
+
+    let mut mod_file_path = dir.to_owned();
+    mod_file_path.push("go.mod");
+    File::create(mod_file_path.as_path())?
+        .write_all(mod_file_src(&package_info.name).as_bytes())?;
+
+    let exports = generate(containers, gen_settings, package_info.name);
+
+    for file in exports.files {
+        let path = dir.join(format!("{}.go", file.content_type));
+        File::create(path.as_path())?
+            .write_all(file.content.to_file_string().unwrap().as_bytes())?;
+    }
+
+    Ok(())
+}
+
+fn mod_file_src(module_path: impl AsRef<str>) -> String {
+    let module_path = module_path.as_ref();
+
+    format!(
+        "module {module_path}
+
+go {GO_VERSION}
+"
+    )
+}
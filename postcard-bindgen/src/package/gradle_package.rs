@@ -0,0 +1,117 @@
+use core::borrow::Borrow;
+use std::{
+    fs::{create_dir_all, File},
+    io::{self, Write},
+    path::Path,
+};
+
+use postcard_bindgen_core::{
+    code_gen::kotlin::{generate, GenerationSettings},
+    registry::ContainerCollection,
+};
+
+use super::{PackageInfo, Version};
+
+const KOTLIN_VERSION: &str = "1.9.24";
+
+/// Builds a Gradle project with Kotlin sources from [ContainerCollection].
+///
+/// The sources are placed in `src/main/kotlin` below the package named after the
+/// package name. The project is a Kotlin JVM library, so it can be included in an
+/// Android build with `includeBuild()` or published to a maven repository.
+///
+/// Fails with [io::ErrorKind::InvalidInput] before writing any files if the bindings don't
+/// pass [ContainerCollection::validate()].
+///
+/// # Example
+/// ```
+/// # use postcard_bindgen::{kotlin::{build_package, GenerationSettings}, PackageInfo, PostcardBindings, generate_bindings};
+/// # use serde::Serialize;
+/// #[derive(Serialize, PostcardBindings)]
+/// struct Test {
+///    field: u8
+/// }
+///
+/// # fn main() {
+/// let parent_dir = std::env::current_dir().unwrap();
+/// let package_info = PackageInfo {
+///    name: "test_package".into(),
+///    version: "0.1.0".try_into().unwrap()
+/// };
+///
+/// build_package(parent_dir.as_path(), package_info, GenerationSettings::enable_all(), generate_bindings!(Test));
+/// # }
+/// ```
+pub fn build_gradle_package(
+    parent_dir: &Path,
+    package_info: PackageInfo,
+    gen_settings: impl Borrow<GenerationSettings>,
+    containers: ContainerCollection,
+) -> io::Result<()> {
+    containers
+        .validate(gen_settings.borrow().into())
+        .map_err(|errors| io::Error::new(io::ErrorKind::InvalidInput, errors))?;
+
+    let mut dir = parent_dir.to_path_buf();
+    dir.push(package_info.name.as_str());
+    std::fs::create_dir_all(&dir)?;
+
+    let package_name = package_info.name.replace("-", "_");
+
+    let mut settings_path = dir.to_owned();
+    settings_path.push("settings.gradle.kts");
+    File::create(settings_path.as_path())?
+        .write_all(settings_file_src(&package_info.name).as_bytes())?;
+
+    let mut build_path = dir.to_owned();
+    build_path.push("build.gradle.kts");
+    File::create(build_path.as_path())?
+        .write_all(build_file_src(&package_name, &package_info.version).as_bytes())?;
+
+    dir.push("src");
+    dir.push("main");
+    dir.push("kotlin");
+    dir.push(&package_name);
+
+    let exports = generate(containers, gen_settings, package_info.name);
+
+    for file in exports.files {
+        let path = dir.join(format!("{}.kt", file.content_type));
+        let dir_path = {
+            let mut p = path.clone();
+            p.pop();
+            p
+        };
+        create_dir_all(dir_path)?;
+        File::create(path.as_path())?
+            .write_all(file.content.to_file_string().unwrap().as_bytes())?;
+    }
+
+    Ok(())
+}
+
+fn settings_file_src(project_name: impl AsRef<str>) -> String {
+    let project_name = project_name.as_ref();
+
+    format!("rootProject.name = \"{project_name}\"\n")
+}
+
+fn build_file_src(package_name: impl AsRef<str>, package_version: &Version) -> String {
+    let package_name = package_name.as_ref();
+    let package_version = package_version.to_string();
+
+    format!(
+        "plugins {{
+    kotlin(\"jvm\") version \"{KOTLIN_VERSION}\"
+    `java-library`
+}}
+
+group = \"{package_name}\"
+version = \"{package_version}\"
+
+repositories {{
+    mavenCentral()
+}}
+",
+    )
+}
@@ -1,5 +1,12 @@
+pub mod cpp_package;
+pub mod go_module;
+pub mod gradle_package;
 pub mod npm_package;
+pub mod nuget_package;
 pub mod pip_module;
+pub mod pub_package;
+pub mod swift_package;
+pub mod ts_package;
 
 use std::{
     error::Error,
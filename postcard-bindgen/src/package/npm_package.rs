@@ -15,6 +15,9 @@ use super::{PackageInfo, Version};
 
 /// Builds a npm package from [ContainerCollection].
 ///
+/// Fails with [io::ErrorKind::InvalidInput] before writing any files if the bindings don't
+/// pass [ContainerCollection::validate()].
+///
 /// # Example
 /// ```
 /// # use postcard_bindgen::{javascript::{build_package, GenerationSettings}, PackageInfo, PostcardBindings, generate_bindings};
@@ -40,6 +43,10 @@ pub fn build_npm_package(
     gen_settings: impl Borrow<GenerationSettings>,
     bindings: ContainerCollection,
 ) -> io::Result<()> {
+    bindings
+        .validate(gen_settings.borrow().into())
+        .map_err(|errors| io::Error::new(io::ErrorKind::InvalidInput, errors))?;
+
     let mut dir = parent_dir.to_path_buf();
     dir.push(package_info.name.as_str());
 
@@ -61,11 +68,14 @@ pub fn build_npm_package(
     let js_export_path = dir.join("index.js");
     let js_tokens = [
         "util",
+        "fingerprint",
         "serializer",
         "deserializer",
         "runtime_checks",
         "ser",
         "des",
+        "cobs",
+        "crc",
     ]
     .into_iter()
     .filter_map(|t| exports.pop_file(t))
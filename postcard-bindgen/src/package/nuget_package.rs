@@ -0,0 +1,101 @@
+use core::borrow::Borrow;
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
+use postcard_bindgen_core::{
+    code_gen::csharp::{generate, GenerationSettings},
+    registry::ContainerCollection,
+};
+
+use super::{PackageInfo, Version};
+
+const TARGET_FRAMEWORK: &str = "net8.0";
+
+/// Builds a .NET class library project with C# sources from [ContainerCollection].
+///
+/// The project file is named after the package and the sources are placed next to it, so
+/// the project can be referenced from another project or packed to a NuGet package with
+/// `dotnet pack`.
+///
+/// Fails with [io::ErrorKind::InvalidInput] before writing any files if the bindings don't
+/// pass [ContainerCollection::validate()].
+///
+/// # Example
+/// ```
+/// # use postcard_bindgen::{csharp::{build_package, GenerationSettings}, PackageInfo, PostcardBindings, generate_bindings};
+/// # use serde::Serialize;
+/// #[derive(Serialize, PostcardBindings)]
+/// struct Test {
+///    field: u8
+/// }
+///
+/// # fn main() {
+/// let parent_dir = std::env::current_dir().unwrap();
+/// let package_info = PackageInfo {
+///    name: "test_package".into(),
+///    version: "0.1.0".try_into().unwrap()
+/// };
+///
+/// build_package(parent_dir.as_path(), package_info, GenerationSettings::enable_all(), generate_bindings!(Test));
+/// # }
+/// ```
+pub fn build_nuget_package(
+    parent_dir: &Path,
+    package_info: PackageInfo,
+    gen_settings: impl Borrow<GenerationSettings>,
+    containers: ContainerCollection,
+) -> io::Result<()> {
+    containers
+        .validate(gen_settings.borrow().into())
+        .map_err(|errors| io::Error::new(io::ErrorKind::InvalidInput, errors))?;
+
+    let mut dir = parent_dir.to_path_buf();
+    dir.push(package_info.name.as_str());
+    std::fs::create_dir_all(&dir)?;
+
+    let namespace = package_info.name.replace("-", "_");
+
+    let mut project_path = dir.to_owned();
+    project_path.push(format!("{}.csproj", package_info.name));
+    File::create(project_path.as_path())?.write_all(
+        project_file_src(&package_info.name, &namespace, &package_info.version).as_bytes(),
+    )?;
+
+    let exports = generate(containers, gen_settings, package_info.name);
+
+    for file in exports.files {
+        let path = dir.join(format!("{}.cs", file.content_type));
+        File::create(path.as_path())?
+            .write_all(file.content.to_file_string().unwrap().as_bytes())?;
+    }
+
+    Ok(())
+}
+
+fn project_file_src(
+    package_name: impl AsRef<str>,
+    namespace: impl AsRef<str>,
+    package_version: &Version,
+) -> String {
+    let package_name = package_name.as_ref();
+    let namespace = namespace.as_ref();
+    let package_version = package_version.to_string();
+
+    format!(
+        "<Project Sdk=\"Microsoft.NET.Sdk\">
+
+  <PropertyGroup>
+    <TargetFramework>{TARGET_FRAMEWORK}</TargetFramework>
+    <Nullable>enable</Nullable>
+    <RootNamespace>{namespace}</RootNamespace>
+    <PackageId>{package_name}</PackageId>
+    <Version>{package_version}</Version>
+  </PropertyGroup>
+
+</Project>
+",
+    )
+}
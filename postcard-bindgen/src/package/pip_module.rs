@@ -14,6 +14,9 @@ use super::{PackageInfo, Version};
 
 /// Builds a pip module from [ContainerCollection].
 ///
+/// Fails with [io::ErrorKind::InvalidInput] before writing any files if the bindings don't
+/// pass [ContainerCollection::validate()].
+///
 /// # Example
 /// ```
 /// # use postcard_bindgen::{python::{build_package, GenerationSettings}, PackageInfo, PostcardBindings, generate_bindings};
@@ -39,6 +42,10 @@ pub fn build_pip_module(
     gen_settings: impl Borrow<GenerationSettings>,
     containers: ContainerCollection,
 ) -> io::Result<()> {
+    containers
+        .validate(gen_settings.borrow().into())
+        .map_err(|errors| io::Error::new(io::ErrorKind::InvalidInput, errors))?;
+
     let mut dir = parent_dir.to_path_buf();
     dir.push(package_info.name.as_str());
     std::fs::create_dir_all(&dir)?;
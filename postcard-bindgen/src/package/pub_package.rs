@@ -0,0 +1,99 @@
+use core::borrow::Borrow;
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
+use postcard_bindgen_core::{
+    code_gen::dart::{generate, GenerationSettings},
+    lang::dart::Tokens,
+    registry::ContainerCollection,
+};
+
+use super::{PackageInfo, Version};
+
+const SDK_CONSTRAINT: &str = "^3.0.0";
+
+/// Builds a Dart pub package from [ContainerCollection].
+///
+/// Pub package names may only contain lowercase letters, digits and underscores, so dashes
+/// in the package name are replaced by underscores. The bindings are written to a single
+/// library `lib/<package_name>.dart`.
+///
+/// Fails with [io::ErrorKind::InvalidInput] before writing any files if the bindings don't
+/// pass [ContainerCollection::validate()].
+///
+/// # Example
+/// ```
+/// # use postcard_bindgen::{dart::{build_package, GenerationSettings}, PackageInfo, PostcardBindings, generate_bindings};
+/// # use serde::Serialize;
+/// #[derive(Serialize, PostcardBindings)]
+/// struct Test {
+///    field: u8
+/// }
+///
+/// # fn main() {
+/// let parent_dir = std::env::current_dir().unwrap();
+/// let package_info = PackageInfo {
+///    name: "test_package".into(),
+///    version: "0.1.0".try_into().unwrap()
+/// };
+///
+/// build_package(parent_dir.as_path(), package_info, GenerationSettings::enable_all(), generate_bindings!(Test));
+/// # }
+/// ```
+pub fn build_pub_package(
+    parent_dir: &Path,
+    package_info: PackageInfo,
+    gen_settings: impl Borrow<GenerationSettings>,
+    containers: ContainerCollection,
+) -> io::Result<()> {
+    containers
+        .validate(gen_settings.borrow().into())
+        .map_err(|errors| io::Error::new(io::ErrorKind::InvalidInput, errors))?;
+
+    let mut dir = parent_dir.to_path_buf();
+    dir.push(package_info.name.as_str());
+
+    let package_name = package_info.name.replace("-", "_");
+
+    let lib_dir = dir.join("lib");
+    std::fs::create_dir_all(&lib_dir)?;
+
+    let mut pubspec_path = dir.to_owned();
+    pubspec_path.push("pubspec.yaml");
+    File::create(pubspec_path.as_path())?
+        .write_all(pubspec_file_src(&package_name, &package_info.version).as_bytes())?;
+
+    let mut exports = generate(containers, gen_settings);
+
+    // a single library, so the generated classes can use the runtime without imports
+    let lib_tokens = ["runtime", "types"]
+        .into_iter()
+        .filter_map(|t| exports.pop_file(t))
+        .fold(Tokens::new(), |mut current, content| {
+            current.append(content);
+            current.line();
+            current
+        });
+
+    let lib_path = lib_dir.join(format!("{package_name}.dart"));
+    File::create(lib_path.as_path())?.write_all(lib_tokens.to_file_string().unwrap().as_bytes())?;
+
+    Ok(())
+}
+
+fn pubspec_file_src(package_name: impl AsRef<str>, package_version: &Version) -> String {
+    let package_name = package_name.as_ref();
+
+    format!(
+        "name: {package_name}
+description: Auto generated bindings for postcard format serializing and deserializing dart to and from bytes.
+version: {package_version}
+
+environment:
+  sdk: {SDK_CONSTRAINT}
+"
+    )
+}
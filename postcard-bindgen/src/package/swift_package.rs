@@ -0,0 +1,101 @@
+use core::borrow::Borrow;
+use std::{
+    fs::{create_dir_all, File},
+    io::{self, Write},
+    path::Path,
+};
+
+use postcard_bindgen_core::{
+    code_gen::swift::{generate, GenerationSettings},
+    registry::ContainerCollection,
+};
+
+use super::PackageInfo;
+
+const SWIFT_TOOLS_VERSION: &str = "5.7";
+
+/// Builds a Swift package from [ContainerCollection].
+///
+/// The package contains one library target, named after the package name with `-`
+/// replaced by `_`, so it can be added to an Xcode project or used as a dependency of
+/// another Swift package. Swift packages are versioned by git tags, the version of the
+/// [PackageInfo] is therefore not written to the package.
+///
+/// Fails with [io::ErrorKind::InvalidInput] before writing any files if the bindings don't
+/// pass [ContainerCollection::validate()].
+///
+/// # Example
+/// ```
+/// # use postcard_bindgen::{swift::{build_package, GenerationSettings}, PackageInfo, PostcardBindings, generate_bindings};
+/// # use serde::Serialize;
+/// #[derive(Serialize, PostcardBindings)]
+/// struct Test {
+///    field: u8
+/// }
+///
+/// # fn main() {
+/// let parent_dir = std::env::current_dir().unwrap();
+/// let package_info = PackageInfo {
+///    name: "test_package".into(),
+///    version: "0.1.0".try_into().unwrap()
+/// };
+///
+/// build_package(parent_dir.as_path(), package_info, GenerationSettings::enable_all(), generate_bindings!(Test));
+/// # }
+/// ```
+pub fn build_swift_package(
+    parent_dir: &Path,
+    package_info: PackageInfo,
+    gen_settings: impl Borrow<GenerationSettings>,
+    containers: ContainerCollection,
+) -> io::Result<()> {
+    containers
+        .validate(gen_settings.borrow().into())
+        .map_err(|errors| io::Error::new(io::ErrorKind::InvalidInput, errors))?;
+
+    let mut dir = parent_dir.to_path_buf();
+    dir.push(package_info.name.as_str());
+    std::fs::create_dir_all(&dir)?;
+
+    let target_name = package_info.name.replace("-", "_");
+
+    let mut manifest_path = dir.to_owned();
+    manifest_path.push("Package.swift");
+    File::create(manifest_path.as_path())?
+        .write_all(manifest_file_src(&package_info.name, &target_name).as_bytes())?;
+
+    dir.push("Sources");
+    dir.push(&target_name);
+    create_dir_all(&dir)?;
+
+    let exports = generate(containers, gen_settings, target_name);
+
+    for file in exports.files {
+        let path = dir.join(format!("{}.swift", file.content_type));
+        File::create(path.as_path())?
+            .write_all(file.content.to_file_string().unwrap().as_bytes())?;
+    }
+
+    Ok(())
+}
+
+fn manifest_file_src(package_name: impl AsRef<str>, target_name: impl AsRef<str>) -> String {
+    let package_name = package_name.as_ref();
+    let target_name = target_name.as_ref();
+
+    format!(
+        "// swift-tools-version:{SWIFT_TOOLS_VERSION}
+import PackageDescription
+
+let package = Package(
+    name: \"{package_name}\",
+    products: [
+        .library(name: \"{target_name}\", targets: [\"{target_name}\"]),
+    ],
+    targets: [
+        .target(name: \"{target_name}\"),
+    ]
+)
+",
+    )
+}
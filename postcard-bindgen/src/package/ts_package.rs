@@ -0,0 +1,122 @@
+use core::borrow::Borrow;
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
+use postcard_bindgen_core::{
+    code_gen::ts::{generate, GenerationSettings},
+    lang::js::Tokens,
+    registry::ContainerCollection,
+};
+
+use super::{PackageInfo, Version};
+
+/// Builds a typescript npm package from [ContainerCollection].
+///
+/// The typescript source is placed in `src/index.ts`. The package compiles it with `tsc`
+/// into `dist` on `npm install` (`prepare` script) or with `npm run build`.
+///
+/// Fails with [io::ErrorKind::InvalidInput] before writing any files if the bindings don't
+/// pass [ContainerCollection::validate()].
+///
+/// # Example
+/// ```
+/// # use postcard_bindgen::{typescript::{build_package, GenerationSettings}, PackageInfo, PostcardBindings, generate_bindings};
+/// # use serde::Serialize;
+/// #[derive(Serialize, PostcardBindings)]
+/// struct Test {
+///     field: u8
+/// }
+///
+/// # fn main() {
+/// let parent_dir = std::env::current_dir().unwrap();
+/// let package_info = PackageInfo {
+///     name: "test-ts-package".into(),
+///     version: "0.1.0".try_into().unwrap()
+/// };
+///
+/// build_package(parent_dir.as_path(), package_info, GenerationSettings::enable_all(), generate_bindings!(Test));
+/// # }
+/// ```
+pub fn build_ts_package(
+    parent_dir: &Path,
+    package_info: PackageInfo,
+    gen_settings: impl Borrow<GenerationSettings>,
+    bindings: ContainerCollection,
+) -> io::Result<()> {
+    bindings
+        .validate(gen_settings.borrow().into())
+        .map_err(|errors| io::Error::new(io::ErrorKind::InvalidInput, errors))?;
+
+    let mut dir = parent_dir.to_path_buf();
+    dir.push(package_info.name.as_str());
+
+    let src_dir = dir.join("src");
+    std::fs::create_dir_all(&src_dir)?;
+
+    let mut exports = generate(bindings, gen_settings);
+
+    let package_json = package_file_src(package_info.name.as_str(), &package_info.version);
+    File::create(dir.join("package.json"))?.write_all(package_json.as_bytes())?;
+
+    File::create(dir.join("tsconfig.json"))?.write_all(TS_CONFIG.as_bytes())?;
+
+    let ts_tokens = [
+        "util",
+        "types",
+        "serializer",
+        "deserializer",
+        "ser",
+        "des",
+        "cobs",
+        "crc",
+    ]
+    .into_iter()
+    .filter_map(|t| exports.pop_file(t))
+    .fold(Tokens::new(), |mut current, content| {
+        current.append(content.clone());
+        current.line();
+        current
+    });
+
+    File::create(src_dir.join("index.ts"))?
+        .write_all(ts_tokens.to_file_string().unwrap().as_bytes())?;
+
+    Ok(())
+}
+
+const TS_CONFIG: &str = "\
+{
+    \"compilerOptions\": {
+        \"target\": \"ES2021\",
+        \"module\": \"nodenext\",
+        \"strict\": true,
+        \"declaration\": true,
+        \"rootDir\": \"src\",
+        \"outDir\": \"dist\"
+    },
+    \"include\": [\"src\"]
+}";
+
+fn package_file_src(package_name: impl AsRef<str>, package_version: &Version) -> String {
+    format!("\
+{{
+    \"name\": \"{}\",
+    \"description\": \"Auto generated bindings for postcard format serializing and deserializing typescript to and from bytes.\",
+    \"version\": \"{}\",
+    \"main\": \"dist/index.js\",
+    \"types\": \"dist/index.d.ts\",
+    \"files\": [\"dist\", \"src\"],
+    \"scripts\": {{
+        \"build\": \"tsc\",
+        \"prepare\": \"tsc\"
+    }},
+    \"devDependencies\": {{
+        \"typescript\": \">=5.0.0\"
+    }}
+}}",
+        package_name.as_ref(), package_version
+    )
+}
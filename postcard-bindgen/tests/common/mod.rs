@@ -0,0 +1,172 @@
+//! Fixtures and helpers shared by the integration tests of the generated packages.
+//!
+//! Every test crate only uses a part of them.
+#![allow(dead_code)]
+
+use std::{
+    collections::BTreeMap,
+    io,
+    num::NonZeroU32,
+    ops::Range,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use postcard_bindgen::{generate_bindings, ContainerCollection, PackageInfo, PostcardBindings};
+use serde::{Deserialize, Serialize};
+
+pub mod shapes {
+    use postcard_bindgen::PostcardBindings;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, PostcardBindings)]
+    pub enum Shape {
+        Empty,
+        Circle(f32),
+        Rect { width: u16, height: u16 },
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, PostcardBindings)]
+pub struct Unit;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, PostcardBindings)]
+pub struct Point(pub i8, pub u64);
+
+/// Holds every kind of value the generated packages have to round trip.
+#[derive(Debug, PartialEq, Serialize, Deserialize, PostcardBindings)]
+pub struct Everything {
+    pub flag: bool,
+    pub small: i16,
+    pub big: i64,
+    pub huge: u128,
+    pub negative: i128,
+    #[serde(with = "postcard::fixint::le")]
+    pub stamp: u64,
+    #[serde(with = "postcard::fixint::be")]
+    pub delta: i32,
+    pub ratio: f64,
+    pub non_zero: NonZeroU32,
+    pub name: String,
+    pub bytes: [u8; 3],
+    pub payload: Vec<u8>,
+    pub points: Vec<Point>,
+    pub map: BTreeMap<String, u32>,
+    pub maybe: Option<shapes::Shape>,
+    pub nothing: Option<u8>,
+    pub tuple: (u8, String),
+    pub range: Range<u16>,
+    pub unit: Unit,
+    pub shapes: Vec<shapes::Shape>,
+}
+
+pub fn sample() -> Everything {
+    Everything {
+        flag: true,
+        small: -300,
+        big: i64::MIN,
+        huge: u128::MAX,
+        negative: -1,
+        stamp: 0x0102_0304_0506_0708,
+        delta: -2,
+        ratio: -2.5,
+        non_zero: NonZeroU32::new(42).unwrap(),
+        name: "grüße".into(),
+        bytes: [1, 2, 3],
+        payload: (0..=255).collect(),
+        points: vec![Point(-1, 1 << 40), Point(i8::MAX, 0)],
+        map: BTreeMap::from([("a".into(), 1), ("b".into(), 300)]),
+        maybe: Some(shapes::Shape::Rect {
+            width: 10,
+            height: 20,
+        }),
+        nothing: None,
+        tuple: (7, "tuple".into()),
+        range: 3..9,
+        unit: Unit,
+        shapes: vec![shapes::Shape::Empty, shapes::Shape::Circle(1.5)],
+    }
+}
+
+pub fn package_info(name: &str) -> PackageInfo {
+    PackageInfo {
+        name: name.into(),
+        version: "0.1.0".try_into().unwrap(),
+    }
+}
+
+/// Builds the package `name` with the bindings of [`Everything`] into `dir` and returns the
+/// directory of the package.
+pub fn build_everything(
+    dir: &Path,
+    name: &str,
+    build_package: impl FnOnce(&Path, PackageInfo, ContainerCollection) -> io::Result<()>,
+) -> PathBuf {
+    build_package(dir, package_info(name), generate_bindings!(Everything)).unwrap();
+    dir.join(name)
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+pub fn from_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+/// CRC algorithms of the generated bindings with the frames Rust postcard produces for them.
+pub fn crc_vectors<T: Serialize>(value: &T) -> Vec<(&'static str, Vec<u8>)> {
+    [
+        ("CRC_32_AIXM", &crc::CRC_32_AIXM),
+        ("CRC_32_AUTOSAR", &crc::CRC_32_AUTOSAR),
+        ("CRC_32_BASE91_D", &crc::CRC_32_BASE91_D),
+        ("CRC_32_BZIP2", &crc::CRC_32_BZIP2),
+        ("CRC_32_CD_ROM_EDC", &crc::CRC_32_CD_ROM_EDC),
+        ("CRC_32_CKSUM", &crc::CRC_32_CKSUM),
+        ("CRC_32_ISCSI", &crc::CRC_32_ISCSI),
+        ("CRC_32_ISO_HDLC", &crc::CRC_32_ISO_HDLC),
+        ("CRC_32_JAMCRC", &crc::CRC_32_JAMCRC),
+        ("CRC_32_MEF", &crc::CRC_32_MEF),
+        ("CRC_32_MPEG_2", &crc::CRC_32_MPEG_2),
+        ("CRC_32_XFER", &crc::CRC_32_XFER),
+    ]
+    .into_iter()
+    .map(|(name, algorithm)| {
+        let crc = crc::Crc::<u32>::new(algorithm);
+        (
+            name,
+            postcard::to_allocvec_crc32(value, crc.digest()).unwrap(),
+        )
+    })
+    .collect()
+}
+
+/// Runs `command` and returns the lines it printed, failing with its output if it didn't succeed.
+pub fn run(command: &mut Command) -> Vec<String> {
+    let output = command.output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(ToOwned::to_owned)
+        .collect()
+}
+
+/// Checks the first lines of a round trip program, which deserializes `rust_bytes` as
+/// [`Everything`] and prints it serialized again, followed by the number of remaining bytes,
+/// the fields of the [`Point`] `7f05` and whether the unknown [`shapes::Shape`] variant `03`
+/// was rejected.
+pub fn assert_everything_round_trip(lines: &[String], rust_bytes: &[u8]) {
+    assert_eq!(lines[0], to_hex(rust_bytes));
+    assert_eq!(lines[1], "0");
+    assert_eq!(lines[2], "127 5");
+    assert_eq!(lines[3], "rejected");
+}
@@ -0,0 +1,124 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use postcard_bindgen::{
+    cpp::{self, GenerationSettings},
+    generate_bindings, PostcardBindings,
+};
+use serde::{Deserialize, Serialize};
+
+mod common;
+
+use common::{package_info, run, sample, to_hex};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, PostcardBindings)]
+struct Fx {
+    #[serde(with = "postcard::fixint::le")]
+    a: u32,
+    #[serde(with = "postcard::fixint::be")]
+    b: i16,
+    c: u32,
+}
+
+fn build_package(dir: &Path) -> PathBuf {
+    cpp::build_package(
+        dir,
+        package_info("cpp-bindings"),
+        GenerationSettings::enable_all(),
+        generate_bindings!(common::Everything, Fx),
+    )
+    .unwrap();
+    dir.join("cpp-bindings")
+}
+
+#[test]
+fn test_build_cpp_package() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let package_dir = build_package(tmp_dir.path());
+    let cmake_lists = std::fs::read_to_string(package_dir.join("CMakeLists.txt")).unwrap();
+    insta::assert_snapshot!("build_cpp_package_cmake_lists", cmake_lists);
+
+    let header =
+        std::fs::read_to_string(package_dir.join("include").join("cpp_bindings.hpp")).unwrap();
+    assert!(header.starts_with("#pragma once"));
+    assert!(header.contains("namespace cpp_bindings::common::shapes"));
+}
+
+#[test]
+#[ignore = "requires g++"]
+fn test_cpp_round_trip() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let package_dir = build_package(tmp_dir.path());
+
+    let rust_bytes = postcard::to_allocvec(&sample()).unwrap();
+    let fx_bytes = postcard::to_allocvec(&Fx {
+        a: 0x0102_0304,
+        b: -2,
+        c: 300,
+    })
+    .unwrap();
+
+    let program = format!(
+        r#"
+#include <cstdio>
+#include <string>
+#include "cpp_bindings.hpp"
+
+std::vector<std::uint8_t> from_hex(const std::string& hex) {{
+    std::vector<std::uint8_t> bytes;
+    for (std::size_t i = 0; i < hex.size(); i += 2) {{
+        bytes.push_back(static_cast<std::uint8_t>(std::stoul(hex.substr(i, 2), nullptr, 16)));
+    }}
+    return bytes;
+}}
+
+void print_hex(const std::vector<std::uint8_t>& bytes) {{
+    for (auto byte : bytes) {{ std::printf("%02x", byte); }}
+    std::printf("\n");
+}}
+
+int main() {{
+    auto [value, rest] = cpp_bindings::deserialize<cpp_bindings::common::Everything>(from_hex("{hex}"));
+    print_hex(cpp_bindings::serialize(value));
+    std::printf("%zu\n", rest.size());
+
+    auto [point, _] = cpp_bindings::deserialize<cpp_bindings::common::Point>(from_hex("7f05"));
+    std::printf("%d %llu\n", point._0, static_cast<unsigned long long>(point._1));
+
+    auto [fx, __] = cpp_bindings::deserialize<cpp_bindings::Fx>(from_hex("{fx}"));
+    std::printf("%x %d %u\n", static_cast<unsigned>(fx.a), static_cast<int>(fx.b), static_cast<unsigned>(fx.c));
+    print_hex(cpp_bindings::serialize(fx));
+
+    try {{
+        cpp_bindings::deserialize<cpp_bindings::common::shapes::Shape>(from_hex("03"));
+        std::printf("accepted\n");
+    }} catch (const cpp_bindings::postcard::Error&) {{
+        std::printf("rejected\n");
+    }}
+}}
+"#,
+        hex = to_hex(&rust_bytes),
+        fx = to_hex(&fx_bytes),
+    );
+    let program_path = tmp_dir.path().join("main.cpp");
+    std::fs::write(&program_path, program).unwrap();
+
+    let binary_path = tmp_dir.path().join("main");
+    run(Command::new("g++")
+        .args(["-std=c++17", "-Wall", "-Wextra", "-Werror", "-I"])
+        .arg(package_dir.join("include"))
+        .arg(&program_path)
+        .arg("-o")
+        .arg(&binary_path));
+
+    let lines = run(&mut Command::new(&binary_path));
+    assert_eq!(lines[0], to_hex(&rust_bytes));
+    assert_eq!(lines[1], "0");
+    assert_eq!(lines[2], "127 5");
+    assert_eq!(to_hex(&fx_bytes), "04030201fffeac02");
+    assert_eq!(lines[3], "1020304 -2 300");
+    assert_eq!(lines[4], "04030201fffeac02");
+    assert_eq!(lines[5], "rejected");
+}
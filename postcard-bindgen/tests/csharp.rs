@@ -0,0 +1,97 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use postcard_bindgen::csharp::{self, GenerationSettings};
+
+mod common;
+
+use common::{assert_everything_round_trip, run, sample, to_hex};
+
+fn build_package(dir: &Path) -> PathBuf {
+    common::build_everything(dir, "csharp-bindings", |dir, package_info, containers| {
+        csharp::build_package(
+            dir,
+            package_info,
+            GenerationSettings::enable_all(),
+            containers,
+        )
+    })
+}
+
+#[test]
+fn test_build_nuget_package() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let project_dir = build_package(tmp_dir.path());
+    let project_file = std::fs::read_to_string(project_dir.join("csharp-bindings.csproj")).unwrap();
+    insta::assert_snapshot!("build_nuget_package_project_file", project_file);
+    assert!(project_dir.join("Runtime.cs").is_file());
+    assert!(project_dir.join("Types.cs").is_file());
+}
+
+#[test]
+#[ignore = "requires dotnet"]
+fn test_csharp_round_trip() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    build_package(tmp_dir.path());
+
+    let rust_bytes = postcard::to_allocvec(&sample()).unwrap();
+
+    let app_dir = tmp_dir.path().join("app");
+    std::fs::create_dir_all(&app_dir).unwrap();
+    std::fs::write(
+        app_dir.join("app.csproj"),
+        r#"<Project Sdk="Microsoft.NET.Sdk">
+
+  <PropertyGroup>
+    <OutputType>Exe</OutputType>
+    <TargetFramework>net8.0</TargetFramework>
+    <Nullable>enable</Nullable>
+  </PropertyGroup>
+
+  <ItemGroup>
+    <ProjectReference Include="../csharp-bindings/csharp-bindings.csproj" />
+  </ItemGroup>
+
+</Project>
+"#,
+    )
+    .unwrap();
+
+    let program = format!(
+        r#"
+using csharp_bindings;
+using csharp_bindings.common;
+
+var (value, rest) = Everything.Deserialize(System.Convert.FromHexString("{hex}"));
+System.Console.WriteLine(System.Convert.ToHexString(value.Serialize()).ToLowerInvariant());
+System.Console.WriteLine(rest.Length);
+
+var (point, _) = Point.Deserialize(System.Convert.FromHexString("7f05"));
+System.Console.WriteLine(point._0 + " " + point._1);
+
+try
+{{
+    csharp_bindings.common.shapes.Shape.Deserialize(System.Convert.FromHexString("03"));
+    System.Console.WriteLine("accepted");
+}}
+catch (PostcardException)
+{{
+    System.Console.WriteLine("rejected");
+}}
+"#,
+        hex = to_hex(&rust_bytes),
+    );
+    std::fs::write(app_dir.join("Program.cs"), program).unwrap();
+
+    // builds the app upfront, so `dotnet run` only prints the output of the program
+    run(Command::new("dotnet").arg("build").arg(&app_dir));
+
+    let lines = run(Command::new("dotnet")
+        .arg("run")
+        .arg("--no-build")
+        .arg("--project")
+        .arg(&app_dir));
+    assert_everything_round_trip(&lines, &rust_bytes);
+}
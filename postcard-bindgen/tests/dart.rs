@@ -0,0 +1,104 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use postcard_bindgen::dart::{self, GenerationSettings};
+
+mod common;
+
+use common::{assert_everything_round_trip, run, sample, to_hex};
+
+fn build_package(dir: &Path) -> PathBuf {
+    common::build_everything(dir, "dart-bindings", |dir, package_info, containers| {
+        dart::build_package(
+            dir,
+            package_info,
+            GenerationSettings::enable_all(),
+            containers,
+        )
+    })
+}
+
+#[test]
+fn test_build_pub_package() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let package_dir = build_package(tmp_dir.path());
+    let pubspec = std::fs::read_to_string(package_dir.join("pubspec.yaml")).unwrap();
+    insta::assert_snapshot!("build_pub_package_pubspec", pubspec);
+    assert!(package_dir.join("lib/dart_bindings.dart").is_file());
+}
+
+#[test]
+#[ignore = "requires dart"]
+fn test_dart_round_trip() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    build_package(tmp_dir.path());
+
+    let rust_bytes = postcard::to_allocvec(&sample()).unwrap();
+
+    let app_dir = tmp_dir.path().join("app");
+    std::fs::create_dir_all(app_dir.join("bin")).unwrap();
+    std::fs::write(
+        app_dir.join("pubspec.yaml"),
+        "name: app
+
+environment:
+  sdk: ^3.0.0
+
+dependencies:
+  dart_bindings:
+    path: ../dart-bindings
+",
+    )
+    .unwrap();
+
+    let program = format!(
+        r#"import "dart:typed_data";
+
+import "package:dart_bindings/dart_bindings.dart";
+
+String toHex(Uint8List bytes) =>
+    bytes.map((b) => b.toRadixString(16).padLeft(2, "0")).join();
+
+Uint8List fromHex(String hex) => Uint8List.fromList([
+      for (var i = 0; i < hex.length; i += 2)
+        int.parse(hex.substring(i, i + 2), radix: 16)
+    ]);
+
+void main() {{
+  final (value, rest) = CommonEverything.deserialize(fromHex("{hex}"));
+  print(toHex(value.serialize()));
+  print(rest.length);
+
+  final (point, _) = CommonPoint.deserialize(Uint8List.fromList([0x7f, 0x05]));
+  print("${{point.v0}} ${{point.v1}}");
+
+  try {{
+    CommonShapesShape.deserialize(Uint8List.fromList([0x03]));
+    print("accepted");
+  }} on PostcardException {{
+    print("rejected");
+  }}
+
+  final (again, _) = CommonEverything.deserialize(value.serialize());
+  print(again == value);
+}}
+"#,
+        hex = to_hex(&rust_bytes),
+    );
+    std::fs::write(app_dir.join("bin/main.dart"), program).unwrap();
+
+    // resolves the dependencies upfront, so `dart run` only prints the output of the program
+    run(Command::new("dart")
+        .arg("pub")
+        .arg("get")
+        .current_dir(&app_dir));
+
+    let lines = run(Command::new("dart")
+        .arg("run")
+        .arg("bin/main.dart")
+        .current_dir(&app_dir));
+    assert_everything_round_trip(&lines, &rust_bytes);
+    assert_eq!(lines[4], "true");
+}
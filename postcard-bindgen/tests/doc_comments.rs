@@ -0,0 +1,136 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use insta::assert_snapshot;
+use postcard_bindgen::{
+    generate_bindings, javascript, python, ContainerCollection, PostcardBindings,
+};
+use serde::Serialize;
+
+mod common;
+
+use common::{package_info, run};
+
+mod sensors {
+    use postcard_bindgen::PostcardBindings;
+    use serde::Serialize;
+
+    /// A reading of one sensor.
+    ///
+    /// Sent every second, e.g.
+    ///
+    ///     Temperature(21.5)
+    #[allow(unused)]
+    #[derive(Serialize, PostcardBindings)]
+    pub enum Reading {
+        /// The sensor is not connected.
+        Missing,
+        /// Temperature in °C.
+        Temperature(f32),
+        Humidity {
+            /// Relative humidity in percent.
+            percent: u8,
+        },
+    }
+}
+
+/// Report sent by the device, ends with */ and """
+#[allow(unused)]
+#[derive(Serialize, PostcardBindings)]
+struct Report {
+    /// Id of the device.
+    id: u32,
+    /** Readings of all sensors,
+    in the order of the sensor ports. */
+    readings: Vec<sensors::Reading>,
+    name: String,
+}
+
+#[test]
+fn test_ts_doc_comments() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    javascript::build_package(
+        tmp_dir.path(),
+        package_info("doc-bindings"),
+        javascript::GenerationSettings::enable_all(),
+        generate_bindings!(Report),
+    )
+    .unwrap();
+
+    let declarations =
+        std::fs::read_to_string(tmp_dir.path().join("doc-bindings").join("index.d.ts")).unwrap();
+    let start = declarations.find("export namespace sensors").unwrap();
+    let end = declarations.find("export type Type").unwrap();
+    assert_snapshot!("ts_doc_comments", &declarations[start..end]);
+}
+
+fn build_python_package(dir: &Path) -> PathBuf {
+    python::build_package(
+        dir,
+        package_info("doc-bindings"),
+        python::GenerationSettings::enable_all(),
+        generate_bindings!(Report),
+    )
+    .unwrap();
+    dir.join("doc-bindings").join("src")
+}
+
+#[test]
+fn test_python_doc_comments() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let types_dir = build_python_package(tmp_dir.path())
+        .join("doc_bindings")
+        .join("types");
+    let report = std::fs::read_to_string(types_dir.join("_report.py")).unwrap();
+    assert_snapshot!("python_doc_comments_report", report);
+    let reading = std::fs::read_to_string(types_dir.join("sensors").join("_reading.py")).unwrap();
+    assert_snapshot!("python_doc_comments_reading", reading);
+}
+
+#[test]
+#[ignore = "requires python3"]
+fn test_python_doc_strings() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let src_dir = build_python_package(tmp_dir.path());
+
+    let program = r#"from doc_bindings import Report, sensors
+
+print(repr(Report.__doc__))
+print(repr(sensors.Reading.__doc__))
+print(repr(sensors.Reading_Missing.__doc__))
+print(repr(sensors.Reading_Temperature.__doc__))
+"#;
+
+    let lines = run(Command::new("python3")
+        .arg("-c")
+        .arg(program)
+        .env("PYTHONPATH", src_dir));
+    assert_eq!(
+        lines,
+        [
+            r#"'Report sent by the device, ends with */ and """'"#,
+            r"'A reading of one sensor.\n\n    Sent every second, e.g.\n\n        Temperature(21.5)\n    '",
+            "'The sensor is not connected.'",
+            "'Temperature in °C.'",
+        ]
+    );
+}
+
+#[test]
+fn test_schema_keeps_doc_comments() {
+    let bindings = generate_bindings!(Report);
+    let schema = bindings.to_schema();
+    let loaded = ContainerCollection::from_schema(&schema).unwrap();
+    assert_eq!(loaded.to_schema(), schema);
+
+    let report = loaded
+        .all_containers()
+        .find(|container| container.name == "Report")
+        .unwrap();
+    assert_eq!(
+        report.doc.as_deref(),
+        Some(r#"Report sent by the device, ends with */ and """"#)
+    );
+}
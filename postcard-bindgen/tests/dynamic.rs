@@ -0,0 +1,332 @@
+use std::{collections::BTreeMap, num::NonZeroU16};
+
+use postcard_bindgen::{
+    dynamic::{decode, encode, from_json, Error, Fields, Value},
+    generate_bindings, PostcardBindings,
+};
+use serde::{Deserialize, Serialize};
+
+mod shapes {
+    use postcard_bindgen::PostcardBindings;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, PostcardBindings)]
+    pub enum Shape {
+        Empty,
+        Circle(f32),
+        Rect { width: u16, height: u16 },
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, PostcardBindings)]
+struct Point(i8, u64);
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, PostcardBindings)]
+struct Unit;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, PostcardBindings)]
+struct Everything {
+    flag: bool,
+    small: i16,
+    big: i64,
+    huge: u128,
+    negative: i128,
+    #[serde(with = "postcard::fixint::le")]
+    stamp: u64,
+    #[serde(with = "postcard::fixint::be")]
+    delta: i32,
+    id: NonZeroU16,
+    ratio: f64,
+    name: String,
+    bytes: [u8; 3],
+    points: Vec<Point>,
+    map: BTreeMap<String, u32>,
+    maybe: Option<shapes::Shape>,
+    nothing: Option<u8>,
+    tuple: (u8, String),
+    range: core::ops::Range<u32>,
+    unit: Unit,
+    shapes: Vec<shapes::Shape>,
+}
+
+fn sample() -> Everything {
+    Everything {
+        flag: true,
+        small: -300,
+        big: i64::MIN,
+        huge: u128::MAX,
+        negative: -1,
+        stamp: 0x0102_0304_0506_0708,
+        delta: -2,
+        id: NonZeroU16::new(7).unwrap(),
+        ratio: -2.5,
+        name: "grüße".into(),
+        bytes: [1, 2, 3],
+        points: vec![Point(-1, 1 << 40), Point(i8::MAX, 0)],
+        map: BTreeMap::from([("a".into(), 1), ("b".into(), 300)]),
+        maybe: Some(shapes::Shape::Rect {
+            width: 10,
+            height: 20,
+        }),
+        nothing: None,
+        tuple: (7, "tuple".into()),
+        range: 3..u32::MAX,
+        unit: Unit,
+        shapes: vec![shapes::Shape::Empty, shapes::Shape::Circle(1.5)],
+    }
+}
+
+#[test]
+fn test_round_trip() {
+    let bindings = generate_bindings!(Everything);
+    let mut bytes = postcard::to_allocvec(&sample()).unwrap();
+    bytes.extend([0xff, 0xee]);
+
+    let (value, rest) = decode(&bindings, "Everything", &bytes).unwrap();
+    assert_eq!(rest, [0xff, 0xee]);
+
+    let encoded = encode(&bindings, "Everything", &value).unwrap();
+    assert_eq!(encoded, bytes[..bytes.len() - 2]);
+
+    let Value::Struct(Fields::Named(fields)) = value else {
+        panic!("expected a struct");
+    };
+    let field = |name: &str| &fields.iter().find(|(n, _)| n == name).unwrap().1;
+    assert_eq!(field("small"), &Value::I16(-300));
+    assert_eq!(field("huge"), &Value::U128(u128::MAX));
+    assert_eq!(field("stamp"), &Value::U64(0x0102_0304_0506_0708));
+    assert_eq!(field("delta"), &Value::I32(-2));
+    assert_eq!(field("name"), &Value::String("grüße".into()));
+    assert_eq!(
+        field("maybe"),
+        &Value::Option(Some(Box::new(Value::Enum {
+            variant: "Rect".into(),
+            fields: Fields::Named(vec![
+                ("width".into(), Value::U16(10)),
+                ("height".into(), Value::U16(20)),
+            ]),
+        })))
+    );
+    assert_eq!(
+        field("points"),
+        &Value::Array(vec![
+            Value::Struct(Fields::Unnamed(vec![Value::I8(-1), Value::U64(1 << 40)])),
+            Value::Struct(Fields::Unnamed(vec![Value::I8(i8::MAX), Value::U64(0)])),
+        ])
+    );
+    assert_eq!(field("unit"), &Value::Struct(Fields::Unit));
+}
+
+#[test]
+fn test_type_paths() {
+    let bindings = generate_bindings!(Everything);
+    let bytes = postcard::to_allocvec(&shapes::Shape::Circle(1.5)).unwrap();
+    let circle = Value::Enum {
+        variant: "Circle".into(),
+        fields: Fields::Unnamed(vec![Value::F32(1.5)]),
+    };
+
+    assert_eq!(
+        decode(&bindings, "shapes::Shape", &bytes).unwrap().0,
+        circle
+    );
+    assert_eq!(
+        decode(&bindings, "dynamic::shapes::Shape", &bytes)
+            .unwrap()
+            .0,
+        circle
+    );
+    assert_eq!(
+        decode(&bindings, "Shape", &bytes),
+        Err(Error::UnknownType("Shape".into()))
+    );
+}
+
+#[test]
+fn test_decode_errors() {
+    let bindings = generate_bindings!(Everything);
+    let bytes = postcard::to_allocvec(&sample()).unwrap();
+
+    assert_eq!(
+        decode(&bindings, "Everything", &bytes[..40]),
+        Err(Error::UnexpectedEnd)
+    );
+    assert_eq!(
+        decode(&bindings, "shapes::Shape", &[3]),
+        Err(Error::InvalidVariantIndex {
+            name: "Shape".into(),
+            index: 3
+        })
+    );
+    assert_eq!(
+        decode(&bindings, "shapes::Shape", &[2, 0x80, 0x80, 0x04, 0]),
+        Err(Error::InvalidVarint)
+    );
+    assert_eq!(
+        decode(&bindings, "Unit", &[]).unwrap(),
+        (Value::Struct(Fields::Unit), &[][..])
+    );
+}
+
+#[test]
+fn test_encode_converts_integers_that_fit() {
+    let bindings = generate_bindings!(Everything);
+    let rect = |width| Value::Enum {
+        variant: "Rect".into(),
+        fields: Fields::Named(vec![
+            ("height".into(), Value::U8(20)),
+            ("width".into(), width),
+        ]),
+    };
+
+    assert_eq!(
+        encode(&bindings, "shapes::Shape", &rect(Value::I64(10))).unwrap(),
+        postcard::to_allocvec(&shapes::Shape::Rect {
+            width: 10,
+            height: 20
+        })
+        .unwrap()
+    );
+    assert_eq!(
+        encode(&bindings, "shapes::Shape", &rect(Value::I64(-1))),
+        Err(Error::IntegerOutOfRange {
+            expected: "u16".into()
+        })
+    );
+    assert_eq!(
+        encode(&bindings, "shapes::Shape", &rect(Value::U32(1 << 16))),
+        Err(Error::IntegerOutOfRange {
+            expected: "u16".into()
+        })
+    );
+}
+
+#[test]
+fn test_encode_errors() {
+    let bindings = generate_bindings!(Everything);
+
+    assert_eq!(
+        encode(
+            &bindings,
+            "shapes::Shape",
+            &Value::Enum {
+                variant: "Square".into(),
+                fields: Fields::Unit,
+            }
+        ),
+        Err(Error::UnknownVariant {
+            name: "Shape".into(),
+            variant: "Square".into()
+        })
+    );
+    assert_eq!(
+        encode(
+            &bindings,
+            "shapes::Shape",
+            &Value::Enum {
+                variant: "Rect".into(),
+                fields: Fields::Named(vec![("width".into(), Value::U16(1))]),
+            }
+        ),
+        Err(Error::MissingField("height".into()))
+    );
+    assert_eq!(
+        encode(
+            &bindings,
+            "Point",
+            &Value::Struct(Fields::Unnamed(vec![Value::I8(1)]))
+        ),
+        Err(Error::LengthMismatch {
+            expected: 2,
+            found: 1
+        })
+    );
+    assert_eq!(
+        encode(
+            &bindings,
+            "Point",
+            &Value::Struct(Fields::Unnamed(vec![
+                Value::String("1".into()),
+                Value::U64(1)
+            ]))
+        ),
+        Err(Error::TypeMismatch {
+            expected: "i8".into()
+        })
+    );
+}
+
+#[test]
+fn test_json_layout_of_serde_json() {
+    let bindings = generate_bindings!(Everything);
+    // serde_json can't serialize integers beyond 64 bit
+    let everything = Everything {
+        huge: u64::MAX as u128,
+        ..sample()
+    };
+    let bytes = postcard::to_allocvec(&everything).unwrap();
+
+    let (value, _) = decode(&bindings, "Everything", &bytes).unwrap();
+    let json = value.to_json();
+    assert_eq!(json["stamp"], everything.stamp);
+    assert_eq!(json["delta"], everything.delta);
+
+    // the fixint modules serialize their integers as bytes
+    let without_fixint = |mut json: serde_json::Value| {
+        let object = json.as_object_mut().unwrap();
+        object.remove("stamp");
+        object.remove("delta");
+        json
+    };
+    assert_eq!(
+        without_fixint(json.clone()),
+        without_fixint(serde_json::to_value(&everything).unwrap())
+    );
+
+    let value = from_json(&bindings, "Everything", &json).unwrap();
+    assert_eq!(encode(&bindings, "Everything", &value).unwrap(), bytes);
+}
+
+#[test]
+fn test_json_conversions() {
+    let bindings = generate_bindings!(Everything);
+    let bytes = postcard::to_allocvec(&sample()).unwrap();
+
+    let (value, _) = decode(&bindings, "Everything", &bytes).unwrap();
+    let mut json = value.to_json();
+    assert_eq!(json["huge"], u128::MAX.to_string());
+
+    // missing optional fields are `None` and integers can be given as strings
+    json.as_object_mut().unwrap().remove("nothing");
+    json["small"] = "-300".into();
+    let value = from_json(&bindings, "Everything", &json).unwrap();
+    assert_eq!(encode(&bindings, "Everything", &value).unwrap(), bytes);
+
+    assert_eq!(
+        from_json(&bindings, "Point", &serde_json::json!([-1, 256])).unwrap(),
+        Value::Struct(Fields::Unnamed(vec![Value::I8(-1), Value::U64(256)]))
+    );
+    assert_eq!(
+        from_json(&bindings, "Point", &serde_json::json!([128, 0])),
+        Err(Error::IntegerOutOfRange {
+            expected: "i8".into()
+        })
+    );
+    assert_eq!(
+        from_json(
+            &bindings,
+            "shapes::Shape",
+            &serde_json::json!({ "Rect": [1, 2] })
+        ),
+        Err(Error::TypeMismatch {
+            expected: "Shape".into()
+        })
+    );
+    assert_eq!(
+        from_json(&bindings, "shapes::Shape", &serde_json::json!("Square")),
+        Err(Error::UnknownVariant {
+            name: "Shape".into(),
+            variant: "Square".into()
+        })
+    );
+}
@@ -0,0 +1,160 @@
+use std::borrow::Cow;
+
+use postcard_bindgen::{
+    __private::{BindingType, ContainerCollection, GenBinding, StructField, ValueType},
+    generate_bindings, PostcardBindings,
+};
+use serde::Serialize;
+
+#[allow(unused)]
+#[derive(Serialize, PostcardBindings)]
+enum Status {
+    Ok,
+    Failed(u16),
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct Frame<T> {
+    id: u8,
+    payload: T,
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct Pair<A: Clone, B>(A, B)
+where
+    B: Copy;
+
+#[derive(Serialize, PostcardBindings)]
+struct Block<const N: usize> {
+    #[serde(with = "serde_arrays")]
+    #[postcard_bindgen(as_type = "[u8; N]")]
+    data: [u8; N],
+}
+
+mod serde_arrays {
+    use serde::{Serialize, Serializer};
+
+    pub fn serialize<S: Serializer, const N: usize>(v: &[u8; N], s: S) -> Result<S::Ok, S::Error> {
+        v.as_slice().serialize(s)
+    }
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct Messages<'a> {
+    frames: Vec<Frame<Status>>,
+    name: &'a str,
+}
+
+mod sensors {
+    use postcard_bindgen::PostcardBindings;
+    use serde::Serialize;
+
+    #[allow(unused)]
+    #[derive(Serialize, PostcardBindings)]
+    pub enum Status {
+        Idle,
+        Reading(u16),
+    }
+}
+
+mod motors {
+    use postcard_bindgen::PostcardBindings;
+    use serde::Serialize;
+
+    #[allow(unused)]
+    #[derive(Serialize, PostcardBindings)]
+    pub enum Status {
+        Stopped,
+        Running { rpm: u32 },
+    }
+}
+
+fn struct_fields(bindings: &ContainerCollection, name: &str) -> Vec<StructField> {
+    let container = bindings
+        .all_containers()
+        .find(|container| container.name == name)
+        .unwrap();
+    match container.r#type {
+        BindingType::Struct(ty) => ty.fields,
+        _ => panic!("expected struct"),
+    }
+}
+
+fn object_name(ty: ValueType) -> Cow<'static, str> {
+    match ty {
+        ValueType::Object(meta) => meta.name,
+        _ => panic!("expected object"),
+    }
+}
+
+#[test]
+fn test_generic_instantiation_names() {
+    assert_eq!(object_name(Frame::<u8>::get_type()), "Frame_u8");
+    assert_eq!(object_name(Frame::<Status>::get_type()), "Frame_Status");
+    assert_eq!(
+        object_name(Frame::<Frame<Option<i32>>>::get_type()),
+        "Frame_Frame_Option_i32"
+    );
+    assert_eq!(
+        object_name(Pair::<Vec<u8>, (bool, f32)>::get_type()),
+        "Pair_Vec_u8_Tuple_bool_f32"
+    );
+    assert_eq!(object_name(Block::<4>::get_type()), "Block_4");
+    assert_eq!(object_name(Messages::get_type()), "Messages");
+
+    assert_eq!(
+        object_name(Frame::<sensors::Status>::get_type()),
+        "Frame_sensors_Status"
+    );
+    assert_eq!(
+        object_name(Frame::<motors::Status>::get_type()),
+        "Frame_motors_Status"
+    );
+}
+
+#[test]
+fn test_generic_instantiations_are_registered_separately() {
+    let bindings = generate_bindings!(Status, Frame<u8>, Frame<Status>, Block<4>, Messages);
+
+    assert_eq!(
+        struct_fields(&bindings, "Frame_u8"),
+        vec![
+            StructField {
+                name: "id".into(),
+                v_type: u8::get_type(),
+                doc: None,
+            },
+            StructField {
+                name: "payload".into(),
+                v_type: u8::get_type(),
+                doc: None,
+            },
+        ]
+    );
+    assert_eq!(
+        struct_fields(&bindings, "Frame_Status")[1].v_type,
+        Status::get_type()
+    );
+    assert_eq!(
+        struct_fields(&bindings, "Block_4")[0].v_type,
+        <[u8; 4]>::get_type()
+    );
+    assert_eq!(
+        struct_fields(&bindings, "Messages")[0].v_type,
+        Vec::<Frame<Status>>::get_type()
+    );
+}
+
+#[test]
+fn test_generic_arguments_from_different_modules_do_not_collide() {
+    let bindings = generate_bindings!(Frame<sensors::Status>, Frame<motors::Status>);
+
+    assert_eq!(
+        struct_fields(&bindings, "Frame_sensors_Status")[1].v_type,
+        sensors::Status::get_type()
+    );
+    assert_eq!(
+        struct_fields(&bindings, "Frame_motors_Status")[1].v_type,
+        motors::Status::get_type()
+    );
+}
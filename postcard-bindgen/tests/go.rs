@@ -0,0 +1,105 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use postcard_bindgen::go::{self, GenerationSettings};
+
+mod common;
+
+use common::{assert_everything_round_trip, run, sample, to_hex, Everything};
+
+fn build_package(dir: &Path) -> PathBuf {
+    common::build_everything(dir, "go-bindings", |dir, package_info, containers| {
+        go::build_package(
+            dir,
+            package_info,
+            GenerationSettings::enable_all(),
+            containers,
+        )
+    })
+}
+
+#[test]
+fn test_build_go_module() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let module_dir = build_package(tmp_dir.path());
+    let mod_file = std::fs::read_to_string(module_dir.join("go.mod")).unwrap();
+    insta::assert_snapshot!("build_go_module_mod_file", mod_file);
+    assert!(module_dir.join("runtime.go").is_file());
+    assert!(module_dir.join("types.go").is_file());
+}
+
+#[test]
+#[ignore = "requires go"]
+fn test_go_round_trip() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    build_package(tmp_dir.path());
+
+    let rust_bytes = postcard::to_allocvec(&Everything {
+        // a single entry, as go iterates maps in random order
+        map: BTreeMap::from([("b".into(), 300)]),
+        ..sample()
+    })
+    .unwrap();
+
+    let app_dir = tmp_dir.path().join("app");
+    std::fs::create_dir_all(&app_dir).unwrap();
+    std::fs::write(
+        app_dir.join("go.mod"),
+        "module app
+
+go 1.21
+
+require go-bindings v0.0.0
+
+replace go-bindings => ../go-bindings
+",
+    )
+    .unwrap();
+
+    let program = format!(
+        r#"package main
+
+import (
+	"encoding/hex"
+	"fmt"
+
+	bindings "go-bindings"
+)
+
+func main() {{
+	data, _ := hex.DecodeString("{hex}")
+	var value bindings.CommonEverything
+	rest, err := value.UnmarshalPostcard(data)
+	if err != nil {{
+		panic(err)
+	}}
+	encoded, err := value.MarshalPostcard()
+	if err != nil {{
+		panic(err)
+	}}
+	fmt.Println(hex.EncodeToString(encoded))
+	fmt.Println(len(rest))
+
+	var point bindings.CommonPoint
+	if _, err := point.UnmarshalPostcard([]byte{{0x7f, 0x05}}); err != nil {{
+		panic(err)
+	}}
+	fmt.Println(point.V0, point.V1)
+
+	if _, _, err := bindings.UnmarshalCommonShapesShape([]byte{{0x03}}); err != nil {{
+		fmt.Println("rejected")
+	}} else {{
+		fmt.Println("accepted")
+	}}
+}}
+"#,
+        hex = to_hex(&rust_bytes),
+    );
+    std::fs::write(app_dir.join("main.go"), program).unwrap();
+
+    let lines = run(Command::new("go").arg("run").arg(".").current_dir(&app_dir));
+    assert_everything_round_trip(&lines, &rust_bytes);
+}
@@ -0,0 +1,321 @@
+use std::{collections::BTreeMap, process::Command};
+
+use postcard_bindgen::{
+    generate_bindings,
+    javascript::{self, GenerationSettings},
+    PostcardBindings,
+};
+use serde::{Deserialize, Serialize};
+
+mod common;
+
+use common::{crc_vectors, package_info, run, to_hex};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, PostcardBindings)]
+enum Shape {
+    Empty,
+    Circle(f32),
+    Rect { width: u16, height: u16 },
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, PostcardBindings)]
+struct Point(i8, u64);
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, PostcardBindings)]
+struct Log {
+    flag: bool,
+    small: i16,
+    wide: i32,
+    count: u32,
+    big: i64,
+    huge: u128,
+    #[serde(with = "postcard::fixint::le")]
+    stamp: u64,
+    #[serde(with = "postcard::fixint::be")]
+    delta: i32,
+    ratio: f64,
+    scale: f32,
+    name: String,
+    bytes: Vec<u8>,
+    fixed: [u8; 3],
+    points: Vec<Point>,
+    map: BTreeMap<String, u32>,
+    maybe: Option<Shape>,
+    shapes: Vec<Shape>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, PostcardBindings)]
+struct Blob(Vec<u8>);
+
+fn sample() -> Log {
+    Log {
+        flag: true,
+        small: -300,
+        wide: i32::MIN,
+        count: u32::MAX,
+        big: i64::MIN,
+        huge: u128::MAX,
+        stamp: 0x0102_0304_0506_0708,
+        delta: -2,
+        ratio: -2.5,
+        scale: 0.1,
+        name: "grüße".into(),
+        bytes: (0..=255).collect(),
+        fixed: [1, 2, 3],
+        points: vec![Point(-1, 1 << 40), Point(i8::MAX, 0)],
+        map: BTreeMap::from([("a".into(), 1), ("b".into(), 300)]),
+        maybe: Some(Shape::Rect {
+            width: 10,
+            height: 20,
+        }),
+        shapes: vec![Shape::Empty, Shape::Circle(1.5)],
+    }
+}
+
+#[test]
+#[ignore = "requires node"]
+fn test_js_round_trip() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    javascript::build_package(
+        tmp_dir.path(),
+        package_info("js-bindings"),
+        GenerationSettings::enable_all(),
+        generate_bindings!(Log),
+    )
+    .unwrap();
+
+    let rust_bytes = postcard::to_allocvec(&sample()).unwrap();
+
+    let script = format!(
+        r#"
+import {{ serialize, deserialize }} from "./js-bindings/index.js"
+
+const to_hex = (bytes) => Array.from(bytes, (b) => b.toString(16).padStart(2, "0")).join("")
+const from_hex = (hex) => new Uint8Array(hex.match(/../g).map((b) => parseInt(b, 16)))
+
+const input = from_hex("{hex}ffee")
+const {{ value, bytes }} = deserialize("Log", input)
+console.log(to_hex(serialize("Log", value)))
+console.log(to_hex(bytes))
+console.log(bytes.buffer === input.buffer)
+try {{ deserialize("Log", input.subarray(0, 40)); console.log("accepted") }} catch (e) {{ console.log("rejected") }}
+console.log(value.bytes instanceof Uint8Array && value.fixed instanceof Uint8Array && value.bytes.buffer !== input.buffer)
+"#,
+        hex = to_hex(&rust_bytes),
+    );
+    let script_path = tmp_dir.path().join("round_trip.mjs");
+    std::fs::write(&script_path, script).unwrap();
+
+    let output = Command::new("node").arg(&script_path).output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines = stdout.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines[0], to_hex(&rust_bytes));
+    // the remaining bytes are a view into the input
+    assert_eq!(lines[1], "ffee");
+    assert_eq!(lines[2], "true");
+    assert_eq!(lines[3], "rejected");
+    // byte arrays are copies of the input
+    assert_eq!(lines[4], "true");
+}
+
+#[test]
+#[ignore = "requires node"]
+fn test_js_cobs() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    javascript::build_package(
+        tmp_dir.path(),
+        package_info("js-cobs-bindings"),
+        GenerationSettings::enable_all().cobs(true),
+        generate_bindings!(Log, Blob),
+    )
+    .unwrap();
+
+    let log_frame = postcard::to_allocvec_cobs(&sample()).unwrap();
+    // the encoded value is a single run of 254 non-zero bytes
+    let blob_frame = postcard::to_allocvec_cobs(&Blob((1..=252).collect())).unwrap();
+    let mut stream = log_frame.clone();
+    stream.extend(&blob_frame);
+    stream.extend(&log_frame);
+
+    let script = format!(
+        r#"
+import {{ serialize_cobs, deserialize_cobs, CobsAccumulator }} from "./js-cobs-bindings/index.js"
+
+const to_hex = (bytes) => Array.from(bytes, (b) => b.toString(16).padStart(2, "0")).join("")
+const from_hex = (hex) => new Uint8Array(hex.match(/../g).map((b) => parseInt(b, 16)))
+
+const stream = from_hex("{stream}")
+const {{ value, bytes }} = deserialize_cobs("Log", stream)
+console.log(to_hex(serialize_cobs("Log", value)))
+console.log(bytes.length)
+console.log(to_hex(serialize_cobs("Blob", deserialize_cobs("Blob", from_hex("{blob}")).value)))
+
+const accumulator = new CobsAccumulator("Log", 2048), values = []
+for (let i = 0; i < stream.length; i += 7) {{
+    const frames = accumulator.feed(stream.subarray(i, i + 7))
+    try {{ for (const value of frames) {{ values.push(value) }} }} catch (e) {{ values.push(e) }}
+}}
+console.log(values.map((v) => typeof v === "string" ? v : v.name).join(","))
+
+try {{ [...new CobsAccumulator("Log", 16).feed(stream)] }} catch (e) {{ console.log(e) }}
+"#,
+        stream = to_hex(&stream),
+        blob = to_hex(&blob_frame),
+    );
+    let script_path = tmp_dir.path().join("cobs.mjs");
+    std::fs::write(&script_path, script).unwrap();
+
+    let output = Command::new("node").arg(&script_path).output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines = stdout.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines[0], to_hex(&log_frame));
+    assert_eq!(lines[1], (blob_frame.len() + log_frame.len()).to_string());
+    assert_eq!(lines[2], to_hex(&blob_frame));
+    // the blob frame doesn't hold a log, which doesn't stop the accumulator
+    assert_eq!(lines[3], "grüße,Bad Variant,grüße");
+    assert_eq!(lines[4], "cobs frame exceeds the maximum size");
+}
+
+#[test]
+#[ignore = "requires node"]
+fn test_js_crc32() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    javascript::build_package(
+        tmp_dir.path(),
+        package_info("js-crc-bindings"),
+        GenerationSettings::enable_all().crc32(true),
+        generate_bindings!(Log),
+    )
+    .unwrap();
+
+    let vectors = crc_vectors(&sample());
+    let checks = vectors
+        .iter()
+        .map(|(name, frame)| format!("check(b.{name}, \"{}\")", to_hex(frame)))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let script = format!(
+        r#"
+import * as b from "./js-crc-bindings/index.js"
+
+const to_hex = (bytes) => Array.from(bytes, (b) => b.toString(16).padStart(2, "0")).join("")
+const from_hex = (hex) => new Uint8Array(hex.match(/../g).map((b) => parseInt(b, 16)))
+
+const check = (algorithm, hex) => {{
+    const frame = from_hex(hex + "ffee")
+    const {{ value, bytes }} = b.deserialize_crc32("Log", frame, algorithm)
+    frame[3] ^= 0x01
+    let corrupted = "accepted"
+    try {{ b.deserialize_crc32("Log", frame, algorithm) }} catch (e) {{ corrupted = e }}
+    console.log(to_hex(b.serialize_crc32("Log", value, algorithm)) === hex, to_hex(bytes), corrupted)
+}}
+
+{checks}
+"#
+    );
+    let script_path = tmp_dir.path().join("crc.mjs");
+    std::fs::write(&script_path, script).unwrap();
+
+    let output = Command::new("node").arg(&script_path).output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(stdout.lines().count(), vectors.len());
+    for line in stdout.lines() {
+        assert_eq!(line, "true ffee crc mismatch");
+    }
+}
+
+#[test]
+#[ignore = "requires node"]
+fn test_js_fingerprint() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let bindings = generate_bindings!(Log);
+    let expected = bindings
+        .all_containers()
+        .map(|container| format!("{} {}", container.name, container.fingerprint()))
+        .collect::<Vec<_>>();
+    let schema_fingerprint = bindings.fingerprint();
+    javascript::build_package(
+        tmp_dir.path(),
+        package_info("js-fingerprint-bindings"),
+        GenerationSettings::enable_all(),
+        bindings,
+    )
+    .unwrap();
+
+    let script = r#"
+import { SCHEMA_FINGERPRINT, CONTAINER_FINGERPRINTS } from "./js-fingerprint-bindings/index.js"
+
+console.log(SCHEMA_FINGERPRINT)
+for (const [name, fingerprint] of Object.entries(CONTAINER_FINGERPRINTS)) {
+    console.log(name, fingerprint)
+}
+"#;
+    let script_path = tmp_dir.path().join("fingerprint.mjs");
+    std::fs::write(&script_path, script).unwrap();
+
+    let output = Command::new("node").arg(&script_path).output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines = stdout.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines[0], schema_fingerprint.to_string());
+    assert_eq!(lines[1..], expected);
+}
+
+#[test]
+#[ignore = "requires node"]
+fn test_js_commonjs_module() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let bindings = generate_bindings!(Log);
+    let schema_fingerprint = bindings.fingerprint();
+    javascript::build_package(
+        tmp_dir.path(),
+        package_info("js-cjs-bindings"),
+        GenerationSettings::enable_all().esm_module(false),
+        bindings,
+    )
+    .unwrap();
+
+    let rust_bytes = postcard::to_allocvec(&sample()).unwrap();
+
+    let script = format!(
+        r#"
+const {{ serialize, deserialize, SCHEMA_FINGERPRINT }} = require("./js-cjs-bindings/index.js")
+
+const to_hex = (bytes) => Array.from(bytes, (b) => b.toString(16).padStart(2, "0")).join("")
+const from_hex = (hex) => new Uint8Array(hex.match(/../g).map((b) => parseInt(b, 16)))
+
+console.log(to_hex(serialize("Log", deserialize("Log", from_hex("{hex}")).value)))
+console.log(SCHEMA_FINGERPRINT)
+"#,
+        hex = to_hex(&rust_bytes),
+    );
+    let script_path = tmp_dir.path().join("commonjs.cjs");
+    std::fs::write(&script_path, script).unwrap();
+
+    let lines = run(Command::new("node").arg(&script_path));
+    assert_eq!(lines, [to_hex(&rust_bytes), schema_fingerprint.to_string()]);
+}
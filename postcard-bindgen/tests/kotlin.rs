@@ -0,0 +1,101 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use postcard_bindgen::kotlin::{self, GenerationSettings};
+
+mod common;
+
+use common::{assert_everything_round_trip, run, sample, to_hex};
+
+fn build_package(dir: &Path) -> PathBuf {
+    common::build_everything(dir, "kotlin-bindings", |dir, package_info, containers| {
+        kotlin::build_package(
+            dir,
+            package_info,
+            GenerationSettings::enable_all(),
+            containers,
+        )
+    })
+}
+
+fn sources_dir(project_dir: &Path) -> PathBuf {
+    project_dir
+        .join("src")
+        .join("main")
+        .join("kotlin")
+        .join("kotlin_bindings")
+}
+
+#[test]
+fn test_build_gradle_package() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let project_dir = build_package(tmp_dir.path());
+
+    let build_file = std::fs::read_to_string(project_dir.join("build.gradle.kts")).unwrap();
+    insta::assert_snapshot!("build_gradle_package_build_file", build_file);
+    let settings_file = std::fs::read_to_string(project_dir.join("settings.gradle.kts")).unwrap();
+    assert_eq!(settings_file, "rootProject.name = \"kotlin-bindings\"\n");
+
+    let sources_dir = sources_dir(&project_dir);
+    assert!(sources_dir.join("common").join("Types.kt").is_file());
+    assert!(sources_dir
+        .join("common")
+        .join("shapes")
+        .join("Types.kt")
+        .is_file());
+    assert!(sources_dir.join("postcard").join("Runtime.kt").is_file());
+}
+
+#[test]
+#[ignore = "requires kotlinc and java"]
+fn test_kotlin_round_trip() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let sources_dir = sources_dir(&build_package(tmp_dir.path()));
+
+    let rust_bytes = postcard::to_allocvec(&sample()).unwrap();
+
+    let program = format!(
+        r#"
+import kotlin_bindings.common.Everything
+import kotlin_bindings.common.Point
+import kotlin_bindings.common.shapes.Shape
+import kotlin_bindings.postcard.PostcardException
+
+fun fromHex(hex: String): ByteArray = hex.chunked(2).map {{ it.toInt(16).toByte() }}.toByteArray()
+
+fun toHex(bytes: ByteArray): String = bytes.joinToString("") {{ "%02x".format(it) }}
+
+fun main() {{
+    val (value, rest) = Everything.deserialize(fromHex("{hex}"))
+    println(toHex(value.serialize()))
+    println(rest.size)
+
+    val (point, _) = Point.deserialize(fromHex("7f05"))
+    println("${{point._0}} ${{point._1}}")
+
+    try {{
+        Shape.deserialize(fromHex("03"))
+        println("accepted")
+    }} catch (e: PostcardException) {{
+        println("rejected")
+    }}
+}}
+"#,
+        hex = to_hex(&rust_bytes),
+    );
+    let program_path = tmp_dir.path().join("Main.kt");
+    std::fs::write(&program_path, program).unwrap();
+
+    let jar_path = tmp_dir.path().join("main.jar");
+    run(Command::new("kotlinc")
+        .arg(&sources_dir)
+        .arg(&program_path)
+        .arg("-include-runtime")
+        .arg("-d")
+        .arg(&jar_path));
+
+    let lines = run(Command::new("java").arg("-jar").arg(&jar_path));
+    assert_everything_round_trip(&lines, &rust_bytes);
+}
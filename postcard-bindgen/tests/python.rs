@@ -1,9 +1,22 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
 use insta::assert_snapshot;
 use postcard_bindgen::{
     generate_bindings,
     python::{self, GenerationSettings},
-    PackageInfo,
+    PackageInfo, PostcardBindings,
 };
+use serde::{Deserialize, Serialize};
+
+mod common;
+
+use common::{crc_vectors, package_info, run, sample, to_hex};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, PostcardBindings)]
+struct Blob(Vec<u8>);
 
 #[test]
 fn test_build_pip_module() {
@@ -53,3 +66,395 @@ fn test_build_pip_module() {
 
     assert_snapshot!("build_pip_module_types", type_file_content);
 }
+
+#[test]
+#[ignore = "requires python3"]
+fn test_python_round_trip() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    python::build_package(
+        tmp_dir.path(),
+        package_info("python-bindings"),
+        GenerationSettings::enable_all(),
+        generate_bindings!(common::Everything),
+    )
+    .unwrap();
+
+    let rust_bytes = postcard::to_allocvec(&sample()).unwrap();
+    let program = format!(
+        r#"from python_bindings import *
+
+value, rest = deserialize(common.Everything, bytes.fromhex("{hex}ffee"))
+encoded = serialize(value)
+print(encoded.hex())
+print(rest.hex())
+print(serialize(deserialize(common.Everything, memoryview(bytearray(encoded)))[0]) == encoded)
+print(value.payload == list(range(256)), value.ratio)
+
+try:
+    deserialize(common.Everything, encoded[:40])
+    print("accepted")
+except Exception:
+    print("rejected")
+"#,
+        hex = to_hex(&rust_bytes),
+    );
+
+    let output = Command::new("python3")
+        .arg("-c")
+        .arg(&program)
+        .env(
+            "PYTHONPATH",
+            tmp_dir.path().join("python-bindings").join("src"),
+        )
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines = stdout.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines[0], to_hex(&rust_bytes));
+    assert_eq!(lines[1], "ffee");
+    assert_eq!(lines[2], "True");
+    assert_eq!(lines[3], "True -2.5");
+    assert_eq!(lines[4], "rejected");
+}
+
+#[test]
+#[ignore = "requires python3"]
+fn test_python_varint_bounds() {
+    #[derive(Serialize, PostcardBindings)]
+    #[allow(unused)]
+    struct Counters {
+        short: u16,
+        long: u32,
+    }
+
+    let tmp_dir = tempfile::tempdir().unwrap();
+    python::build_package(
+        tmp_dir.path(),
+        package_info("varint-bindings"),
+        GenerationSettings::enable_all(),
+        generate_bindings!(Counters),
+    )
+    .unwrap();
+
+    // the longest valid varints, a byte too many and a last byte out of range
+    let program = r#"from varint_bindings import *
+
+for hex in ["ffff03ffffffff0f", "8080800100", "ffff0400", "00ffffffff1f"]:
+    try:
+        value, _ = deserialize(Counters, bytes.fromhex(hex))
+        print(value.short, value.long)
+    except Exception:
+        print("rejected")
+"#;
+
+    let output = Command::new("python3")
+        .arg("-c")
+        .arg(program)
+        .env(
+            "PYTHONPATH",
+            tmp_dir.path().join("varint-bindings").join("src"),
+        )
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines = stdout.lines().collect::<Vec<_>>();
+
+    assert_eq!(
+        lines,
+        ["65535 4294967295", "rejected", "rejected", "rejected"]
+    );
+}
+
+#[test]
+#[ignore = "requires python3"]
+fn test_python_fingerprint() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let bindings = generate_bindings!(common::Everything);
+    let expected = bindings
+        .all_containers()
+        .map(|container| format!("{} {}", container.name, container.fingerprint()))
+        .collect::<Vec<_>>();
+    let schema_fingerprint = bindings.fingerprint();
+    python::build_package(
+        tmp_dir.path(),
+        package_info("fingerprint-bindings"),
+        GenerationSettings::enable_all(),
+        bindings,
+    )
+    .unwrap();
+
+    let program = r#"from fingerprint_bindings import SCHEMA_FINGERPRINT, CONTAINER_FINGERPRINTS
+
+print(SCHEMA_FINGERPRINT)
+# containers are keyed by their dotted path in the package
+print("common.shapes.Shape" in CONTAINER_FINGERPRINTS)
+for name, fingerprint in CONTAINER_FINGERPRINTS.items():
+    print(name.split(".")[-1], fingerprint)
+"#;
+
+    let output = Command::new("python3")
+        .arg("-c")
+        .arg(program)
+        .env(
+            "PYTHONPATH",
+            tmp_dir.path().join("fingerprint-bindings").join("src"),
+        )
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines = stdout.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines[0], schema_fingerprint.to_string());
+    assert_eq!(lines[1], "True");
+    assert_eq!(lines[2..], expected);
+}
+
+fn build_micropython_package(dir: &Path) -> PathBuf {
+    python::build_package(
+        dir,
+        package_info("micropython-bindings"),
+        GenerationSettings::enable_all().micropython(true),
+        generate_bindings!(common::Everything),
+    )
+    .unwrap();
+    dir.join("micropython-bindings").join("src")
+}
+
+#[test]
+fn test_build_pip_module_micropython() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let mod_dir = build_micropython_package(tmp_dir.path()).join("micropython_bindings");
+    let entries = std::fs::read_dir(&mod_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(entries, ["__init__.py"]);
+
+    let module = std::fs::read_to_string(mod_dir.join("__init__.py")).unwrap();
+    assert!(!module.contains("typing"));
+    assert!(!module.contains("dataclass"));
+}
+
+fn micropython_round_trip(interpreter: &str) {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let src_dir = build_micropython_package(tmp_dir.path());
+
+    let rust_bytes = postcard::to_allocvec(&sample()).unwrap();
+    let program = format!(
+        r#"import sys
+import binascii
+
+sys.path.insert(0, "{src}")
+
+import micropython_bindings as b
+
+value, rest = b.deserialize(b.Everything, binascii.unhexlify("{hex}"))
+encoded = b.serialize(value)
+print(binascii.hexlify(encoded).decode())
+print(len(rest))
+print(b.deserialize(b.Everything, encoded)[0] == value)
+print(value.points[0][0], value.points[0][1])
+
+try:
+    b.deserialize(b.Shape, bytes([3]))
+    print("accepted")
+except TypeError:
+    print("rejected")
+
+print(b.SCHEMA_FINGERPRINT, b.CONTAINER_FINGERPRINTS["Shape"])
+"#,
+        src = src_dir.display(),
+        hex = to_hex(&rust_bytes),
+    );
+    let bindings = generate_bindings!(common::Everything);
+    let shape = bindings
+        .all_containers()
+        .find(|c| c.name == "Shape")
+        .unwrap();
+    let fingerprints = format!("{} {}", bindings.fingerprint(), shape.fingerprint());
+
+    let lines = run(Command::new(interpreter).arg("-c").arg(&program));
+    assert_eq!(lines[0], to_hex(&rust_bytes));
+    assert_eq!(lines[1], "0");
+    assert_eq!(lines[2], "True");
+    assert_eq!(lines[3], "-1 1099511627776");
+    assert_eq!(lines[4], "rejected");
+    assert_eq!(lines[5], fingerprints);
+}
+
+#[test]
+#[ignore = "requires micropython"]
+fn test_micropython_round_trip() {
+    micropython_round_trip("micropython");
+}
+
+#[test]
+#[ignore = "requires python3"]
+fn test_micropython_module_on_cpython() {
+    micropython_round_trip("python3");
+}
+
+#[test]
+#[ignore = "requires python3"]
+fn test_python_cobs() {
+    let log_frame = postcard::to_allocvec_cobs(&sample()).unwrap();
+    // the encoded value is a single run of 254 non-zero bytes
+    let blob_frame = postcard::to_allocvec_cobs(&Blob((1..=252).collect())).unwrap();
+    let mut stream = log_frame.clone();
+    stream.extend(&blob_frame);
+    stream.extend(&log_frame);
+
+    for micropython in [false, true] {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        python::build_package(
+            tmp_dir.path(),
+            package_info("cobs-bindings"),
+            GenerationSettings::enable_all()
+                .micropython(micropython)
+                .cobs(true),
+            generate_bindings!(common::Everything, Blob),
+        )
+        .unwrap();
+
+        // the micropython module is flat
+        let everything = if micropython {
+            "b.Everything"
+        } else {
+            "b.common.Everything"
+        };
+        let program = format!(
+            r#"import cobs_bindings as b
+
+stream = bytes.fromhex("{stream}")
+value, rest = b.deserialize_cobs({everything}, stream)
+print(b.serialize_cobs(value).hex())
+print(len(rest))
+print(b.serialize_cobs(b.deserialize_cobs(b.Blob, bytes.fromhex("{blob}"))[0]).hex())
+
+accumulator = b.CobsAccumulator({everything}, 2048)
+names = []
+for i in range(0, len(stream), 7):
+    frames = accumulator.feed(stream[i:i + 7])
+    try:
+        for value in frames:
+            names.append(value.name)
+    except Exception as e:
+        names.append(type(e).__name__)
+print(",".join(names))
+"#,
+            stream = to_hex(&stream),
+            blob = to_hex(&blob_frame),
+        );
+
+        let output = Command::new("python3")
+            .arg("-c")
+            .arg(&program)
+            .env(
+                "PYTHONPATH",
+                tmp_dir.path().join("cobs-bindings").join("src"),
+            )
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let lines = stdout.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines[0], to_hex(&log_frame));
+        assert_eq!(lines[1], (blob_frame.len() + log_frame.len()).to_string());
+        assert_eq!(lines[2], to_hex(&blob_frame));
+        // the blob frame doesn't hold a value of the type, which doesn't stop the accumulator
+        let names = lines[3].split(',').collect::<Vec<_>>();
+        assert_eq!(names.len(), 3);
+        assert_eq!(names[0], "grüße");
+        assert_eq!(names[2], "grüße");
+    }
+}
+
+#[test]
+#[ignore = "requires python3"]
+fn test_python_crc32() {
+    let vectors = crc_vectors(&sample());
+    let checks = vectors
+        .iter()
+        .map(|(name, frame)| format!("check(b.{name}, \"{}\")", to_hex(frame)))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    for micropython in [false, true] {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        python::build_package(
+            tmp_dir.path(),
+            package_info("crc-bindings"),
+            GenerationSettings::enable_all()
+                .micropython(micropython)
+                .crc32(true),
+            generate_bindings!(common::Everything),
+        )
+        .unwrap();
+
+        // the micropython module is flat
+        let everything = if micropython {
+            "b.Everything"
+        } else {
+            "b.common.Everything"
+        };
+        let program = format!(
+            r#"import crc_bindings as b
+
+def check(algorithm, hex):
+    frame = bytearray.fromhex(hex + "ffee")
+    value, rest = b.deserialize_crc32({everything}, frame, algorithm)
+    frame[3] ^= 0x01
+    try:
+        b.deserialize_crc32({everything}, frame, algorithm)
+        corrupted = "accepted"
+    except Exception as e:
+        corrupted = str(e)
+    print(b.serialize_crc32(value, algorithm).hex() == hex, rest.hex(), corrupted)
+
+{checks}
+"#
+        );
+
+        let output = Command::new("python3")
+            .arg("-c")
+            .arg(&program)
+            .env(
+                "PYTHONPATH",
+                tmp_dir.path().join("crc-bindings").join("src"),
+            )
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let stdout = String::from_utf8(output.stdout).unwrap();
+
+        assert_eq!(stdout.lines().count(), vectors.len());
+        for line in stdout.lines() {
+            assert_eq!(line, "True ffee crc mismatch");
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use postcard::fixint;
+use postcard_bindgen::{
+    __private::{
+        BindingType, ContainerCollection, EnumVariant, EnumVariantType, FixintBe, FixintLe,
+        GenBinding, StructField,
+    },
+    generate_bindings, PostcardBindings,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PostcardBindings)]
+#[serde(transparent)]
+struct Meters(u32);
+
+#[derive(Clone, Serialize, Deserialize, PostcardBindings)]
+#[serde(into = "u16", from = "u16")]
+struct Code {
+    value: u16,
+}
+
+impl From<Code> for u16 {
+    fn from(code: Code) -> Self {
+        code.value
+    }
+}
+
+impl From<u16> for Code {
+    fn from(value: u16) -> Self {
+        Self { value }
+    }
+}
+
+mod bool_as_u8 {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(v: &bool, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_u8(*v as u8)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<bool, D::Error> {
+        Ok(u8::deserialize(d)? != 0)
+    }
+}
+
+#[derive(Serialize, Deserialize, PostcardBindings)]
+struct Settings {
+    #[serde(rename = "distance")]
+    length: Meters,
+    #[serde(skip)]
+    cache: Option<u64>,
+    #[serde(default)]
+    retries: u8,
+    #[serde(with = "bool_as_u8")]
+    #[postcard_bindgen(as_type = "u8")]
+    enabled: bool,
+}
+
+mod little_endian {
+    pub use postcard::fixint::le::{deserialize, serialize};
+}
+
+#[derive(Serialize, Deserialize, PostcardBindings)]
+struct Reading {
+    #[serde(with = "postcard::fixint::le")]
+    raw: u32,
+    #[serde(with = "fixint::be")]
+    offset: i16,
+    #[serde(with = "little_endian")]
+    #[postcard_bindgen(fixint = "le")]
+    id: u64,
+}
+
+#[derive(Serialize, Deserialize, PostcardBindings)]
+enum Command {
+    Move {
+        distance: Meters,
+        #[serde(skip)]
+        hint: u8,
+    },
+    #[serde(rename = "Stop")]
+    Halt,
+    #[serde(skip)]
+    Internal,
+}
+
+fn container(bindings: ContainerCollection, name: &str) -> BindingType {
+    bindings
+        .all_containers()
+        .find(|container| container.name == name)
+        .unwrap()
+        .r#type
+}
+
+#[test]
+fn test_proxy_containers_use_the_serialized_type() {
+    assert_eq!(Meters::get_type(), u32::get_type());
+    assert_eq!(Code::get_type(), u16::get_type());
+
+    let bindings = generate_bindings!(Meters, Code);
+    assert_eq!(bindings.all_containers().count(), 0);
+}
+
+#[test]
+fn test_struct_field_attributes() {
+    let BindingType::Struct(ty) = container(generate_bindings!(Settings), "Settings") else {
+        panic!("expected struct");
+    };
+
+    assert_eq!(
+        ty.fields,
+        vec![
+            StructField {
+                name: "distance".into(),
+                v_type: u32::get_type(),
+                doc: None,
+            },
+            StructField {
+                name: "retries".into(),
+                v_type: u8::get_type(),
+                doc: None,
+            },
+            StructField {
+                name: "enabled".into(),
+                v_type: u8::get_type(),
+                doc: None,
+            },
+        ]
+    );
+
+    // the registered layout matches the bytes serde writes
+    let value = Settings {
+        length: Meters(300),
+        cache: Some(7),
+        retries: 3,
+        enabled: true,
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+    assert_eq!(bytes, vec![0xac, 0x02, 0x03, 0x01]);
+
+    let decoded = postcard::from_bytes::<Settings>(&bytes).unwrap();
+    assert_eq!(decoded.cache, None);
+    assert!(decoded.enabled);
+}
+
+#[test]
+fn test_enum_variant_attributes() {
+    let BindingType::Enum(ty) = container(generate_bindings!(Command), "Command") else {
+        panic!("expected enum");
+    };
+
+    assert_eq!(
+        ty.variants,
+        vec![
+            EnumVariant {
+                index: 0,
+                name: "Move".into(),
+                inner_type: EnumVariantType::NewType(vec![StructField {
+                    name: "distance".into(),
+                    v_type: u32::get_type(),
+                    doc: None,
+                }]),
+                doc: None,
+            },
+            EnumVariant {
+                index: 1,
+                name: "Stop".into(),
+                inner_type: EnumVariantType::Empty,
+                doc: None,
+            },
+        ]
+    );
+
+    // skipped variants can't be serialized
+    assert!(postcard::to_allocvec(&Command::Internal).is_err());
+}
+
+#[test]
+fn test_fixint_fields() {
+    let BindingType::Struct(ty) = container(generate_bindings!(Reading), "Reading") else {
+        panic!("expected struct");
+    };
+
+    assert_eq!(
+        ty.fields,
+        vec![
+            StructField {
+                name: "raw".into(),
+                v_type: FixintLe::<u32>::get_type(),
+                doc: None,
+            },
+            StructField {
+                name: "offset".into(),
+                v_type: FixintBe::<i16>::get_type(),
+                doc: None,
+            },
+            StructField {
+                name: "id".into(),
+                v_type: FixintLe::<u64>::get_type(),
+                doc: None,
+            },
+        ]
+    );
+    assert_ne!(FixintLe::<u32>::get_type(), u32::get_type());
+
+    let value = Reading {
+        raw: 0x01020304,
+        offset: -2,
+        id: 5,
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+    assert_eq!(
+        bytes,
+        vec![0x04, 0x03, 0x02, 0x01, 0xff, 0xfe, 5, 0, 0, 0, 0, 0, 0, 0]
+    );
+}
+
+#[test]
+fn test_unsupported_attributes_are_rejected() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,101 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use postcard_bindgen::swift::{self, GenerationSettings};
+
+mod common;
+
+use common::{assert_everything_round_trip, run, sample, to_hex, Everything};
+
+fn build_package(dir: &Path) -> PathBuf {
+    common::build_everything(dir, "swift-bindings", |dir, package_info, containers| {
+        swift::build_package(
+            dir,
+            package_info,
+            GenerationSettings::enable_all(),
+            containers,
+        )
+    })
+}
+
+fn sources(package_dir: &Path) -> [PathBuf; 6] {
+    let sources_dir = package_dir.join("Sources").join("swift_bindings");
+    ["Runtime", "Types", "Encoder", "Ser", "Decoder", "Des"]
+        .map(|name| sources_dir.join(format!("{name}.swift")))
+}
+
+#[test]
+fn test_build_swift_package() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let package_dir = build_package(tmp_dir.path());
+    let manifest = std::fs::read_to_string(package_dir.join("Package.swift")).unwrap();
+    insta::assert_snapshot!("build_swift_package_manifest", manifest);
+
+    for source in sources(&package_dir) {
+        assert!(source.is_file());
+    }
+}
+
+#[test]
+#[ignore = "requires swiftc"]
+fn test_swift_round_trip() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let sources = sources(&build_package(tmp_dir.path()));
+
+    let rust_bytes = postcard::to_allocvec(&Everything {
+        // a single entry, as swift dictionaries have no stable order
+        map: BTreeMap::from([("b".into(), 300)]),
+        ..sample()
+    })
+    .unwrap();
+
+    let program = format!(
+        r#"
+func fromHex(_ hex: String) -> [UInt8] {{
+    var bytes: [UInt8] = []
+    var index = hex.startIndex
+    while index < hex.endIndex {{
+        let next = hex.index(index, offsetBy: 2)
+        bytes.append(UInt8(hex[index..<next], radix: 16)!)
+        index = next
+    }}
+    return bytes
+}}
+
+func toHex(_ bytes: [UInt8]) -> String {{
+    bytes.map {{ String($0, radix: 16).count == 1 ? "0" + String($0, radix: 16) : String($0, radix: 16) }}.joined()
+}}
+
+let (value, rest) = try common.Everything.deserialize(fromHex("{hex}"))
+print(toHex(try value.serialize()))
+print(rest.count)
+
+let (point, _) = try common.Point.deserialize(fromHex("7f05"))
+print("\(point._0) \(point._1)")
+
+do {{
+    _ = try common.shapes.Shape.deserialize(fromHex("03"))
+    print("accepted")
+}} catch PostcardError.unknownVariant {{
+    print("rejected")
+}}
+"#,
+        hex = to_hex(&rust_bytes),
+    );
+    let program_path = tmp_dir.path().join("main.swift");
+    std::fs::write(&program_path, program).unwrap();
+
+    let binary_path = tmp_dir.path().join("main");
+    run(Command::new("swiftc")
+        .args(["-module-name", "swift_bindings"])
+        .args(sources.iter())
+        .arg(&program_path)
+        .arg("-o")
+        .arg(&binary_path));
+
+    let lines = run(&mut Command::new(&binary_path));
+    assert_everything_round_trip(&lines, &rust_bytes);
+}
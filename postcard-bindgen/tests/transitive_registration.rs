@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+
+use postcard_bindgen::{generate_bindings, PostcardBindings};
+use serde::Serialize;
+
+#[derive(Serialize, PostcardBindings)]
+struct A1Meta {
+    id: u8,
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct Header(u16);
+
+#[derive(Serialize, PostcardBindings)]
+struct Frame<T> {
+    payload: T,
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct Packet {
+    meta: Option<A1Meta>,
+    headers: HashMap<u8, Vec<Header>>,
+}
+
+#[allow(unused)]
+#[derive(Serialize, PostcardBindings)]
+enum Protocol {
+    Empty,
+    Data(Frame<Packet>),
+    Batch { packets: [Packet; 2] },
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct Tree {
+    children: Vec<Tree>,
+    leaf: Option<Leaf>,
+}
+
+#[derive(Serialize, PostcardBindings)]
+#[serde(transparent)]
+struct Leaf(A1Meta);
+
+fn names(bindings: postcard_bindgen::__private::ContainerCollection) -> Vec<String> {
+    bindings
+        .all_containers()
+        .map(|c| c.name.to_string())
+        .collect()
+}
+
+#[test]
+fn test_referenced_containers_are_registered() {
+    let mut names = names(generate_bindings!(Protocol));
+    names.sort();
+    assert_eq!(
+        names,
+        vec!["A1Meta", "Frame_Packet", "Header", "Packet", "Protocol"]
+    );
+}
+
+#[test]
+fn test_containers_are_registered_once() {
+    let names = names(generate_bindings!(Packet, A1Meta, Protocol, Packet));
+    assert_eq!(names.len(), 5);
+}
+
+#[test]
+fn test_self_referencing_containers() {
+    let mut names = names(generate_bindings!(Tree));
+    names.sort();
+    assert_eq!(names, vec!["A1Meta", "Tree"]);
+}
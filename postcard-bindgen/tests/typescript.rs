@@ -0,0 +1,225 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use insta::assert_snapshot;
+use postcard_bindgen::{
+    generate_bindings,
+    typescript::{self, GenerationSettings},
+    ContainerCollection, PostcardBindings,
+};
+use serde::{Deserialize, Serialize};
+
+mod common;
+
+use common::{crc_vectors, package_info, run, sample, to_hex, Everything};
+
+#[test]
+fn test_build_ts_package() {
+    #[derive(postcard_bindgen::PostcardBindings)]
+    #[allow(unused)]
+    struct Test {
+        field: u8,
+    }
+
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let package_name = "test-ts-bindings";
+
+    let res = typescript::build_package(
+        tmp_dir.path(),
+        package_info(package_name),
+        GenerationSettings::enable_all(),
+        generate_bindings!(Test),
+    );
+
+    assert!(res.is_ok());
+
+    let package_dir = tmp_dir.path().join(package_name);
+    assert!(package_dir.exists());
+    assert!(package_dir.is_dir());
+
+    let package_json = std::fs::read_to_string(package_dir.join("package.json")).unwrap();
+    assert_snapshot!("build_ts_package_package_json", package_json);
+
+    let ts_config = std::fs::read_to_string(package_dir.join("tsconfig.json")).unwrap();
+    assert_snapshot!("build_ts_package_tsconfig", ts_config);
+
+    let index_file = package_dir.join("src").join("index.ts");
+    assert!(index_file.exists());
+    assert!(index_file.is_file());
+
+    let index_content = std::fs::read_to_string(index_file).unwrap();
+    assert!(index_content.contains("function serialize_TEST(s: Serializer, v: Test): void"));
+    assert!(index_content.contains("function deserialize_TEST(d: Deserializer): Test"));
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, PostcardBindings)]
+struct Blob(Vec<u8>);
+
+fn build_package(
+    dir: &Path,
+    gen_settings: GenerationSettings,
+    bindings: ContainerCollection,
+) -> PathBuf {
+    typescript::build_package(dir, package_info("ts-bindings"), gen_settings, bindings).unwrap();
+    dir.join("ts-bindings")
+}
+
+fn tsc(package_dir: &Path, args: &[&str]) {
+    run(Command::new("tsc").arg("-p").arg(package_dir).args(args));
+}
+
+#[test]
+#[ignore = "requires tsc"]
+fn test_ts_package_type_checks() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let package_dir = build_package(
+        tmp_dir.path(),
+        GenerationSettings::enable_all().cobs(true).crc32(true),
+        generate_bindings!(Everything, Blob),
+    );
+
+    tsc(&package_dir, &["--noEmit"]);
+}
+
+#[test]
+#[ignore = "requires tsc and node"]
+fn test_ts_round_trip() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let package_dir = build_package(
+        tmp_dir.path(),
+        GenerationSettings::enable_all(),
+        generate_bindings!(Everything),
+    );
+    tsc(&package_dir, &[]);
+
+    let rust_bytes = postcard::to_allocvec(&sample()).unwrap();
+
+    let script = format!(
+        r#"
+const {{ serialize, deserialize }} = require("./ts-bindings/dist/index.js")
+
+const to_hex = (bytes) => Array.from(bytes, (b) => b.toString(16).padStart(2, "0")).join("")
+const from_hex = (hex) => new Uint8Array(hex.match(/../g).map((b) => parseInt(b, 16)))
+
+const {{ value, bytes }} = deserialize("common.Everything", from_hex("{hex}ffee"))
+console.log(to_hex(serialize("common.Everything", value)))
+console.log(to_hex(bytes))
+console.log(value.stamp, value.delta, value.name)
+"#,
+        hex = to_hex(&rust_bytes),
+    );
+    let script_path = tmp_dir.path().join("round_trip.cjs");
+    std::fs::write(&script_path, script).unwrap();
+
+    let lines = run(Command::new("node").arg(&script_path));
+
+    assert_eq!(lines[0], to_hex(&rust_bytes));
+    assert_eq!(lines[1], "ffee");
+    assert_eq!(lines[2], "72623859790382856n -2 grüße");
+}
+
+#[test]
+#[ignore = "requires tsc and node"]
+fn test_ts_cobs() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let package_dir = build_package(
+        tmp_dir.path(),
+        GenerationSettings::enable_all().cobs(true),
+        generate_bindings!(Everything, Blob),
+    );
+    tsc(&package_dir, &[]);
+
+    let value_frame = postcard::to_allocvec_cobs(&sample()).unwrap();
+    // the encoded value is a single run of 254 non-zero bytes
+    let blob_frame = postcard::to_allocvec_cobs(&Blob((1..=252).collect())).unwrap();
+    let mut stream = value_frame.clone();
+    stream.extend(&blob_frame);
+    stream.extend(&value_frame);
+
+    let script = format!(
+        r#"
+const {{ serialize_cobs, deserialize_cobs, CobsAccumulator }} = require("./ts-bindings/dist/index.js")
+
+const to_hex = (bytes) => Array.from(bytes, (b) => b.toString(16).padStart(2, "0")).join("")
+const from_hex = (hex) => new Uint8Array(hex.match(/../g).map((b) => parseInt(b, 16)))
+
+const stream = from_hex("{stream}")
+const {{ value, bytes }} = deserialize_cobs("common.Everything", stream)
+console.log(to_hex(serialize_cobs("common.Everything", value)))
+console.log(bytes.length)
+console.log(to_hex(serialize_cobs("Blob", deserialize_cobs("Blob", from_hex("{blob}")).value)))
+
+const accumulator = new CobsAccumulator("common.Everything", 2048), values = []
+for (let i = 0; i < stream.length; i += 7) {{
+    const frames = accumulator.feed(stream.subarray(i, i + 7))
+    try {{ for (const value of frames) {{ values.push(value) }} }} catch (e) {{ values.push(e) }}
+}}
+console.log(values.map((v) => typeof v === "string" ? v : v.name).join(","))
+
+try {{ [...new CobsAccumulator("common.Everything", 16).feed(stream)] }} catch (e) {{ console.log(e) }}
+"#,
+        stream = to_hex(&stream),
+        blob = to_hex(&blob_frame),
+    );
+    let script_path = tmp_dir.path().join("cobs.cjs");
+    std::fs::write(&script_path, script).unwrap();
+
+    let lines = run(Command::new("node").arg(&script_path));
+
+    assert_eq!(lines[0], to_hex(&value_frame));
+    assert_eq!(lines[1], (blob_frame.len() + value_frame.len()).to_string());
+    assert_eq!(lines[2], to_hex(&blob_frame));
+    // the blob frame doesn't hold an `Everything`, which doesn't stop the accumulator
+    assert_eq!(lines[3], "grüße,Bad Variant,grüße");
+    assert_eq!(lines[4], "cobs frame exceeds the maximum size");
+}
+
+#[test]
+#[ignore = "requires tsc and node"]
+fn test_ts_crc32() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let package_dir = build_package(
+        tmp_dir.path(),
+        GenerationSettings::enable_all().crc32(true),
+        generate_bindings!(Everything),
+    );
+    tsc(&package_dir, &[]);
+
+    let vectors = crc_vectors(&sample());
+    let checks = vectors
+        .iter()
+        .map(|(name, frame)| format!("check(b.{name}, \"{}\")", to_hex(frame)))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let script = format!(
+        r#"
+const b = require("./ts-bindings/dist/index.js")
+
+const to_hex = (bytes) => Array.from(bytes, (b) => b.toString(16).padStart(2, "0")).join("")
+const from_hex = (hex) => new Uint8Array(hex.match(/../g).map((b) => parseInt(b, 16)))
+
+const check = (algorithm, hex) => {{
+    const frame = from_hex(hex + "ffee")
+    const {{ value, bytes }} = b.deserialize_crc32("common.Everything", frame, algorithm)
+    frame[3] ^= 0x01
+    let corrupted = "accepted"
+    try {{ b.deserialize_crc32("common.Everything", frame, algorithm) }} catch (e) {{ corrupted = e }}
+    console.log(to_hex(b.serialize_crc32("common.Everything", value, algorithm)) === hex, to_hex(bytes), corrupted)
+}}
+
+{checks}
+"#
+    );
+    let script_path = tmp_dir.path().join("crc.cjs");
+    std::fs::write(&script_path, script).unwrap();
+
+    let lines = run(Command::new("node").arg(&script_path));
+
+    assert_eq!(lines.len(), vectors.len());
+    for line in lines {
+        assert_eq!(line, "true ffee crc mismatch");
+    }
+}
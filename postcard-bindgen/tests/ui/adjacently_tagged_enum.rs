@@ -0,0 +1,10 @@
+use postcard_bindgen::PostcardBindings;
+use serde::Serialize;
+
+#[derive(Serialize, PostcardBindings)]
+#[serde(tag = "type", content = "data")]
+enum Message {
+    Ping(u8),
+}
+
+fn main() {}
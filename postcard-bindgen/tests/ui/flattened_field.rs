@@ -0,0 +1,15 @@
+use postcard_bindgen::PostcardBindings;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct Inner {
+    value: u8,
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct Outer {
+    #[serde(flatten)]
+    inner: Inner,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use postcard_bindgen::PostcardBindings;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PostcardBindings)]
+#[serde(from = "u16")]
+struct Code {
+    value: u16,
+}
+
+impl From<u16> for Code {
+    fn from(value: u16) -> Self {
+        Self { value }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+use postcard_bindgen::PostcardBindings;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize, PostcardBindings)]
+#[serde(into = "u16", from = "u32")]
+struct Code {
+    value: u16,
+}
+
+impl From<Code> for u16 {
+    fn from(code: Code) -> Self {
+        code.value
+    }
+}
+
+impl From<u32> for Code {
+    fn from(value: u32) -> Self {
+        Self { value: value as u16 }
+    }
+}
+
+fn main() {}
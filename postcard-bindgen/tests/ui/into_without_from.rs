@@ -0,0 +1,16 @@
+use postcard_bindgen::PostcardBindings;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize, PostcardBindings)]
+#[serde(into = "u16")]
+struct Code {
+    value: u16,
+}
+
+impl From<Code> for u16 {
+    fn from(code: Code) -> Self {
+        code.value
+    }
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use postcard_bindgen::PostcardBindings;
+use serde::{Serialize, Serializer};
+
+fn as_u8<S: Serializer>(value: &bool, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_u8(*value as u8)
+}
+
+#[derive(Serialize, PostcardBindings)]
+enum Message {
+    #[serde(serialize_with = "as_u8")]
+    Enabled(bool),
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use postcard_bindgen::PostcardBindings;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PostcardBindings)]
+struct Config {
+    #[serde(skip_deserializing)]
+    cache: u8,
+    value: u8,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use postcard_bindgen::PostcardBindings;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PostcardBindings)]
+enum Message {
+    Ping(u8),
+    #[serde(skip_deserializing)]
+    Internal,
+}
+
+fn main() {}
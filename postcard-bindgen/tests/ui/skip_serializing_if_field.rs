@@ -0,0 +1,10 @@
+use postcard_bindgen::PostcardBindings;
+use serde::Serialize;
+
+#[derive(Serialize, PostcardBindings)]
+struct Config {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<u8>,
+}
+
+fn main() {}
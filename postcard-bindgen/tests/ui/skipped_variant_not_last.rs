@@ -0,0 +1,11 @@
+use postcard_bindgen::PostcardBindings;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PostcardBindings)]
+enum Message {
+    #[serde(skip)]
+    Internal,
+    Ping(u8),
+}
+
+fn main() {}
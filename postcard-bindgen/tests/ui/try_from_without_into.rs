@@ -0,0 +1,20 @@
+use postcard_bindgen::PostcardBindings;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PostcardBindings)]
+#[serde(try_from = "u16")]
+struct Code {
+    value: u8,
+}
+
+impl TryFrom<u16> for Code {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        Ok(Self {
+            value: value.try_into()?,
+        })
+    }
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use postcard_bindgen::PostcardBindings;
+use serde::Serialize;
+
+#[derive(Serialize, PostcardBindings)]
+enum Message {
+    Ping(u8),
+    #[serde(untagged)]
+    Other(u16),
+}
+
+fn main() {}
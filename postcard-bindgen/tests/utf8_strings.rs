@@ -0,0 +1,246 @@
+use std::process::Command;
+
+use postcard_bindgen::{generate_bindings, javascript, python, typescript, PostcardBindings};
+use serde::{Deserialize, Serialize};
+
+mod common;
+
+use common::{from_hex, package_info, run, to_hex};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, PostcardBindings)]
+struct Text {
+    ascii: String,
+    latin: String,
+    cjk: String,
+    emoji: String,
+    words: Vec<String>,
+}
+
+/// Holds at most four bytes, which are two characters of `äöü`.
+#[cfg(feature = "heapless")]
+#[derive(Serialize, PostcardBindings)]
+struct Label {
+    name: heapless::String<4>,
+}
+
+fn sample() -> Text {
+    Text {
+        ascii: "hello".into(),
+        latin: "grüße, élève".into(),
+        cjk: "日本語のテキスト".into(),
+        emoji: "🦀 postcard 🚀".into(),
+        words: vec!["ä".into(), "€".into(), "𝄞".into(), "".into()],
+    }
+}
+
+// length prefix of two followed by an invalid continuation sequence
+const INVALID_UTF8: &str = "02c328";
+
+fn assert_round_trip(lines: &[String], rust_bytes: &[u8]) {
+    // bytes from the rust serializer deserialized and serialized again
+    assert_eq!(lines[0], to_hex(rust_bytes));
+    // value constructed in the foreign language, deserialized in rust
+    assert_eq!(
+        postcard::from_bytes::<Text>(&from_hex(&lines[1])).unwrap(),
+        sample()
+    );
+    assert_eq!(lines[2], "rejected");
+}
+
+fn string_list(strings: &[String]) -> String {
+    strings
+        .iter()
+        .map(|s| format!("{s:?}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[test]
+#[ignore = "requires node"]
+fn test_js_utf8_round_trip() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    javascript::build_package(
+        tmp_dir.path(),
+        package_info("utf8-js-bindings"),
+        javascript::GenerationSettings::enable_all(),
+        generate_bindings!(Text),
+    )
+    .unwrap();
+
+    let value = sample();
+    let rust_bytes = postcard::to_allocvec(&value).unwrap();
+
+    let script = format!(
+        r#"
+import {{ serialize, deserialize }} from "./utf8-js-bindings/index.js"
+
+const to_hex = (bytes) => Array.from(bytes, (b) => b.toString(16).padStart(2, "0")).join("")
+const from_hex = (hex) => new Uint8Array(hex.match(/../g).map((b) => parseInt(b, 16)))
+
+console.log(to_hex(serialize("Text", deserialize("Text", from_hex("{hex}")).value)))
+console.log(to_hex(serialize("Text", {{ ascii: {ascii:?}, latin: {latin:?}, cjk: {cjk:?}, emoji: {emoji:?}, words: [{words}] }})))
+try {{ deserialize("Text", from_hex("{INVALID_UTF8}")); console.log("accepted") }} catch (e) {{ console.log("rejected") }}
+"#,
+        hex = to_hex(&rust_bytes),
+        ascii = value.ascii,
+        latin = value.latin,
+        cjk = value.cjk,
+        emoji = value.emoji,
+        words = string_list(&value.words),
+    );
+    let script_path = tmp_dir.path().join("round_trip.mjs");
+    std::fs::write(&script_path, script).unwrap();
+
+    let lines = run(Command::new("node").arg(&script_path));
+    assert_round_trip(&lines, &rust_bytes);
+}
+
+#[test]
+#[ignore = "requires tsc and node"]
+fn test_ts_utf8_round_trip() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    typescript::build_package(
+        tmp_dir.path(),
+        package_info("utf8-ts-bindings"),
+        typescript::GenerationSettings::enable_all(),
+        generate_bindings!(Text),
+    )
+    .unwrap();
+    run(Command::new("tsc")
+        .arg("-p")
+        .arg(tmp_dir.path().join("utf8-ts-bindings")));
+
+    let value = sample();
+    let rust_bytes = postcard::to_allocvec(&value).unwrap();
+
+    let script = format!(
+        r#"
+const {{ serialize, deserialize }} = require("./utf8-ts-bindings/dist/index.js")
+
+const to_hex = (bytes) => Array.from(bytes, (b) => b.toString(16).padStart(2, "0")).join("")
+const from_hex = (hex) => new Uint8Array(hex.match(/../g).map((b) => parseInt(b, 16)))
+
+console.log(to_hex(serialize("Text", deserialize("Text", from_hex("{hex}")).value)))
+console.log(to_hex(serialize("Text", {{ ascii: {ascii:?}, latin: {latin:?}, cjk: {cjk:?}, emoji: {emoji:?}, words: [{words}] }})))
+try {{ deserialize("Text", from_hex("{INVALID_UTF8}")); console.log("accepted") }} catch (e) {{ console.log("rejected") }}
+"#,
+        hex = to_hex(&rust_bytes),
+        ascii = value.ascii,
+        latin = value.latin,
+        cjk = value.cjk,
+        emoji = value.emoji,
+        words = string_list(&value.words),
+    );
+    let script_path = tmp_dir.path().join("round_trip.cjs");
+    std::fs::write(&script_path, script).unwrap();
+
+    let lines = run(Command::new("node").arg(&script_path));
+    assert_round_trip(&lines, &rust_bytes);
+}
+
+#[test]
+#[ignore = "requires python3"]
+fn test_python_utf8_round_trip() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    python::build_package(
+        tmp_dir.path(),
+        package_info("utf8_py_bindings"),
+        python::GenerationSettings::enable_all(),
+        generate_bindings!(Text),
+    )
+    .unwrap();
+
+    let value = sample();
+    let rust_bytes = postcard::to_allocvec(&value).unwrap();
+
+    let script = format!(
+        r#"
+from utf8_py_bindings import *
+
+value, _ = deserialize(Text, bytes.fromhex("{hex}"))
+print(serialize(value).hex())
+print(serialize(Text(ascii={ascii:?}, latin={latin:?}, cjk={cjk:?}, emoji={emoji:?}, words=[{words}])).hex())
+try:
+    deserialize(Text, bytes.fromhex("{INVALID_UTF8}"))
+    print("accepted")
+except UnicodeDecodeError:
+    print("rejected")
+"#,
+        hex = to_hex(&rust_bytes),
+        ascii = value.ascii,
+        latin = value.latin,
+        cjk = value.cjk,
+        emoji = value.emoji,
+        words = string_list(&value.words),
+    );
+    let script_path = tmp_dir.path().join("round_trip.py");
+    std::fs::write(&script_path, script).unwrap();
+
+    let lines = run(Command::new("python3").arg(&script_path).env(
+        "PYTHONPATH",
+        tmp_dir.path().join("utf8_py_bindings").join("src"),
+    ));
+    assert_round_trip(&lines, &rust_bytes);
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+#[ignore = "requires node"]
+fn test_js_utf8_max_length() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    javascript::build_package(
+        tmp_dir.path(),
+        package_info("utf8-js-bindings"),
+        javascript::GenerationSettings::enable_all(),
+        generate_bindings!(Label),
+    )
+    .unwrap();
+
+    let script = r#"
+import { serialize } from "./utf8-js-bindings/index.js"
+
+for (const name of ["äö", "äöü"]) {
+    try { serialize("Label", { name }); console.log("accepted") } catch (e) { console.log("rejected") }
+}
+"#;
+    let script_path = tmp_dir.path().join("max_length.mjs");
+    std::fs::write(&script_path, script).unwrap();
+
+    let lines = run(Command::new("node").arg(&script_path));
+    // the length is checked in bytes, not in characters
+    assert_eq!(lines, ["accepted", "rejected"]);
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+#[ignore = "requires python3"]
+fn test_python_utf8_max_length() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    python::build_package(
+        tmp_dir.path(),
+        package_info("utf8_py_bindings"),
+        python::GenerationSettings::enable_all(),
+        generate_bindings!(Label),
+    )
+    .unwrap();
+
+    let script = r#"
+from utf8_py_bindings import *
+
+for name in ["äö", "äöü"]:
+    try:
+        serialize(Label(name=name))
+        print("accepted")
+    except AssertionError:
+        print("rejected")
+"#;
+    let script_path = tmp_dir.path().join("max_length.py");
+    std::fs::write(&script_path, script).unwrap();
+
+    let lines = run(Command::new("python3").arg(&script_path).env(
+        "PYTHONPATH",
+        tmp_dir.path().join("utf8_py_bindings").join("src"),
+    ));
+    // the length is checked in bytes, not in characters
+    assert_eq!(lines, ["accepted", "rejected"]);
+}
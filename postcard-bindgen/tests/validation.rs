@@ -0,0 +1,228 @@
+use std::io;
+
+use postcard_bindgen::{
+    dart, generate_bindings, go, javascript, python, PackageInfo, PostcardBindings,
+    ValidationErrorReason, ValidationErrors,
+};
+use serde::Serialize;
+
+#[allow(unused)]
+#[derive(Serialize, PostcardBindings)]
+enum Command {
+    Stop,
+    Move(u8),
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Serialize, PostcardBindings)]
+struct Command_Stop;
+
+mod a {
+    use postcard_bindgen::PostcardBindings;
+    use serde::Serialize;
+
+    #[derive(Serialize, PostcardBindings)]
+    pub struct Status(pub u8);
+
+    #[derive(Serialize, PostcardBindings)]
+    pub struct BC;
+
+    #[allow(unused)]
+    #[derive(Serialize, PostcardBindings)]
+    pub enum Shape {
+        Circle(f32),
+    }
+
+    // named like the variant `Shape::Circle` if the names are joined
+    #[derive(Serialize, PostcardBindings)]
+    pub struct ShapeCircle;
+}
+
+mod b {
+    use postcard_bindgen::PostcardBindings;
+    use serde::Serialize;
+
+    #[derive(Serialize, PostcardBindings)]
+    pub struct Status(pub u16);
+}
+
+// `a_b::C` and `a::BC` get the same name if the module path and the name are joined
+mod a_b {
+    use postcard_bindgen::PostcardBindings;
+    use serde::Serialize;
+
+    #[derive(Serialize, PostcardBindings)]
+    pub struct C;
+}
+
+fn package_info(name: &str) -> PackageInfo {
+    PackageInfo {
+        name: name.into(),
+        version: "0.1.0".try_into().unwrap(),
+    }
+}
+
+fn assert_variant_clash(err: io::Error) {
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    let errors = err
+        .into_inner()
+        .unwrap()
+        .downcast::<ValidationErrors>()
+        .unwrap();
+    assert_eq!(errors.0.len(), 1);
+    assert_eq!(errors.0[0].container, "Command");
+    assert_eq!(
+        errors.0[0].reason,
+        ValidationErrorReason::VariantNameClash {
+            variant: "Stop".into(),
+            class_name: "Command_Stop".into(),
+        }
+    );
+}
+
+#[test]
+fn test_invalid_bindings_are_not_written() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+
+    let err = python::build_package(
+        tmp_dir.path(),
+        package_info("invalid_py"),
+        python::GenerationSettings::enable_all(),
+        generate_bindings!(Command, Command_Stop),
+    )
+    .unwrap_err();
+    assert_variant_clash(err);
+
+    assert_eq!(std::fs::read_dir(tmp_dir.path()).unwrap().count(), 0);
+}
+
+#[test]
+fn test_variant_classes_are_only_checked_where_generated() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+
+    javascript::build_package(
+        tmp_dir.path(),
+        package_info("valid-js"),
+        javascript::GenerationSettings::enable_all(),
+        generate_bindings!(Command, Command_Stop),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_flattened_bindings_are_validated() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+
+    javascript::build_package(
+        tmp_dir.path(),
+        package_info("modules-js"),
+        javascript::GenerationSettings::enable_all(),
+        generate_bindings!(Command, a::Status, b::Status),
+    )
+    .unwrap();
+
+    let err = javascript::build_package(
+        tmp_dir.path(),
+        package_info("flattened-js"),
+        javascript::GenerationSettings::enable_all().module_structure(false),
+        generate_bindings!(Command, a::Status, b::Status),
+    )
+    .unwrap_err();
+    let errors = err
+        .into_inner()
+        .unwrap()
+        .downcast::<ValidationErrors>()
+        .unwrap();
+    assert_eq!(errors.0.len(), 1);
+    assert_eq!(errors.0[0].reason, ValidationErrorReason::DuplicateName);
+
+    let err = python::build_package(
+        tmp_dir.path(),
+        package_info("micropython_py"),
+        python::GenerationSettings::enable_all().micropython(true),
+        generate_bindings!(Command, a::Status, b::Status),
+    )
+    .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    assert!(!tmp_dir.path().join("flattened-js").exists());
+    assert!(!tmp_dir.path().join("micropython_py").exists());
+}
+
+fn reasons(err: io::Error) -> Vec<ValidationErrorReason> {
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    let errors = err
+        .into_inner()
+        .unwrap()
+        .downcast::<ValidationErrors>()
+        .unwrap();
+    errors.0.into_iter().map(|error| error.reason).collect()
+}
+
+#[test]
+fn test_go_type_names_are_validated() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+
+    let err = go::build_package(
+        tmp_dir.path(),
+        package_info("clashing-go"),
+        go::GenerationSettings::enable_all(),
+        generate_bindings!(a_b::C, a::BC, a::Shape, a::ShapeCircle),
+    )
+    .unwrap_err();
+    assert_eq!(
+        reasons(err),
+        [
+            ValidationErrorReason::TypeNameClash {
+                type_name: "ABC".into()
+            },
+            ValidationErrorReason::VariantNameClash {
+                variant: "Circle".into(),
+                class_name: "AShapeCircle".into(),
+            },
+        ]
+    );
+    assert!(!tmp_dir.path().join("clashing-go").exists());
+
+    go::build_package(
+        tmp_dir.path(),
+        package_info("valid-go"),
+        go::GenerationSettings::enable_all(),
+        generate_bindings!(a_b::C, a::Shape),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_dart_class_names_are_validated() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+
+    let err = dart::build_package(
+        tmp_dir.path(),
+        package_info("clashing_dart"),
+        dart::GenerationSettings::enable_all(),
+        generate_bindings!(a_b::C, a::BC, a::Shape, a::ShapeCircle),
+    )
+    .unwrap_err();
+    assert_eq!(
+        reasons(err),
+        [
+            ValidationErrorReason::TypeNameClash {
+                type_name: "ABC".into()
+            },
+            ValidationErrorReason::VariantNameClash {
+                variant: "Circle".into(),
+                class_name: "AShapeCircle".into(),
+            },
+        ]
+    );
+    assert!(!tmp_dir.path().join("clashing_dart").exists());
+
+    dart::build_package(
+        tmp_dir.path(),
+        package_info("valid_dart"),
+        dart::GenerationSettings::enable_all(),
+        generate_bindings!(a_b::C, a::Shape),
+    )
+    .unwrap();
+}